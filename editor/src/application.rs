@@ -1,5 +1,6 @@
 use crate::dispatcher::Dispatcher;
 use crate::messages::prelude::*;
+use crate::node_graph_executor::ExportConfig;
 
 pub use graphene_core::uuid::*;
 
@@ -24,6 +25,18 @@ impl Editor {
 	pub fn poll_node_graph_evaluation(&mut self, responses: &mut VecDeque<Message>) {
 		self.dispatcher.poll_node_graph_evaluation(responses);
 	}
+
+	/// Synchronously renders a document to SVG text, using the bounds and scale given by the export config.
+	/// Unlike exporting, this doesn't go through the frontend, so it works in native tests and tooling.
+	/// The document doesn't need to be open, so one loaded with [`DocumentMessageHandler::with_name_and_content`] can be rendered too.
+	pub fn render_document_svg(&mut self, document: &mut DocumentMessageHandler, export_config: ExportConfig) -> Result<String, String> {
+		self.dispatcher.message_handlers.portfolio_message_handler.executor.render_document_svg(document, export_config)
+	}
+
+	/// Synchronously renders a document to RGBA pixels, using the bounds and scale given by the export config.
+	pub fn render_document_pixels(&mut self, document: &mut DocumentMessageHandler, export_config: ExportConfig) -> Result<image::RgbaImage, String> {
+		self.dispatcher.message_handlers.portfolio_message_handler.executor.render_document_pixels(document, export_config)
+	}
 }
 
 impl Default for Editor {
@@ -33,6 +33,9 @@ pub const SLOWING_DIVISOR: f64 = 10.;
 pub const NUDGE_AMOUNT: f64 = 1.;
 pub const BIG_NUDGE_AMOUNT: f64 = 10.;
 
+// Guides
+pub const GUIDE_GRAB_TOLERANCE: f64 = 4.;
+
 // Select tool
 pub const SELECTION_TOLERANCE: f64 = 5.;
 pub const SELECTION_DRAG_ANGLE: f64 = 90.;
@@ -70,6 +73,7 @@ pub const COLOR_OVERLAY_BLUE: &str = "#00a8ff";
 pub const COLOR_OVERLAY_YELLOW: &str = "#ffc848";
 pub const COLOR_OVERLAY_WHITE: &str = "#ffffff";
 pub const COLOR_OVERLAY_GRAY: &str = "#cccccc";
+pub const COLOR_OVERLAY_GUIDE: &str = "#e64dff";
 
 // Fonts
 pub const DEFAULT_FONT_FAMILY: &str = "Cabin";
@@ -78,5 +82,7 @@ pub const DEFAULT_FONT_STYLE: &str = "Normal (400)";
 // Document
 pub const DEFAULT_DOCUMENT_NAME: &str = "Untitled Document";
 pub const FILE_SAVE_SUFFIX: &str = ".graphite";
-pub const MAX_UNDO_HISTORY_LEN: usize = 100; // TODO: Add this to user preferences
+pub const DEFAULT_MACRO_NAME: &str = "Untitled Macro";
+pub const MACRO_SAVE_SUFFIX: &str = ".graphite-macro";
+pub const DEFAULT_UNDO_HISTORY_BUDGET: f64 = 256.; // In megabytes, changeable in the preferences
 pub const AUTO_SAVE_TIMEOUT_SECONDS: u64 = 15;
@@ -21,6 +21,7 @@ pub struct DispatcherMessageHandlers {
 	input_preprocessor_message_handler: InputPreprocessorMessageHandler,
 	key_mapping_message_handler: KeyMappingMessageHandler,
 	layout_message_handler: LayoutMessageHandler,
+	macro_recorder_message_handler: MacroRecorderMessageHandler,
 	pub portfolio_message_handler: PortfolioMessageHandler,
 	preferences_message_handler: PreferencesMessageHandler,
 	tool_message_handler: ToolMessageHandler,
@@ -30,7 +31,7 @@ pub struct DispatcherMessageHandlers {
 /// For optimization, these are messages guaranteed to be redundant when repeated.
 /// The last occurrence of the message in the message queue is sufficient to ensure correct behavior.
 /// In addition, these messages do not change any state in the backend (aside from caches).
-const SIDE_EFFECT_FREE_MESSAGES: &[MessageDiscriminant] = &[
+pub(crate) const SIDE_EFFECT_FREE_MESSAGES: &[MessageDiscriminant] = &[
 	MessageDiscriminant::Portfolio(PortfolioMessageDiscriminant::Document(DocumentMessageDiscriminant::NodeGraph(NodeGraphMessageDiscriminant::SendGraph))),
 	MessageDiscriminant::Portfolio(PortfolioMessageDiscriminant::Document(DocumentMessageDiscriminant::PropertiesPanel(
 		PropertiesPanelMessageDiscriminant::Refresh,
@@ -84,6 +85,9 @@ impl Dispatcher {
 			// Print the message at a verbosity level of `log`
 			self.log_message(&message, &self.message_queues, self.message_handlers.debug_message_handler.message_logging_verbosity);
 
+			// Add the message to the macro if one is being recorded and the message belongs in it
+			self.message_handlers.macro_recorder_message_handler.capture(&message, self.message_queues.len());
+
 			// Create a new queue for the child messages
 			let mut queue = VecDeque::new();
 
@@ -152,13 +156,21 @@ impl Dispatcher {
 
 					self.message_handlers.layout_message_handler.process_message(message, &mut queue, action_input_mapping);
 				}
+				Message::MacroRecorder(message) => {
+					let active_tool_type = self.message_handlers.tool_message_handler.tool_state.tool_data.active_tool_type;
+
+					self.message_handlers
+						.macro_recorder_message_handler
+						.process_message(message, &mut queue, MacroRecorderMessageData { active_tool_type });
+				}
 				Message::Portfolio(message) => {
 					let ipp = &self.message_handlers.input_preprocessor_message_handler;
 					let preferences = &self.message_handlers.preferences_message_handler;
+					let macro_recorder = &self.message_handlers.macro_recorder_message_handler;
 
 					self.message_handlers
 						.portfolio_message_handler
-						.process_message(message, &mut queue, PortfolioMessageData { ipp, preferences });
+						.process_message(message, &mut queue, PortfolioMessageData { ipp, preferences, macro_recorder });
 				}
 				Message::Preferences(message) => {
 					self.message_handlers.preferences_message_handler.process_message(message, &mut queue, ());
@@ -199,6 +211,7 @@ impl Dispatcher {
 		list.extend(self.message_handlers.input_preprocessor_message_handler.actions());
 		list.extend(self.message_handlers.key_mapping_message_handler.actions());
 		list.extend(self.message_handlers.debug_message_handler.actions());
+		list.extend(self.message_handlers.macro_recorder_message_handler.actions());
 		if self.message_handlers.portfolio_message_handler.active_document().is_some() {
 			list.extend(self.message_handlers.tool_message_handler.actions());
 		}
@@ -260,6 +273,7 @@ impl Dispatcher {
 #[cfg(test)]
 mod test {
 	use crate::application::Editor;
+	use crate::messages::macro_recorder::utility_types::RecordedMacro;
 	use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
 	use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 	use crate::messages::prelude::*;
@@ -457,4 +471,117 @@ mod test {
 			}
 		}
 	}
+
+	#[test]
+	#[cfg_attr(miri, ignore)]
+	fn demo_art_nodes_match_their_definitions() {
+		use crate::messages::portfolio::document::node_graph::document_node_types::{resolve_document_node_type, upgrade_saved_nodes, UPGRADED_NODE_TYPES};
+		use graph_craft::document::{DocumentNode, DocumentNodeImplementation, NodeNetwork};
+
+		fn upgraded_nodes(network: &mut NodeNetwork) -> Vec<&mut DocumentNode> {
+			let mut nodes = Vec::new();
+			for node in network.nodes.values_mut() {
+				if let DocumentNodeImplementation::Network(nested_network) = &mut node.implementation {
+					nodes.extend(upgraded_nodes(nested_network));
+				} else if UPGRADED_NODE_TYPES.contains(&node.name.as_str()) {
+					nodes.push(node);
+				}
+			}
+			nodes
+		}
+
+		init_logger();
+		let mut editor = Editor::create();
+
+		for (document_name, _, file_name) in crate::messages::dialog::simple_dialogs::ARTWORK {
+			let document_serialized_content = std::fs::read_to_string(format!("../demo-artwork/{file_name}")).unwrap();
+
+			// The files are saved with the current definitions, so they don't rely on being upgraded when opened, which deserializing them directly skips
+			let mut document: DocumentMessageHandler = serde_json::from_str(&document_serialized_content).unwrap();
+			for node in upgraded_nodes(&mut document.network) {
+				let definition = resolve_document_node_type(&node.name).unwrap();
+				assert_eq!(node.inputs.len(), definition.inputs.len(), "Demo artwork '{document_name}' has an outdated {} node", node.name);
+				assert_eq!(node.implementation, definition.implementation, "Demo artwork '{document_name}' has an outdated {} node", node.name);
+
+				// A copy saved before its last input was added, inside a nested network, is upgraded to match its definition again
+				let mut outdated_node = node.clone();
+				outdated_node.inputs.pop();
+				outdated_node.implementation = DocumentNodeImplementation::ProtoNode("graphene_core::ops::IdentityNode".into());
+				let mut network = NodeNetwork::default();
+				network.nodes.insert(
+					NodeId(0),
+					DocumentNode {
+						implementation: DocumentNodeImplementation::Network(NodeNetwork {
+							nodes: [(NodeId(0), outdated_node)].into_iter().collect(),
+							..Default::default()
+						}),
+						..Default::default()
+					},
+				);
+				upgrade_saved_nodes(network.nodes.values_mut());
+				let upgraded = upgraded_nodes(&mut network);
+				assert_eq!(upgraded[0].inputs, node.inputs);
+				assert_eq!(upgraded[0].implementation, definition.implementation);
+			}
+
+			let export_config = crate::node_graph_executor::ExportConfig {
+				bounds: crate::messages::frontend::utility_types::ExportBounds::AllArtwork,
+				..Default::default()
+			};
+			let svg = editor.render_document_svg(&mut document, export_config);
+			assert!(svg.is_ok(), "Demo artwork '{document_name}' failed to render: {svg:?}");
+		}
+	}
+
+	#[test]
+	fn picking_up_a_guide_without_moving_it_adds_no_history() {
+		use crate::messages::input_mapper::utility_types::input_mouse::ViewportBounds;
+		use crate::messages::portfolio::document::guides::utility_types::GuideDirection;
+
+		let mut editor = Editor::create();
+		editor.new_document();
+		editor.handle_message(InputPreprocessorMessage::BoundsOfViewports {
+			bounds_of_viewports: vec![ViewportBounds::from_slice(&[0., 0., 1920., 1080.])],
+		});
+
+		editor.move_mouse(100., 100.);
+		editor.handle_message(GuideMessage::BeginDragFromRuler {
+			direction: GuideDirection::Horizontal,
+		});
+		editor.handle_message(GuideMessage::EndDrag);
+
+		// Clicking the guide picks it up and drops it in place
+		editor.handle_message(GuideMessage::PointerDown);
+		editor.handle_message(GuideMessage::EndDrag);
+
+		// So the only step to undo is adding it
+		editor.handle_message(DocumentMessage::Undo);
+		let document = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap();
+		assert!(document.guides.guides.is_empty());
+	}
+
+	#[test]
+	fn replay_macro_with_parameters() {
+		let mut editor = Editor::create();
+		editor.new_document();
+
+		let recorded_macro = RecordedMacro {
+			messages: vec![DocumentMessage::RenameDocument { new_name: "${name}".to_string() }.into()],
+		};
+		let serialized_macro = recorded_macro.serialize().unwrap();
+
+		// The macro's parameters are asked for before it's replayed
+		let responses = editor.handle_message(DialogMessage::RequestMacroParametersDialog { serialized_macro });
+		assert!(responses.iter().any(|response| matches!(response, FrontendMessage::DisplayDialog { .. })));
+
+		editor.handle_message(MacroParametersDialogMessage::Value {
+			index: 0,
+			value: "Parameterized".to_string(),
+		});
+		editor.handle_message(DialogMessage::CloseDialogAndThen {
+			followups: vec![MacroParametersDialogMessage::Submit.into()],
+		});
+		let document = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap();
+		assert_eq!(document.name, "Parameterized");
+	}
 }
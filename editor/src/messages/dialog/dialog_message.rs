@@ -7,6 +7,8 @@ pub enum DialogMessage {
 	#[child]
 	ExportDialog(ExportDialogMessage),
 	#[child]
+	MacroParametersDialog(MacroParametersDialogMessage),
+	#[child]
 	NewDocumentDialog(NewDocumentDialogMessage),
 	#[child]
 	PreferencesDialog(PreferencesDialogMessage),
@@ -33,6 +35,10 @@ pub enum DialogMessage {
 	RequestLicensesDialogWithLocalizedCommitDate {
 		localized_commit_year: String,
 	},
+	/// Asks for values for the parameters of a saved macro, if it has any, before replaying it.
+	RequestMacroParametersDialog {
+		serialized_macro: String,
+	},
 	RequestNewDocumentDialog,
 	RequestPreferencesDialog,
 }
@@ -1,5 +1,6 @@
 use super::simple_dialogs::{self, AboutGraphiteDialog, ComingSoonDialog, DemoArtworkDialog, LicensesDialog};
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::macro_recorder::utility_types::macro_parameters;
 use crate::messages::prelude::*;
 use crate::messages::tool::common_functionality::graph_modification_utils::is_layer_fed_by_node_of_name;
 
@@ -12,6 +13,7 @@ pub struct DialogMessageData<'a> {
 #[derive(Debug, Default, Clone)]
 pub struct DialogMessageHandler {
 	export_dialog: ExportDialogMessageHandler,
+	macro_parameters_dialog: MacroParametersDialogMessageHandler,
 	new_document_dialog: NewDocumentDialogMessageHandler,
 	preferences_dialog: PreferencesDialogMessageHandler,
 }
@@ -22,6 +24,7 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 
 		match message {
 			DialogMessage::ExportDialog(message) => self.export_dialog.process_message(message, responses, ExportDialogMessageData { portfolio }),
+			DialogMessage::MacroParametersDialog(message) => self.macro_parameters_dialog.process_message(message, responses, ()),
 			DialogMessage::NewDocumentDialog(message) => self.new_document_dialog.process_message(message, responses, ()),
 			DialogMessage::PreferencesDialog(message) => self.preferences_dialog.process_message(message, responses, PreferencesDialogMessageData { preferences }),
 
@@ -76,13 +79,10 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 						.all_layers()
 						.filter(|&layer| is_layer_fed_by_node_of_name(layer, &document.network, "Artboard"))
 						.map(|layer| {
-							(
-								layer,
-								format!("Artboard: {}", {
-									index += 1;
-									format!("Untitled {index}")
-								}),
-							)
+							index += 1;
+							let alias = document.network.nodes.get(&layer.to_node()).map(|node| node.alias.clone()).unwrap_or_default();
+							let name = if alias.is_empty() { format!("Untitled {index}") } else { alias };
+							(layer, name)
 						})
 						.collect();
 
@@ -100,6 +100,19 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 
 				dialog.send_dialog_to_frontend(responses);
 			}
+			DialogMessage::RequestMacroParametersDialog { serialized_macro } => {
+				let parameters: Vec<_> = macro_parameters(&serialized_macro).into_iter().map(|name| (name.to_string(), String::new())).collect();
+
+				if parameters.is_empty() {
+					responses.add(MacroRecorderMessage::Replay {
+						serialized_macro,
+						substitutions: HashMap::new(),
+					});
+				} else {
+					self.macro_parameters_dialog = MacroParametersDialogMessageHandler { serialized_macro, parameters };
+					self.macro_parameters_dialog.send_dialog_to_frontend(responses);
+				}
+			}
 			DialogMessage::RequestNewDocumentDialog => {
 				self.new_document_dialog = NewDocumentDialogMessageHandler {
 					name: portfolio.generate_new_document_name(),
@@ -14,6 +14,7 @@ pub struct ExportDialogMessageHandler {
 	pub scale_factor: f64,
 	pub bounds: ExportBounds,
 	pub transparent_background: bool,
+	/// The name of each artboard in the document
 	pub artboards: HashMap<LayerNodeIdentifier, String>,
 	pub has_selection: bool,
 }
@@ -28,13 +29,25 @@ impl MessageHandler<ExportDialogMessage, ExportDialogMessageData<'_>> for Export
 			ExportDialogMessage::TransparentBackground(transparent_background) => self.transparent_background = transparent_background,
 			ExportDialogMessage::ExportBounds(export_area) => self.bounds = export_area,
 
-			ExportDialogMessage::Submit => responses.add_front(PortfolioMessage::SubmitDocumentExport {
-				file_name: portfolio.active_document().map(|document| document.name.clone()).unwrap_or_default(),
-				file_type: self.file_type,
-				scale_factor: self.scale_factor,
-				bounds: self.bounds,
-				transparent_background: self.file_type != FileType::Jpg && self.transparent_background,
-			}),
+			ExportDialogMessage::Submit => {
+				let document_name = portfolio.active_document().map(|document| document.name.clone()).unwrap_or_default();
+				let exports = if self.bounds == ExportBounds::AllArtboards {
+					self.artboard_file_names().into_iter().map(|(layer, file_name)| (file_name, ExportBounds::Artboard(layer))).collect()
+				} else {
+					vec![(document_name, self.bounds)]
+				};
+
+				// Queued in reverse since each export is pushed to the front
+				for (file_name, bounds) in exports.into_iter().rev() {
+					responses.add_front(PortfolioMessage::SubmitDocumentExport {
+						file_name,
+						file_type: self.file_type,
+						scale_factor: self.scale_factor,
+						bounds,
+						transparent_background: self.file_type != FileType::Jpg && self.transparent_background,
+					});
+				}
+			}
 		}
 
 		self.send_dialog_to_frontend(responses);
@@ -43,6 +56,29 @@ impl MessageHandler<ExportDialogMessage, ExportDialogMessageData<'_>> for Export
 	advertise_actions! {ExportDialogUpdate;}
 }
 
+impl ExportDialogMessageHandler {
+	/// Names the file of each artboard after it, in order of the names, numbering artboards which share a name so their files don't replace each other.
+	/// Names are compared ignoring case, as they are by the file systems of Windows and macOS.
+	fn artboard_file_names(&self) -> Vec<(LayerNodeIdentifier, String)> {
+		let mut artboards: Vec<_> = self.artboards.iter().map(|(&layer, name)| (layer, if name.is_empty() { "Artboard" } else { name.as_str() })).collect();
+		artboards.sort_by_key(|&(layer, name)| (name, layer));
+
+		let mut used_names = HashSet::new();
+		artboards
+			.into_iter()
+			.map(|(layer, name)| {
+				let mut file_name = name.to_string();
+				let mut number = 2;
+				while !used_names.insert(file_name.to_lowercase()) {
+					file_name = format!("{name} {number}");
+					number += 1;
+				}
+				(layer, file_name)
+			})
+			.collect()
+	}
+}
+
 impl DialogLayoutHolder for ExportDialogMessageHandler {
 	const ICON: &'static str = "File";
 	const TITLE: &'static str = "Export";
@@ -67,7 +103,7 @@ impl DialogLayoutHolder for ExportDialogMessageHandler {
 
 impl LayoutHolder for ExportDialogMessageHandler {
 	fn layout(&self) -> Layout {
-		let entries = [(FileType::Png, "PNG"), (FileType::Jpg, "JPG"), (FileType::Svg, "SVG")]
+		let entries = [(FileType::Png, "PNG"), (FileType::Jpg, "JPG"), (FileType::Svg, "SVG"), (FileType::Webp, "WebP"), (FileType::Pdf, "PDF")]
 			.into_iter()
 			.map(|(val, name)| RadioEntryData::new(format!("{val:?}")).label(name).on_update(move |_| ExportDialogMessage::FileType(val).into()))
 			.collect();
@@ -85,16 +121,17 @@ impl LayoutHolder for ExportDialogMessageHandler {
 				.unit("")
 				.min(0.)
 				.max((1_u64 << std::f64::MANTISSA_DIGITS) as f64)
-				.disabled(self.file_type == FileType::Svg)
+				.disabled(self.file_type == FileType::Svg || self.file_type == FileType::Pdf)
 				.on_update(|number_input: &NumberInput| ExportDialogMessage::ScaleFactor(number_input.value.unwrap()).into())
 				.min_width(200)
 				.widget_holder(),
 		];
 
-		let artboards = self.artboards.iter().map(|(&layer, name)| (ExportBounds::Artboard(layer), name.to_string(), false));
+		let artboards = self.artboards.iter().map(|(&layer, name)| (ExportBounds::Artboard(layer), format!("Artboard: {name}"), false));
 		let mut export_area_options = vec![
 			(ExportBounds::AllArtwork, "All Artwork".to_string(), false),
 			(ExportBounds::Selection, "Selection".to_string(), !self.has_selection),
+			(ExportBounds::AllArtboards, "All Artboards (One File Each)".to_string(), self.artboards.is_empty()),
 		];
 		export_area_options.extend(artboards);
 		let index = export_area_options.iter().position(|(val, _, _)| val == &self.bounds).unwrap();
@@ -131,3 +168,23 @@ impl LayoutHolder for ExportDialogMessageHandler {
 		]))
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use graph_craft::document::NodeId;
+
+	#[test]
+	fn number_artboards_which_share_a_name() {
+		let layer = |id| LayerNodeIdentifier::new_unchecked(NodeId(id));
+		let names = [(1, "Cover"), (2, "Back"), (3, "cover"), (4, ""), (5, "Cover"), (6, "Cover 2")];
+		let dialog = ExportDialogMessageHandler {
+			artboards: names.into_iter().map(|(id, name)| (layer(id), name.to_string())).collect(),
+			..Default::default()
+		};
+
+		let file_names = dialog.artboard_file_names();
+		let expected = [(4, "Artboard"), (2, "Back"), (1, "Cover"), (5, "Cover 2"), (6, "Cover 2 2"), (3, "cover 3")];
+		assert_eq!(file_names, expected.map(|(id, name)| (layer(id), name.to_string())));
+	}
+}
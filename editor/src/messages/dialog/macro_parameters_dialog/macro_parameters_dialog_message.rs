@@ -0,0 +1,9 @@
+use crate::messages::prelude::*;
+
+#[impl_message(Message, DialogMessage, MacroParametersDialog)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum MacroParametersDialogMessage {
+	Value { index: usize, value: String },
+
+	Submit,
+}
@@ -0,0 +1,82 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+/// A dialog to allow users to give values for the `${name}` placeholders of a macro before it's replayed.
+#[derive(Debug, Clone, Default)]
+pub struct MacroParametersDialogMessageHandler {
+	pub serialized_macro: String,
+	/// The names of the macro's placeholders, in the order they first appear in it, and their values.
+	pub parameters: Vec<(String, String)>,
+}
+
+impl MessageHandler<MacroParametersDialogMessage, ()> for MacroParametersDialogMessageHandler {
+	fn process_message(&mut self, message: MacroParametersDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			MacroParametersDialogMessage::Value { index, value } => {
+				if let Some((_, parameter_value)) = self.parameters.get_mut(index) {
+					*parameter_value = value;
+				}
+			}
+			MacroParametersDialogMessage::Submit => {
+				responses.add(MacroRecorderMessage::Replay {
+					serialized_macro: self.serialized_macro.clone(),
+					substitutions: self.parameters.iter().cloned().collect(),
+				});
+			}
+		}
+
+		self.send_dialog_to_frontend(responses);
+	}
+
+	advertise_actions! {MacroParametersDialogUpdate;}
+}
+
+impl DialogLayoutHolder for MacroParametersDialogMessageHandler {
+	const ICON: &'static str = "File";
+	const TITLE: &'static str = "Replay Macro";
+
+	fn layout_buttons(&self) -> Layout {
+		let widgets = vec![
+			TextButton::new("Replay")
+				.emphasized(true)
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![MacroParametersDialogMessage::Submit.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for MacroParametersDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let description = vec![TextLabel::new("The macro's parameters are replaced by these values, as they're written, when it's replayed.")
+			.multiline(true)
+			.widget_holder()];
+
+		let parameters = self.parameters.iter().enumerate().map(|(index, (name, value))| {
+			let widgets = vec![
+				TextLabel::new(name).table_align(true).min_width(90).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextInput::new(value)
+					.on_update(move |text_input: &TextInput| {
+						MacroParametersDialogMessage::Value {
+							index,
+							value: text_input.value.clone(),
+						}
+						.into()
+					})
+					.min_width(204)
+					.widget_holder(),
+			];
+			LayoutGroup::Row { widgets }
+		});
+
+		Layout::WidgetLayout(WidgetLayout::new(std::iter::once(LayoutGroup::Row { widgets: description }).chain(parameters).collect()))
+	}
+}
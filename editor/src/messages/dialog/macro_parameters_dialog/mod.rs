@@ -0,0 +1,7 @@
+mod macro_parameters_dialog_message;
+mod macro_parameters_dialog_message_handler;
+
+#[doc(inline)]
+pub use macro_parameters_dialog_message::{MacroParametersDialogMessage, MacroParametersDialogMessageDiscriminant};
+#[doc(inline)]
+pub use macro_parameters_dialog_message_handler::MacroParametersDialogMessageHandler;
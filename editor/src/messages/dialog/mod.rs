@@ -9,6 +9,7 @@ mod dialog_message;
 mod dialog_message_handler;
 
 pub mod export_dialog;
+pub mod macro_parameters_dialog;
 pub mod new_document_dialog;
 pub mod preferences_dialog;
 pub mod simple_dialogs;
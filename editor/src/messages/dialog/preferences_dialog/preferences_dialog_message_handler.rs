@@ -46,6 +46,24 @@ impl PreferencesDialogMessageHandler {
 				.widget_holder(),
 		];
 
+		let undo_history_budget = vec![
+			TextLabel::new("History").min_width(60).italic(true).widget_holder(),
+			TextLabel::new("Undo Memory Budget").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.undo_history_budget))
+				.tooltip("Memory each document's undo history may use before its oldest steps are forgotten")
+				.unit(" MB")
+				.min(1.)
+				.min_width(200)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::UndoHistoryBudget {
+						megabytes: number_input.value.unwrap(),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
 		let imaginate_server_hostname = vec![
 			TextLabel::new("Imaginate").min_width(60).italic(true).widget_holder(),
 			TextLabel::new("Server Hostname").table_align(true).widget_holder(),
@@ -71,6 +89,7 @@ impl PreferencesDialogMessageHandler {
 
 		Layout::WidgetLayout(WidgetLayout::new(vec![
 			LayoutGroup::Row { widgets: zoom_with_scroll },
+			LayoutGroup::Row { widgets: undo_history_budget },
 			LayoutGroup::Row { widgets: imaginate_server_hostname },
 			LayoutGroup::Row { widgets: imaginate_refresh_frequency },
 		]))
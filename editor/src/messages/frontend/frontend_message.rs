@@ -86,6 +86,7 @@ pub enum FrontendMessage {
 	TriggerLoadAutoSaveDocuments,
 	TriggerLoadPreferences,
 	TriggerOpenDocument,
+	TriggerOpenMacro,
 	TriggerPaste,
 	TriggerRefreshBoundsOfViewports,
 	TriggerRevokeBlobUrl {
@@ -169,6 +170,11 @@ pub enum FrontendMessage {
 		#[serde(rename = "setColorChoice")]
 		set_color_choice: Option<String>,
 	},
+	UpdateHistoryPanelLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
 	UpdateInputHints {
 		#[serde(rename = "hintData")]
 		hint_data: HintData,
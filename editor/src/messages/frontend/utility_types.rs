@@ -35,6 +35,8 @@ pub enum FileType {
 	Png,
 	Jpg,
 	Svg,
+	Webp,
+	Pdf,
 }
 
 impl FileType {
@@ -43,6 +45,8 @@ impl FileType {
 			FileType::Png => "image/png",
 			FileType::Jpg => "image/jpeg",
 			FileType::Svg => "image/svg+xml",
+			FileType::Webp => "image/webp",
+			FileType::Pdf => "application/pdf",
 		}
 	}
 }
@@ -52,5 +56,7 @@ pub enum ExportBounds {
 	#[default]
 	AllArtwork,
 	Selection,
+	/// Exports each artboard to its own file, named after the artboard
+	AllArtboards,
 	Artboard(LayerNodeIdentifier),
 }
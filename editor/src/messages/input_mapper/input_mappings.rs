@@ -8,6 +8,7 @@ use crate::messages::input_mapper::utility_types::misc::{KeyMappingEntries, Mapp
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
 use crate::messages::prelude::*;
 use crate::messages::tool::tool_messages::brush_tool::BrushToolMessageOptionsUpdate;
+use crate::messages::tool::tool_messages::brush_tool::SamplingToolOptionsUpdate;
 use crate::messages::tool::tool_messages::select_tool::SelectToolPointerKeys;
 
 use glam::DVec2;
@@ -45,6 +46,22 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(Mmb); action_dispatch=NavigationMessage::EndCanvasPTZWithClick { commit_key: Mmb }),
 		entry!(KeyDown(Rmb); action_dispatch=NavigationMessage::EndCanvasPTZWithClick { commit_key: Rmb }),
 		//
+		// GuideMessage
+		entry!(PointerMove; action_dispatch=GuideMessage::PointerMove),
+		entry!(KeyUp(Lmb); action_dispatch=GuideMessage::EndDrag),
+		entry!(KeyDown(Rmb); action_dispatch=GuideMessage::AbortDrag),
+		entry!(KeyDown(Escape); action_dispatch=GuideMessage::AbortDrag),
+		entry!(KeyDown(Lmb); action_dispatch=GuideMessage::PointerDown),
+		//
+		// SelectModeMessage
+		entry!(PointerMove; action_dispatch=SelectModeMessage::PointerMove),
+		entry!(KeyUp(Lmb); action_dispatch=SelectModeMessage::PointerUp),
+		entry!(KeyDown(Rmb); action_dispatch=SelectModeMessage::Abort),
+		entry!(KeyDown(Escape); action_dispatch=SelectModeMessage::Abort),
+		entry!(KeyDown(Lmb); action_dispatch=SelectModeMessage::PointerDown { extend: Shift, subtract: Alt }),
+		entry!(KeyDown(KeyD); modifiers=[Accel], action_dispatch=SelectModeMessage::Deselect),
+		entry!(KeyDown(Escape); action_dispatch=SelectModeMessage::Deselect),
+		//
 		// ===============
 		// NORMAL PRIORITY
 		// ===============
@@ -267,6 +284,26 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(Rmb); action_dispatch=BrushToolMessage::Abort),
 		entry!(KeyDown(Escape); action_dispatch=BrushToolMessage::Abort),
 		//
+		// HealToolMessage
+		entry!(PointerMove; action_dispatch=HealToolMessage::PointerMove),
+		entry!(KeyDown(Lmb); action_dispatch=HealToolMessage::DragStart),
+		entry!(KeyDown(Lmb); modifiers=[Alt], action_dispatch=HealToolMessage::SetSource),
+		entry!(KeyUp(Lmb); action_dispatch=HealToolMessage::DragStop),
+		entry!(KeyDown(BracketLeft); action_dispatch=HealToolMessage::UpdateOptions(SamplingToolOptionsUpdate::ChangeDiameter(-BRUSH_SIZE_CHANGE_KEYBOARD))),
+		entry!(KeyDown(BracketRight); action_dispatch=HealToolMessage::UpdateOptions(SamplingToolOptionsUpdate::ChangeDiameter(BRUSH_SIZE_CHANGE_KEYBOARD))),
+		entry!(KeyDown(Rmb); action_dispatch=HealToolMessage::Abort),
+		entry!(KeyDown(Escape); action_dispatch=HealToolMessage::Abort),
+		//
+		// CloneToolMessage
+		entry!(PointerMove; action_dispatch=CloneToolMessage::PointerMove),
+		entry!(KeyDown(Lmb); action_dispatch=CloneToolMessage::DragStart),
+		entry!(KeyDown(Lmb); modifiers=[Alt], action_dispatch=CloneToolMessage::SetSource),
+		entry!(KeyUp(Lmb); action_dispatch=CloneToolMessage::DragStop),
+		entry!(KeyDown(BracketLeft); action_dispatch=CloneToolMessage::UpdateOptions(SamplingToolOptionsUpdate::ChangeDiameter(-BRUSH_SIZE_CHANGE_KEYBOARD))),
+		entry!(KeyDown(BracketRight); action_dispatch=CloneToolMessage::UpdateOptions(SamplingToolOptionsUpdate::ChangeDiameter(BRUSH_SIZE_CHANGE_KEYBOARD))),
+		entry!(KeyDown(Rmb); action_dispatch=CloneToolMessage::Abort),
+		entry!(KeyDown(Escape); action_dispatch=CloneToolMessage::Abort),
+		//
 		// ToolMessage
 		entry!(KeyDown(KeyV); action_dispatch=ToolMessage::ActivateToolSelect),
 		entry!(KeyDown(KeyZ); action_dispatch=ToolMessage::ActivateToolNavigate),
@@ -282,6 +319,8 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(KeyE); action_dispatch=ToolMessage::ActivateToolEllipse),
 		entry!(KeyDown(KeyY); action_dispatch=ToolMessage::ActivateToolPolygon),
 		entry!(KeyDown(KeyB); action_dispatch=ToolMessage::ActivateToolBrush),
+		entry!(KeyDown(KeyJ); action_dispatch=ToolMessage::ActivateToolHeal),
+		entry!(KeyDown(KeyC); action_dispatch=ToolMessage::ActivateToolClone),
 		entry!(KeyDown(KeyX); modifiers=[Accel, Shift], action_dispatch=ToolMessage::ResetColors),
 		entry!(KeyDown(KeyX); modifiers=[Shift], action_dispatch=ToolMessage::SwapColors),
 		entry!(KeyDown(KeyC); modifiers=[Alt], action_dispatch=ToolMessage::SelectRandomPrimaryColor),
@@ -338,6 +377,9 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
 		entry!(KeyDown(ArrowRight); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
 		//
+		// GuideMessage
+		entry!(KeyDown(Semicolon); modifiers=[Accel], action_dispatch=GuideMessage::ToggleVisibility),
+		//
 		// TransformLayerMessage
 		entry!(KeyDown(KeyG); action_dispatch=TransformLayerMessage::BeginGrab),
 		entry!(KeyDown(KeyR); action_dispatch=TransformLayerMessage::BeginRotate),
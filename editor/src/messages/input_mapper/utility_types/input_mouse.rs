@@ -60,11 +60,27 @@ impl ScrollDelta {
 	}
 }
 
+/// Input from a pen or stylus, which is reported as full pressure without tilt for other pointing devices.
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StylusState {
+	/// Pressure from 0 to 1
+	pub pressure: f64,
+	/// Tilt along the X and Y axes in degrees, from -90 to 90
+	pub tilt: DVec2,
+}
+
+impl Default for StylusState {
+	fn default() -> Self {
+		Self { pressure: 1., tilt: DVec2::ZERO }
+	}
+}
+
 #[derive(Debug, Copy, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct MouseState {
 	pub position: ViewportPosition,
 	pub mouse_keys: MouseKeys,
 	pub scroll_delta: ScrollDelta,
+	pub stylus: StylusState,
 }
 
 impl MouseState {
@@ -77,6 +93,7 @@ impl MouseState {
 			position: (x, y).into(),
 			mouse_keys: MouseKeys::default(),
 			scroll_delta: ScrollDelta::default(),
+			stylus: StylusState::default(),
 		}
 	}
 
@@ -87,6 +104,7 @@ impl MouseState {
 			position,
 			mouse_keys,
 			scroll_delta: ScrollDelta::default(),
+			stylus: StylusState::default(),
 		}
 	}
 
@@ -103,6 +121,7 @@ pub struct EditorMouseState {
 	pub editor_position: EditorPosition,
 	pub mouse_keys: MouseKeys,
 	pub scroll_delta: ScrollDelta,
+	pub stylus: StylusState,
 }
 
 impl EditorMouseState {
@@ -115,6 +134,7 @@ impl EditorMouseState {
 			editor_position: (x, y).into(),
 			mouse_keys: MouseKeys::default(),
 			scroll_delta: ScrollDelta::default(),
+			stylus: StylusState::default(),
 		}
 	}
 
@@ -125,6 +145,7 @@ impl EditorMouseState {
 			editor_position,
 			mouse_keys,
 			scroll_delta: ScrollDelta::default(),
+			stylus: StylusState::default(),
 		}
 	}
 
@@ -133,6 +154,7 @@ impl EditorMouseState {
 			position: self.editor_position - active_viewport_bounds.top_left,
 			mouse_keys: self.mouse_keys,
 			scroll_delta: self.scroll_delta,
+			stylus: self.stylus,
 		}
 	}
 }
@@ -154,6 +154,10 @@ pub struct FrameTimeInfo {
 }
 
 impl FrameTimeInfo {
+	pub fn timestamp(&self) -> Duration {
+		self.timestamp
+	}
+
 	pub fn frame_duration(&self) -> Option<Duration> {
 		self.prev_timestamp.map(|prev| self.timestamp - prev)
 	}
@@ -70,6 +70,7 @@ impl MessageHandler<InputPreprocessorMessage, InputPreprocessorMessageData> for
 
 				let mouse_state = editor_mouse_state.to_mouse_state(&self.viewport_bounds);
 				self.mouse.position = mouse_state.position;
+				self.mouse.stylus = mouse_state.stylus;
 
 				self.translate_mouse_event(mouse_state, true, responses);
 			}
@@ -78,6 +79,7 @@ impl MessageHandler<InputPreprocessorMessage, InputPreprocessorMessageData> for
 
 				let mouse_state = editor_mouse_state.to_mouse_state(&self.viewport_bounds);
 				self.mouse.position = mouse_state.position;
+				self.mouse.stylus = mouse_state.stylus;
 
 				responses.add(InputMapperMessage::PointerMove);
 
@@ -89,6 +91,7 @@ impl MessageHandler<InputPreprocessorMessage, InputPreprocessorMessageData> for
 
 				let mouse_state = editor_mouse_state.to_mouse_state(&self.viewport_bounds);
 				self.mouse.position = mouse_state.position;
+				self.mouse.stylus = mouse_state.stylus;
 
 				self.translate_mouse_event(mouse_state, false, responses);
 			}
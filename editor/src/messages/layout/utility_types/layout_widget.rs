@@ -32,6 +32,8 @@ pub enum LayoutTarget {
 	DocumentBar,
 	/// Contains the dropdown for design / select / guide mode found on the top left of the canvas.
 	DocumentMode,
+	/// The list of history states shown in the History panel.
+	HistoryPanel,
 	/// Options for opacity seen at the top of the Layers panel.
 	LayersPanelOptions,
 	/// The dropdown menu at the very top of the application: File, Edit, etc.
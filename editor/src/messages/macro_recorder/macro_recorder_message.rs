@@ -0,0 +1,16 @@
+use crate::messages::prelude::*;
+
+#[impl_message(Message, MacroRecorder)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum MacroRecorderMessage {
+	StartRecording,
+	StopRecording,
+	SaveRecording,
+	ReplayRecording,
+	OpenMacro,
+	/// Replays a saved macro against the active document, after replacing each `${name}` placeholder in it with the substitution for `name`.
+	Replay {
+		serialized_macro: String,
+		substitutions: HashMap<String, String>,
+	},
+}
@@ -0,0 +1,93 @@
+use super::utility_types::{MacroRecording, RecordedMacro};
+use crate::consts::{DEFAULT_MACRO_NAME, MACRO_SAVE_SUFFIX};
+use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::ToolType;
+
+pub struct MacroRecorderMessageData {
+	pub active_tool_type: ToolType,
+}
+
+#[derive(Debug, Default)]
+pub struct MacroRecorderMessageHandler {
+	recording: Option<MacroRecording>,
+	recorded_macro: Option<RecordedMacro>,
+}
+
+impl MacroRecorderMessageHandler {
+	pub fn is_recording(&self) -> bool {
+		self.recording.is_some()
+	}
+
+	pub fn has_recorded_macro(&self) -> bool {
+		self.recorded_macro.is_some()
+	}
+
+	/// Called by the dispatcher with each message it's about to process, and the depth of the message in its queues, to record the ones which edit the document.
+	pub fn capture(&mut self, message: &Message, depth: usize) {
+		if let Some(recording) = &mut self.recording {
+			recording.capture(message, depth);
+		}
+	}
+}
+
+impl MessageHandler<MacroRecorderMessage, MacroRecorderMessageData> for MacroRecorderMessageHandler {
+	fn process_message(&mut self, message: MacroRecorderMessage, responses: &mut VecDeque<Message>, data: MacroRecorderMessageData) {
+		let MacroRecorderMessageData { active_tool_type } = data;
+
+		match message {
+			MacroRecorderMessage::StartRecording => {
+				// The recorded clicks and drags depend on the tool, so the macro starts by switching to the tool that was active
+				self.recording = Some(MacroRecording::new(vec![ToolMessage::ActivateTool { tool_type: active_tool_type }.into()]));
+
+				// Refresh the menu entries for recording
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			MacroRecorderMessage::StopRecording => {
+				if let Some(recording) = self.recording.take() {
+					self.recorded_macro = Some(recording.finish());
+				}
+
+				// Refresh the menu entries for recording
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			MacroRecorderMessage::SaveRecording => {
+				let Some(recorded_macro) = &self.recorded_macro else { return };
+
+				match recorded_macro.serialize() {
+					Ok(document) => responses.add(FrontendMessage::TriggerDownloadTextFile {
+						document,
+						name: format!("{DEFAULT_MACRO_NAME}{MACRO_SAVE_SUFFIX}"),
+					}),
+					Err(description) => responses.add(DialogMessage::DisplayDialogError {
+						title: "Failed to save macro".to_string(),
+						description,
+					}),
+				}
+			}
+			MacroRecorderMessage::ReplayRecording => {
+				if let Some(recorded_macro) = &self.recorded_macro {
+					responses.extend(recorded_macro.messages.iter().cloned());
+				}
+			}
+			MacroRecorderMessage::OpenMacro => {
+				// This message wraps the frontend message so it can be listed as an action, which isn't possible for frontend messages
+				responses.add(FrontendMessage::TriggerOpenMacro);
+			}
+			MacroRecorderMessage::Replay { serialized_macro, substitutions } => match RecordedMacro::deserialize(&serialized_macro, &substitutions) {
+				Ok(recorded_macro) => responses.extend(recorded_macro.messages),
+				Err(description) => responses.add(DialogMessage::DisplayDialogError {
+					title: "Failed to replay macro".to_string(),
+					description,
+				}),
+			},
+		}
+	}
+
+	advertise_actions!(MacroRecorderMessageDiscriminant;
+		StartRecording,
+		StopRecording,
+		SaveRecording,
+		ReplayRecording,
+		OpenMacro,
+	);
+}
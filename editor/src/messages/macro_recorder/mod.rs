@@ -0,0 +1,9 @@
+mod macro_recorder_message;
+mod macro_recorder_message_handler;
+
+pub mod utility_types;
+
+#[doc(inline)]
+pub use macro_recorder_message::{MacroRecorderMessage, MacroRecorderMessageDiscriminant};
+#[doc(inline)]
+pub use macro_recorder_message_handler::{MacroRecorderMessageData, MacroRecorderMessageHandler};
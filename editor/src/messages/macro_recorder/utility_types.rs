@@ -0,0 +1,270 @@
+use crate::dispatcher::SIDE_EFFECT_FREE_MESSAGES;
+use crate::messages::prelude::*;
+
+/// Tool messages with these names follow the pointer as it hovers, or update tools when modifier keys are pressed, without changing the document.
+const POINTER_NOISE_NAME_ENDINGS: &[&str] = &["PointerMove", "PointerOutsideViewport"];
+
+/// Whether a message belongs in a macro, which keeps only the messages that change the active document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroCapture {
+	/// The message is kept in the macro. Replaying it leads to the same messages it led to while recording, so those aren't kept.
+	Record,
+	/// The message and the messages it leads to are left out of the macro.
+	Ignore,
+	/// The message is left out of the macro, but the messages it leads to are considered for it.
+	PassThrough,
+}
+
+impl MacroCapture {
+	pub fn of(message: &Message) -> Self {
+		let discriminant = message.to_discriminant();
+		if SIDE_EFFECT_FREE_MESSAGES.contains(&discriminant) {
+			return Self::Ignore;
+		}
+
+		match message {
+			// Messages standing in for others, such as a clicked widget for its callback or a dialog for the messages its buttons send
+			Message::Batched(_) | Message::Dialog(_) | Message::KeyMapping(_) | Message::Layout(_) => Self::PassThrough,
+			Message::InputPreprocessor(message) => match message {
+				// Key presses are kept as the actions they're mapped to, so the shortcuts which open or close documents aren't replayed
+				InputPreprocessorMessage::KeyDown { .. } | InputPreprocessorMessage::KeyUp { .. } => Self::PassThrough,
+				InputPreprocessorMessage::BoundsOfViewports { .. } | InputPreprocessorMessage::FrameTimeAdvance { .. } => Self::Ignore,
+				// Dragging edits the document, but hovering doesn't
+				InputPreprocessorMessage::PointerMove { editor_mouse_state, .. } if editor_mouse_state.mouse_keys.is_empty() => Self::Ignore,
+				_ => Self::Record,
+			},
+			Message::Portfolio(message) => match message {
+				// Switching between documents, so a macro applies to whichever document is active when it's replayed
+				PortfolioMessage::CloseActiveDocumentWithConfirmation
+				| PortfolioMessage::CloseAllDocuments
+				| PortfolioMessage::CloseAllDocumentsWithConfirmation
+				| PortfolioMessage::CloseDocument { .. }
+				| PortfolioMessage::CloseDocumentWithConfirmation { .. }
+				| PortfolioMessage::DeleteDocument { .. }
+				| PortfolioMessage::DestroyAllDocuments
+				| PortfolioMessage::DocumentPassMessage { .. }
+				| PortfolioMessage::NewDocumentWithName { .. }
+				| PortfolioMessage::NextDocument
+				| PortfolioMessage::OpenDocumentFile { .. }
+				| PortfolioMessage::OpenDocumentFileWithId { .. }
+				| PortfolioMessage::PrevDocument
+				| PortfolioMessage::SelectDocument { .. } => Self::Ignore,
+				// File pickers, whose chosen files arrive in messages of their own
+				PortfolioMessage::Import | PortfolioMessage::OpenDocument => Self::Ignore,
+				// Traffic between the editor and the frontend or background tasks
+				PortfolioMessage::AutoSaveActiveDocument
+				| PortfolioMessage::AutoSaveAllDocuments
+				| PortfolioMessage::AutoSaveDocument { .. }
+				| PortfolioMessage::FontLoaded { .. }
+				| PortfolioMessage::ImaginateCheckServerStatus
+				| PortfolioMessage::ImaginatePollServerStatus
+				| PortfolioMessage::ImaginatePreferences
+				| PortfolioMessage::ImaginateServerHostname
+				| PortfolioMessage::LoadDocumentResources { .. }
+				| PortfolioMessage::LoadFont { .. }
+				| PortfolioMessage::MenuBar(_)
+				| PortfolioMessage::SubmitGraphRender { .. }
+				| PortfolioMessage::UpdateDocumentWidgets
+				| PortfolioMessage::UpdateOpenDocumentsList => Self::Ignore,
+				_ => Self::Record,
+			},
+			Message::Tool(ToolMessage::Text(TextToolMessage::UpdateBounds { .. })) => Self::Ignore,
+			Message::Tool(_) if POINTER_NOISE_NAME_ENDINGS.iter().any(|ending| discriminant.local_name().ends_with(ending)) => Self::Ignore,
+			Message::Tool(_) => Self::Record,
+			// A macro replayed while recording is kept as the messages it replays
+			Message::MacroRecorder(MacroRecorderMessage::Replay { .. } | MacroRecorderMessage::ReplayRecording) => Self::PassThrough,
+			_ => Self::Ignore,
+		}
+	}
+}
+
+/// A macro in the middle of being recorded.
+#[derive(Debug, Clone, Default)]
+pub struct MacroRecording {
+	messages: Vec<Message>,
+	/// The depth in the dispatcher's message queues of the last message which was recorded or ignored.
+	/// The messages it leads to are processed deeper in the queues until the next message at its depth or shallower, and they are skipped.
+	decided_depth: Option<usize>,
+}
+
+impl MacroRecording {
+	pub fn new(messages: Vec<Message>) -> Self {
+		Self { messages, decided_depth: None }
+	}
+
+	/// Records the message if it belongs in the macro, as it's about to be processed at the given depth in the dispatcher's message queues.
+	pub fn capture(&mut self, message: &Message, depth: usize) {
+		if matches!(self.decided_depth, Some(decided_depth) if depth > decided_depth) {
+			return;
+		}
+		self.decided_depth = None;
+
+		match MacroCapture::of(message) {
+			MacroCapture::Record => {
+				self.messages.push(message.clone());
+				self.decided_depth = Some(depth);
+			}
+			MacroCapture::Ignore => self.decided_depth = Some(depth),
+			MacroCapture::PassThrough => {}
+		}
+	}
+
+	pub fn finish(self) -> RecordedMacro {
+		RecordedMacro { messages: self.messages }
+	}
+}
+
+/// The messages of a recorded macro, which are saved as a JSON file and replayed in order against the active document.
+///
+/// The pointer positions of clicks and drags are in viewport space, so they land on the same parts of another document only if it's viewed the same way.
+/// Messages which refer to layers or nodes by their IDs only apply to documents with the same IDs, such as those made from a common template,
+/// unless the IDs are replaced by parameters.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RecordedMacro {
+	pub messages: Vec<Message>,
+}
+
+impl RecordedMacro {
+	pub fn serialize(&self) -> Result<String, String> {
+		serde_json::to_string_pretty(self).map_err(|error| format!("The recorded messages can't be saved: {error}"))
+	}
+
+	/// Parses a saved macro once its parameters have been substituted, as described by [`substitute_parameters`].
+	pub fn deserialize(serialized_macro: &str, substitutions: &HashMap<String, String>) -> Result<Self, String> {
+		let serialized_macro = substitute_parameters(serialized_macro, substitutions)?;
+		serde_json::from_str(&serialized_macro).map_err(|error| format!("The macro isn't valid: {error}"))
+	}
+}
+
+/// The names of the `${name}` placeholders in the text, in the order they first appear, which need substitutions as described by [`substitute_parameters`].
+pub fn macro_parameters(text: &str) -> Vec<&str> {
+	let mut names = Vec::new();
+
+	let mut rest = text;
+	while let Some((_, name, after)) = split_at_placeholder(rest) {
+		if !names.contains(&name) {
+			names.push(name);
+		}
+		rest = after;
+	}
+
+	names
+}
+
+/// Replaces each `${name}` placeholder in the text with the substitution for `name`, whose characters may be letters, digits, `_` or `-`.
+/// The substitutions are inserted as they are, so a placeholder for part of a string goes inside its quotes, and one for a number doesn't.
+/// Fails with the names of any placeholders without a substitution.
+pub fn substitute_parameters(text: &str, substitutions: &HashMap<String, String>) -> Result<String, String> {
+	let mut substituted = String::with_capacity(text.len());
+	let mut missing: Vec<&str> = Vec::new();
+
+	let mut rest = text;
+	while let Some((before, name, after)) = split_at_placeholder(rest) {
+		substituted.push_str(before);
+		match substitutions.get(name) {
+			Some(value) => substituted.push_str(value),
+			None => {
+				if !missing.contains(&name) {
+					missing.push(name);
+				}
+			}
+		}
+		rest = after;
+	}
+	substituted.push_str(rest);
+
+	if missing.is_empty() {
+		Ok(substituted)
+	} else {
+		Err(format!("No values were given for these parameters of the macro: {}", missing.join(", ")))
+	}
+}
+
+/// Splits the text around its first `${name}` placeholder, into the text before it, its name, and the text after it.
+fn split_at_placeholder(text: &str) -> Option<(&str, &str, &str)> {
+	let mut searched = 0;
+	while let Some(offset) = text[searched..].find("${") {
+		let start = searched + offset;
+		let after = &text[start + 2..];
+		let name_length = after
+			.find(|character: char| !(character.is_ascii_alphanumeric() || character == '_' || character == '-'))
+			.unwrap_or(after.len());
+
+		// Text which only looks like the start of a placeholder is left alone
+		if name_length > 0 && after[name_length..].starts_with('}') {
+			return Some((&text[..start], &after[..name_length], &after[name_length + 1..]));
+		}
+		searched = start + 2;
+	}
+
+	None
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::messages::input_mapper::utility_types::input_keyboard::{Key, ModifierKeys};
+	use crate::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, MouseKeys};
+	use crate::messages::tool::utility_types::ToolType;
+
+	fn pointer_move(mouse_keys: MouseKeys) -> Message {
+		let editor_mouse_state = EditorMouseState { mouse_keys, ..Default::default() };
+		let modifier_keys = ModifierKeys::empty();
+		InputPreprocessorMessage::PointerMove { editor_mouse_state, modifier_keys }.into()
+	}
+
+	#[test]
+	fn record_document_changes_only() {
+		let key_down: Message = InputPreprocessorMessage::KeyDown {
+			key: Key::KeyA,
+			key_repeat: false,
+			modifier_keys: ModifierKeys::CONTROL,
+		}
+		.into();
+		let select_all: Message = DocumentMessage::SelectAllLayers.into();
+		let redraw: Message = OverlaysMessage::Draw.into();
+		let activate_tool: Message = ToolMessage::ActivateTool { tool_type: ToolType::Rectangle }.into();
+
+		assert_eq!(MacroCapture::of(&pointer_move(MouseKeys::empty())), MacroCapture::Ignore);
+		assert_eq!(MacroCapture::of(&pointer_move(MouseKeys::LEFT)), MacroCapture::Record);
+		assert_eq!(MacroCapture::of(&key_down), MacroCapture::PassThrough);
+		assert_eq!(MacroCapture::of(&select_all), MacroCapture::Record);
+		assert_eq!(MacroCapture::of(&redraw), MacroCapture::Ignore);
+		assert_eq!(MacroCapture::of(&PortfolioMessage::OpenDocument.into()), MacroCapture::Ignore);
+		assert_eq!(MacroCapture::of(&FrontendMessage::TriggerPaste.into()), MacroCapture::Ignore);
+
+		// A key press passes through to the action it's mapped to, which is recorded along with the next drag but none of the messages they lead to
+		let mut recording = MacroRecording::new(vec![activate_tool.clone()]);
+		recording.capture(&key_down, 1);
+		recording.capture(&select_all, 2);
+		recording.capture(&redraw, 3);
+		recording.capture(&pointer_move(MouseKeys::empty()), 1);
+		recording.capture(&select_all, 2);
+		recording.capture(&pointer_move(MouseKeys::LEFT), 1);
+		recording.capture(&select_all, 2);
+		assert_eq!(recording.finish().messages, [activate_tool, select_all, pointer_move(MouseKeys::LEFT)]);
+	}
+
+	#[test]
+	fn substitute_macro_parameters() {
+		let substitutions = HashMap::from([("name".to_string(), "Background".to_string()), ("opacity".to_string(), "0.5".to_string())]);
+
+		let text = r#"{"name": "${name} copy", "opacity": ${opacity}, "price": "$5", "template": "${not a placeholder}"}"#;
+		let substituted = substitute_parameters(text, &substitutions).unwrap();
+		assert_eq!(substituted, r#"{"name": "Background copy", "opacity": 0.5, "price": "$5", "template": "${not a placeholder}"}"#);
+
+		let error = substitute_parameters("${name} ${width} ${height} ${width}", &substitutions).unwrap_err();
+		assert!(error.ends_with(": width, height"));
+		assert_eq!(macro_parameters(text), ["name", "opacity"]);
+		assert_eq!(macro_parameters("${name} ${width} ${height} ${width} ${}"), ["name", "width", "height"]);
+
+		let recorded = RecordedMacro {
+			messages: vec![DocumentMessage::SelectAllLayers.into(), NavigationMessage::CanvasZoomSet { zoom_factor: 2. }.into()],
+		};
+		let serialized = recorded.serialize().unwrap().replace("2.0", "${zoom}");
+		let zoom = HashMap::from([("zoom".to_string(), "4".to_string())]);
+		let zoom_set: Message = NavigationMessage::CanvasZoomSet { zoom_factor: 4. }.into();
+		assert_eq!(RecordedMacro::deserialize(&serialized, &zoom).unwrap().messages[1], zoom_set);
+		assert!(RecordedMacro::deserialize(&serialized, &HashMap::new()).is_err());
+	}
+}
@@ -26,6 +26,8 @@ pub enum Message {
 	#[child]
 	Layout(LayoutMessage),
 	#[child]
+	MacroRecorder(MacroRecorderMessage),
+	#[child]
 	Portfolio(PortfolioMessage),
 	#[child]
 	Preferences(PreferencesMessage),
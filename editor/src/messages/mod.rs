@@ -8,6 +8,7 @@ pub mod globals;
 pub mod input_mapper;
 pub mod input_preprocessor;
 pub mod layout;
+pub mod macro_recorder;
 pub mod message;
 pub mod portfolio;
 pub mod preferences;
@@ -1,16 +1,17 @@
 use crate::messages::input_mapper::utility_types::input_keyboard::Key;
 use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
-use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GridSnapping};
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, DocumentMode, FlipAxis, GridSnapping};
 use crate::messages::prelude::*;
 
+use bezier_rs::BooleanOperation;
 use graph_craft::document::{NodeId, NodeNetwork};
 use graphene_core::raster::BlendMode;
 use graphene_core::raster::Image;
 use graphene_core::vector::style::ViewMode;
 use graphene_core::Color;
 
-use glam::DAffine2;
+use glam::{DAffine2, DVec2};
 
 use super::utility_types::misc::{OptionBoundsSnapping, OptionPointSnapping};
 
@@ -22,6 +23,8 @@ pub enum DocumentMessage {
 	#[child]
 	GraphOperation(GraphOperationMessage),
 	#[child]
+	Guide(GuideMessage),
+	#[child]
 	Navigation(NavigationMessage),
 	#[child]
 	NodeGraph(NodeGraphMessage),
@@ -29,6 +32,8 @@ pub enum DocumentMessage {
 	Overlays(OverlaysMessage),
 	#[child]
 	PropertiesPanel(PropertiesPanelMessage),
+	#[child]
+	SelectMode(SelectModeMessage),
 
 	// Messages
 	AbortTransaction,
@@ -39,7 +44,11 @@ pub enum DocumentMessage {
 	BackupDocument {
 		network: NodeNetwork,
 	},
+	BooleanOperationSelectedLayers {
+		operation: BooleanOperation,
+	},
 	ClearArtboards,
+	ClearHistoryPanel,
 	ClearLayersPanel,
 	CommitTransaction,
 	CreateEmptyFolder,
@@ -51,6 +60,9 @@ pub enum DocumentMessage {
 	DeselectAllLayers,
 	DocumentHistoryBackward,
 	DocumentHistoryForward,
+	DocumentHistoryJump {
+		steps: isize,
+	},
 	DocumentStructureChanged,
 	DuplicateSelectedLayers,
 	FlipSelectedLayers {
@@ -69,6 +81,10 @@ pub enum DocumentMessage {
 		imaginate_node: Vec<NodeId>,
 		then_generate: bool,
 	},
+	ImaginateRescale {
+		imaginate_node: Vec<NodeId>,
+		resolution: DVec2,
+	},
 	ImportSvg {
 		id: NodeId,
 		svg: String,
@@ -117,6 +133,9 @@ pub enum DocumentMessage {
 	SetBlendModeForSelectedLayers {
 		blend_mode: BlendMode,
 	},
+	SetDocumentMode {
+		document_mode: DocumentMode,
+	},
 	SetOpacityForSelectedLayers {
 		opacity: f64,
 	},
@@ -126,10 +145,14 @@ pub enum DocumentMessage {
 	SetRangeSelectionLayer {
 		new_layer: Option<LayerNodeIdentifier>,
 	},
+	SetTextBeingEdited {
+		layer: Option<LayerNodeIdentifier>,
+	},
 	SetSnapping {
 		snapping_enabled: Option<bool>,
 		bounding_box_snapping: Option<OptionBoundsSnapping>,
 		geometry_snapping: Option<OptionPointSnapping>,
+		guide_snapping: Option<bool>,
 	},
 	SetViewMode {
 		view_mode: ViewMode,
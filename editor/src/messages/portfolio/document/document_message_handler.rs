@@ -1,4 +1,7 @@
+use super::guides::utility_types::{Guide, Guides};
+use super::select_mode::utility_types::{SelectionMask, SelectionShape};
 use super::utility_types::clipboards::Clipboard;
+use super::utility_types::document_history::{DocumentHistory, DocumentState};
 use super::utility_types::error::EditorError;
 use super::utility_types::misc::{BoundingBoxSnapTarget, GeometrySnapTarget, OptionBoundsSnapping, OptionPointSnapping, SnappingOptions, SnappingState};
 use super::utility_types::nodes::{CollapsedLayers, SelectedNodes};
@@ -7,6 +10,7 @@ use crate::consts::{ASYMPTOTIC_EFFECT, DEFAULT_DOCUMENT_NAME, FILE_SAVE_SUFFIX,
 use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
+use crate::messages::portfolio::document::node_graph::document_node_types::upgrade_saved_nodes;
 use crate::messages::portfolio::document::node_graph::NodeGraphHandlerData;
 use crate::messages::portfolio::document::overlays::grid_overlays::{grid_overlay, overlay_options};
 use crate::messages::portfolio::document::properties_panel::utility_types::PropertiesPanelMessageHandlerData;
@@ -15,13 +19,14 @@ use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate,
 use crate::messages::portfolio::document::utility_types::nodes::RawBuffer;
 use crate::messages::portfolio::utility_types::PersistentData;
 use crate::messages::prelude::*;
-use crate::messages::tool::common_functionality::graph_modification_utils::{get_blend_mode, get_opacity};
+use crate::messages::tool::common_functionality::graph_modification_utils::{get_blend_mode, get_opacity, is_vector_layer, NodeGraphLayer};
 use crate::messages::tool::utility_types::ToolType;
 use crate::node_graph_executor::NodeGraphExecutor;
 
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::FlowType;
 use graph_craft::document::{DocumentNode, DocumentNodeImplementation, DocumentNodeMetadata, NodeId, NodeInput, NodeNetwork, NodeOutput};
+use graphene_core::raster::color::Color;
 use graphene_core::raster::BlendMode;
 use graphene_core::raster::ImageFrame;
 use graphene_core::renderer::ClickTarget;
@@ -39,6 +44,7 @@ pub struct DocumentMessageData<'a> {
 	pub ipp: &'a InputPreprocessorMessageHandler,
 	pub persistent_data: &'a PersistentData,
 	pub executor: &'a mut NodeGraphExecutor,
+	pub preferences: &'a PreferencesMessageHandler,
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -48,6 +54,8 @@ pub struct DocumentMessageHandler {
 	// ======================
 	//
 	#[serde(skip)]
+	guide_handler: GuideMessageHandler,
+	#[serde(skip)]
 	navigation_handler: NavigationMessageHandler,
 	#[serde(skip)]
 	node_graph_handler: NodeGraphMessageHandler,
@@ -55,6 +63,8 @@ pub struct DocumentMessageHandler {
 	overlays_message_handler: OverlaysMessageHandler,
 	#[serde(skip)]
 	properties_panel_message_handler: PropertiesPanelMessageHandler,
+	#[serde(skip)]
+	select_mode_handler: SelectModeMessageHandler,
 
 	// ============================================
 	// Fields that are saved in the document format
@@ -101,17 +111,17 @@ pub struct DocumentMessageHandler {
 	/// The current user choices for snapping behavior, including whether snapping is enabled at all.
 	#[serde(default = "default_snapping_state")]
 	pub snapping_state: SnappingState,
+	/// The guide lines that the user has dragged out of the rulers, and whether they're shown and locked.
+	#[serde(default = "default_guides")]
+	pub guides: Guides,
 
 	// =============================================
 	// Fields omitted from the saved document format
 	// =============================================
 	//
-	/// Stack of document network snapshots for previous history states.
-	#[serde(skip)]
-	document_undo_history: VecDeque<NodeNetwork>,
-	/// Stack of document network snapshots for future history states.
+	/// The previous and future history states of the document network, for undo and redo.
 	#[serde(skip)]
-	document_redo_history: VecDeque<NodeNetwork>,
+	document_history: DocumentHistory,
 	/// Hash of the document snapshot that was most recently saved to disk by the user.
 	#[serde(skip)]
 	saved_hash: Option<u64>,
@@ -129,6 +139,15 @@ pub struct DocumentMessageHandler {
 	/// This is updated frequently, whenever the information it's derived from changes.
 	#[serde(skip)]
 	pub metadata: DocumentMetadata,
+	/// The area of the document selected in Select Mode, which confines raster editing such as brush strokes.
+	#[serde(skip)]
+	pub selection_mask: SelectionMask,
+	/// The text layer being edited in the Text tool's textbox, whose text is left out of the artwork rendered in the viewport while the textbox shows it.
+	#[serde(skip)]
+	text_being_edited: Option<LayerNodeIdentifier>,
+	/// The guides as they were when the guide being dragged was picked up, which are placed into the history once the drag ends if it changed them.
+	#[serde(skip)]
+	guides_before_drag: Option<Vec<Guide>>,
 }
 
 impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessageHandler {
@@ -138,10 +157,37 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			ipp,
 			persistent_data,
 			executor,
+			preferences,
 		} = data;
+		self.document_history.set_budget(preferences.undo_history_budget_bytes());
 
 		match message {
 			// Sub-messages
+			DocumentMessage::Guide(message) => {
+				let guides_before = self.guides.guides.clone();
+				let was_dragging = self.guide_handler.is_dragging();
+
+				let data = GuideMessageData {
+					guides: &mut self.guides,
+					metadata: &self.metadata,
+					ipp,
+					document_mode: self.document_mode,
+				};
+				self.guide_handler.process_message(message, responses, data);
+
+				// Edits to the guides are recorded in the history like the transactions of the tools, beginning from the guides as they were before the edit.
+				// A drag is only recorded once it ends, so picking up a guide and dropping it in place (or aborting the drag) doesn't add an entry.
+				let dragging = self.guide_handler.is_dragging();
+				if !was_dragging && dragging {
+					self.guides_before_drag = Some(guides_before);
+				} else if !dragging {
+					let guides_before = if was_dragging { self.guides_before_drag.take().unwrap_or(guides_before) } else { guides_before };
+					if self.guides.guides != guides_before {
+						let network = self.network.clone();
+						self.backup_with_state(DocumentState { network, guides: guides_before }, responses);
+					}
+				}
+			}
 			DocumentMessage::Navigation(message) => {
 				let document_bounds = self.metadata().document_bounds_viewport_space();
 				let data = NavigationMessageData {
@@ -170,6 +216,19 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				self.properties_panel_message_handler
 					.process_message(message, responses, (persistent_data, properties_panel_message_handler_data));
 			}
+			DocumentMessage::SelectMode(message) => {
+				let data = SelectModeMessageData {
+					new_layer_parent: self.new_layer_parent(true),
+					selection_mask: &mut self.selection_mask,
+					network: &self.network,
+					metadata: &self.metadata,
+					selected_nodes: &self.selected_nodes,
+					ipp,
+					document_mode: self.document_mode,
+				};
+
+				self.select_mode_handler.process_message(message, responses, data);
+			}
 			DocumentMessage::NodeGraph(message) => {
 				self.node_graph_handler.process_message(
 					message,
@@ -202,6 +261,7 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			DocumentMessage::AbortTransaction => {
 				if !self.undo_in_progress {
 					self.undo(responses);
+					self.update_history_panel_widgets(responses);
 					responses.add(OverlaysMessage::Draw);
 				}
 			}
@@ -240,10 +300,53 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				}
 			}
 			DocumentMessage::BackupDocument { network } => self.backup_with_document(network, responses),
+			DocumentMessage::BooleanOperationSelectedLayers { operation } => {
+				let parent = self
+					.metadata()
+					.deepest_common_ancestor(self.selected_nodes.selected_layers(self.metadata()), false)
+					.unwrap_or(LayerNodeIdentifier::ROOT);
+
+				// Only the selected shapes directly inside the common parent are combined, ordered from the bottom of the stack to the top
+				let layers = parent
+					.children(self.metadata())
+					.filter(|&layer| self.selected_nodes.selected_unlocked_layers(self.metadata()).any(|selected| selected == layer))
+					.filter(|&layer| is_vector_layer(layer, &self.network))
+					.collect::<Vec<_>>();
+				if layers.len() < 2 {
+					return;
+				}
+
+				responses.add(DocumentMessage::StartTransaction);
+
+				let insert_index = DocumentMessageHandler::get_calculated_insert_index(&self.metadata, &self.selected_nodes, parent);
+				let id = NodeId(generate_uuid());
+				responses.add(GraphOperationMessage::NewBooleanOperationLayer {
+					id,
+					operation,
+					layers: layers.iter().rev().map(|layer| layer.to_node()).collect(),
+					parent,
+					insert_index,
+				});
+
+				// The original layers are removed from the stack while their content stays connected to the new layer
+				for layer in layers {
+					responses.add(DocumentMessage::DeleteLayer { id: layer.to_node() });
+				}
+
+				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![id] });
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(DocumentMessage::DocumentStructureChanged);
+			}
 			DocumentMessage::ClearArtboards => {
 				self.backup(responses);
 				responses.add(GraphOperationMessage::ClearArtboards);
 			}
+			DocumentMessage::ClearHistoryPanel => {
+				responses.add(LayoutMessage::SendLayout {
+					layout: Layout::WidgetLayout(Default::default()),
+					layout_target: LayoutTarget::HistoryPanel,
+				});
+			}
 			DocumentMessage::ClearLayersPanel => {
 				// Send an empty layer list
 				let data_buffer: RawBuffer = Self::default().serialize_root();
@@ -255,7 +358,7 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					layout_target: LayoutTarget::LayersPanelOptions,
 				});
 			}
-			DocumentMessage::CommitTransaction => (),
+			DocumentMessage::CommitTransaction => self.update_history_panel_widgets(responses),
 			DocumentMessage::CreateEmptyFolder => {
 				let id = NodeId(generate_uuid());
 
@@ -300,6 +403,12 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			}
 			DocumentMessage::DocumentHistoryBackward => self.undo_with_history(responses),
 			DocumentMessage::DocumentHistoryForward => self.redo_with_history(responses),
+			DocumentMessage::DocumentHistoryJump { steps } => {
+				let message = if steps < 0 { DocumentMessage::Undo } else { DocumentMessage::Redo };
+				for _ in 0..steps.unsigned_abs() {
+					responses.add(message.clone());
+				}
+			}
 			DocumentMessage::DocumentStructureChanged => {
 				self.update_layers_panel_options_bar_widgets(responses);
 
@@ -481,6 +590,33 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					responses.add(DocumentMessage::ImaginateGenerate);
 				}
 			}
+			DocumentMessage::ImaginateRescale { imaginate_node, resolution } => {
+				// The layer whose chain of nodes includes the Imaginate node
+				let Some(&node_id) = imaginate_node.first() else { return };
+				let Some(layer) = self.metadata().all_layers().find(|&layer| {
+					NodeGraphLayer::new(layer, &self.network)
+						.horizontal_layer_flow()
+						.skip(1)
+						.take_while(|(node, _)| !node.is_layer)
+						.any(|(_, id)| id == node_id)
+				}) else {
+					warn!("The Imaginate node isn't part of a layer, so there are no layer dimensions to set");
+					return;
+				};
+
+				// Images span the unit square, so the length of each axis of the transform is the layer's size along it
+				let transform = self.metadata().upstream_transform(layer.to_node());
+				let x_axis = transform.matrix2.x_axis.try_normalize().unwrap_or(DVec2::X) * resolution.x;
+				let y_axis = transform.matrix2.y_axis.try_normalize().unwrap_or(DVec2::Y) * resolution.y;
+
+				responses.add(DocumentMessage::StartTransaction);
+				responses.add(GraphOperationMessage::TransformSet {
+					layer,
+					transform: DAffine2::from_cols(x_axis, y_axis, transform.translation),
+					transform_in: TransformIn::Local,
+					skip_rerender: false,
+				});
+			}
 			DocumentMessage::ImportSvg {
 				id,
 				svg,
@@ -785,6 +921,13 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					responses.add(GraphOperationMessage::BlendModeSet { layer, blend_mode });
 				}
 			}
+			DocumentMessage::SetDocumentMode { document_mode } => {
+				self.document_mode = document_mode;
+
+				responses.add(BroadcastEvent::ToolAbort);
+				responses.add(GuideMessage::UpdateHints);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
 			DocumentMessage::SetOpacityForSelectedLayers { opacity } => {
 				self.backup(responses);
 				let opacity = opacity.clamp(0., 1.);
@@ -801,10 +944,17 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			DocumentMessage::SetRangeSelectionLayer { new_layer } => {
 				self.layer_range_selection_reference = new_layer;
 			}
+			DocumentMessage::SetTextBeingEdited { layer } => {
+				if self.text_being_edited != layer {
+					self.text_being_edited = layer;
+					responses.add(NodeGraphMessage::RunDocumentGraph);
+				}
+			}
 			DocumentMessage::SetSnapping {
 				snapping_enabled,
 				bounding_box_snapping,
 				geometry_snapping,
+				guide_snapping,
 			} => {
 				if let Some(state) = snapping_enabled {
 					self.snapping_state.snapping_enabled = state
@@ -859,6 +1009,9 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 						self.snapping_state.nodes.tangents = state
 					};
 				}
+				if let Some(state) = guide_snapping {
+					self.snapping_state.guides = state
+				};
 			}
 			DocumentMessage::SetViewMode { view_mode } => {
 				self.view_mode = view_mode;
@@ -1052,9 +1205,19 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			));
 			common.extend(self.node_graph_handler.actions_additional_if_node_graph_is_open());
 		}
+		// Additional actions if the document is in Guide Mode
+		if self.document_mode == DocumentMode::GuideMode {
+			common.extend(self.guide_handler.actions_additional_in_guide_mode());
+		}
+		// Additional actions if the document is in Select Mode
+		if self.document_mode == DocumentMode::SelectMode {
+			common.extend(self.select_mode_handler.actions_additional_in_select_mode());
+		}
 		// More additional actions
 		common.extend(self.node_graph_handler.actions());
 		common.extend(self.navigation_handler.actions());
+		common.extend(self.guide_handler.actions());
+		common.extend(self.select_mode_handler.actions());
 
 		common
 	}
@@ -1137,6 +1300,24 @@ impl DocumentMessageHandler {
 		&self.network
 	}
 
+	/// The network rendered in the viewport, which leaves out the text of the layer being edited in the Text tool's textbox without changing the document.
+	pub fn network_for_viewport(&self) -> NodeNetwork {
+		let mut network = self.network.clone();
+		let Some(layer) = self.text_being_edited else { return network };
+
+		let layer = NodeGraphLayer::new(layer, &self.network);
+		if let Some(fill) = layer.upstream_node_id_from_name("Fill").and_then(|node_id| network.nodes.get_mut(&node_id)) {
+			// The outlines of text in older documents are drawn without their fill
+			fill.visible = false;
+		} else if let Some(typography) = layer.upstream_node_id_from_name("Typography").and_then(|node_id| network.nodes.get_mut(&node_id)) {
+			if let Some(color) = typography.inputs.get_mut(8) {
+				*color = NodeInput::value(TaggedValue::Color(Color::TRANSPARENT), false);
+			}
+		}
+
+		network
+	}
+
 	pub fn metadata(&self) -> &DocumentMetadata {
 		&self.metadata
 	}
@@ -1148,7 +1329,9 @@ impl DocumentMessageHandler {
 	}
 
 	pub fn deserialize_document(serialized_content: &str) -> Result<Self, EditorError> {
-		serde_json::from_str(serialized_content).map_err(|e| EditorError::DocumentDeserialization(e.to_string()))
+		let mut document: Self = serde_json::from_str(serialized_content).map_err(|e| EditorError::DocumentDeserialization(e.to_string()))?;
+		upgrade_saved_nodes(document.network.nodes.values_mut());
+		Ok(document)
 	}
 
 	pub fn with_name(name: String, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> Self {
@@ -1234,11 +1417,14 @@ impl DocumentMessageHandler {
 
 	/// Places a document into the history system
 	fn backup_with_document(&mut self, network: NodeNetwork, responses: &mut VecDeque<Message>) {
-		self.document_redo_history.clear();
-		self.document_undo_history.push_back(network);
-		if self.document_undo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
-			self.document_undo_history.pop_front();
-		}
+		let guides = self.guides.guides.clone();
+		self.backup_with_state(DocumentState { network, guides }, responses);
+	}
+
+	/// Places a state of the document into the history system
+	fn backup_with_state(&mut self, state: DocumentState, responses: &mut VecDeque<Message>) {
+		self.document_history.backup(state);
+		self.update_history_panel_widgets(responses);
 
 		// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
 		responses.add(PortfolioMessage::UpdateOpenDocumentsList);
@@ -1261,47 +1447,47 @@ impl DocumentMessageHandler {
 	}
 
 	pub fn undo_with_history(&mut self, responses: &mut VecDeque<Message>) {
-		let Some(previous_network) = self.undo(responses) else { return };
+		let Some(previous_state) = self.undo(responses) else { return };
 
-		self.document_redo_history.push_back(previous_network);
-		if self.document_redo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
-			self.document_redo_history.pop_front();
-		}
+		self.document_history.push_redo(&self.network, &self.guides.guides, &previous_state);
+		self.update_history_panel_widgets(responses);
 	}
-	pub fn undo(&mut self, responses: &mut VecDeque<Message>) -> Option<NodeNetwork> {
+	pub fn undo(&mut self, responses: &mut VecDeque<Message>) -> Option<DocumentState> {
 		// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
 		responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 		// If there is no history return and don't broadcast SelectionChanged
-		let Some(network) = self.document_undo_history.pop_back() else { return None };
+		let previous_state = self.document_history.undo(&mut self.network, &mut self.guides.guides)?;
 
 		responses.add(BroadcastEvent::SelectionChanged);
+		if previous_state.guides != self.guides.guides {
+			responses.add(PortfolioMessage::UpdateDocumentWidgets);
+		}
 
-		let previous_network = std::mem::replace(&mut self.network, network);
-		Some(previous_network)
+		Some(previous_state)
 	}
-	pub fn redo(&mut self, responses: &mut VecDeque<Message>) -> Option<NodeNetwork> {
+	pub fn redo(&mut self, responses: &mut VecDeque<Message>) -> Option<DocumentState> {
 		// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
 		responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 		// If there is no history return and don't broadcast SelectionChanged
-		let Some(network) = self.document_redo_history.pop_back() else { return None };
+		let previous_state = self.document_history.redo(&mut self.network, &mut self.guides.guides)?;
 
 		responses.add(BroadcastEvent::SelectionChanged);
+		if previous_state.guides != self.guides.guides {
+			responses.add(PortfolioMessage::UpdateDocumentWidgets);
+		}
 
-		let previous_network = std::mem::replace(&mut self.network, network);
-		Some(previous_network)
+		Some(previous_state)
 	}
 	pub fn redo_with_history(&mut self, responses: &mut VecDeque<Message>) {
 		// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
-		let Some(previous_network) = self.redo(responses) else { return };
+		let Some(previous_state) = self.redo(responses) else { return };
 
-		self.document_undo_history.push_back(previous_network);
-		if self.document_undo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
-			self.document_undo_history.pop_front();
-		}
+		self.document_history.push_undo(previous_state);
+		self.update_history_panel_widgets(responses);
 	}
 
 	pub fn current_hash(&self) -> Option<u64> {
-		self.document_undo_history.iter().last().map(|network| network.current_hash())
+		self.document_history.current_hash()
 	}
 
 	pub fn is_auto_saved(&self) -> bool {
@@ -1429,29 +1615,123 @@ impl DocumentMessageHandler {
 	pub fn update_document_widgets(&self, responses: &mut VecDeque<Message>) {
 		// Document mode (dropdown menu at the left of the bar above the viewport, before the tool options)
 
-		let document_mode_layout = WidgetLayout::new(vec![LayoutGroup::Row {
-			widgets: vec![
-				DropdownInput::new(
-					vec![vec![
-						MenuListEntry::new(format!("{:?}", DocumentMode::DesignMode))
-							.label(DocumentMode::DesignMode.to_string())
-							.icon(DocumentMode::DesignMode.icon_name()),
-						MenuListEntry::new(format!("{:?}", DocumentMode::SelectMode))
-							.label(DocumentMode::SelectMode.to_string())
-							.icon(DocumentMode::SelectMode.icon_name())
-							.on_commit(|_| DialogMessage::RequestComingSoonDialog { issue: Some(330) }.into()),
-						MenuListEntry::new(format!("{:?}", DocumentMode::GuideMode))
-							.label(DocumentMode::GuideMode.to_string())
-							.icon(DocumentMode::GuideMode.icon_name())
-							.on_commit(|_| DialogMessage::RequestComingSoonDialog { issue: Some(331) }.into()),
-					]])
-					.selected_index(Some(self.document_mode as u32))
-					.draw_icon(true)
-					.interactive(false) // TODO: set to true when dialogs are not spawned
+		let mut document_mode_widgets = vec![
+			DropdownInput::new(vec![vec![
+				MenuListEntry::new(format!("{:?}", DocumentMode::DesignMode))
+					.label(DocumentMode::DesignMode.to_string())
+					.icon(DocumentMode::DesignMode.icon_name())
+					.on_commit(|_| {
+						DocumentMessage::SetDocumentMode {
+							document_mode: DocumentMode::DesignMode,
+						}
+						.into()
+					}),
+				MenuListEntry::new(format!("{:?}", DocumentMode::SelectMode))
+					.label(DocumentMode::SelectMode.to_string())
+					.icon(DocumentMode::SelectMode.icon_name())
+					.on_commit(|_| {
+						DocumentMessage::SetDocumentMode {
+							document_mode: DocumentMode::SelectMode,
+						}
+						.into()
+					}),
+				MenuListEntry::new(format!("{:?}", DocumentMode::GuideMode))
+					.label(DocumentMode::GuideMode.to_string())
+					.icon(DocumentMode::GuideMode.icon_name())
+					.on_commit(|_| {
+						DocumentMessage::SetDocumentMode {
+							document_mode: DocumentMode::GuideMode,
+						}
+						.into()
+					}),
+			]])
+			.selected_index(Some(self.document_mode as u32))
+			.draw_icon(true)
+			.widget_holder(),
+			Separator::new(SeparatorType::Section).widget_holder(),
+		];
+		if self.document_mode == DocumentMode::SelectMode {
+			let no_selection = self.selection_mask.is_empty();
+			document_mode_widgets.extend([
+				RadioInput::new(vec![
+					RadioEntryData::new("rectangle")
+						.icon("VectorRectangleTool")
+						.tooltip("Rectangular Marquee")
+						.on_update(|_| SelectModeMessage::SetShape { shape: SelectionShape::Rectangle }.into()),
+					RadioEntryData::new("ellipse")
+						.icon("VectorEllipseTool")
+						.tooltip("Elliptical Marquee")
+						.on_update(|_| SelectModeMessage::SetShape { shape: SelectionShape::Ellipse }.into()),
+					RadioEntryData::new("lasso")
+						.icon("VectorFreehandTool")
+						.tooltip("Lasso")
+						.on_update(|_| SelectModeMessage::SetShape { shape: SelectionShape::Lasso }.into()),
+					RadioEntryData::new("magic_wand")
+						.icon("NodeMagicWand")
+						.tooltip("Magic Wand")
+						.on_update(|_| SelectModeMessage::SetShape { shape: SelectionShape::MagicWand }.into()),
+				])
+				.selected_index(Some(self.select_mode_handler.shape() as u32))
+				.widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+			]);
+			if self.select_mode_handler.shape() == SelectionShape::MagicWand {
+				let options = self.select_mode_handler.magic_wand_options();
+				let contiguous_tooltip = "Only select pixels connected to the one clicked on, instead of every matching pixel of the image";
+				document_mode_widgets.extend([
+					NumberInput::new(Some(options.tolerance))
+						.label("Tolerance")
+						.min(0.)
+						.max(100.)
+						.mode_range()
+						.unit("%")
+						.tooltip("How different the colors of the selected pixels may be from the one clicked on")
+						.on_update(|number_input: &NumberInput| {
+							SelectModeMessage::SetMagicWandTolerance {
+								tolerance: number_input.value.unwrap(),
+							}
+							.into()
+						})
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					CheckboxInput::new(options.contiguous)
+						.tooltip(contiguous_tooltip)
+						.on_update(|&CheckboxInput { checked, .. }| SelectModeMessage::SetMagicWandContiguous { contiguous: checked }.into())
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					TextLabel::new("Contiguous").tooltip(contiguous_tooltip).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+				]);
+			}
+			document_mode_widgets.extend([
+				TextButton::new("Deselect")
+					.tooltip("Clear the selection")
+					.tooltip_shortcut(action_keys!(SelectModeMessageDiscriminant::Deselect))
+					.disabled(no_selection)
+					.on_update(|_| SelectModeMessage::Deselect.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("Mask Layers")
+					.tooltip("Mask the selected raster layers to the selection with a Mask node")
+					.disabled(no_selection)
+					.on_update(|_| SelectModeMessage::MaskSelectedLayers.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("To Path")
+					.tooltip("Create a vector layer outlining the selection")
+					.disabled(no_selection)
+					.on_update(|_| SelectModeMessage::ConvertToPath.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("To Layer")
+					.tooltip("Create a raster layer which is white inside the selection and black outside, for use as a mask")
+					.disabled(no_selection)
+					.on_update(|_| SelectModeMessage::ConvertToLayer.into())
 					.widget_holder(),
 				Separator::new(SeparatorType::Section).widget_holder(),
-			],
-		}]);
+			]);
+		}
+		let document_mode_layout = WidgetLayout::new(vec![LayoutGroup::Row { widgets: document_mode_widgets }]);
 
 		responses.add(LayoutMessage::SendLayout {
 			layout: Layout::WidgetLayout(document_mode_layout),
@@ -1490,6 +1770,7 @@ impl DocumentMessageHandler {
 						snapping_enabled: Some(snapping_enabled),
 						bounding_box_snapping: None,
 						geometry_snapping: None,
+						guide_snapping: None,
 					}
 					.into()
 				})
@@ -1526,6 +1807,7 @@ impl DocumentMessageHandler {
 												corners: if enum_type == BoundingBoxSnapTarget::Corner { Some(input.checked) } else { None },
 											}),
 											geometry_snapping: None,
+											guide_snapping: None,
 										}
 										.into()
 									})
@@ -1549,6 +1831,7 @@ impl DocumentMessageHandler {
 														anchors: Some(input.checked),
 														..Default::default()
 													}),
+													guide_snapping: None,
 												}
 												.into()
 											})
@@ -1582,6 +1865,7 @@ impl DocumentMessageHandler {
 														tangents: if enum_type == GeometrySnapTarget::Tangent { Some(input.checked) } else { None },
 														path_intersections: if enum_type == GeometrySnapTarget::Intersection { Some(input.checked) } else { None },
 													}),
+													guide_snapping: None,
 												}
 												.into()
 											})
@@ -1592,10 +1876,60 @@ impl DocumentMessageHandler {
 							),
 						),
 					)
+					.chain([
+						LayoutGroup::Row {
+							widgets: vec![TextLabel::new(SnappingOptions::Guides.to_string()).widget_holder()],
+						},
+						LayoutGroup::Row {
+							widgets: vec![
+								CheckboxInput::new(snapping_state.guides)
+									.on_update(|input: &CheckboxInput| {
+										DocumentMessage::SetSnapping {
+											snapping_enabled: None,
+											bounding_box_snapping: None,
+											geometry_snapping: None,
+											guide_snapping: Some(input.checked),
+										}
+										.into()
+									})
+									.widget_holder(),
+								TextLabel::new("Guide Lines").widget_holder(),
+							],
+						},
+					])
 					.collect(),
 				)
 				.widget_holder(),
 			Separator::new(SeparatorType::Related).widget_holder(),
+			CheckboxInput::new(self.guides.visible)
+				.icon("ViewportGuideMode")
+				.tooltip("Guides")
+				.tooltip_shortcut(action_keys!(GuideMessageDiscriminant::ToggleVisibility))
+				.on_update(|optional_input: &CheckboxInput| GuideMessage::SetVisibility { visible: optional_input.checked }.into())
+				.widget_holder(),
+			PopoverButton::new()
+				.popover_layout(vec![
+					LayoutGroup::Row {
+						widgets: vec![TextLabel::new("Guides").bold(true).widget_holder()],
+					},
+					LayoutGroup::Row {
+						widgets: vec![
+							CheckboxInput::new(self.guides.locked)
+								.on_update(|input: &CheckboxInput| GuideMessage::SetLocked { locked: input.checked }.into())
+								.widget_holder(),
+							TextLabel::new("Lock Guides").widget_holder(),
+						],
+					},
+					LayoutGroup::Row {
+						widgets: vec![TextButton::new("Clear Guides")
+							.tooltip("Remove all guides from the document")
+							.disabled(self.guides.guides.is_empty())
+							.on_update(|_| GuideMessage::DeleteAll.into())
+							.widget_holder()],
+					},
+				])
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
 			CheckboxInput::new(self.snapping_state.grid_snapping)
 				.icon("Grid")
 				.tooltip("Grid")
@@ -1619,11 +1953,12 @@ impl DocumentMessageHandler {
 				RadioEntryData::new("pixels")
 					.icon("ViewModePixels")
 					.tooltip("View Mode: Pixels")
-					.on_update(|_| DialogMessage::RequestComingSoonDialog { issue: Some(320) }.into()),
+					.on_update(|_| DocumentMessage::SetViewMode { view_mode: ViewMode::Pixels }.into()),
 			])
 			.selected_index(match self.view_mode {
 				ViewMode::Normal => Some(0),
-				_ => Some(1),
+				ViewMode::Outline => Some(1),
+				ViewMode::Pixels => Some(2),
 			})
 			.widget_holder(),
 			PopoverButton::new()
@@ -1874,6 +2209,29 @@ impl DocumentMessageHandler {
 		});
 	}
 
+	pub fn update_history_panel_widgets(&self, responses: &mut VecDeque<Message>) {
+		let history_states = self.document_history.states(&self.network, &self.guides.guides).into_iter().map(|state| {
+			let steps = state.steps;
+			let tooltip = match steps {
+				0 => "Current state",
+				_ if steps < 0 => "Undo to this state",
+				_ => "Redo to this state",
+			};
+			LayoutGroup::Row {
+				widgets: vec![TextButton::new(state.name)
+					.emphasized(steps == 0)
+					.tooltip(tooltip)
+					.on_update(move |_| DocumentMessage::DocumentHistoryJump { steps }.into())
+					.widget_holder()],
+			}
+		});
+
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout::new(history_states.collect())),
+			layout_target: LayoutTarget::HistoryPanel,
+		});
+	}
+
 	pub fn selected_layers_reorder(&mut self, relative_index_offset: isize, responses: &mut VecDeque<Message>) {
 		self.backup(responses);
 
@@ -1919,10 +2277,12 @@ impl Default for DocumentMessageHandler {
 			// ======================
 			// Child message handlers
 			// ======================
+			guide_handler: GuideMessageHandler::default(),
 			navigation_handler: NavigationMessageHandler::default(),
 			node_graph_handler: NodeGraphMessageHandler::default(),
 			overlays_message_handler: OverlaysMessageHandler::default(),
 			properties_panel_message_handler: PropertiesPanelMessageHandler::default(),
+			select_mode_handler: SelectModeMessageHandler::default(),
 			// ============================================
 			// Fields that are saved in the document format
 			// ============================================
@@ -1939,15 +2299,18 @@ impl Default for DocumentMessageHandler {
 			// =============================================
 			// Fields omitted from the saved document format
 			// =============================================
-			document_undo_history: VecDeque::new(),
-			document_redo_history: VecDeque::new(),
+			document_history: DocumentHistory::default(),
 			saved_hash: None,
 			auto_saved_hash: None,
 			undo_in_progress: false,
 			graph_view_overlay_open: false,
 			snapping_state: SnappingState::default(),
+			guides: Guides::default(),
 			layer_range_selection_reference: None,
 			metadata: Default::default(),
+			selection_mask: SelectionMask::default(),
+			text_being_edited: None,
+			guides_before_drag: None,
 		}
 	}
 }
@@ -2000,6 +2363,10 @@ fn default_graph_view_overlay_open() -> bool {
 fn default_snapping_state() -> SnappingState {
 	DocumentMessageHandler::default().snapping_state
 }
+#[inline(always)]
+fn default_guides() -> Guides {
+	DocumentMessageHandler::default().guides
+}
 
 fn root_network() -> NodeNetwork {
 	{
@@ -3,7 +3,7 @@ use super::utility_types::VectorDataModification;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::prelude::*;
 
-use bezier_rs::Subpath;
+use bezier_rs::{BooleanOperation, Subpath};
 use graph_craft::document::DocumentNode;
 use graph_craft::document::NodeId;
 use graphene_core::raster::BlendMode;
@@ -33,6 +33,14 @@ pub enum GraphOperationMessage {
 		layer: LayerNodeIdentifier,
 		fill: Fill,
 	},
+	FloodFill {
+		layer: LayerNodeIdentifier,
+		seed: DVec2,
+		color: Color,
+		tolerance: f64,
+		contiguous: bool,
+		antialias: bool,
+	},
 	InsertLayerAtStackIndex {
 		layer_id: NodeId,
 		parent: NodeId,
@@ -91,10 +99,21 @@ pub enum GraphOperationMessage {
 		layer: LayerNodeIdentifier,
 		strokes: Vec<BrushStroke>,
 	},
+	MaskSet {
+		layer: LayerNodeIdentifier,
+		stencil: ImageFrame<Color>,
+	},
 	NewArtboard {
 		id: NodeId,
 		artboard: Artboard,
 	},
+	NewBooleanOperationLayer {
+		id: NodeId,
+		operation: BooleanOperation,
+		layers: Vec<NodeId>,
+		parent: LayerNodeIdentifier,
+		insert_index: isize,
+	},
 	NewBitmapLayer {
 		id: NodeId,
 		image_frame: ImageFrame<Color>,
@@ -119,6 +138,7 @@ pub enum GraphOperationMessage {
 		text: String,
 		font: Font,
 		size: f64,
+		color: Color,
 		parent: LayerNodeIdentifier,
 		insert_index: isize,
 	},
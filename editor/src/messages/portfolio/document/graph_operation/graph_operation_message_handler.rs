@@ -4,13 +4,15 @@ use crate::messages::portfolio::document::node_graph::document_node_types::resol
 use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
 use crate::messages::portfolio::document::utility_types::nodes::{CollapsedLayers, SelectedNodes};
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils::is_vector_layer;
 
 use bezier_rs::{ManipulatorGroup, Subpath};
 use graph_craft::document::{generate_uuid, NodeId, NodeInput, NodeNetwork};
+use graphene_core::raster::{Image, ImageFrame};
 use graphene_core::renderer::Quad;
 use graphene_core::text::Font;
 use graphene_core::uuid::ManipulatorGroupId;
-use graphene_core::vector::style::{Fill, Gradient, GradientType, LineCap, LineJoin, Stroke};
+use graphene_core::vector::style::{Fill, Gradient, GradientSpreadMethod, GradientType, LineCap, LineJoin, Stroke};
 use graphene_core::Color;
 
 use glam::{DAffine2, DVec2, IVec2};
@@ -121,6 +123,18 @@ impl MessageHandler<GraphOperationMessage, GraphOperationMessageData<'_>> for Gr
 					modify_inputs.fill_set(fill);
 				}
 			}
+			GraphOperationMessage::FloodFill {
+				layer,
+				seed,
+				color,
+				tolerance,
+				contiguous,
+				antialias,
+			} => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer.to_node(), document_network, document_metadata, node_graph, responses) {
+					modify_inputs.flood_fill(seed, color, tolerance, contiguous, antialias);
+				}
+			}
 			GraphOperationMessage::InsertLayerAtStackIndex { layer_id, parent, insert_index } => {
 				let (post_node_id, pre_node_id, post_node_input_index) = ModifyInputsContext::get_post_node_with_index(&document_network, parent, insert_index);
 
@@ -276,6 +290,11 @@ impl MessageHandler<GraphOperationMessage, GraphOperationMessageData<'_>> for Gr
 					modify_inputs.brush_modify(strokes);
 				}
 			}
+			GraphOperationMessage::MaskSet { layer, stencil } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer.to_node(), document_network, document_metadata, node_graph, responses) {
+					modify_inputs.mask_set(stencil);
+				}
+			}
 			GraphOperationMessage::MoveSelectedSiblingsToChild { new_parent } => {
 				let group_layer = LayerNodeIdentifier::new(new_parent, &document_network);
 				let Some(group_parent) = group_layer.parent(&document_metadata) else {
@@ -419,6 +438,23 @@ impl MessageHandler<GraphOperationMessage, GraphOperationMessageData<'_>> for Gr
 
 				load_network_structure(document_network, document_metadata, selected_nodes, collapsed);
 			}
+			GraphOperationMessage::NewBooleanOperationLayer {
+				id,
+				operation,
+				layers,
+				parent,
+				insert_index,
+			} => {
+				// Boolean operations only combine vector data, so there's nothing to make without at least two vector layers
+				let vector_layers = layers.iter().filter(|&&layer| is_vector_layer(LayerNodeIdentifier::new_unchecked(layer), document_network)).count();
+				if vector_layers >= 2 {
+					let mut modify_inputs = ModifyInputsContext::new(document_network, document_metadata, node_graph, responses);
+					if let Some(layer) = modify_inputs.create_layer_with_insert_index(id, insert_index, parent) {
+						modify_inputs.insert_boolean_operation(operation, &layers, layer);
+					}
+					load_network_structure(document_network, document_metadata, selected_nodes, collapsed);
+				}
+			}
 			GraphOperationMessage::NewVectorLayer { id, subpaths, parent, insert_index } => {
 				let mut modify_inputs = ModifyInputsContext::new(document_network, document_metadata, node_graph, responses);
 				if let Some(layer) = modify_inputs.create_layer_with_insert_index(id, insert_index, parent) {
@@ -431,12 +467,13 @@ impl MessageHandler<GraphOperationMessage, GraphOperationMessageData<'_>> for Gr
 				text,
 				font,
 				size,
+				color,
 				parent,
 				insert_index,
 			} => {
 				let mut modify_inputs = ModifyInputsContext::new(document_network, document_metadata, node_graph, responses);
 				if let Some(layer) = modify_inputs.create_layer_with_insert_index(id, insert_index, parent) {
-					modify_inputs.insert_text(text, font, size, layer);
+					modify_inputs.insert_text(text, font, size, color, layer);
 				}
 				load_network_structure(document_network, document_metadata, selected_nodes, collapsed);
 			}
@@ -482,8 +519,16 @@ impl MessageHandler<GraphOperationMessage, GraphOperationMessageData<'_>> for Gr
 				};
 				let mut modify_inputs = ModifyInputsContext::new(document_network, document_metadata, node_graph, responses);
 
-				import_usvg_node(&mut modify_inputs, &usvg::Node::Group(Box::new(tree.root)), transform, id, parent, insert_index);
+				let mut image_errors = Vec::new();
+				import_usvg_node(&mut modify_inputs, &usvg::Node::Group(Box::new(tree.root)), transform, id, parent, insert_index, &mut image_errors);
 				load_network_structure(document_network, document_metadata, selected_nodes, collapsed);
+
+				if !image_errors.is_empty() {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Some images in the SVG couldn't be imported".to_string(),
+						description: image_errors.join("\n"),
+					});
+				}
 			}
 		}
 	}
@@ -506,7 +551,70 @@ fn usvg_transform(c: usvg::Transform) -> DAffine2 {
 	DAffine2::from_cols_array(&[c.sx as f64, c.ky as f64, c.kx as f64, c.sy as f64, c.tx as f64, c.ty as f64])
 }
 
-fn import_usvg_node(modify_inputs: &mut ModifyInputsContext, node: &usvg::Node, transform: DAffine2, id: NodeId, parent: LayerNodeIdentifier, insert_index: isize) {
+/// Decodes the PNG or JPEG data of an image embedded in an SVG.
+fn usvg_image_frame(kind: &usvg::ImageKind) -> Result<ImageFrame<Color>, String> {
+	let (data, format) = match kind {
+		usvg::ImageKind::PNG(data) => (data, image::ImageFormat::Png),
+		usvg::ImageKind::JPEG(data) => (data, image::ImageFormat::Jpeg),
+		usvg::ImageKind::GIF(_) => return Err("GIF images aren't supported".to_string()),
+		usvg::ImageKind::SVG(_) => return Err("Nested SVG images aren't supported".to_string()),
+	};
+	let decoded = image::load_from_memory_with_format(data, format)
+		.map_err(|error| format!("An embedded {format:?} image couldn't be decoded: {error}"))?
+		.to_rgba8();
+	let (width, height) = decoded.dimensions();
+	if width == 0 || height == 0 {
+		return Err(format!("An embedded {format:?} image is empty"));
+	}
+
+	Ok(ImageFrame {
+		image: Image::from_image_data(decoded.as_raw(), width, height),
+		..Default::default()
+	})
+}
+
+/// Maps the image's unit square to the area it occupies within its view box according to `preserveAspectRatio`.
+/// Images using `slice` are scaled to cover the view box but are not clipped to it.
+fn usvg_image_fit(view_box: &usvg::ViewBox, image_size: DVec2) -> DAffine2 {
+	let rect = view_box.rect;
+	let position = DVec2::new(rect.x() as f64, rect.y() as f64);
+	let size = DVec2::new(rect.width() as f64, rect.height() as f64);
+
+	let alignment = match view_box.aspect.align {
+		usvg::Align::None => return DAffine2::from_scale_angle_translation(size, 0., position),
+		usvg::Align::XMinYMin => DVec2::new(0., 0.),
+		usvg::Align::XMidYMin => DVec2::new(0.5, 0.),
+		usvg::Align::XMaxYMin => DVec2::new(1., 0.),
+		usvg::Align::XMinYMid => DVec2::new(0., 0.5),
+		usvg::Align::XMidYMid => DVec2::new(0.5, 0.5),
+		usvg::Align::XMaxYMid => DVec2::new(1., 0.5),
+		usvg::Align::XMinYMax => DVec2::new(0., 1.),
+		usvg::Align::XMidYMax => DVec2::new(0.5, 1.),
+		usvg::Align::XMaxYMax => DVec2::new(1., 1.),
+	};
+	let scale = if view_box.aspect.slice {
+		(size / image_size).max_element()
+	} else {
+		(size / image_size).min_element()
+	};
+	let fitted_size = image_size * scale;
+
+	DAffine2::from_scale_angle_translation(fitted_size, 0., position + (size - fitted_size) * alignment)
+}
+
+/// Adds a layer for the node and its descendants. Images which can't be decoded are left out, without a layer, and their errors are collected.
+fn import_usvg_node(modify_inputs: &mut ModifyInputsContext, node: &usvg::Node, transform: DAffine2, id: NodeId, parent: LayerNodeIdentifier, insert_index: isize, image_errors: &mut Vec<String>) {
+	let image_frame = match node {
+		usvg::Node::Image(image) => match usvg_image_frame(&image.kind) {
+			Ok(image_frame) => Some(image_frame),
+			Err(error) => {
+				image_errors.push(error);
+				return;
+			}
+		},
+		_ => None,
+	};
+
 	let Some(layer) = modify_inputs.create_layer_with_insert_index(id, insert_index, parent) else {
 		return;
 	};
@@ -514,7 +622,7 @@ fn import_usvg_node(modify_inputs: &mut ModifyInputsContext, node: &usvg::Node,
 	match node {
 		usvg::Node::Group(group) => {
 			for child in &group.children {
-				import_usvg_node(modify_inputs, child, transform, NodeId(generate_uuid()), LayerNodeIdentifier::new_unchecked(layer), -1);
+				import_usvg_node(modify_inputs, child, transform, NodeId(generate_uuid()), LayerNodeIdentifier::new_unchecked(layer), -1, image_errors);
 			}
 			modify_inputs.layer_node = Some(layer);
 		}
@@ -542,43 +650,64 @@ fn import_usvg_node(modify_inputs: &mut ModifyInputsContext, node: &usvg::Node,
 				bounds_transform,
 				transformed_bound_transform,
 			);
-			apply_usvg_stroke(&path.stroke, modify_inputs);
+			apply_usvg_stroke(
+				&path.stroke,
+				modify_inputs,
+				transform * usvg_transform(node.abs_transform()),
+				bounds_transform,
+				transformed_bound_transform,
+			);
 		}
-		usvg::Node::Image(_image) => {
-			warn!("Skip image")
+		usvg::Node::Image(image) => {
+			let Some(image_frame) = image_frame else { return };
+			let image_size = DVec2::new(image_frame.image.width as f64, image_frame.image.height as f64);
+			modify_inputs.insert_image_data(image_frame, layer);
+
+			// The layer's pivot is the center of the image's unit square
+			let center = DAffine2::from_translation(DVec2::splat(0.5));
+			let fit = usvg_image_fit(&image.view_box, image_size);
+			modify_inputs.modify_inputs("Transform", true, |inputs, _node_id, _metadata| {
+				transform_utils::update_transform(inputs, center.inverse() * transform * usvg_transform(node.abs_transform()) * fit * center);
+			});
 		}
 		usvg::Node::Text(text) => {
 			let font = Font::new(crate::consts::DEFAULT_FONT_FAMILY.to_string(), crate::consts::DEFAULT_FONT_STYLE.to_string());
-			modify_inputs.insert_text(text.chunks.iter().map(|chunk| chunk.text.clone()).collect(), font, 24., layer);
-			modify_inputs.fill_set(Fill::Solid(Color::BLACK));
+			modify_inputs.insert_text(text.chunks.iter().map(|chunk| chunk.text.clone()).collect(), font, 24., Color::BLACK, layer);
 		}
 	}
 }
 
-fn apply_usvg_stroke(stroke: &Option<usvg::Stroke>, modify_inputs: &mut ModifyInputsContext) {
+fn apply_usvg_stroke(stroke: &Option<usvg::Stroke>, modify_inputs: &mut ModifyInputsContext, transform: DAffine2, bounds_transform: DAffine2, transformed_bound_transform: DAffine2) {
 	if let Some(stroke) = stroke {
-		if let usvg::Paint::Color(color) = &stroke.paint {
-			modify_inputs.stroke_set(Stroke {
-				color: Some(usvg_color(*color, stroke.opacity.get())),
-				weight: stroke.width.get() as f64,
-				dash_lengths: stroke.dasharray.as_ref().map(|lengths| lengths.iter().map(|&length| length as f64).collect()).unwrap_or_default(),
-				dash_offset: stroke.dashoffset as f64,
-				line_cap: match stroke.linecap {
-					usvg::LineCap::Butt => LineCap::Butt,
-					usvg::LineCap::Round => LineCap::Round,
-					usvg::LineCap::Square => LineCap::Square,
-				},
-				line_join: match stroke.linejoin {
-					usvg::LineJoin::Miter => LineJoin::Miter,
-					usvg::LineJoin::MiterClip => LineJoin::Miter,
-					usvg::LineJoin::Round => LineJoin::Round,
-					usvg::LineJoin::Bevel => LineJoin::Bevel,
-				},
-				line_join_miter_limit: stroke.miterlimit.get() as f64,
-			})
-		} else {
-			warn!("Skip non-solid stroke")
-		}
+		let (color, gradient) = match &stroke.paint {
+			usvg::Paint::Color(color) => (Some(usvg_color(*color, stroke.opacity.get())), None),
+			paint => match usvg_gradient(paint, transform, bounds_transform, transformed_bound_transform) {
+				Some(gradient) => (None, Some(gradient)),
+				None => {
+					warn!("Skip pattern stroke");
+					return;
+				}
+			},
+		};
+		modify_inputs.stroke_set(Stroke {
+			color,
+			gradient,
+			weight: stroke.width.get() as f64,
+			dash_lengths: stroke.dasharray.as_ref().map(|lengths| lengths.iter().map(|&length| length as f64).collect()).unwrap_or_default(),
+			dash_offset: stroke.dashoffset as f64,
+			line_cap: match stroke.linecap {
+				usvg::LineCap::Butt => LineCap::Butt,
+				usvg::LineCap::Round => LineCap::Round,
+				usvg::LineCap::Square => LineCap::Square,
+			},
+			line_join: match stroke.linejoin {
+				usvg::LineJoin::Miter => LineJoin::Miter,
+				usvg::LineJoin::MiterClip => LineJoin::Miter,
+				usvg::LineJoin::Round => LineJoin::Round,
+				usvg::LineJoin::Bevel => LineJoin::Bevel,
+			},
+			line_join_miter_limit: stroke.miterlimit.get() as f64,
+		})
 	}
 }
 
@@ -586,57 +715,77 @@ fn apply_usvg_fill(fill: &Option<usvg::Fill>, modify_inputs: &mut ModifyInputsCo
 	if let Some(fill) = &fill {
 		modify_inputs.fill_set(match &fill.paint {
 			usvg::Paint::Color(color) => Fill::solid(usvg_color(*color, fill.opacity.get())),
-			usvg::Paint::LinearGradient(linear) => {
-				let local = [DVec2::new(linear.x1 as f64, linear.y1 as f64), DVec2::new(linear.x2 as f64, linear.y2 as f64)];
-
-				let to_doc_transform = if linear.base.units == usvg::Units::UserSpaceOnUse {
-					transform
-				} else {
-					transformed_bound_transform
-				};
-				let to_doc = to_doc_transform * usvg_transform(linear.transform);
-
-				let document = [to_doc.transform_point2(local[0]), to_doc.transform_point2(local[1])];
-				let layer = [transform.inverse().transform_point2(document[0]), transform.inverse().transform_point2(document[1])];
-
-				let [start, end] = [bounds_transform.inverse().transform_point2(layer[0]), bounds_transform.inverse().transform_point2(layer[1])];
-
-				Fill::Gradient(Gradient {
-					start,
-					end,
-					transform: DAffine2::IDENTITY,
-					gradient_type: GradientType::Linear,
-					positions: linear.stops.iter().map(|stop| (stop.offset.get() as f64, usvg_color(stop.color, stop.opacity.get()))).collect(),
-				})
-			}
-			usvg::Paint::RadialGradient(radial) => {
-				let local = [DVec2::new(radial.cx as f64, radial.cy as f64), DVec2::new(radial.fx as f64, radial.fy as f64)];
-
-				let to_doc_transform = if radial.base.units == usvg::Units::UserSpaceOnUse {
-					transform
-				} else {
-					transformed_bound_transform
-				};
-				let to_doc = to_doc_transform * usvg_transform(radial.transform);
-
-				let document = [to_doc.transform_point2(local[0]), to_doc.transform_point2(local[1])];
-				let layer = [transform.inverse().transform_point2(document[0]), transform.inverse().transform_point2(document[1])];
+			paint => match usvg_gradient(paint, transform, bounds_transform, transformed_bound_transform) {
+				Some(gradient) => Fill::Gradient(gradient),
+				None => {
+					warn!("Skip pattern");
+					return;
+				}
+			},
+		});
+	}
+}
 
-				let [start, end] = [bounds_transform.inverse().transform_point2(layer[0]), bounds_transform.inverse().transform_point2(layer[1])];
+/// Converts a usvg gradient paint into a [`Gradient`] positioned relative to the bounds of the path it is painted on, or `None` for other kinds of paint.
+fn usvg_gradient(paint: &usvg::Paint, transform: DAffine2, bounds_transform: DAffine2, transformed_bound_transform: DAffine2) -> Option<Gradient> {
+	match paint {
+		usvg::Paint::LinearGradient(linear) => {
+			let local = [DVec2::new(linear.x1 as f64, linear.y1 as f64), DVec2::new(linear.x2 as f64, linear.y2 as f64)];
+
+			let to_doc_transform = if linear.base.units == usvg::Units::UserSpaceOnUse {
+				transform
+			} else {
+				transformed_bound_transform
+			};
+			let to_doc = to_doc_transform * usvg_transform(linear.transform);
+
+			let document = [to_doc.transform_point2(local[0]), to_doc.transform_point2(local[1])];
+			let layer = [transform.inverse().transform_point2(document[0]), transform.inverse().transform_point2(document[1])];
+
+			let [start, end] = [bounds_transform.inverse().transform_point2(layer[0]), bounds_transform.inverse().transform_point2(layer[1])];
+
+			Some(Gradient {
+				start,
+				end,
+				transform: DAffine2::IDENTITY,
+				gradient_type: GradientType::Linear,
+				spread_method: usvg_spread_method(linear.spread_method),
+				positions: linear.stops.iter().map(|stop| (stop.offset.get() as f64, usvg_color(stop.color, stop.opacity.get()))).collect(),
+			})
+		}
+		usvg::Paint::RadialGradient(radial) => {
+			let local = [DVec2::new(radial.cx as f64, radial.cy as f64), DVec2::new(radial.fx as f64, radial.fy as f64)];
+
+			let to_doc_transform = if radial.base.units == usvg::Units::UserSpaceOnUse {
+				transform
+			} else {
+				transformed_bound_transform
+			};
+			let to_doc = to_doc_transform * usvg_transform(radial.transform);
+
+			let document = [to_doc.transform_point2(local[0]), to_doc.transform_point2(local[1])];
+			let layer = [transform.inverse().transform_point2(document[0]), transform.inverse().transform_point2(document[1])];
+
+			let [start, end] = [bounds_transform.inverse().transform_point2(layer[0]), bounds_transform.inverse().transform_point2(layer[1])];
+
+			Some(Gradient {
+				start,
+				end,
+				transform: DAffine2::IDENTITY,
+				gradient_type: GradientType::Radial,
+				spread_method: usvg_spread_method(radial.spread_method),
+				positions: radial.stops.iter().map(|stop| (stop.offset.get() as f64, usvg_color(stop.color, stop.opacity.get()))).collect(),
+			})
+		}
+		usvg::Paint::Color(_) | usvg::Paint::Pattern(_) => None,
+	}
+}
 
-				Fill::Gradient(Gradient {
-					start,
-					end,
-					transform: DAffine2::IDENTITY,
-					gradient_type: GradientType::Radial,
-					positions: radial.stops.iter().map(|stop| (stop.offset.get() as f64, usvg_color(stop.color, stop.opacity.get()))).collect(),
-				})
-			}
-			usvg::Paint::Pattern(_) => {
-				warn!("Skip pattern");
-				return;
-			}
-		});
+fn usvg_spread_method(spread_method: usvg::SpreadMethod) -> GradientSpreadMethod {
+	match spread_method {
+		usvg::SpreadMethod::Pad => GradientSpreadMethod::Pad,
+		usvg::SpreadMethod::Reflect => GradientSpreadMethod::Reflect,
+		usvg::SpreadMethod::Repeat => GradientSpreadMethod::Repeat,
 	}
 }
 
@@ -683,3 +832,107 @@ fn convert_usvg_path(path: &usvg::Path) -> Vec<Subpath<ManipulatorGroupId>> {
 	subpaths.push(Subpath::new(groups, false));
 	subpaths
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::application::Editor;
+	use crate::test_utils::EditorTestUtils;
+
+	/// A 4x4 PNG whose header is intact but which has no image data
+	const TRUNCATED_PNG: &str = "iVBORw0KGgoAAAANSUhEUgAAAAQAAAAECAYAAACp8Z5+AAAAAElFTkSuQmCC";
+
+	#[test]
+	fn decode_embedded_images() {
+		let mut png = std::io::Cursor::new(Vec::new());
+		image::RgbaImage::from_pixel(3, 2, image::Rgba([255, 0, 0, 255]))
+			.write_to(&mut png, image::ImageOutputFormat::Png)
+			.unwrap();
+		let image_frame = usvg_image_frame(&usvg::ImageKind::PNG(std::sync::Arc::new(png.into_inner()))).unwrap();
+		assert_eq!((image_frame.image.width, image_frame.image.height), (3, 2));
+		assert_eq!(image_frame.image.data[0], Color::RED);
+
+		assert!(usvg_image_frame(&usvg::ImageKind::PNG(std::sync::Arc::new(b"not a png".to_vec()))).is_err());
+		assert!(usvg_image_frame(&usvg::ImageKind::GIF(std::sync::Arc::new(Vec::new()))).is_err());
+	}
+
+	#[test]
+	fn skip_images_which_fail_to_decode() {
+		let mut editor = Editor::create();
+		editor.new_document();
+
+		let svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="4"><image width="4" height="4" href="data:image/png;base64,{TRUNCATED_PNG}"/></svg>"#);
+		let responses = editor.handle_message(GraphOperationMessage::NewSvg {
+			id: NodeId(1),
+			svg,
+			transform: DAffine2::IDENTITY,
+			parent: LayerNodeIdentifier::ROOT,
+			insert_index: -1,
+		});
+
+		// Only the layer of the SVG's root group is left
+		let document = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap();
+		let layers: Vec<_> = document.metadata().all_layers().collect();
+		assert_eq!(layers, [LayerNodeIdentifier::new_unchecked(NodeId(1))]);
+		assert!(responses.iter().any(|response| matches!(response, FrontendMessage::DisplayDialog { .. })));
+	}
+
+	#[test]
+	fn combine_only_vector_layers() {
+		let mut editor = Editor::create();
+		editor.new_document();
+
+		let rectangle = || vec![Subpath::new_rect(DVec2::ZERO, DVec2::splat(10.))];
+		let parent = LayerNodeIdentifier::ROOT;
+		editor.handle_message(GraphOperationMessage::NewVectorLayer {
+			id: NodeId(1),
+			subpaths: rectangle(),
+			parent,
+			insert_index: -1,
+		});
+		editor.handle_message(GraphOperationMessage::NewTextLayer {
+			id: NodeId(2),
+			text: "Text".to_string(),
+			font: Font::new(crate::consts::DEFAULT_FONT_FAMILY.into(), crate::consts::DEFAULT_FONT_STYLE.into()),
+			size: 24.,
+			color: Color::BLACK,
+			parent,
+			insert_index: -1,
+		});
+		let boolean_operation_nodes = |editor: &Editor| {
+			let document = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap();
+			document.network().nodes.values().filter(|node| node.name == "Boolean Operation").count()
+		};
+
+		// A text layer can't be combined, which leaves only one shape
+		let operation = bezier_rs::BooleanOperation::Union;
+		editor.handle_message(GraphOperationMessage::NewBooleanOperationLayer {
+			id: NodeId(3),
+			operation,
+			layers: vec![NodeId(1), NodeId(2)],
+			parent,
+			insert_index: -1,
+		});
+		let document = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap();
+		assert_eq!(document.metadata().all_layers().count(), 2);
+		assert_eq!(boolean_operation_nodes(&editor), 0);
+
+		editor.handle_message(GraphOperationMessage::NewVectorLayer {
+			id: NodeId(4),
+			subpaths: rectangle(),
+			parent,
+			insert_index: -1,
+		});
+		let layers = vec![NodeId(1), NodeId(2), NodeId(4)];
+		editor.handle_message(GraphOperationMessage::NewBooleanOperationLayer {
+			id: NodeId(5),
+			operation,
+			layers,
+			parent,
+			insert_index: -1,
+		});
+		let document = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap();
+		assert!(document.metadata().all_layers().any(|layer| layer.to_node() == NodeId(5)));
+		assert_eq!(boolean_operation_nodes(&editor), 1);
+	}
+}
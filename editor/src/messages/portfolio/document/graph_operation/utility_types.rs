@@ -1,8 +1,9 @@
 use crate::messages::portfolio::document::node_graph::document_node_types::resolve_document_node_type;
 use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils::is_vector_layer;
 
-use bezier_rs::Subpath;
+use bezier_rs::{BooleanOperation, Subpath};
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{generate_uuid, DocumentNode, NodeId, NodeInput, NodeNetwork};
 use graphene_core::raster::{BlendMode, ImageFrame};
@@ -307,28 +308,65 @@ impl<'a> ModifyInputsContext<'a> {
 		self.responses.add(NodeGraphMessage::RunDocumentGraph);
 	}
 
-	pub fn insert_text(&mut self, text: String, font: Font, size: f64, layer: NodeId) {
-		let text = resolve_document_node_type("Text").expect("Text node does not exist").to_document_node(
+	/// Combines the content of the given layers, ordered from bottom to top, with a chain of boolean operation nodes feeding into the new layer.
+	/// The content nodes are shared rather than copied, so the original shapes remain editable in the node graph.
+	/// Layers whose content isn't vector data are left out.
+	pub fn insert_boolean_operation(&mut self, operation: BooleanOperation, layers: &[NodeId], layer: NodeId) {
+		let mut contents: Vec<NodeInput> = layers
+			.iter()
+			.filter(|&&layer| is_vector_layer(LayerNodeIdentifier::new_unchecked(layer), self.document_network))
+			.filter_map(|layer| self.document_network.nodes.get(layer)?.inputs.get(1).cloned())
+			.filter(|input| input.as_node().is_some())
+			.collect();
+
+		// Subtracting the back shapes removes every lower shape from the topmost one, so the result is accumulated from the top down into the upper input
+		let accumulated_index = if operation == BooleanOperation::SubtractBack {
+			contents.reverse();
+			1
+		} else {
+			0
+		};
+		let Some((first, rest)) = contents.split_first() else { return };
+		if rest.is_empty() {
+			return;
+		}
+
+		let node_type = resolve_document_node_type("Boolean Operation").expect("Boolean Operation node does not exist");
+		let boolean_ids: Vec<NodeId> = rest.iter().map(|_| NodeId(generate_uuid())).collect();
+		let (mut post_node_id, mut post_node_input_index) = (layer, 1);
+		for (index, content) in rest.iter().enumerate().rev() {
+			let accumulated = if index == 0 { first.clone() } else { NodeInput::node(boolean_ids[index - 1], 0) };
+			let mut inputs = [Some(content.clone()), Some(content.clone()), Some(NodeInput::value(TaggedValue::BooleanOperation(operation), false))];
+			inputs[accumulated_index] = Some(accumulated);
+			let boolean_node = node_type.to_document_node_default_inputs(inputs, Default::default());
+
+			self.insert_node_before(boolean_ids[index], post_node_id, post_node_input_index, boolean_node, IVec2::new(-8, 0));
+			(post_node_id, post_node_input_index) = (boolean_ids[index], accumulated_index);
+		}
+		self.responses.add(NodeGraphMessage::RunDocumentGraph);
+	}
+
+	pub fn insert_text(&mut self, text: String, font: Font, size: f64, color: Color, layer: NodeId) {
+		let typography = resolve_document_node_type("Typography").expect("Typography node does not exist").to_document_node_default_inputs(
 			[
-				NodeInput::Network(graph_craft::concrete!(graphene_std::wasm_application_io::WasmEditorApi)),
-				NodeInput::value(TaggedValue::String(text), false),
-				NodeInput::value(TaggedValue::Font(font), false),
-				NodeInput::value(TaggedValue::F64(size), false),
+				Some(NodeInput::Network(graph_craft::concrete!(graphene_std::wasm_application_io::WasmEditorApi))),
+				Some(NodeInput::value(TaggedValue::String(text), false)),
+				Some(NodeInput::value(TaggedValue::Font(font), false)),
+				Some(NodeInput::value(TaggedValue::F64(size), false)),
+				None,
+				None,
+				None,
+				None,
+				Some(NodeInput::value(TaggedValue::Color(color), false)),
 			],
 			Default::default(),
 		);
 		let transform = resolve_document_node_type("Transform").expect("Transform node does not exist").default_document_node();
-		let fill = resolve_document_node_type("Fill").expect("Fill node does not exist").default_document_node();
-		let stroke = resolve_document_node_type("Stroke").expect("Stroke node does not exist").default_document_node();
 
-		let stroke_id = NodeId(generate_uuid());
-		self.insert_node_before(stroke_id, layer, 1, stroke, IVec2::new(-8, 0));
-		let fill_id = NodeId(generate_uuid());
-		self.insert_node_before(fill_id, stroke_id, 0, fill, IVec2::new(-8, 0));
 		let transform_id = NodeId(generate_uuid());
-		self.insert_node_before(transform_id, fill_id, 0, transform, IVec2::new(-8, 0));
-		let text_id = NodeId(generate_uuid());
-		self.insert_node_before(text_id, transform_id, 0, text, IVec2::new(-8, 0));
+		self.insert_node_before(transform_id, layer, 1, transform, IVec2::new(-8, 0));
+		let typography_id = NodeId(generate_uuid());
+		self.insert_node_before(typography_id, transform_id, 0, typography, IVec2::new(-8, 0));
 		self.responses.add(NodeGraphMessage::RunDocumentGraph);
 	}
 
@@ -473,6 +511,7 @@ impl<'a> ModifyInputsContext<'a> {
 				inputs[5] = NodeInput::value(TaggedValue::DVec2(gradient.end), false);
 				inputs[6] = NodeInput::value(TaggedValue::DAffine2(gradient.transform), false);
 				inputs[7] = NodeInput::value(TaggedValue::GradientPositions(gradient.positions), false);
+				inputs[8] = NodeInput::value(TaggedValue::GradientSpreadMethod(gradient.spread_method), false);
 			}
 		});
 	}
@@ -498,6 +537,7 @@ impl<'a> ModifyInputsContext<'a> {
 			inputs[5] = NodeInput::value(TaggedValue::LineCap(stroke.line_cap), false);
 			inputs[6] = NodeInput::value(TaggedValue::LineJoin(stroke.line_join), false);
 			inputs[7] = NodeInput::value(TaggedValue::F64(stroke.line_join_miter_limit), false);
+			inputs[8] = NodeInput::value(TaggedValue::OptionalGradient(stroke.gradient), false);
 		});
 	}
 
@@ -613,6 +653,55 @@ impl<'a> ModifyInputsContext<'a> {
 		});
 	}
 
+	pub fn mask_set(&mut self, stencil: ImageFrame<Color>) {
+		self.modify_inputs("Mask", false, |inputs, _node_id, _metadata| {
+			inputs[1] = NodeInput::value(TaggedValue::ImageFrame(stencil), true);
+		});
+	}
+
+	/// Adds a Flood Fill node to the layer's raster data. Every fill gets a node of its own, so it's applied on top of the earlier ones.
+	/// It goes upstream of the layer's Transform node (if there is one) so the fill's seed is given in the same space as the image and follows it when the layer is moved.
+	pub fn flood_fill(&mut self, seed: DVec2, color: Color, tolerance: f64, contiguous: bool, antialias: bool) {
+		let Some(layer) = self.layer_node else { return };
+		let transform_node = self
+			.document_network
+			.upstream_flow_back_from_nodes(vec![layer], graph_craft::document::FlowType::HorizontalFlow)
+			.skip(1)
+			.take_while(|(node, _)| !node.is_layer)
+			.find(|(node, _)| node.name == "Transform")
+			.map(|(_, node_id)| node_id);
+		let (post_node_id, input_index) = transform_node.map_or((layer, 1), |node_id| (node_id, 0));
+		let upstream_input = self
+			.document_network
+			.nodes
+			.get(&post_node_id)
+			.and_then(|node| node.inputs.get(input_index))
+			.cloned()
+			.filter(|input| input.as_node().is_some());
+
+		let Some(node_type) = resolve_document_node_type("Flood Fill") else {
+			warn!("Node type \"Flood Fill\" doesn't exist");
+			return;
+		};
+		let mut document_node = node_type.to_document_node_default_inputs([upstream_input], Default::default());
+		document_node.inputs[1] = NodeInput::value(TaggedValue::DVec2(seed), false);
+		document_node.inputs[2] = NodeInput::value(TaggedValue::Color(color), false);
+		document_node.inputs[3] = NodeInput::value(TaggedValue::F64(tolerance), false);
+		document_node.inputs[4] = NodeInput::value(TaggedValue::Bool(contiguous), false);
+		document_node.inputs[5] = NodeInput::value(TaggedValue::Bool(antialias), false);
+
+		let node_id = NodeId(generate_uuid());
+		let offset = IVec2::new(-8, 0);
+		if self.insert_node_before(node_id, post_node_id, input_index, document_node, offset).is_none() {
+			return;
+		}
+		self.shift_upstream(node_id, offset, false);
+
+		self.node_graph.network.clear();
+		self.responses.add(PropertiesPanelMessage::Refresh);
+		self.responses.add(NodeGraphMessage::RunDocumentGraph);
+	}
+
 	pub fn resize_artboard(&mut self, location: IVec2, dimensions: IVec2) {
 		self.modify_inputs("Artboard", false, |inputs, _node_id, _metadata| {
 			let mut dimensions = dimensions;
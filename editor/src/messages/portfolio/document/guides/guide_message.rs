@@ -0,0 +1,20 @@
+use super::utility_types::GuideDirection;
+use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::prelude::*;
+
+#[impl_message(Message, DocumentMessage, Guide)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum GuideMessage {
+	// Messages
+	AbortDrag,
+	BeginDragFromRuler { direction: GuideDirection },
+	DeleteAll,
+	Draw(OverlayContext),
+	EndDrag,
+	PointerDown,
+	PointerMove,
+	SetLocked { locked: bool },
+	SetVisibility { visible: bool },
+	ToggleVisibility,
+	UpdateHints,
+}
@@ -0,0 +1,233 @@
+use super::utility_types::{Guide, GuideDirection, Guides};
+use crate::consts::{COLOR_OVERLAY_BLUE, COLOR_OVERLAY_GUIDE, GUIDE_GRAB_TOLERANCE};
+use crate::messages::frontend::utility_types::MouseCursorIcon;
+use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
+use crate::messages::portfolio::document::utility_types::document_metadata::DocumentMetadata;
+use crate::messages::portfolio::document::utility_types::misc::DocumentMode;
+use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
+
+use glam::DVec2;
+
+pub struct GuideMessageData<'a> {
+	pub guides: &'a mut Guides,
+	pub metadata: &'a DocumentMetadata,
+	pub ipp: &'a InputPreprocessorMessageHandler,
+	pub document_mode: DocumentMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GuideDrag {
+	index: usize,
+	/// Where the guide was before the drag, or `None` if it's a new guide being dragged out of a ruler.
+	original_position: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GuideMessageHandler {
+	drag: Option<GuideDrag>,
+	cursor: MouseCursorIcon,
+}
+
+impl MessageHandler<GuideMessage, GuideMessageData<'_>> for GuideMessageHandler {
+	fn process_message(&mut self, message: GuideMessage, responses: &mut VecDeque<Message>, data: GuideMessageData) {
+		let GuideMessageData { guides, metadata, ipp, document_mode } = data;
+		let document_to_viewport = metadata.document_to_viewport;
+		let pointer_position = |direction: GuideDirection| document_to_viewport.inverse().transform_point2(ipp.mouse.position)[direction.axis()];
+
+		match message {
+			GuideMessage::AbortDrag => {
+				let Some(GuideDrag { index, original_position }) = self.drag.take() else { return };
+
+				match original_position {
+					Some(position) => guides.guides[index].position = position,
+					None => {
+						guides.guides.remove(index);
+					}
+				}
+
+				responses.add(GuideMessage::UpdateHints);
+				responses.add(OverlaysMessage::Draw);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			GuideMessage::BeginDragFromRuler { direction } => {
+				// Dragging out a new guide would be confusing if the others stayed hidden
+				guides.visible = true;
+				guides.guides.push(Guide {
+					direction,
+					position: pointer_position(direction),
+				});
+				self.drag = Some(GuideDrag {
+					index: guides.guides.len() - 1,
+					original_position: None,
+				});
+
+				self.start_drag(direction, responses);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			GuideMessage::DeleteAll => {
+				self.drag = None;
+				guides.guides.clear();
+
+				responses.add(OverlaysMessage::Draw);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			GuideMessage::Draw(mut overlay_context) => {
+				if !guides.visible {
+					return;
+				}
+
+				let viewport_size = overlay_context.size;
+				let to_document = document_to_viewport.inverse();
+				let center = to_document.transform_point2(viewport_size / 2.);
+				let half_length = to_document.transform_vector2(viewport_size).length();
+
+				for (index, guide) in guides.guides.iter().enumerate() {
+					let [start, end] = guide.segment(center, half_length);
+					let dragged = self.drag.is_some_and(|drag| drag.index == index);
+					let color = if dragged { COLOR_OVERLAY_BLUE } else { COLOR_OVERLAY_GUIDE };
+					overlay_context.line(document_to_viewport.transform_point2(start), document_to_viewport.transform_point2(end), Some(color));
+				}
+			}
+			GuideMessage::EndDrag => {
+				let Some(GuideDrag { index, .. }) = self.drag.take() else { return };
+
+				// Guides dropped outside of the viewport, such as back onto a ruler, are removed
+				let viewport_size = ipp.viewport_bounds.size();
+				let inside_viewport = ipp.mouse.position.cmpge(DVec2::ZERO).all() && ipp.mouse.position.cmple(viewport_size).all();
+				if inside_viewport {
+					guides.guides[index].position = pointer_position(guides.guides[index].direction);
+				} else {
+					guides.guides.remove(index);
+				}
+
+				responses.add(GuideMessage::UpdateHints);
+				responses.add(OverlaysMessage::Draw);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			GuideMessage::PointerDown => {
+				if guides.locked || !guides.visible {
+					return;
+				}
+				let Some(index) = guides.guide_at(ipp.mouse.position, document_to_viewport, GUIDE_GRAB_TOLERANCE) else {
+					return;
+				};
+
+				self.drag = Some(GuideDrag {
+					index,
+					original_position: Some(guides.guides[index].position),
+				});
+
+				self.start_drag(guides.guides[index].direction, responses);
+			}
+			GuideMessage::PointerMove => {
+				if let Some(GuideDrag { index, .. }) = self.drag {
+					guides.guides[index].position = pointer_position(guides.guides[index].direction);
+
+					responses.add(OverlaysMessage::Draw);
+					return;
+				}
+
+				// Hovering over a guide in Guide Mode shows that it can be dragged
+				let hovered = (guides.visible && !guides.locked)
+					.then(|| guides.guide_at(ipp.mouse.position, document_to_viewport, GUIDE_GRAB_TOLERANCE))
+					.flatten()
+					.map(|index| guides.guides[index].direction);
+				self.set_cursor(hovered.map_or(MouseCursorIcon::Default, Self::drag_cursor), responses);
+			}
+			GuideMessage::SetLocked { locked } => {
+				guides.locked = locked;
+
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			GuideMessage::SetVisibility { visible } => {
+				guides.visible = visible;
+
+				responses.add(OverlaysMessage::Draw);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			GuideMessage::ToggleVisibility => {
+				responses.add(GuideMessage::SetVisibility { visible: !guides.visible });
+			}
+			GuideMessage::UpdateHints => {
+				match document_mode {
+					DocumentMode::GuideMode => {}
+					DocumentMode::SelectMode => {
+						self.cursor = MouseCursorIcon::Default;
+						responses.add(SelectModeMessage::UpdateHints);
+						return;
+					}
+					DocumentMode::DesignMode => {
+						self.cursor = MouseCursorIcon::Default;
+						responses.add(ToolMessage::UpdateHints);
+						responses.add(ToolMessage::UpdateCursor);
+						return;
+					}
+				}
+
+				let hint_data = HintData(vec![
+					HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Move Guide")]),
+					HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Drag onto Ruler to Remove")]),
+					HintGroup(vec![HintInfo::keys([Key::Accel, Key::Semicolon], "Show/Hide Guides")]),
+				]);
+				responses.add(FrontendMessage::UpdateInputHints { hint_data });
+				self.cursor = MouseCursorIcon::Default;
+				responses.add(FrontendMessage::UpdateMouseCursor { cursor: self.cursor });
+			}
+		}
+	}
+
+	fn actions(&self) -> ActionList {
+		let mut common = actions!(GuideMessageDiscriminant;
+			DeleteAll,
+			ToggleVisibility,
+		);
+
+		if self.drag.is_some() {
+			let dragging = actions!(GuideMessageDiscriminant;
+				AbortDrag,
+				EndDrag,
+				PointerMove,
+			);
+			common.extend(dragging);
+		}
+
+		common
+	}
+}
+
+impl GuideMessageHandler {
+	pub fn is_dragging(&self) -> bool {
+		self.drag.is_some()
+	}
+
+	/// Actions which take over the pointer from the tools while the document is in Guide Mode.
+	pub fn actions_additional_in_guide_mode(&self) -> ActionList {
+		actions!(GuideMessageDiscriminant;
+			PointerDown,
+			PointerMove,
+		)
+	}
+
+	fn drag_cursor(direction: GuideDirection) -> MouseCursorIcon {
+		match direction {
+			GuideDirection::Horizontal => MouseCursorIcon::NSResize,
+			GuideDirection::Vertical => MouseCursorIcon::EWResize,
+		}
+	}
+
+	fn set_cursor(&mut self, cursor: MouseCursorIcon, responses: &mut VecDeque<Message>) {
+		if self.cursor != cursor {
+			self.cursor = cursor;
+			responses.add(FrontendMessage::UpdateMouseCursor { cursor });
+		}
+	}
+
+	fn start_drag(&mut self, direction: GuideDirection, responses: &mut VecDeque<Message>) {
+		self.set_cursor(Self::drag_cursor(direction), responses);
+		responses.add(FrontendMessage::UpdateInputHints {
+			hint_data: HintData(vec![HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()])]),
+		});
+		responses.add(OverlaysMessage::Draw);
+	}
+}
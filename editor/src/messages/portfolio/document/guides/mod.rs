@@ -0,0 +1,9 @@
+mod guide_message;
+mod guide_message_handler;
+
+pub mod utility_types;
+
+#[doc(inline)]
+pub use guide_message::{GuideMessage, GuideMessageDiscriminant};
+#[doc(inline)]
+pub use guide_message_handler::{GuideMessageData, GuideMessageHandler};
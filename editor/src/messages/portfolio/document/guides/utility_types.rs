@@ -0,0 +1,118 @@
+use glam::{DAffine2, DVec2};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum GuideDirection {
+	/// A guide running left to right, dragged out of the ruler along the top of the viewport.
+	Horizontal,
+	/// A guide running top to bottom, dragged out of the ruler along the left of the viewport.
+	Vertical,
+}
+
+impl GuideDirection {
+	/// The index of the document space axis which the guide's position is measured along.
+	pub fn axis(self) -> usize {
+		match self {
+			Self::Horizontal => 1,
+			Self::Vertical => 0,
+		}
+	}
+}
+
+/// A line across the whole canvas at a fixed position in document space, used to line up artwork while laying it out.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Guide {
+	pub direction: GuideDirection,
+	/// The y coordinate of a horizontal guide, or the x coordinate of a vertical guide, in document space.
+	pub position: f64,
+}
+
+impl Guide {
+	/// The point on the guide closest to the given document space point.
+	pub fn project(&self, document_point: DVec2) -> DVec2 {
+		let mut projected = document_point;
+		projected[self.direction.axis()] = self.position;
+		projected
+	}
+
+	/// A stretch of the guide in document space, reaching the length in either direction from the point on it closest to the center.
+	pub fn segment(&self, center: DVec2, half_length: f64) -> [DVec2; 2] {
+		let center = self.project(center);
+		let along = match self.direction {
+			GuideDirection::Horizontal => DVec2::X,
+			GuideDirection::Vertical => DVec2::Y,
+		};
+		[center - along * half_length, center + along * half_length]
+	}
+
+	/// The distance in viewport space between the viewport space point and the guide.
+	pub fn viewport_distance(&self, viewport_point: DVec2, document_to_viewport: DAffine2) -> f64 {
+		let document_point = document_to_viewport.inverse().transform_point2(viewport_point);
+		document_to_viewport.transform_point2(self.project(document_point)).distance(viewport_point)
+	}
+}
+
+/// The guides of a document, which are saved with it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Guides {
+	pub guides: Vec<Guide>,
+	/// Hidden guides are neither drawn nor snapped to.
+	pub visible: bool,
+	/// Locked guides can't be moved or removed by dragging them.
+	pub locked: bool,
+}
+
+impl Default for Guides {
+	fn default() -> Self {
+		Self {
+			guides: Vec::new(),
+			visible: true,
+			locked: false,
+		}
+	}
+}
+
+impl Guides {
+	/// The index of the closest guide within the tolerance of the viewport space point.
+	pub fn guide_at(&self, viewport_point: DVec2, document_to_viewport: DAffine2, tolerance: f64) -> Option<usize> {
+		self.guides
+			.iter()
+			.enumerate()
+			.map(|(index, guide)| (index, guide.viewport_distance(viewport_point, document_to_viewport)))
+			.filter(|&(_, distance)| distance <= tolerance)
+			.min_by(|(_, a), (_, b)| a.total_cmp(b))
+			.map(|(index, _)| index)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn guides_under_the_pointer() {
+		let guides = Guides {
+			guides: vec![
+				Guide {
+					direction: GuideDirection::Horizontal,
+					position: 10.,
+				},
+				Guide {
+					direction: GuideDirection::Vertical,
+					position: 20.,
+				},
+			],
+			..Default::default()
+		};
+		// Zoomed in to 200% and panned right by 5 pixels
+		let document_to_viewport = DAffine2::from_scale_angle_translation(DVec2::splat(2.), 0., DVec2::new(5., 0.));
+
+		assert_eq!(guides.guide_at(DVec2::new(100., 23.), document_to_viewport, 4.), Some(0));
+		assert_eq!(guides.guide_at(DVec2::new(44., 100.), document_to_viewport, 4.), Some(1));
+		assert_eq!(guides.guide_at(DVec2::new(44., 22.), document_to_viewport, 4.), Some(1));
+		assert_eq!(guides.guide_at(DVec2::new(100., 100.), document_to_viewport, 4.), None);
+
+		let [start, end] = guides.guides[1].segment(DVec2::new(0., 50.), 100.);
+		assert_eq!(start, DVec2::new(20., -50.));
+		assert_eq!(end, DVec2::new(20., 150.));
+	}
+}
@@ -2,10 +2,12 @@ mod document_message;
 mod document_message_handler;
 
 pub mod graph_operation;
+pub mod guides;
 pub mod navigation;
 pub mod node_graph;
 pub mod overlays;
 pub mod properties_panel;
+pub mod select_mode;
 pub mod utility_types;
 
 #[doc(inline)]
@@ -15,9 +15,9 @@ use graph_craft::ProtoNodeIdentifier;
 use graphene_core::raster::brush_cache::BrushCache;
 use graphene_core::raster::{
 	BlendMode, CellularDistanceFunction, CellularReturnType, Color, DomainWarpType, FractalType, Image, ImageFrame, LuminanceCalculation, NoiseType, RedGreenBlue, RedGreenBlueAlpha, RelativeAbsolute,
-	SelectiveColorChoice,
+	ResampleFilter, SelectiveColorChoice,
 };
-use graphene_core::text::Font;
+use graphene_core::text::{Font, TextAlignment};
 use graphene_core::transform::Footprint;
 use graphene_core::vector::VectorData;
 use graphene_core::*;
@@ -2304,6 +2304,60 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::node_section_font,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Typography",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::text::TypographyNode<_, _, _, _, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Text", TaggedValue::String("Lorem ipsum".to_string()), false),
+				DocumentInputType::value("Font", TaggedValue::Font(Font::new(DEFAULT_FONT_FAMILY.into(), DEFAULT_FONT_STYLE.into())), false),
+				DocumentInputType::value("Size", TaggedValue::F64(24.), false),
+				DocumentInputType::value("Tracking", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Leading", TaggedValue::F64(1.2), false),
+				DocumentInputType::value("Alignment", TaggedValue::TextAlignment(TextAlignment::Left), false),
+				DocumentInputType::value("Box Width", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Color", TaggedValue::Color(Color::BLACK), false),
+			],
+			outputs: vec![DocumentOutputType::new("Typography", FrontendGraphDataType::General)],
+			properties: node_properties::typography_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Append Text",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::text::AppendTextNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType {
+					name: "Typography",
+					data_type: FrontendGraphDataType::General,
+					default: NodeInput::value(TaggedValue::None, true),
+				},
+				DocumentInputType {
+					name: "Appended",
+					data_type: FrontendGraphDataType::General,
+					default: NodeInput::value(TaggedValue::None, true),
+				},
+			],
+			outputs: vec![DocumentOutputType::new("Typography", FrontendGraphDataType::General)],
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Outline Text",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::text::OutlineTextNode<_>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType {
+					name: "Typography",
+					data_type: FrontendGraphDataType::General,
+					default: NodeInput::value(TaggedValue::None, true),
+				},
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Transform",
 			category: "Transform",
@@ -2363,7 +2417,7 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 		DocumentNodeDefinition {
 			name: "Fill",
 			category: "Vector",
-			implementation: DocumentNodeImplementation::proto("graphene_core::vector::SetFillNode<_, _, _, _, _, _, _>"),
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::SetFillNode<_, _, _, _, _, _, _, _>"),
 			inputs: vec![
 				DocumentInputType::value("Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
 				DocumentInputType::value("Fill Type", TaggedValue::FillType(vector::style::FillType::Solid), false),
@@ -2373,6 +2427,7 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 				DocumentInputType::value("End", TaggedValue::DVec2(DVec2::new(1., 0.5)), false),
 				DocumentInputType::value("Transform", TaggedValue::DAffine2(DAffine2::IDENTITY), false),
 				DocumentInputType::value("Positions", TaggedValue::GradientPositions(vec![(0., Color::BLACK), (1., Color::WHITE)]), false),
+				DocumentInputType::value("Spread Method", TaggedValue::GradientSpreadMethod(vector::style::GradientSpreadMethod::Pad), false),
 			],
 			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
 			properties: node_properties::fill_properties,
@@ -2381,7 +2436,7 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 		DocumentNodeDefinition {
 			name: "Stroke",
 			category: "Vector",
-			implementation: DocumentNodeImplementation::proto("graphene_core::vector::SetStrokeNode<_, _, _, _, _, _, _>"),
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::SetStrokeNode<_, _, _, _, _, _, _, _>"),
 			inputs: vec![
 				DocumentInputType::value("Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
 				DocumentInputType::value("Color", TaggedValue::OptionalColor(Some(Color::BLACK)), false),
@@ -2391,11 +2446,69 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 				DocumentInputType::value("Line Cap", TaggedValue::LineCap(graphene_core::vector::style::LineCap::Butt), false),
 				DocumentInputType::value("Line Join", TaggedValue::LineJoin(graphene_core::vector::style::LineJoin::Miter), false),
 				DocumentInputType::value("Miter Limit", TaggedValue::F64(4.), false),
+				DocumentInputType::value("Gradient", TaggedValue::OptionalGradient(None), false),
 			],
 			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
 			properties: node_properties::stroke_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Drop Shadow",
+			category: "Layer Effects",
+			implementation: DocumentNodeImplementation::proto("graphene_core::effects::DropShadowNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Graphical Data", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("Color", TaggedValue::Color(Color::BLACK.with_alpha(0.75)), false),
+				DocumentInputType::value("Offset", TaggedValue::DVec2(DVec2::splat(5.)), false),
+				DocumentInputType::value("Blur", TaggedValue::F64(5.), false),
+				DocumentInputType::value("Blend Mode", TaggedValue::BlendMode(BlendMode::Multiply), false),
+			],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::GraphicGroup)],
+			properties: node_properties::shadow_effect_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Inner Shadow",
+			category: "Layer Effects",
+			implementation: DocumentNodeImplementation::proto("graphene_core::effects::InnerShadowNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Graphical Data", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("Color", TaggedValue::Color(Color::BLACK.with_alpha(0.75)), false),
+				DocumentInputType::value("Offset", TaggedValue::DVec2(DVec2::splat(5.)), false),
+				DocumentInputType::value("Blur", TaggedValue::F64(5.), false),
+				DocumentInputType::value("Blend Mode", TaggedValue::BlendMode(BlendMode::Multiply), false),
+			],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::GraphicGroup)],
+			properties: node_properties::shadow_effect_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Outer Glow",
+			category: "Layer Effects",
+			implementation: DocumentNodeImplementation::proto("graphene_core::effects::OuterGlowNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Graphical Data", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("Color", TaggedValue::Color(Color::from_rgbaf32_unchecked(1., 1., 0.75, 0.75)), false),
+				DocumentInputType::value("Size", TaggedValue::F64(15.), false),
+				DocumentInputType::value("Blend Mode", TaggedValue::BlendMode(BlendMode::Screen), false),
+			],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::GraphicGroup)],
+			properties: node_properties::outer_glow_effect_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Stroke Effect",
+			category: "Layer Effects",
+			implementation: DocumentNodeImplementation::proto("graphene_core::effects::StrokeEffectNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Graphical Data", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("Color", TaggedValue::Color(Color::BLACK), false),
+				DocumentInputType::value("Width", TaggedValue::F64(3.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::GraphicGroup)],
+			properties: node_properties::stroke_effect_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Bounding Box",
 			category: "Vector",
@@ -2414,6 +2527,20 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::node_no_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Offset Path",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::OffsetPathNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
+				DocumentInputType::value("Distance", TaggedValue::F64(10.), false),
+				DocumentInputType::value("Line Join", TaggedValue::LineJoin(graphene_core::vector::style::LineJoin::Miter), false),
+				DocumentInputType::value("Miter Limit", TaggedValue::F64(4.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::offset_path_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Repeat",
 			category: "Vector",
@@ -2548,6 +2675,20 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::morph_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Boolean Operation",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::BooleanOperationNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Lower Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
+				DocumentInputType::value("Upper Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
+				DocumentInputType::value("Operation", TaggedValue::BooleanOperation(bezier_rs::BooleanOperation::Union), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			manual_composition: Some(concrete!(Footprint)),
+			properties: node_properties::boolean_operation_properties,
+			..Default::default()
+		},
 		// TODO: This needs to work with resolution-aware (raster with footprint, post-Cull node) data.
 		DocumentNodeDefinition {
 			name: "Image Segmentation",
@@ -2611,6 +2752,100 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::image_color_palette,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Gaussian Blur",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::filter::GaussianBlurNode<_>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Radius", TaggedValue::F64(5.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::blur_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Box Blur",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::filter::BoxBlurNode<_>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Radius", TaggedValue::F64(5.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::blur_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Motion Blur",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::filter::MotionBlurNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Angle", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Distance", TaggedValue::F64(10.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::motion_blur_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Unsharp Mask",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::filter::UnsharpMaskNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Radius", TaggedValue::F64(2.), false),
+				DocumentInputType::value("Amount", TaggedValue::F64(100.), false),
+				DocumentInputType::value("Threshold", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::unsharp_mask_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Convolution",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::filter::ConvolutionNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				// A sharpening kernel, as a starting point for editing
+				DocumentInputType::value("Kernel", TaggedValue::VecF64(vec![0., -1., 0., -1., 5., -1., 0., -1., 0.]), false),
+				DocumentInputType::value("Normalize", TaggedValue::Bool(true), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::convolution_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Resample",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::filter::ResampleNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Resolution", TaggedValue::UVec2((512, 512).into()), false),
+				DocumentInputType::value("Filter", TaggedValue::ResampleFilter(ResampleFilter::Bicubic), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::resample_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Flood Fill",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::flood_fill::FloodFillNode<_, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Seed", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("Color", TaggedValue::Color(Color::BLACK), false),
+				DocumentInputType::value("Tolerance", TaggedValue::F64(10.), false),
+				DocumentInputType::value("Contiguous", TaggedValue::Bool(true), false),
+				DocumentInputType::value("Anti-aliasing", TaggedValue::Bool(true), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::flood_fill_properties,
+			..Default::default()
+		},
 	]
 }
 
@@ -2709,6 +2944,22 @@ pub fn resolve_document_node_type(name: &str) -> Option<&DocumentNodeDefinition>
 	DOCUMENT_NODE_TYPES.iter().find(|node| node.name == name)
 }
 
+/// The nodes whose definitions have gained inputs since documents were saved with them, which are upgraded by [`upgrade_saved_nodes`].
+pub const UPGRADED_NODE_TYPES: &[&str] = &["Fill", "Stroke"];
+
+/// Upgrades the nodes of an opened document or pasted data, including those in nested networks, which were saved before their definitions gained inputs.
+pub fn upgrade_saved_nodes<'a>(nodes: impl IntoIterator<Item = &'a mut DocumentNode>) {
+	for node in nodes {
+		if let DocumentNodeImplementation::Network(nested_network) = &mut node.implementation {
+			upgrade_saved_nodes(nested_network.nodes.values_mut());
+		} else if UPGRADED_NODE_TYPES.contains(&node.name.as_str()) {
+			if let Some(definition) = resolve_document_node_type(&node.name) {
+				definition.upgrade_saved_node(node);
+			}
+		}
+	}
+}
+
 pub fn collect_node_types() -> Vec<FrontendNodeType> {
 	DOCUMENT_NODE_TYPES
 		.iter()
@@ -2747,6 +2998,18 @@ impl DocumentNodeDefinition {
 	pub fn default_document_node(&self) -> DocumentNode {
 		self.to_document_node(self.inputs.iter().map(|input| input.default.clone()), DocumentNodeMetadata::default())
 	}
+
+	/// Brings a node saved before inputs were appended to this definition up to date,
+	/// giving it the missing inputs with their default values and the current implementation which takes them.
+	pub fn upgrade_saved_node(&self, node: &mut DocumentNode) {
+		let saved_input_count = node.inputs.len();
+		if saved_input_count >= self.inputs.len() {
+			return;
+		}
+
+		node.inputs.extend(self.inputs[saved_input_count..].iter().map(|input| input.default.clone()));
+		node.implementation = self.implementation.clone();
+	}
 }
 
 pub fn wrap_network_in_scope(mut network: NodeNetwork, hash: u64) -> NodeNetwork {
@@ -2832,28 +3095,24 @@ pub fn new_image_network(output_offset: i32, output_node_id: NodeId) -> NodeNetw
 }
 
 pub fn new_text_network(text: String, font: Font, size: f64) -> NodeNetwork {
-	let text_generator = resolve_document_node_type("Text").expect("Text node does not exist");
+	let typography = resolve_document_node_type("Typography").expect("Typography node does not exist");
 	let transform = resolve_document_node_type("Transform").expect("Transform node does not exist");
-	let fill = resolve_document_node_type("Fill").expect("Fill node does not exist");
-	let stroke = resolve_document_node_type("Stroke").expect("Stroke node does not exist");
 	let output = resolve_document_node_type("Output").expect("Output node does not exist");
 
 	let mut network = NodeNetwork {
 		imports: vec![NodeId(0)],
 		..Default::default()
 	};
-	network.push_node(text_generator.to_document_node(
+	network.push_node(typography.to_document_node_default_inputs(
 		[
-			NodeInput::Network(concrete!(WasmEditorApi)),
-			NodeInput::value(TaggedValue::String(text), false),
-			NodeInput::value(TaggedValue::Font(font), false),
-			NodeInput::value(TaggedValue::F64(size), false),
+			Some(NodeInput::Network(concrete!(WasmEditorApi))),
+			Some(NodeInput::value(TaggedValue::String(text), false)),
+			Some(NodeInput::value(TaggedValue::Font(font), false)),
+			Some(NodeInput::value(TaggedValue::F64(size), false)),
 		],
 		DocumentNodeMetadata::position((0, 4)),
 	));
 	network.push_node(transform.to_document_node_default_inputs([None], Default::default()));
-	network.push_node(fill.to_document_node_default_inputs([None], Default::default()));
-	network.push_node(stroke.to_document_node_default_inputs([None], Default::default()));
 	network.push_node(output.to_document_node_default_inputs([None], Default::default()));
 	network
 }
@@ -87,6 +87,11 @@ pub enum NodeGraphMessage {
 		nodes: Vec<NodeId>,
 	},
 	SendGraph,
+	SetInputExpression {
+		node_id: NodeId,
+		input_index: usize,
+		expression: String,
+	},
 	SetInputValue {
 		node_id: NodeId,
 		input_index: usize,
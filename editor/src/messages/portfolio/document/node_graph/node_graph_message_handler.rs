@@ -1,4 +1,4 @@
-use graph_craft::document::{DocumentNode, FlowType, NodeId, NodeInput, NodeNetwork, NodeOutput, Source};
+use graph_craft::document::{DocumentNode, FlowType, InlineRust, NodeId, NodeInput, NodeNetwork, NodeOutput, Source};
 use graph_craft::proto::GraphErrors;
 use graphene_core::*;
 use interpreted_executor::dynamic_executor::ResolvedDocumentNodeTypes;
@@ -10,7 +10,7 @@ use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::graph_operation::load_network_structure;
 use crate::messages::portfolio::document::graph_operation::utility_types::ModifyInputsContext;
-use crate::messages::portfolio::document::node_graph::document_node_types::{resolve_document_node_type, DocumentInputType, NodePropertiesContext};
+use crate::messages::portfolio::document::node_graph::document_node_types::{resolve_document_node_type, upgrade_saved_nodes, DocumentInputType, NodePropertiesContext};
 use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
 use crate::messages::portfolio::document::utility_types::nodes::{CollapsedLayers, LayerPanelEntry, SelectedNodes};
 use crate::messages::prelude::*;
@@ -439,7 +439,7 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 					return;
 				};
 
-				let data = match serde_json::from_str::<Vec<(NodeId, DocumentNode)>>(&serialized_nodes) {
+				let mut data = match serde_json::from_str::<Vec<(NodeId, DocumentNode)>>(&serialized_nodes) {
 					Ok(d) => d,
 					Err(e) => {
 						warn!("Invalid node data {e:?}");
@@ -451,6 +451,9 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 					return;
 				}
 
+				// The nodes might have been copied from an older version of the editor
+				upgrade_saved_nodes(data.iter_mut().map(|(_, node)| node));
+
 				// Shift nodes until it is not in the same position as another node
 				let mut shift = IVec2::ZERO;
 				while data
@@ -499,6 +502,26 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 					self.send_graph(network, graph_view_overlay_open, document_metadata, selected_nodes, collapsed, responses);
 				}
 			}
+			NodeGraphMessage::SetInputExpression { node_id, input_index, expression } => {
+				let Some(network) = document_network.nested_network(&self.network) else { return };
+				let Some(node) = network.nodes.get(&node_id) else { return };
+
+				// The expression keeps the type of the value it replaces
+				let ty = match node.inputs.get(input_index) {
+					Some(NodeInput::Value { tagged_value, .. }) => tagged_value.ty(),
+					Some(NodeInput::Inline(inline)) => inline.ty.clone(),
+					_ => {
+						warn!("Tried to write an expression on input {input_index} of node {node_id}, which is not a value");
+						return;
+					}
+				};
+				let input = NodeInput::Inline(InlineRust::new(expression, ty));
+				responses.add(NodeGraphMessage::SetNodeInput { node_id, input_index, input });
+				responses.add(PropertiesPanelMessage::Refresh);
+				if network.connected_to_output(node_id) {
+					responses.add(NodeGraphMessage::RunDocumentGraph);
+				}
+			}
 			NodeGraphMessage::SetInputValue { node_id, input_index, value } => {
 				if let Some(network) = document_network.nested_network(&self.network) {
 					if let Some(node) = network.nodes.get(&node_id) {
@@ -8,14 +8,17 @@ use crate::messages::prelude::*;
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{DocumentNode, NodeId, NodeInput};
 use graph_craft::imaginate_input::{ImaginateSamplingMethod, ImaginateServerStatus, ImaginateStatus};
+use graph_craft::inline_expression::{Expression, Value};
+use graph_craft::Type;
 use graphene_core::memo::IORecord;
 use graphene_core::raster::{
 	BlendMode, CellularDistanceFunction, CellularReturnType, Color, DomainWarpType, FractalType, ImageFrame, LuminanceCalculation, NoiseType, RedGreenBlue, RedGreenBlueAlpha, RelativeAbsolute,
-	SelectiveColorChoice,
+	ResampleFilter, SelectiveColorChoice,
 };
-use graphene_core::text::Font;
-use graphene_core::vector::style::{FillType, GradientType, LineCap, LineJoin};
+use graphene_core::text::{Font, TextAlignment};
+use graphene_core::vector::style::{FillType, GradientSpreadMethod, GradientType, LineCap, LineJoin};
 
+use bezier_rs::BooleanOperation;
 use glam::{DVec2, IVec2, UVec2};
 
 pub fn string_properties(text: impl Into<String>) -> Vec<LayoutGroup> {
@@ -369,10 +372,68 @@ fn number_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, na
 				.on_commit(commit_value)
 				.widget_holder(),
 		])
+	} else if let NodeInput::Inline(inline) = &document_node.inputs[index] {
+		let (expression, ty) = (inline.expr.clone(), inline.ty.clone());
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextInput::new(inline.expr.clone())
+				.on_update(move |text_input: &TextInput| {
+					let expression = text_input.value.clone();
+					NodeGraphMessage::SetInputExpression {
+						node_id,
+						input_index: index,
+						expression,
+					}
+					.into()
+				})
+				.on_commit(commit_value)
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			IconButton::new("Edit", 16)
+				.active(true)
+				.tooltip("Replace the formula with a fixed value")
+				.on_update(optionally_update_value(move |_: &IconButton| inline_expression_value(&expression, &ty), node_id, index))
+				.on_commit(commit_value)
+				.widget_holder(),
+		])
+	}
+
+	let number = match &document_node.inputs[index] {
+		NodeInput::Value { tagged_value, exposed: false } => match *tagged_value {
+			TaggedValue::F64(x) => Some(x.to_string()),
+			TaggedValue::U32(x) => Some(x.to_string()),
+			TaggedValue::F32(x) => Some(x.to_string()),
+			_ => None,
+		},
+		_ => None,
+	};
+	if let Some(number) = number {
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Related).widget_holder(),
+			IconButton::new("Edit", 16)
+				.tooltip("Write a formula for this input, which can use the values of the node's other inputs as i0, i1, i2…")
+				.on_update(move |_: &IconButton| {
+					let expression = number.clone();
+					NodeGraphMessage::SetInputExpression {
+						node_id,
+						input_index: index,
+						expression,
+					}
+					.into()
+				})
+				.on_commit(commit_value)
+				.widget_holder(),
+		]);
 	}
 	widgets
 }
 
+/// The value of an inline expression that doesn't depend on any other inputs, or the default value of its type.
+fn inline_expression_value(expression: &str, ty: &Type) -> Option<TaggedValue> {
+	let value = Expression::parse(expression).and_then(|expression| expression.evaluate(&Default::default()));
+	value.and_then(|value| value.to_tagged_value(ty)).ok().or_else(|| Value::default_for(ty)?.to_tagged_value(ty).ok())
+}
+
 // TODO: Generalize this instead of using a separate function per dropdown menu enum
 fn color_channel(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
 	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
@@ -561,6 +622,32 @@ fn domain_warp_type(document_node: &DocumentNode, node_id: NodeId, index: usize,
 	LayoutGroup::Row { widgets }.with_tooltip("Type of domain warp")
 }
 
+// TODO: Generalize this instead of using a separate function per dropdown menu enum
+fn resample_filter(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::ResampleFilter(resample_filter),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = ResampleFilter::list()
+			.iter()
+			.map(|resample_filter| {
+				MenuListEntry::new(format!("{resample_filter:?}"))
+					.label(resample_filter.to_string())
+					.on_update(update_value(move |_| TaggedValue::ResampleFilter(*resample_filter), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries]).selected_index(Some(resample_filter as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Filter which reconstructs the image between its pixels when it's resampled")
+}
+
 // TODO: Generalize this instead of using a separate function per dropdown menu enum
 fn blend_mode(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
 	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
@@ -647,6 +734,31 @@ fn line_cap_widget(document_node: &DocumentNode, node_id: NodeId, index: usize,
 	LayoutGroup::Row { widgets }
 }
 
+fn text_alignment_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::TextAlignment(alignment),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = [("Left", TextAlignment::Left), ("Center", TextAlignment::Center), ("Right", TextAlignment::Right)]
+			.into_iter()
+			.map(|(name, val)| {
+				RadioEntryData::new(format!("{val:?}"))
+					.label(name)
+					.on_update(update_value(move |_| TaggedValue::TextAlignment(val), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(alignment as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }
+}
+
 fn line_join_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
 	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
 	if let &NodeInput::Value {
@@ -672,6 +784,37 @@ fn line_join_widget(document_node: &DocumentNode, node_id: NodeId, index: usize,
 	LayoutGroup::Row { widgets }
 }
 
+fn boolean_operation_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::BooleanOperation(boolean_operation),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = [
+			("Union", BooleanOperation::Union),
+			("Subtract Front", BooleanOperation::SubtractFront),
+			("Subtract Back", BooleanOperation::SubtractBack),
+			("Intersect", BooleanOperation::Intersect),
+			("Difference", BooleanOperation::Difference),
+		]
+		.into_iter()
+		.map(|(name, val)| {
+			MenuListEntry::new(format!("{val:?}"))
+				.label(name)
+				.on_update(update_value(move |_| TaggedValue::BooleanOperation(val), node_id, index))
+				.on_commit(commit_value)
+		})
+		.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries]).selected_index(Some(boolean_operation as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }
+}
+
 fn fill_type_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
 	let mut widgets = start_widgets(document_node, node_id, index, "Fill Type", FrontendGraphDataType::General, true);
 	if let &NodeInput::Value {
@@ -710,16 +853,15 @@ fn gradient_type_widget(document_node: &DocumentNode, node_id: NodeId, index: us
 		exposed: false,
 	} = &document_node.inputs[index]
 	{
-		let entries = vec![
-			RadioEntryData::new("linear")
-				.label("Linear")
-				.on_update(update_value(move |_| TaggedValue::GradientType(GradientType::Linear), node_id, index))
-				.on_commit(commit_value),
-			RadioEntryData::new("radial")
-				.label("Radial")
-				.on_update(update_value(move |_| TaggedValue::GradientType(GradientType::Radial), node_id, index))
-				.on_commit(commit_value),
-		];
+		let entries = GradientType::list()
+			.into_iter()
+			.map(|gradient_type| {
+				RadioEntryData::new(format!("{gradient_type:?}").to_lowercase())
+					.label(format!("{gradient_type:?}"))
+					.on_update(update_value(move |_| TaggedValue::GradientType(gradient_type), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
 
 		widgets.extend_from_slice(&[
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
@@ -729,6 +871,36 @@ fn gradient_type_widget(document_node: &DocumentNode, node_id: NodeId, index: us
 	LayoutGroup::Row { widgets }
 }
 
+fn gradient_spread_method_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, "Spread Method", FrontendGraphDataType::General, true);
+	if let Some(&NodeInput::Value {
+		tagged_value: TaggedValue::GradientSpreadMethod(spread_method),
+		exposed: false,
+	}) = document_node.inputs.get(index)
+	{
+		let entries = GradientSpreadMethod::list()
+			.into_iter()
+			.map(|spread_method| {
+				RadioEntryData::new(format!("{spread_method:?}").to_lowercase())
+					.label(format!("{spread_method:?}"))
+					.tooltip(match spread_method {
+						GradientSpreadMethod::Pad => "Extend the colors of the first and last stops past the ends of the gradient",
+						GradientSpreadMethod::Reflect => "Repeat the gradient past its ends, mirroring every other repetition",
+						GradientSpreadMethod::Repeat => "Repeat the gradient past its ends",
+					})
+					.on_update(update_value(move |_| TaggedValue::GradientSpreadMethod(spread_method), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(spread_method as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }
+}
+
 fn gradient_row(row: &mut Vec<WidgetHolder>, positions: &Vec<(f64, Color)>, index: usize, node_id: NodeId, input_index: usize) {
 	let label = TextLabel::new(format!("Gradient: {:.0}%", positions[index].0 * 100.)).tooltip("Adjustable by dragging the gradient stops in the viewport with the Gradient tool active");
 	row.push(label.widget_holder());
@@ -1421,6 +1593,30 @@ pub fn opacity_properties(document_node: &DocumentNode, node_id: NodeId, _contex
 	vec![LayoutGroup::Row { widgets: gamma }]
 }
 
+pub fn shadow_effect_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let color = color_widget(document_node, node_id, 1, "Color", ColorButton::default(), true);
+	let offset = vec2_widget(document_node, node_id, 2, "Offset", "X", "Y", " px", None, add_blank_assist);
+	let blur = number_widget(document_node, node_id, 3, "Blur", NumberInput::default().min(0.).unit(" px"), true);
+	let blend_mode = blend_mode(document_node, node_id, 4, "Blend Mode", true);
+
+	vec![color, offset, LayoutGroup::Row { widgets: blur }, blend_mode]
+}
+
+pub fn outer_glow_effect_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let color = color_widget(document_node, node_id, 1, "Color", ColorButton::default(), true);
+	let size = number_widget(document_node, node_id, 2, "Size", NumberInput::default().min(0.).unit(" px"), true);
+	let blend_mode = blend_mode(document_node, node_id, 3, "Blend Mode", true);
+
+	vec![color, LayoutGroup::Row { widgets: size }, blend_mode]
+}
+
+pub fn stroke_effect_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let color = color_widget(document_node, node_id, 1, "Color", ColorButton::default(), true);
+	let width = number_widget(document_node, node_id, 2, "Width", NumberInput::default().min(0.).unit(" px"), true);
+
+	vec![color, LayoutGroup::Row { widgets: width }]
+}
+
 pub fn blend_mode_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	vec![blend_mode(document_node, node_id, 1, "Blend Mode", true)]
 }
@@ -1758,6 +1954,31 @@ pub fn node_section_font(document_node: &DocumentNode, node_id: NodeId, _context
 	result
 }
 
+pub fn typography_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let text = text_area_widget(document_node, node_id, 1, "Text", true);
+	let (font, style) = font_inputs(document_node, node_id, 2, "Font", true);
+	let size = number_widget(document_node, node_id, 3, "Size", NumberInput::default().unit(" px").min(1.), true);
+	let tracking = number_widget(document_node, node_id, 4, "Tracking", NumberInput::default().unit(" px"), true);
+	let leading = number_widget(document_node, node_id, 5, "Leading", NumberInput::default().min(0.).step(0.1), true);
+	let alignment = text_alignment_widget(document_node, node_id, 6, "Alignment", true);
+	let box_width = number_widget(document_node, node_id, 7, "Box Width", NumberInput::default().unit(" px").min(0.), true);
+	let color = color_widget(document_node, node_id, 8, "Color", ColorButton::default(), true);
+
+	let mut result = vec![LayoutGroup::Row { widgets: text }, LayoutGroup::Row { widgets: font }];
+	if let Some(style) = style {
+		result.push(LayoutGroup::Row { widgets: style });
+	}
+	result.extend([
+		LayoutGroup::Row { widgets: size },
+		LayoutGroup::Row { widgets: tracking },
+		LayoutGroup::Row { widgets: leading },
+		alignment,
+		LayoutGroup::Row { widgets: box_width },
+		color,
+	]);
+	result
+}
+
 pub fn imaginate_properties(document_node: &DocumentNode, node_id: NodeId, context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let imaginate_node = [context.nested_path, &[node_id]].concat();
 
@@ -1998,7 +2219,16 @@ pub fn imaginate_properties(document_node: &DocumentNode, node_id: NodeId, conte
 				Separator::new(SeparatorType::Unrelated).widget_holder(),
 				IconButton::new("Rescale", 24)
 					.tooltip("Set the layer dimensions to this resolution")
-					.on_update(move |_| DialogMessage::RequestComingSoonDialog { issue: None }.into())
+					.on_update({
+						let imaginate_node = imaginate_node.clone();
+						move |_| {
+							DocumentMessage::ImaginateRescale {
+								imaginate_node: imaginate_node.clone(),
+								resolution: vec2,
+							}
+							.into()
+						}
+					})
 					.widget_holder(),
 				Separator::new(SeparatorType::Unrelated).widget_holder(),
 				CheckboxInput::new(!dimensions_is_auto || transform_not_connected)
@@ -2260,6 +2490,14 @@ pub fn generate_node_properties(document_node: &DocumentNode, node_id: NodeId, c
 	}
 }
 
+pub fn offset_path_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let distance = number_widget(document_node, node_id, 1, "Distance", NumberInput::default().unit(" px"), true);
+	let line_join = line_join_widget(document_node, node_id, 2, "Line Join", true);
+	let miter_limit = number_widget(document_node, node_id, 3, "Miter Limit", NumberInput::default().min(0.), true);
+
+	vec![LayoutGroup::Row { widgets: distance }, line_join, LayoutGroup::Row { widgets: miter_limit }]
+}
+
 pub fn stroke_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let color_index = 1;
 	let weight_index = 2;
@@ -2268,6 +2506,7 @@ pub fn stroke_properties(document_node: &DocumentNode, node_id: NodeId, _context
 	let line_cap_index = 5;
 	let line_join_index = 6;
 	let miter_limit_index = 7;
+	let gradient_index = 8;
 
 	let color = color_widget(document_node, node_id, color_index, "Color", ColorButton::default(), true);
 	let weight = number_widget(document_node, node_id, weight_index, "Weight", NumberInput::default().unit("px").min(0.), true);
@@ -2276,9 +2515,11 @@ pub fn stroke_properties(document_node: &DocumentNode, node_id: NodeId, _context
 	let line_cap = line_cap_widget(document_node, node_id, line_cap_index, "Line Cap", true);
 	let line_join = line_join_widget(document_node, node_id, line_join_index, "Line Join", true);
 	let miter_limit = number_widget(document_node, node_id, miter_limit_index, "Miter Limit", NumberInput::default().min(0.), true);
+	let gradient = stroke_gradient_widget(document_node, node_id, gradient_index);
 
 	vec![
 		color,
+		LayoutGroup::Row { widgets: gradient },
 		LayoutGroup::Row { widgets: weight },
 		LayoutGroup::Row { widgets: dash_lengths },
 		LayoutGroup::Row { widgets: dash_offset },
@@ -2288,6 +2529,34 @@ pub fn stroke_properties(document_node: &DocumentNode, node_id: NodeId, _context
 	]
 }
 
+/// Shows whether the stroke is painted with a gradient (which overrides its color) and offers to switch it back to the solid color.
+fn stroke_gradient_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> Vec<WidgetHolder> {
+	let mut widgets = start_widgets(document_node, node_id, index, "Gradient", FrontendGraphDataType::General, true);
+
+	if let Some(NodeInput::Value {
+		tagged_value: TaggedValue::OptionalGradient(gradient),
+		exposed: false,
+	}) = &document_node.inputs.get(index)
+	{
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+		if let Some(gradient) = gradient {
+			widgets.extend_from_slice(&[
+				TextLabel::new(format!("{:?}, {} stops", gradient.gradient_type, gradient.positions.len())).widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("Use Color")
+					.tooltip("Remove the gradient so the stroke is painted with its color")
+					.on_update(update_value(|_: &TextButton| TaggedValue::OptionalGradient(None), node_id, index))
+					.on_commit(commit_value)
+					.widget_holder(),
+			]);
+		} else {
+			widgets.push(TextLabel::new("None").widget_holder());
+		}
+	}
+
+	widgets
+}
+
 pub fn repeat_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let direction = vec2_widget(document_node, node_id, 1, "Direction", "X", "Y", " px", None, add_blank_assist);
 	let angle = number_widget(document_node, node_id, 2, "Angle", NumberInput::default().unit("°"), true);
@@ -2385,12 +2654,19 @@ pub fn morph_properties(document_node: &DocumentNode, node_id: NodeId, _context:
 	]
 }
 
+pub fn boolean_operation_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let operation = boolean_operation_widget(document_node, node_id, 2, "Operation", true);
+
+	vec![operation]
+}
+
 /// Fill Node Widgets LayoutGroup
 pub fn fill_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let fill_type_index = 1;
 	let solid_color_index = 2;
 	let gradient_type_index = 3;
 	let positions_index = 7;
+	let spread_method_index = 8;
 
 	let fill_type = if let &NodeInput::Value {
 		tagged_value: TaggedValue::FillType(fill_type),
@@ -2417,6 +2693,8 @@ pub fn fill_properties(document_node: &DocumentNode, node_id: NodeId, _context:
 	if fill_type.is_none() || gradient {
 		let gradient_type_switch = gradient_type_widget(document_node, node_id, gradient_type_index);
 		widgets.push(gradient_type_switch);
+		let spread_method_switch = gradient_spread_method_widget(document_node, node_id, spread_method_index);
+		widgets.push(spread_method_switch);
 		gradient_positions(&mut widgets, document_node, "Gradient Positions", node_id, positions_index);
 	}
 
@@ -2450,3 +2728,54 @@ pub fn image_color_palette(document_node: &DocumentNode, node_id: NodeId, _conte
 
 	vec![LayoutGroup::Row { widgets: size }]
 }
+
+pub fn blur_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let radius = number_widget(document_node, node_id, 1, "Radius", NumberInput::default().min(0.).unit(" px"), true);
+
+	vec![LayoutGroup::Row { widgets: radius }]
+}
+
+pub fn motion_blur_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let angle = number_widget(document_node, node_id, 1, "Angle", NumberInput::default().min(-180.).max(180.).unit("°"), true);
+	let distance = number_widget(document_node, node_id, 2, "Distance", NumberInput::default().min(0.).unit(" px"), true);
+
+	vec![LayoutGroup::Row { widgets: angle }, LayoutGroup::Row { widgets: distance }]
+}
+
+pub fn unsharp_mask_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let radius = number_widget(document_node, node_id, 1, "Radius", NumberInput::default().min(0.).unit(" px"), true);
+	let amount = number_widget(document_node, node_id, 2, "Amount", NumberInput::default().min(0.).max(500.).unit("%"), true);
+	let threshold = number_widget(document_node, node_id, 3, "Threshold", NumberInput::default().percentage(), true);
+
+	vec![LayoutGroup::Row { widgets: radius }, LayoutGroup::Row { widgets: amount }, LayoutGroup::Row { widgets: threshold }]
+}
+
+pub fn convolution_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let kernel = vec_f64_input(document_node, node_id, 1, "Kernel", TextInput::default().centered(true), true);
+	let normalize = bool_widget(document_node, node_id, 2, "Normalize", true);
+
+	vec![LayoutGroup::Row { widgets: kernel }, LayoutGroup::Row { widgets: normalize }]
+}
+
+pub fn resample_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let resolution = vec2_widget(document_node, node_id, 1, "Resolution", "W", "H", " px", Some(0.), add_blank_assist);
+	let filter = resample_filter(document_node, node_id, 2, "Filter", true);
+
+	vec![resolution, filter]
+}
+
+pub fn flood_fill_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let seed = vec2_widget(document_node, node_id, 1, "Seed", "X", "Y", "", None, add_blank_assist);
+	let color = color_widget(document_node, node_id, 2, "Color", ColorButton::default(), true);
+	let tolerance = number_widget(document_node, node_id, 3, "Tolerance", NumberInput::default().percentage(), true);
+	let contiguous = bool_widget(document_node, node_id, 4, "Contiguous", true);
+	let antialias = bool_widget(document_node, node_id, 5, "Anti-aliasing", true);
+
+	vec![
+		seed,
+		color,
+		LayoutGroup::Row { widgets: tolerance },
+		LayoutGroup::Row { widgets: contiguous },
+		LayoutGroup::Row { widgets: antialias },
+	]
+}
@@ -40,6 +40,14 @@ impl MessageHandler<OverlaysMessage, OverlaysMessageData<'_>> for OverlaysMessag
 						render_context: context.clone(),
 						size: size.as_dvec2(),
 					}));
+					responses.add(GuideMessage::Draw(OverlayContext {
+						render_context: context.clone(),
+						size: size.as_dvec2(),
+					}));
+					responses.add(SelectModeMessage::Draw(OverlayContext {
+						render_context: context.clone(),
+						size: size.as_dvec2(),
+					}));
 					for provider in &self.overlay_providers {
 						responses.add(provider(OverlayContext {
 							render_context: context.clone(),
@@ -0,0 +1,9 @@
+mod select_mode_message;
+mod select_mode_message_handler;
+
+pub mod utility_types;
+
+#[doc(inline)]
+pub use select_mode_message::{SelectModeMessage, SelectModeMessageDiscriminant};
+#[doc(inline)]
+pub use select_mode_message_handler::{SelectModeMessageData, SelectModeMessageHandler};
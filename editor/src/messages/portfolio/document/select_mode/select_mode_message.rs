@@ -0,0 +1,23 @@
+use super::utility_types::SelectionShape;
+use crate::messages::input_mapper::utility_types::input_keyboard::Key;
+use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::prelude::*;
+
+#[impl_message(Message, DocumentMessage, SelectMode)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum SelectModeMessage {
+	// Messages
+	Abort,
+	ConvertToLayer,
+	ConvertToPath,
+	Deselect,
+	Draw(OverlayContext),
+	MaskSelectedLayers,
+	PointerDown { extend: Key, subtract: Key },
+	PointerMove,
+	PointerUp,
+	SetMagicWandContiguous { contiguous: bool },
+	SetMagicWandTolerance { tolerance: f64 },
+	SetShape { shape: SelectionShape },
+	UpdateHints,
+}
@@ -0,0 +1,312 @@
+use super::utility_types::{pixel_outlines, MagicWandOptions, SelectionCombination, SelectionMask, SelectionShape};
+use crate::messages::frontend::utility_types::MouseCursorIcon;
+use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
+use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
+use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
+use crate::messages::portfolio::document::utility_types::misc::DocumentMode;
+use crate::messages::portfolio::document::utility_types::nodes::SelectedNodes;
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils::{self, get_image_frame, get_upstream_raster_transform, is_layer_fed_by_node_of_name};
+use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
+
+use bezier_rs::Subpath;
+use graph_craft::document::{NodeId, NodeNetwork};
+use graphene_core::raster::ImageFrame;
+use graphene_core::uuid::{generate_uuid, ManipulatorGroupId};
+use graphene_std::flood_fill::{flood_fill_coverage, seed_pixel};
+
+use glam::{DAffine2, DVec2};
+
+pub struct SelectModeMessageData<'a> {
+	pub selection_mask: &'a mut SelectionMask,
+	pub network: &'a NodeNetwork,
+	pub metadata: &'a DocumentMetadata,
+	pub selected_nodes: &'a SelectedNodes,
+	pub new_layer_parent: LayerNodeIdentifier,
+	pub ipp: &'a InputPreprocessorMessageHandler,
+	pub document_mode: DocumentMode,
+}
+
+/// An area being dragged out or traced with the pointer, which is combined with the selection when the pointer is released.
+#[derive(Debug, Clone, PartialEq)]
+struct SelectionDrag {
+	combination: SelectionCombination,
+	/// The document space points which the pointer has passed through, starting with where it was pressed.
+	points: Vec<DVec2>,
+}
+
+impl SelectionDrag {
+	/// The outline of the dragged area in document space, or `None` if it doesn't enclose anything yet.
+	fn area(&self, shape: SelectionShape) -> Option<Subpath<ManipulatorGroupId>> {
+		let (&start, &end) = (self.points.first()?, self.points.last()?);
+		let enclosing = match shape {
+			SelectionShape::Rectangle | SelectionShape::Ellipse => (end - start).abs().min_element() > 0.,
+			SelectionShape::Lasso => self.points.len() > 2,
+			// The magic wand selects by clicking, so it never drags out an area
+			SelectionShape::MagicWand => false,
+		};
+		if !enclosing {
+			return None;
+		}
+
+		Some(match shape {
+			SelectionShape::Rectangle => Subpath::new_rect(start, end),
+			SelectionShape::Ellipse => Subpath::new_ellipse(start, end),
+			SelectionShape::Lasso => Subpath::from_anchors(self.points.iter().copied(), true),
+			SelectionShape::MagicWand => return None,
+		})
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SelectModeMessageHandler {
+	shape: SelectionShape,
+	magic_wand_options: MagicWandOptions,
+	drag: Option<SelectionDrag>,
+}
+
+impl MessageHandler<SelectModeMessage, SelectModeMessageData<'_>> for SelectModeMessageHandler {
+	fn process_message(&mut self, message: SelectModeMessage, responses: &mut VecDeque<Message>, data: SelectModeMessageData) {
+		let SelectModeMessageData {
+			selection_mask,
+			network,
+			metadata,
+			selected_nodes,
+			new_layer_parent,
+			ipp,
+			document_mode,
+		} = data;
+		let document_to_viewport = metadata.document_to_viewport;
+		let pointer_position = document_to_viewport.inverse().transform_point2(ipp.mouse.position);
+
+		match message {
+			SelectModeMessage::Abort => {
+				if self.drag.take().is_none() {
+					return;
+				}
+
+				responses.add(SelectModeMessage::UpdateHints);
+				responses.add(OverlaysMessage::Draw);
+			}
+			SelectModeMessage::ConvertToLayer => {
+				let Some(mut image_frame) = selection_mask.rasterize() else { return };
+
+				// Like a pasted image, the layer's transform places its pixels in the document
+				let transform = metadata.transform_to_document(new_layer_parent).inverse() * image_frame.transform;
+				image_frame.transform = Default::default();
+
+				responses.add(DocumentMessage::StartTransaction);
+				let layer = graph_modification_utils::new_image_layer(image_frame, NodeId(generate_uuid()), new_layer_parent, responses);
+				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![layer.to_node()] });
+				responses.add(GraphOperationMessage::TransformSet {
+					layer,
+					transform,
+					transform_in: TransformIn::Local,
+					skip_rerender: false,
+				});
+			}
+			SelectModeMessage::ConvertToPath => {
+				if selection_mask.is_empty() {
+					return;
+				}
+
+				let document_to_parent = metadata.transform_to_document(new_layer_parent).inverse();
+				let subpaths = selection_mask
+					.subpaths()
+					.iter()
+					.map(|subpath| {
+						let mut subpath = subpath.clone();
+						subpath.apply_transform(document_to_parent);
+						subpath
+					})
+					.collect();
+
+				responses.add(DocumentMessage::StartTransaction);
+				graph_modification_utils::new_vector_layer(subpaths, NodeId(generate_uuid()), new_layer_parent, responses);
+			}
+			SelectModeMessage::Deselect => {
+				self.drag = None;
+				selection_mask.clear();
+
+				responses.add(OverlaysMessage::Draw);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			SelectModeMessage::Draw(mut overlay_context) => {
+				overlay_context.outline(selection_mask.subpaths().iter(), document_to_viewport);
+
+				if let Some(area) = self.drag.as_ref().and_then(|drag| drag.area(self.shape)) {
+					overlay_context.outline(std::iter::once(&area), document_to_viewport);
+				}
+			}
+			SelectModeMessage::MaskSelectedLayers => {
+				let Some(stencil) = selection_mask.rasterize() else { return };
+
+				// Only raster content can be masked by the Mask node
+				let raster_layers: Vec<_> = selected_nodes
+					.selected_layers(metadata)
+					.filter(|&layer| is_layer_fed_by_node_of_name(layer, network, "Image") || is_layer_fed_by_node_of_name(layer, network, "Brush"))
+					.collect();
+				if raster_layers.is_empty() {
+					return;
+				}
+
+				responses.add(DocumentMessage::StartTransaction);
+				for layer in raster_layers {
+					// The Mask node sits directly upstream of the layer, where the raster data has already been placed in the space of the layer's parent
+					let parent = layer.parent(metadata).unwrap_or_default();
+					let stencil = ImageFrame {
+						transform: metadata.transform_to_document(parent).inverse() * stencil.transform,
+						..stencil.clone()
+					};
+					responses.add(GraphOperationMessage::MaskSet { layer, stencil });
+				}
+			}
+			SelectModeMessage::PointerDown { extend, subtract } => {
+				let combination = SelectionCombination::from_keys(ipp.keyboard.get(extend as usize), ipp.keyboard.get(subtract as usize));
+
+				// The magic wand selects with a single click rather than a drag
+				if self.shape == SelectionShape::MagicWand {
+					match self.magic_wand_area(pointer_position, network, metadata, selected_nodes) {
+						Some(area) => selection_mask.combine(area, combination),
+						None if combination == SelectionCombination::Replace => selection_mask.clear(),
+						None => {}
+					}
+
+					responses.add(OverlaysMessage::Draw);
+					responses.add(PortfolioMessage::UpdateDocumentWidgets);
+					return;
+				}
+
+				self.drag = Some(SelectionDrag {
+					combination,
+					points: vec![pointer_position],
+				});
+
+				responses.add(FrontendMessage::UpdateInputHints {
+					hint_data: HintData(vec![HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()])]),
+				});
+			}
+			SelectModeMessage::PointerMove => {
+				// Pointer movement is ignored (rather than left to the tools) until an area is being selected
+				let Some(drag) = &mut self.drag else { return };
+
+				// A rectangle or ellipse only needs the corners it's dragged between
+				if self.shape != SelectionShape::Lasso && drag.points.len() > 1 {
+					drag.points.pop();
+				}
+				drag.points.push(pointer_position);
+
+				responses.add(OverlaysMessage::Draw);
+			}
+			SelectModeMessage::PointerUp => {
+				let Some(drag) = self.drag.take() else { return };
+
+				match drag.area(self.shape) {
+					Some(area) => selection_mask.combine(vec![area], drag.combination),
+					// Clicking without dragging out an area deselects, unless it's modifying the selection
+					None if drag.combination == SelectionCombination::Replace => selection_mask.clear(),
+					None => {}
+				}
+
+				responses.add(SelectModeMessage::UpdateHints);
+				responses.add(OverlaysMessage::Draw);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			SelectModeMessage::SetMagicWandContiguous { contiguous } => {
+				self.magic_wand_options.contiguous = contiguous;
+
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			SelectModeMessage::SetMagicWandTolerance { tolerance } => {
+				self.magic_wand_options.tolerance = tolerance;
+
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			SelectModeMessage::SetShape { shape } => {
+				self.shape = shape;
+
+				responses.add(SelectModeMessage::UpdateHints);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			SelectModeMessage::UpdateHints => {
+				if document_mode != DocumentMode::SelectMode {
+					return;
+				}
+
+				let hint_data = HintData(vec![
+					match self.shape {
+						SelectionShape::MagicWand => HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Select Matching Color")]),
+						_ => HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Select Area")]),
+					},
+					HintGroup(vec![
+						HintInfo::keys([Key::Shift], "Add").prepend_plus(),
+						HintInfo::keys([Key::Alt], "Subtract").prepend_plus(),
+						HintInfo::keys([Key::Shift, Key::Alt], "Intersect").prepend_plus(),
+					]),
+					HintGroup(vec![HintInfo::keys([Key::Accel, Key::KeyD], "Deselect")]),
+				]);
+				responses.add(FrontendMessage::UpdateInputHints { hint_data });
+				responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Crosshair });
+			}
+		}
+	}
+
+	fn actions(&self) -> ActionList {
+		if self.drag.is_some() {
+			actions!(SelectModeMessageDiscriminant;
+				Abort,
+				PointerMove,
+				PointerUp,
+			)
+		} else {
+			actions!(SelectModeMessageDiscriminant;)
+		}
+	}
+}
+
+impl SelectModeMessageHandler {
+	/// Actions which take over the pointer from the tools while the document is in Select Mode.
+	pub fn actions_additional_in_select_mode(&self) -> ActionList {
+		actions!(SelectModeMessageDiscriminant;
+			Deselect,
+			PointerDown,
+			PointerMove,
+		)
+	}
+
+	pub fn shape(&self) -> SelectionShape {
+		self.shape
+	}
+
+	pub fn magic_wand_options(&self) -> MagicWandOptions {
+		self.magic_wand_options
+	}
+
+	/// The outlines (in document space) of the pixels matching the one clicked on in the image of the first selected image layer under the pointer.
+	/// The image is sampled from the layer's Image node, so it doesn't include changes made to it by the layer's other nodes.
+	fn magic_wand_area(&self, document_position: DVec2, network: &NodeNetwork, metadata: &DocumentMetadata, selected_nodes: &SelectedNodes) -> Option<Vec<Subpath<ManipulatorGroupId>>> {
+		selected_nodes.selected_layers(metadata).find_map(|layer| {
+			let image_frame = get_image_frame(layer, network)?;
+			let parent = layer.parent(metadata).unwrap_or_default();
+			let frame_to_document = metadata.transform_to_document(parent) * get_upstream_raster_transform(layer, network, metadata);
+			let seed = seed_pixel(image_frame, frame_to_document.inverse().transform_point2(document_position))?;
+
+			let MagicWandOptions { tolerance, contiguous } = self.magic_wand_options;
+			let (width, height) = (image_frame.image.width as usize, image_frame.image.height as usize);
+			let filled: Vec<_> = flood_fill_coverage(&image_frame.image, seed, tolerance, contiguous, false)
+				.into_iter()
+				.map(|coverage| coverage > 0.)
+				.collect();
+
+			let size = DVec2::new(width as f64, height as f64);
+			let pixel_to_document = frame_to_document * image_frame.transform * DAffine2::from_scale(size.recip());
+			let outlines = pixel_outlines(&filled, width, height);
+			Some(
+				outlines
+					.into_iter()
+					.map(|outline| Subpath::from_anchors(outline.into_iter().map(|point| pixel_to_document.transform_point2(point)), true))
+					.collect(),
+			)
+		})
+	}
+}
@@ -0,0 +1,247 @@
+use bezier_rs::{BezierHandles, BooleanOperation, Subpath};
+use graphene_core::raster::{Color, Image, ImageFrame};
+use graphene_core::uuid::ManipulatorGroupId;
+use graphene_core::vector::brush_stroke::polygon_coverage;
+
+use glam::{DAffine2, DVec2};
+use std::collections::HashMap;
+
+/// How many straight lines each curve of the selection is divided into when it's flattened into polygons.
+const CURVE_FLATTENING_STEPS: usize = 16;
+/// The largest width or height of the image a selection is rasterized into. Larger selections are rasterized at less than one pixel per document unit.
+const MAX_RASTER_SIZE: f64 = 4096.;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SelectionShape {
+	/// A rectangular marquee dragged out from one corner to the opposite one.
+	#[default]
+	Rectangle,
+	/// An elliptical marquee filling the rectangle dragged out from one corner to the opposite one.
+	Ellipse,
+	/// A freehand area traced by the pointer and closed by a straight line back to where it started.
+	Lasso,
+	/// The pixels of a selected image layer's image which match the color of the one clicked on.
+	MagicWand,
+}
+
+/// How the magic wand decides which pixels match the one clicked on, like the Fill tool does for raster layers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MagicWandOptions {
+	/// How different the colors of the selected pixels may be from the clicked pixel, as a percentage of the largest possible difference.
+	pub tolerance: f64,
+	/// Whether only pixels connected to the clicked pixel are selected, instead of every matching pixel of the image.
+	pub contiguous: bool,
+}
+
+impl Default for MagicWandOptions {
+	fn default() -> Self {
+		Self { tolerance: 10., contiguous: true }
+	}
+}
+
+/// How a newly drawn area is combined with the existing selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionCombination {
+	Replace,
+	Add,
+	Subtract,
+	Intersect,
+}
+
+impl SelectionCombination {
+	/// The combination chosen by holding the extend (add) and subtract keys, which intersect when held together.
+	pub fn from_keys(extend: bool, subtract: bool) -> Self {
+		match (extend, subtract) {
+			(false, false) => Self::Replace,
+			(true, false) => Self::Add,
+			(false, true) => Self::Subtract,
+			(true, true) => Self::Intersect,
+		}
+	}
+}
+
+/// Traces the outlines of the filled pixels of an image (given in row-major order) as closed polygons in pixel coordinates.
+/// Outer outlines run clockwise (with the y-axis pointing down) and the outlines of holes run counterclockwise, so the polygons enclose exactly the filled pixels by the nonzero rule.
+pub fn pixel_outlines(filled: &[bool], width: usize, height: usize) -> Vec<Vec<DVec2>> {
+	let is_filled = |x: i64, y: i64| x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height && filled[y as usize * width + x as usize];
+
+	// Each side of a filled pixel which borders an unfilled one is an edge of an outline, directed so the filled pixel is on its right
+	let mut edges: HashMap<(i64, i64), Vec<(i64, i64)>> = HashMap::new();
+	for y in 0..height as i64 {
+		for x in 0..width as i64 {
+			if !is_filled(x, y) {
+				continue;
+			}
+			let sides = [
+				((x, y - 1), (x, y), (x + 1, y)),
+				((x + 1, y), (x + 1, y), (x + 1, y + 1)),
+				((x, y + 1), (x + 1, y + 1), (x, y + 1)),
+				((x - 1, y), (x, y + 1), (x, y)),
+			];
+			for ((neighbor_x, neighbor_y), start, end) in sides {
+				if !is_filled(neighbor_x, neighbor_y) {
+					edges.entry(start).or_default().push(end);
+				}
+			}
+		}
+	}
+
+	// Every corner has as many edges leaving it as arriving, so following the edges from any corner leads back around to it.
+	// Where two filled pixels only touch at a corner, either way around it gives the same enclosed area.
+	let mut outlines = Vec::new();
+	while let Some(&start) = edges.keys().next() {
+		let mut corners = vec![start];
+		let mut corner = start;
+		loop {
+			let Some(ends) = edges.get_mut(&corner) else { break };
+			let end = ends.pop().expect("Corners without edges are removed");
+			if ends.is_empty() {
+				edges.remove(&corner);
+			}
+			if end == start {
+				break;
+			}
+			corners.push(end);
+			corner = end;
+		}
+
+		// Only the corners where the outline turns are kept
+		let count = corners.len();
+		let turning = (0..count).filter(|&index| {
+			let (previous, current, next) = (corners[(index + count - 1) % count], corners[index], corners[(index + 1) % count]);
+			(current.0 - previous.0, current.1 - previous.1) != (next.0 - current.0, next.1 - current.1)
+		});
+		outlines.push(turning.map(|index| DVec2::new(corners[index].0 as f64, corners[index].1 as f64)).collect());
+	}
+
+	outlines
+}
+
+/// The area of the document selected in Select Mode, which confines raster editing such as brush strokes. It's empty when nothing is selected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelectionMask {
+	/// The closed outlines of the selected area in document space, filled by the nonzero rule.
+	subpaths: Vec<Subpath<ManipulatorGroupId>>,
+}
+
+impl SelectionMask {
+	pub fn is_empty(&self) -> bool {
+		self.subpaths.is_empty()
+	}
+
+	pub fn subpaths(&self) -> &[Subpath<ManipulatorGroupId>] {
+		&self.subpaths
+	}
+
+	pub fn clear(&mut self) {
+		self.subpaths.clear();
+	}
+
+	/// Combines the area enclosed by the subpaths (in document space) with the selection.
+	pub fn combine(&mut self, area: Vec<Subpath<ManipulatorGroupId>>, combination: SelectionCombination) {
+		let operation = match combination {
+			// Tracing the outline of the area by itself resolves any self-intersections of a lasso
+			SelectionCombination::Replace => {
+				self.subpaths = Subpath::boolean_operation(&area, &[], BooleanOperation::Union);
+				return;
+			}
+			SelectionCombination::Add => BooleanOperation::Union,
+			SelectionCombination::Subtract => BooleanOperation::SubtractFront,
+			SelectionCombination::Intersect => BooleanOperation::Intersect,
+		};
+		self.subpaths = Subpath::boolean_operation(&self.subpaths, &area, operation);
+	}
+
+	/// The bounding box of the selection in document space.
+	pub fn bounds(&self) -> Option<[DVec2; 2]> {
+		self.subpaths
+			.iter()
+			.filter_map(|subpath| subpath.bounding_box())
+			.reduce(|[a_min, a_max], [b_min, b_max]| [a_min.min(b_min), a_max.max(b_max)])
+	}
+
+	/// The selection's outlines with their curves flattened into straight lines, as closed polygons in document space.
+	pub fn polygons(&self) -> Vec<Vec<DVec2>> {
+		self.subpaths
+			.iter()
+			.map(|subpath| {
+				let mut polygon = Vec::new();
+				for bezier in subpath.iter() {
+					match bezier.handles {
+						BezierHandles::Linear => polygon.push(bezier.start()),
+						// Every point of the lookup table but the last, which starts the next curve
+						_ => polygon.extend(bezier.compute_lookup_table(Some(CURVE_FLATTENING_STEPS), None).take(CURVE_FLATTENING_STEPS)),
+					}
+				}
+				polygon
+			})
+			.filter(|polygon| polygon.len() > 2)
+			.collect()
+	}
+
+	/// Renders the selection as a grayscale image which is white where the document is selected and black elsewhere, with anti-aliased edges.
+	/// A black border surrounds the selected area, so sampling beyond the edges of the image (as the Mask node does) stays outside the selection.
+	pub fn rasterize(&self) -> Option<ImageFrame<Color>> {
+		let [min, max] = self.bounds()?;
+		let pixel_size = ((max - min).max_element() / MAX_RASTER_SIZE).max(1.);
+		// Bounds which are a rounding error away from a pixel boundary (such as from an intersection in a boolean operation) don't get a pixel of their own
+		let min = ((min / pixel_size) + 1e-6).floor() * pixel_size - pixel_size;
+		let max = ((max / pixel_size) - 1e-6).ceil() * pixel_size + pixel_size;
+		let size = ((max - min) / pixel_size).round().as_uvec2();
+
+		let pixel_to_document = DAffine2::from_scale_angle_translation(DVec2::splat(pixel_size), 0., min);
+		let coverage = polygon_coverage(&self.polygons(), size.x as usize, size.y as usize, pixel_to_document);
+		let image = Image {
+			width: size.x,
+			height: size.y,
+			data: coverage.into_iter().map(|coverage| Color::from_rgbaf32_unchecked(coverage, coverage, coverage, 1.)).collect(),
+			..Default::default()
+		};
+
+		Some(ImageFrame {
+			image,
+			transform: DAffine2::from_scale_angle_translation(max - min, 0., min),
+			..Default::default()
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn combine_and_rasterize_selections() {
+		let mut mask = SelectionMask::default();
+		mask.combine(vec![Subpath::new_rect(DVec2::ZERO, DVec2::new(4., 2.))], SelectionCombination::Replace);
+		mask.combine(vec![Subpath::new_rect(DVec2::new(2., -1.), DVec2::new(6., 3.))], SelectionCombination::Subtract);
+		let [min, max] = mask.bounds().unwrap();
+		assert!(min.abs_diff_eq(DVec2::ZERO, 1e-9) && max.abs_diff_eq(DVec2::splat(2.), 1e-9));
+
+		// The selected 2x2 square is surrounded by a one pixel black border
+		let frame = mask.rasterize().unwrap();
+		assert_eq!((frame.image.width, frame.image.height), (4, 4));
+		assert_eq!(frame.transform, DAffine2::from_scale_angle_translation(DVec2::splat(4.), 0., DVec2::splat(-1.)));
+		let white: Vec<_> = frame.image.data.iter().map(|pixel| pixel.r() == 1.).collect();
+		assert_eq!(white, [[false; 4], [false, true, true, false], [false, true, true, false], [false; 4]].concat());
+
+		mask.combine(vec![Subpath::new_rect(DVec2::new(10., 10.), DVec2::new(11., 11.))], SelectionCombination::Intersect);
+		assert!(mask.is_empty());
+		assert!(mask.rasterize().is_none());
+	}
+
+	#[test]
+	fn trace_pixel_outlines() {
+		// A 3x3 ring of pixels with a hole in the middle
+		let filled = [[true; 3], [true, false, true], [true; 3]].concat();
+		let mut outlines = pixel_outlines(&filled, 3, 3);
+		outlines.sort_by_key(|outline| std::cmp::Reverse(outline.len()));
+		assert_eq!(outlines.len(), 2);
+
+		let signed_area = |outline: &[DVec2]| outline.iter().zip(outline.iter().cycle().skip(1)).map(|(a, b)| a.perp_dot(*b)).sum::<f64>() / 2.;
+		let [outer, hole] = [&outlines[0], &outlines[1]];
+		assert_eq!((outer.len(), hole.len()), (4, 4));
+		assert_eq!((signed_area(outer), signed_area(hole)), (9., -1.));
+		assert!(outer.contains(&DVec2::new(3., 3.)) && hole.contains(&DVec2::new(2., 2.)));
+	}
+}
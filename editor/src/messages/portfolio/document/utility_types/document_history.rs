@@ -0,0 +1,551 @@
+use crate::consts::DEFAULT_UNDO_HISTORY_BUDGET;
+use crate::messages::portfolio::document::guides::utility_types::Guide;
+
+use graph_craft::document::{DocumentNode, DocumentNodeImplementation, DocumentNodeMetadata, NodeId, NodeInput, NodeNetwork, NodeOutput};
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+
+const INITIAL_STATE_NAME: &str = "Initial State";
+const GUIDES_CHANGED_NAME: &str = "Change Guides";
+
+/// The parts of a document that its history records.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DocumentState {
+	pub network: NodeNetwork,
+	pub guides: Vec<Guide>,
+}
+
+impl DocumentState {
+	pub fn current_hash(&self) -> u64 {
+		let mut hasher = DefaultHasher::new();
+		self.network.current_hash().hash(&mut hasher);
+		for guide in &self.guides {
+			guide.direction.hash(&mut hasher);
+			guide.position.to_bits().hash(&mut hasher);
+		}
+		hasher.finish()
+	}
+}
+
+/// The structural changes that turn one [`NodeNetwork`] into another.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct NetworkDiff {
+	nodes: Vec<(NodeId, NodeChange)>,
+	imports: Option<Vec<NodeId>>,
+	exports: Option<Vec<NodeOutput>>,
+	previous_outputs: Option<Option<Vec<NodeOutput>>>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+enum NodeChange {
+	/// The node is added, or replaced as a whole because more than its inputs, position, or inner network changed.
+	Set(Box<DocumentNode>),
+	Remove,
+	Modify {
+		inputs: Vec<(usize, NodeInput)>,
+		metadata: Option<DocumentNodeMetadata>,
+		network: Option<NetworkDiff>,
+	},
+}
+
+impl NodeChange {
+	fn between(from: &DocumentNode, to: &DocumentNode) -> Option<Self> {
+		if from == to {
+			return None;
+		}
+
+		// Destructured so a new field on the node can't be forgotten here
+		let DocumentNode {
+			alias,
+			name,
+			inputs,
+			manual_composition,
+			has_primary_output,
+			implementation: _,
+			is_layer,
+			visible,
+			locked,
+			metadata: _,
+			skip_deduplication,
+			world_state_hash,
+			original_location,
+		} = from;
+		let same_attributes = *alias == to.alias
+			&& *name == to.name
+			&& inputs.len() == to.inputs.len()
+			&& *manual_composition == to.manual_composition
+			&& *has_primary_output == to.has_primary_output
+			&& *is_layer == to.is_layer
+			&& *visible == to.visible
+			&& *locked == to.locked
+			&& *skip_deduplication == to.skip_deduplication
+			&& *world_state_hash == to.world_state_hash
+			&& *original_location == to.original_location;
+		let network = match (&from.implementation, &to.implementation) {
+			(DocumentNodeImplementation::Network(from), DocumentNodeImplementation::Network(to)) if same_attributes => Some(NetworkDiff::between(from, to)).filter(|diff| !diff.is_empty()),
+			(from, to) if same_attributes && from == to => None,
+			_ => return Some(Self::Set(Box::new(to.clone()))),
+		};
+
+		let inputs = from
+			.inputs
+			.iter()
+			.zip(&to.inputs)
+			.enumerate()
+			.filter(|(_, (from, to))| from != to)
+			.map(|(index, (_, to))| (index, to.clone()))
+			.collect();
+		let metadata = (from.metadata != to.metadata).then(|| to.metadata.clone());
+		Some(Self::Modify { inputs, metadata, network })
+	}
+}
+
+impl NetworkDiff {
+	/// Finds the changes that turn the `from` network into the `to` network, only copying the nodes (or parts of nodes) that differ.
+	pub fn between(from: &NodeNetwork, to: &NodeNetwork) -> Self {
+		let mut nodes = Vec::new();
+		for (&id, node) in &from.nodes {
+			match to.nodes.get(&id) {
+				Some(target) => nodes.extend(NodeChange::between(node, target).map(|change| (id, change))),
+				None => nodes.push((id, NodeChange::Remove)),
+			}
+		}
+		let added = to.nodes.iter().filter(|(id, _)| !from.nodes.contains_key(id));
+		nodes.extend(added.map(|(&id, node)| (id, NodeChange::Set(Box::new(node.clone())))));
+
+		Self {
+			nodes,
+			imports: (from.imports != to.imports).then(|| to.imports.clone()),
+			exports: (from.exports != to.exports).then(|| to.exports.clone()),
+			previous_outputs: (from.previous_outputs != to.previous_outputs).then(|| to.previous_outputs.clone()),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.nodes.is_empty() && self.imports.is_none() && self.exports.is_none() && self.previous_outputs.is_none()
+	}
+
+	/// Applies the changes to the network they were found from, turning it into the network they were found to.
+	pub fn apply(self, network: &mut NodeNetwork) {
+		for (id, change) in self.nodes {
+			match change {
+				NodeChange::Set(node) => {
+					network.nodes.insert(id, *node);
+				}
+				NodeChange::Remove => {
+					network.nodes.remove(&id);
+				}
+				NodeChange::Modify {
+					inputs,
+					metadata,
+					network: inner_diff,
+				} => {
+					let Some(node) = network.nodes.get_mut(&id) else {
+						warn!("History diff modifies the node {id} which is missing from the network");
+						continue;
+					};
+					for (index, input) in inputs {
+						if let Some(node_input) = node.inputs.get_mut(index) {
+							*node_input = input;
+						}
+					}
+					if let Some(metadata) = metadata {
+						node.metadata = metadata;
+					}
+					if let (Some(inner_diff), DocumentNodeImplementation::Network(inner_network)) = (inner_diff, &mut node.implementation) {
+						inner_diff.apply(inner_network);
+					}
+				}
+			}
+		}
+
+		if let Some(imports) = self.imports {
+			network.imports = imports;
+		}
+		if let Some(exports) = self.exports {
+			network.exports = exports;
+		}
+		if let Some(previous_outputs) = self.previous_outputs {
+			network.previous_outputs = previous_outputs;
+		}
+	}
+
+	/// Names the action that the diff represents, such as "Add Rectangle" or "Change Transform".
+	/// The `base` is the network the diff applies to, which comes after the diff's result in the history when `reverse` is set, in which case the name describes undoing the diff.
+	fn describe(&self, base: &NodeNetwork, reverse: bool) -> String {
+		let (added_verb, removed_verb) = if reverse { ("Delete", "Add") } else { ("Add", "Delete") };
+
+		let changes = self.nodes.iter().filter_map(|(id, change)| {
+			let existing = base.nodes.get(id);
+			match change {
+				NodeChange::Set(node) if existing.is_none() => Some((added_verb, node.as_ref())),
+				NodeChange::Set(node) => Some(("Change", node.as_ref())),
+				NodeChange::Remove => existing.map(|node| (removed_verb, node)),
+				NodeChange::Modify { .. } => existing.map(|node| ("Change", node)),
+			}
+		});
+		let changes: Vec<_> = changes.collect();
+
+		let Some(verb) = ["Add", "Delete", "Change"].into_iter().find(|verb| changes.iter().any(|(change_verb, _)| change_verb == verb)) else {
+			let outputs_changed = self.exports.is_some() || self.previous_outputs.is_some();
+			return if outputs_changed { "Change Graph Output".into() } else { "Unchanged".into() };
+		};
+		let nodes: Vec<_> = changes.into_iter().filter(|(change_verb, _)| *change_verb == verb).map(|(_, node)| node).collect();
+
+		let mut layers = nodes.iter().filter(|node| node.is_layer);
+		let node = match (nodes.as_slice(), layers.next(), layers.next()) {
+			([node], _, _) | (_, Some(node), None) => node,
+			_ => return format!("{verb} {} Nodes", nodes.len()),
+		};
+		let name = if node.alias.is_empty() { &node.name } else { &node.alias };
+		format!("{verb} {name}")
+	}
+}
+
+/// Names the action that the diff, along with a change to the guides, represents. See [`NetworkDiff::describe`].
+fn describe(diff: &NetworkDiff, guides_changed: bool, base: &NodeNetwork, reverse: bool) -> String {
+	if diff.is_empty() && guides_changed {
+		GUIDES_CHANGED_NAME.to_string()
+	} else {
+		diff.describe(base, reverse)
+	}
+}
+
+/// A state in the history, stored as the diff from the neighbouring state that is kept around.
+#[derive(Clone, Debug)]
+struct HistoryEntry {
+	/// The name of the action that led to this state.
+	name: String,
+	diff: NetworkDiff,
+	/// The guides of this state, which are few enough to keep whole, or `None` if they're the same as those of the state the diff applies to.
+	guides: Option<Vec<Guide>>,
+	/// An estimate of the memory used by the diff and guides, in bytes.
+	size: usize,
+}
+
+impl HistoryEntry {
+	fn new(name: String, diff: NetworkDiff, guides: Option<Vec<Guide>>) -> Self {
+		let size = estimated_size(&diff) + guides.as_ref().map_or(0, |guides| guides.len() * std::mem::size_of::<Guide>());
+		Self { name, diff, guides, size }
+	}
+}
+
+/// Estimates the memory a diff occupies by the length of its serialized form, which is dominated by any image and brush stroke data it holds.
+fn estimated_size(diff: &NetworkDiff) -> usize {
+	struct ByteCounter(usize);
+	impl std::io::Write for ByteCounter {
+		fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+			self.0 += buf.len();
+			Ok(buf.len())
+		}
+		fn flush(&mut self) -> std::io::Result<()> {
+			Ok(())
+		}
+	}
+
+	let mut counter = ByteCounter(std::mem::size_of::<HistoryEntry>());
+	if let Err(error) = serde_json::to_writer(&mut counter, diff) {
+		warn!("Could not estimate the size of a history entry: {error}");
+	}
+	counter.0
+}
+
+/// A state listed in the History panel.
+pub struct HistoryState {
+	pub name: String,
+	/// The number of undo (when negative) or redo (when positive) steps that return the document to this state, which is zero for the current state.
+	pub steps: isize,
+}
+
+/// The undo and redo history of a document.
+/// Only the most recently backed up state is kept whole, and every other state is stored as the diff from its neighbour so a step only costs the memory of what it changed.
+/// Once the diffs use more memory than the budget allows, the oldest states are forgotten.
+#[derive(Clone, Debug)]
+pub struct DocumentHistory {
+	/// The most recently backed up state, with the name of the action that led to it.
+	head: Option<(DocumentState, String)>,
+	/// The earlier states, oldest first, each stored as the diff that turns the state after it into this one.
+	undo: VecDeque<HistoryEntry>,
+	/// The undone states, furthest first, each stored as the diff that turns the state before it into this one.
+	redo: Vec<HistoryEntry>,
+	/// The number of bytes the diffs may use before the oldest states are forgotten.
+	budget: usize,
+}
+
+impl Default for DocumentHistory {
+	fn default() -> Self {
+		Self {
+			head: None,
+			undo: VecDeque::new(),
+			redo: Vec::new(),
+			budget: (DEFAULT_UNDO_HISTORY_BUDGET * 1024. * 1024.) as usize,
+		}
+	}
+}
+
+impl DocumentHistory {
+	pub fn set_budget(&mut self, budget: usize) {
+		self.budget = budget;
+	}
+
+	/// Hash of the most recently backed up state.
+	pub fn current_hash(&self) -> Option<u64> {
+		self.head.as_ref().map(|(state, _)| state.current_hash())
+	}
+
+	/// Records the state as the newest one, forgetting any undone states.
+	pub fn backup(&mut self, state: DocumentState) {
+		self.redo.clear();
+		self.push_undo(state);
+	}
+
+	/// Records the state as the newest one while keeping the undone states, as done when redoing.
+	pub fn push_undo(&mut self, state: DocumentState) {
+		let name = match self.head.take() {
+			Some((previous, previous_name)) => {
+				let diff = NetworkDiff::between(&state.network, &previous.network);
+				let guides = (previous.guides != state.guides).then_some(previous.guides);
+				let name = describe(&diff, guides.is_some(), &state.network, true);
+				self.undo.push_back(HistoryEntry::new(previous_name, diff, guides));
+				name
+			}
+			None => INITIAL_STATE_NAME.to_string(),
+		};
+		self.head = Some((state, name));
+
+		self.trim();
+	}
+
+	/// Replaces the network and guides with the newest recorded state, returning the replaced state.
+	pub fn undo(&mut self, network: &mut NodeNetwork, guides: &mut Vec<Guide>) -> Option<DocumentState> {
+		let (head, _) = self.head.take()?;
+
+		if let Some(entry) = self.undo.pop_back() {
+			let mut previous = head.clone();
+			entry.diff.apply(&mut previous.network);
+			if let Some(guides) = entry.guides {
+				previous.guides = guides;
+			}
+			self.head = Some((previous, entry.name));
+		}
+
+		Some(DocumentState {
+			network: std::mem::replace(network, head.network),
+			guides: std::mem::replace(guides, head.guides),
+		})
+	}
+
+	/// Records the state replaced by an undo so it can be redone from the current network and guides.
+	pub fn push_redo(&mut self, network: &NodeNetwork, guides: &[Guide], undone: &DocumentState) {
+		let diff = NetworkDiff::between(network, &undone.network);
+		let undone_guides = (guides != undone.guides).then(|| undone.guides.clone());
+		let name = describe(&diff, undone_guides.is_some(), network, false);
+		self.redo.push(HistoryEntry::new(name, diff, undone_guides));
+
+		self.trim();
+	}
+
+	/// Replaces the network and guides with the most recently undone state, returning the replaced state.
+	pub fn redo(&mut self, network: &mut NodeNetwork, guides: &mut Vec<Guide>) -> Option<DocumentState> {
+		let entry = self.redo.pop()?;
+
+		let mut next = network.clone();
+		entry.diff.apply(&mut next);
+		let next_guides = entry.guides.unwrap_or_else(|| guides.clone());
+
+		Some(DocumentState {
+			network: std::mem::replace(network, next),
+			guides: std::mem::replace(guides, next_guides),
+		})
+	}
+
+	/// Lists the states from oldest to newest, including the current one and the states that can be redone.
+	pub fn states(&self, network: &NodeNetwork, guides: &[Guide]) -> Vec<HistoryState> {
+		let undo_count = self.undo.len() as isize;
+		let mut states: Vec<_> = (self.undo.iter().enumerate())
+			.map(|(index, entry)| HistoryState {
+				name: entry.name.clone(),
+				steps: index as isize - undo_count - 1,
+			})
+			.collect();
+
+		match &self.head {
+			Some((head, name)) if head.network == *network && head.guides == guides => states.push(HistoryState { name: name.clone(), steps: 0 }),
+			Some((head, name)) => {
+				states.push(HistoryState { name: name.clone(), steps: -1 });
+				let name = describe(&NetworkDiff::between(&head.network, network), head.guides != guides, &head.network, false);
+				states.push(HistoryState { name, steps: 0 });
+			}
+			None => states.push(HistoryState {
+				name: INITIAL_STATE_NAME.to_string(),
+				steps: 0,
+			}),
+		}
+
+		let redo_states = self.redo.iter().rev().enumerate().map(|(index, entry)| HistoryState {
+			name: entry.name.clone(),
+			steps: index as isize + 1,
+		});
+		states.extend(redo_states);
+
+		states
+	}
+
+	/// Forgets the oldest undo states, and then the furthest redo states, until the diffs fit within the budget.
+	fn trim(&mut self) {
+		let mut size: usize = self.undo.iter().chain(&self.redo).map(|entry| entry.size).sum();
+
+		while size > self.budget {
+			let Some(entry) = self.undo.pop_front().or_else(|| (!self.redo.is_empty()).then(|| self.redo.remove(0))) else {
+				break;
+			};
+			size -= entry.size;
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use graph_craft::document::value::TaggedValue;
+
+	fn node(name: &str, value: f64) -> DocumentNode {
+		DocumentNode {
+			name: name.into(),
+			inputs: vec![NodeInput::value(TaggedValue::F64(value), false)],
+			..Default::default()
+		}
+	}
+
+	fn network(nodes: impl IntoIterator<Item = (u64, DocumentNode)>) -> NodeNetwork {
+		NodeNetwork {
+			exports: vec![NodeOutput::new(NodeId(0), 0)],
+			nodes: nodes.into_iter().map(|(id, node)| (NodeId(id), node)).collect(),
+			..Default::default()
+		}
+	}
+
+	fn state(network: NodeNetwork) -> DocumentState {
+		DocumentState { network, guides: Vec::new() }
+	}
+
+	#[test]
+	fn diff_roundtrip() {
+		let inner = network([(0, node("Inner", 1.))]);
+		let mut outer_node = node("Outer", 2.);
+		outer_node.implementation = DocumentNodeImplementation::Network(inner);
+		let from = network([(0, node("Kept", 1.)), (1, node("Removed", 2.)), (2, outer_node.clone())]);
+
+		let mut changed_outer = outer_node;
+		changed_outer.metadata.position = (4, 2).into();
+		if let DocumentNodeImplementation::Network(inner) = &mut changed_outer.implementation {
+			inner.nodes.get_mut(&NodeId(0)).unwrap().inputs[0] = NodeInput::value(TaggedValue::F64(3.), false);
+		}
+		let to = network([(0, node("Kept", 1.)), (2, changed_outer), (3, node("Added", 4.))]);
+
+		let diff = NetworkDiff::between(&from, &to);
+		assert_eq!(diff.nodes.len(), 3);
+		assert!(diff
+			.nodes
+			.iter()
+			.any(|(id, change)| *id == NodeId(2) && matches!(change, NodeChange::Modify { inputs, metadata: Some(_), network: Some(_) } if inputs.is_empty())));
+
+		let mut applied = from.clone();
+		diff.apply(&mut applied);
+		assert_eq!(applied, to);
+		assert!(NetworkDiff::between(&to, &to).is_empty());
+	}
+
+	#[test]
+	fn undo_and_redo() {
+		let first = network([(0, node("Rectangle", 1.))]);
+		let second = network([(0, node("Rectangle", 1.)), (1, node("Transform", 2.))]);
+		let third = network([(0, node("Rectangle", 5.)), (1, node("Transform", 2.))]);
+
+		let mut history = DocumentHistory::default();
+		history.backup(state(first.clone()));
+		history.backup(state(second.clone()));
+		let mut live = third.clone();
+		let mut guides = Vec::new();
+
+		let names: Vec<_> = history.states(&live, &guides).into_iter().map(|state| (state.name, state.steps)).collect();
+		assert_eq!(names, [("Initial State".to_string(), -2), ("Add Transform".to_string(), -1), ("Change Rectangle".to_string(), 0)]);
+
+		let undone = history.undo(&mut live, &mut guides).unwrap();
+		history.push_redo(&live, &guides, &undone);
+		assert_eq!(live, second);
+		let undone = history.undo(&mut live, &mut guides).unwrap();
+		history.push_redo(&live, &guides, &undone);
+		assert_eq!(live, first);
+		assert_eq!(history.states(&live, &guides).iter().map(|state| state.steps).collect::<Vec<_>>(), [0, 1, 2]);
+
+		let redone = history.redo(&mut live, &mut guides).unwrap();
+		history.push_undo(redone);
+		assert_eq!(live, second);
+		let redone = history.redo(&mut live, &mut guides).unwrap();
+		history.push_undo(redone);
+		assert_eq!(live, third);
+		assert!(history.redo(&mut live, &mut guides).is_none());
+	}
+
+	#[test]
+	fn undo_and_redo_guides() {
+		use crate::messages::portfolio::document::guides::utility_types::GuideDirection;
+
+		let live_network = network([(0, node("Rectangle", 1.))]);
+		let guide = |position| Guide {
+			direction: GuideDirection::Vertical,
+			position,
+		};
+
+		let mut history = DocumentHistory::default();
+		history.backup(state(live_network.clone()));
+		let hash_without_guides = history.current_hash();
+		history.backup(DocumentState {
+			network: live_network.clone(),
+			guides: vec![guide(10.)],
+		});
+		assert_ne!(history.current_hash(), hash_without_guides);
+
+		let mut live = live_network.clone();
+		let mut guides = vec![guide(20.)];
+		let names: Vec<_> = history.states(&live, &guides).into_iter().map(|state| state.name).collect();
+		assert_eq!(names, ["Initial State", "Change Guides", "Change Guides"]);
+
+		let undone = history.undo(&mut live, &mut guides).unwrap();
+		history.push_redo(&live, &guides, &undone);
+		assert_eq!(guides, [guide(10.)]);
+		let undone = history.undo(&mut live, &mut guides).unwrap();
+		history.push_redo(&live, &guides, &undone);
+		assert!(guides.is_empty());
+		assert_eq!(live, live_network);
+
+		let redone = history.redo(&mut live, &mut guides).unwrap();
+		history.push_undo(redone);
+		assert_eq!(guides, [guide(10.)]);
+		let redone = history.redo(&mut live, &mut guides).unwrap();
+		history.push_undo(redone);
+		assert_eq!(guides, [guide(20.)]);
+	}
+
+	#[test]
+	fn forgets_states_beyond_the_budget() {
+		let mut history = DocumentHistory::default();
+		history.set_budget(0);
+		for value in 0..5 {
+			history.backup(state(network([(0, node("Rectangle", value as f64))])));
+		}
+
+		let mut live = network([(0, node("Rectangle", 5.))]);
+		let mut guides = Vec::new();
+		assert!(history.undo.is_empty());
+		assert_eq!(
+			history.undo(&mut live, &mut guides).map(|state| state.network.nodes[&NodeId(0)].inputs.clone()),
+			Some(vec![NodeInput::value(TaggedValue::F64(5.), false)])
+		);
+		assert_eq!(live, network([(0, node("Rectangle", 4.))]));
+		assert!(history.undo(&mut live, &mut guides).is_none());
+	}
+}
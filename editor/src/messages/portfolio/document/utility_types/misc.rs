@@ -64,6 +64,8 @@ pub struct SnappingState {
 	pub grid: GridSnapping,
 	pub tolerance: f64,
 	pub artboards: bool,
+	#[serde(default = "default_guide_snapping")]
+	pub guides: bool,
 }
 impl Default for SnappingState {
 	fn default() -> Self {
@@ -95,9 +97,13 @@ impl Default for SnappingState {
 			},
 			tolerance: 8.,
 			artboards: true,
+			guides: true,
 		}
 	}
 }
+fn default_guide_snapping() -> bool {
+	SnappingState::default().guides
+}
 impl SnappingState {
 	pub const fn target_enabled(&self, target: SnapTarget) -> bool {
 		if !self.snapping_enabled {
@@ -121,6 +127,7 @@ impl SnappingState {
 			},
 			SnapTarget::Board(_) => self.artboards,
 			SnapTarget::Grid(_) => self.grid_snapping,
+			SnapTarget::Guide => self.guides,
 			_ => false,
 		}
 	}
@@ -331,6 +338,7 @@ pub enum SnapTarget {
 	Geometry(GeometrySnapTarget),
 	Board(BoardSnapTarget),
 	Grid(GridSnapTarget),
+	Guide,
 }
 impl SnapTarget {
 	pub fn is_some(&self) -> bool {
@@ -344,6 +352,7 @@ impl SnapTarget {
 pub enum SnappingOptions {
 	BoundingBoxes,
 	Geometry,
+	Guides,
 }
 
 impl fmt::Display for SnappingOptions {
@@ -351,6 +360,7 @@ impl fmt::Display for SnappingOptions {
 		match self {
 			SnappingOptions::BoundingBoxes => write!(f, "Bounding Boxes"),
 			SnappingOptions::Geometry => write!(f, "Geometry"),
+			SnappingOptions::Guides => write!(f, "Guides"),
 		}
 	}
 }
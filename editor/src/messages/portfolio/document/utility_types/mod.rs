@@ -1,4 +1,5 @@
 pub mod clipboards;
+pub mod document_history;
 pub mod document_metadata;
 pub mod error;
 pub mod misc;
@@ -6,20 +6,31 @@ use crate::messages::prelude::*;
 pub struct MenuBarMessageData {
 	pub has_active_document: bool,
 	pub rulers_visible: bool,
+	pub recording_macro: bool,
+	pub has_recorded_macro: bool,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct MenuBarMessageHandler {
 	has_active_document: bool,
 	rulers_visible: bool,
+	recording_macro: bool,
+	has_recorded_macro: bool,
 }
 
 impl MessageHandler<MenuBarMessage, MenuBarMessageData> for MenuBarMessageHandler {
 	fn process_message(&mut self, message: MenuBarMessage, responses: &mut VecDeque<Message>, data: MenuBarMessageData) {
-		let MenuBarMessageData { has_active_document, rulers_visible } = data;
+		let MenuBarMessageData {
+			has_active_document,
+			rulers_visible,
+			recording_macro,
+			has_recorded_macro,
+		} = data;
 
 		self.has_active_document = has_active_document;
 		self.rulers_visible = rulers_visible;
+		self.recording_macro = recording_macro;
+		self.has_recorded_macro = has_recorded_macro;
 
 		match message {
 			MenuBarMessage::SendLayout => self.send_layout(responses, LayoutTarget::MenuBar),
@@ -161,6 +172,47 @@ impl LayoutHolder for MenuBarMessageHandler {
 							..MenuBarEntry::default()
 						},
 					],
+					vec![MenuBarEntry {
+						label: "Macro".into(),
+						action: MenuBarEntry::no_action(),
+						children: MenuBarEntryChildren(vec![
+							vec![MenuBarEntry {
+								label: "Record".into(),
+								icon: Some(if self.recording_macro { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+								action: if self.recording_macro {
+									MenuBarEntry::create_action(|_| MacroRecorderMessage::StopRecording.into())
+								} else {
+									MenuBarEntry::create_action(|_| MacroRecorderMessage::StartRecording.into())
+								},
+								..MenuBarEntry::default()
+							}],
+							vec![
+								MenuBarEntry {
+									label: "Replay Recording".into(),
+									shortcut: action_keys!(MacroRecorderMessageDiscriminant::ReplayRecording),
+									action: MenuBarEntry::create_action(|_| MacroRecorderMessage::ReplayRecording.into()),
+									disabled: no_active_document || !self.has_recorded_macro,
+									..MenuBarEntry::default()
+								},
+								MenuBarEntry {
+									label: "Save Recording…".into(),
+									shortcut: action_keys!(MacroRecorderMessageDiscriminant::SaveRecording),
+									action: MenuBarEntry::create_action(|_| MacroRecorderMessage::SaveRecording.into()),
+									disabled: !self.has_recorded_macro,
+									..MenuBarEntry::default()
+								},
+							],
+							vec![MenuBarEntry {
+								label: "Replay from File…".into(),
+								icon: Some("Folder".into()),
+								shortcut: action_keys!(MacroRecorderMessageDiscriminant::OpenMacro),
+								action: MenuBarEntry::create_action(|_| MacroRecorderMessage::OpenMacro.into()),
+								disabled: no_active_document,
+								..MenuBarEntry::default()
+							}],
+						]),
+						..MenuBarEntry::default()
+					}],
 				]),
 			),
 			MenuBarEntry::new_root(
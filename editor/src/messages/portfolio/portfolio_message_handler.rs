@@ -4,6 +4,7 @@ use crate::consts::DEFAULT_DOCUMENT_NAME;
 use crate::messages::dialog::simple_dialogs;
 use crate::messages::frontend::utility_types::FrontendDocumentDetails;
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::node_graph::document_node_types::upgrade_saved_nodes;
 use crate::messages::portfolio::document::utility_types::clipboards::{Clipboard, CopyBufferEntry, INTERNAL_CLIPBOARD_COUNT};
 use crate::messages::portfolio::document::DocumentMessageData;
 use crate::messages::prelude::*;
@@ -18,6 +19,7 @@ use std::sync::Arc;
 pub struct PortfolioMessageData<'a> {
 	pub ipp: &'a InputPreprocessorMessageHandler,
 	pub preferences: &'a PreferencesMessageHandler,
+	pub macro_recorder: &'a MacroRecorderMessageHandler,
 }
 
 #[derive(Debug, Default)]
@@ -33,7 +35,7 @@ pub struct PortfolioMessageHandler {
 
 impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMessageHandler {
 	fn process_message(&mut self, message: PortfolioMessage, responses: &mut VecDeque<Message>, data: PortfolioMessageData) {
-		let PortfolioMessageData { ipp, preferences } = data;
+		let PortfolioMessageData { ipp, preferences, macro_recorder } = data;
 
 		match message {
 			// Sub-messages
@@ -46,8 +48,13 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					rulers_visible = document.rulers_visible;
 				}
 
-				self.menu_bar_message_handler
-					.process_message(message, responses, MenuBarMessageData { has_active_document, rulers_visible });
+				let data = MenuBarMessageData {
+					has_active_document,
+					rulers_visible,
+					recording_macro: macro_recorder.is_recording(),
+					has_recorded_macro: macro_recorder.has_recorded_macro(),
+				};
+				self.menu_bar_message_handler.process_message(message, responses, data);
 			}
 			PortfolioMessage::Document(message) => {
 				if let Some(document_id) = self.active_document_id {
@@ -57,6 +64,7 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 							ipp,
 							persistent_data: &self.persistent_data,
 							executor: &mut self.executor,
+							preferences,
 						};
 						document.process_message(message, responses, document_inputs)
 					}
@@ -71,6 +79,7 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 						ipp,
 						persistent_data: &self.persistent_data,
 						executor: &mut self.executor,
+						preferences,
 					};
 					document.process_message(message, responses, document_inputs)
 				}
@@ -116,6 +125,7 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					// Clear relevant UI layouts if there are no documents
 					responses.add(PropertiesPanelMessage::Clear);
 					responses.add(DocumentMessage::ClearLayersPanel);
+					responses.add(DocumentMessage::ClearHistoryPanel);
 					let hint_data = HintData(vec![HintGroup(vec![])]);
 					responses.add(FrontendMessage::UpdateInputHints { hint_data });
 				}
@@ -140,6 +150,7 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					// Clear UI layouts that assume the existence of a document
 					responses.add(PropertiesPanelMessage::Clear);
 					responses.add(DocumentMessage::ClearLayersPanel);
+					responses.add(DocumentMessage::ClearHistoryPanel);
 					let hint_data = HintData(vec![HintGroup(vec![])]);
 					responses.add(FrontendMessage::UpdateInputHints { hint_data });
 				}
@@ -397,7 +408,10 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			}
 			PortfolioMessage::PasteSerializedData { data } => {
 				if let Some(document) = self.active_document() {
-					if let Ok(data) = serde_json::from_str::<Vec<CopyBufferEntry>>(&data) {
+					if let Ok(mut data) = serde_json::from_str::<Vec<CopyBufferEntry>>(&data) {
+						// The data might have been copied from an older version of the editor
+						upgrade_saved_nodes(data.iter_mut().flat_map(|entry| entry.nodes.values_mut()));
+
 						let parent = document.new_layer_parent(false);
 
 						responses.add(DocumentMessage::DeselectAllLayers);
@@ -451,6 +465,9 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				responses.add(NavigationMessage::CanvasPan { delta: (0., 0.).into() });
 				responses.add(NodeGraphMessage::RunDocumentGraph);
 				responses.add(DocumentMessage::GraphViewOverlay { open: node_graph_open });
+				if let Some(document) = self.documents.get(&document_id) {
+					document.update_history_panel_widgets(responses);
+				}
 			}
 			PortfolioMessage::SubmitDocumentExport {
 				file_name,
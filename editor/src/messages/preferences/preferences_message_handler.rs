@@ -1,3 +1,4 @@
+use crate::consts::DEFAULT_UNDO_HISTORY_BUDGET;
 use crate::messages::input_mapper::key_mapping::MappingVariant;
 use crate::messages::prelude::*;
 use graph_craft::imaginate_input::ImaginatePreferences;
@@ -7,6 +8,13 @@ pub struct PreferencesMessageHandler {
 	pub imaginate_server_hostname: String,
 	pub imaginate_refresh_frequency: f64,
 	pub zoom_with_scroll: bool,
+	/// The memory each document's undo history may use, in megabytes.
+	#[serde(default = "default_undo_history_budget")]
+	pub undo_history_budget: f64,
+}
+
+fn default_undo_history_budget() -> f64 {
+	DEFAULT_UNDO_HISTORY_BUDGET
 }
 
 impl PreferencesMessageHandler {
@@ -15,6 +23,10 @@ impl PreferencesMessageHandler {
 			host_name: self.imaginate_server_hostname.clone(),
 		}
 	}
+
+	pub fn undo_history_budget_bytes(&self) -> usize {
+		(self.undo_history_budget * 1024. * 1024.) as usize
+	}
 }
 
 impl Default for PreferencesMessageHandler {
@@ -24,6 +36,7 @@ impl Default for PreferencesMessageHandler {
 			imaginate_server_hostname: host_name,
 			imaginate_refresh_frequency: 1.,
 			zoom_with_scroll: matches!(MappingVariant::default(), MappingVariant::ZoomWithScroll),
+			undo_history_budget: DEFAULT_UNDO_HISTORY_BUDGET,
 		}
 	}
 }
@@ -77,6 +90,9 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 				responses.add(KeyMappingMessage::ModifyMapping(variant));
 				responses.add(FrontendMessage::UpdateZoomWithScroll { zoom_with_scroll });
 			}
+			PreferencesMessage::UndoHistoryBudget { megabytes } => {
+				self.undo_history_budget = megabytes;
+			}
 		}
 
 		responses.add(FrontendMessage::TriggerSavePreferences { preferences: self.clone() });
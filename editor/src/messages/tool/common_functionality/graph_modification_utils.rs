@@ -1,3 +1,4 @@
+use crate::messages::portfolio::document::graph_operation::transform_utils::{get_current_normalized_pivot, get_current_transform};
 use crate::messages::portfolio::document::graph_operation::utility_types::VectorDataModification;
 use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
 use crate::messages::prelude::*;
@@ -5,12 +6,12 @@ use crate::messages::prelude::*;
 use bezier_rs::{ManipulatorGroup, Subpath};
 use graph_craft::document::{value::TaggedValue, DocumentNode, NodeId, NodeInput, NodeNetwork};
 use graphene_core::raster::{BlendMode, ImageFrame};
-use graphene_core::text::Font;
+use graphene_core::text::{Font, TextRun, Typography};
 use graphene_core::uuid::ManipulatorGroupId;
 use graphene_core::vector::style::{FillType, Gradient};
 use graphene_core::Color;
 
-use glam::DVec2;
+use glam::{DAffine2, DVec2};
 use std::collections::VecDeque;
 
 /// Create a new vector layer from a vector of [`bezier_rs::Subpath`].
@@ -114,12 +115,16 @@ pub fn get_gradient(layer: LayerNodeIdentifier, document_network: &NodeNetwork)
 	let TaggedValue::GradientPositions(positions) = inputs.get(7)?.as_value()? else {
 		return None;
 	};
+	let TaggedValue::GradientSpreadMethod(spread_method) = inputs.get(8)?.as_value()? else {
+		return None;
+	};
 	Some(Gradient {
 		start: *start,
 		end: *end,
 		transform: *transform,
 		positions: positions.clone(),
 		gradient_type: *gradient_type,
+		spread_method: *spread_method,
 	})
 }
 
@@ -160,13 +165,21 @@ pub fn get_fill_id(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -
 	NodeGraphLayer::new(layer, document_network).upstream_node_id_from_name("Fill")
 }
 
+/// Gets the ID of the Typography node, or of the Text node generating outlines in older documents
 pub fn get_text_id(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> Option<NodeId> {
-	NodeGraphLayer::new(layer, document_network).upstream_node_id_from_name("Text")
+	let layer = NodeGraphLayer::new(layer, document_network);
+	layer.upstream_node_id_from_name("Typography").or_else(|| layer.upstream_node_id_from_name("Text"))
+}
+
+/// Whether the layer is made by a Typography node, or by a Text node generating outlines in older documents
+pub fn is_text_layer(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> bool {
+	is_layer_fed_by_node_of_name(layer, document_network, "Typography") || is_layer_fed_by_node_of_name(layer, document_network, "Text")
 }
 
-/// Gets properties from the Text node
+/// Gets properties from the Typography node, or from the Text node which shares its first inputs
 pub fn get_text(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> Option<(&String, &Font, f64)> {
-	let inputs = NodeGraphLayer::new(layer, document_network).find_node_inputs("Text")?;
+	let layer = NodeGraphLayer::new(layer, document_network);
+	let inputs = layer.find_node_inputs("Typography").or_else(|| layer.find_node_inputs("Text"))?;
 	let NodeInput::Value {
 		tagged_value: TaggedValue::String(text),
 		..
@@ -194,6 +207,33 @@ pub fn get_text(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> O
 	Some((text, font, font_size))
 }
 
+/// Gets the formatting and text of the Typography node as a single run, which isn't laid out yet
+pub fn get_typography(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> Option<Typography> {
+	let inputs = NodeGraphLayer::new(layer, document_network).find_node_inputs("Typography")?;
+	let (Some(TaggedValue::String(text)), Some(TaggedValue::Font(font)), Some(&TaggedValue::F64(font_size))) = (inputs.get(1)?.as_value(), inputs.get(2)?.as_value(), inputs.get(3)?.as_value()) else {
+		return None;
+	};
+	let (Some(&TaggedValue::F64(tracking)), Some(&TaggedValue::F64(leading)), Some(&TaggedValue::TextAlignment(alignment))) =
+		(inputs.get(4)?.as_value(), inputs.get(5)?.as_value(), inputs.get(6)?.as_value())
+	else {
+		return None;
+	};
+	let (Some(&TaggedValue::F64(box_width)), Some(&TaggedValue::Color(color))) = (inputs.get(7)?.as_value(), inputs.get(8)?.as_value()) else {
+		return None;
+	};
+
+	let run = TextRun {
+		text: text.clone(),
+		font: font.clone(),
+		font_size,
+		tracking,
+		leading,
+		color,
+	};
+	// As in the Typography node, a box width of zero means the text is only broken at explicit line breaks
+	Some(Typography::new(vec![run], alignment, (box_width > 0.).then_some(box_width)))
+}
+
 pub fn get_stroke_width(layer: LayerNodeIdentifier, network: &NodeNetwork) -> Option<f64> {
 	let weight_node_input_index = 2;
 	if let TaggedValue::F64(width) = NodeGraphLayer::new(layer, network).find_input("Stroke", weight_node_input_index)? {
@@ -203,11 +243,43 @@ pub fn get_stroke_width(layer: LayerNodeIdentifier, network: &NodeNetwork) -> Op
 	}
 }
 
+/// Get the transform of the Transform node in the layer's primary flow, which places the raster data upstream of it (such as an image from its Image node) in the space of the layer's parent.
+/// This is the identity if the layer has no Transform node.
+pub fn get_upstream_raster_transform(layer: LayerNodeIdentifier, document_network: &NodeNetwork, document_metadata: &DocumentMetadata) -> DAffine2 {
+	let Some((node, node_id)) = NodeGraphLayer::new(layer, document_network)
+		.horizontal_layer_flow()
+		.skip(1)
+		.take_while(|(node, _)| !node.is_layer)
+		.find(|(node, _)| node.name == "Transform")
+	else {
+		return DAffine2::IDENTITY;
+	};
+
+	let upstream = document_metadata.upstream_transform(node_id);
+	let pivot = DAffine2::from_translation(upstream.transform_point2(get_current_normalized_pivot(&node.inputs)));
+	pivot * get_current_transform(&node.inputs) * pivot.inverse()
+}
+
+/// Get the image of a bitmap layer from its Image node, before any of the layer's other nodes have modified it
+pub fn get_image_frame(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> Option<&ImageFrame<Color>> {
+	let image_node_input_index = 0;
+	if let TaggedValue::ImageFrame(image_frame) = NodeGraphLayer::new(layer, document_network).find_input("Image", image_node_input_index)? {
+		Some(image_frame)
+	} else {
+		None
+	}
+}
+
 /// Checks if a specified layer uses an upstream node matching the given name.
 pub fn is_layer_fed_by_node_of_name(layer: LayerNodeIdentifier, document_network: &NodeNetwork, node_name: &str) -> bool {
 	NodeGraphLayer::new(layer, document_network).find_node_inputs(node_name).is_some()
 }
 
+/// Whether the layer's content is vector data, made by a Shape node, a Boolean Operation node, or a Text node generating outlines in older documents
+pub fn is_vector_layer(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> bool {
+	get_subpaths(layer, document_network).is_some() || ["Boolean Operation", "Text"].iter().any(|name| is_layer_fed_by_node_of_name(layer, document_network, name))
+}
+
 /// Convert subpaths to an iterator of manipulator groups
 pub fn get_manipulator_groups(subpaths: &[Subpath<ManipulatorGroupId>]) -> impl Iterator<Item = &bezier_rs::ManipulatorGroup<ManipulatorGroupId>> + DoubleEndedIterator {
 	subpaths.iter().flat_map(|subpath| subpath.manipulator_groups())
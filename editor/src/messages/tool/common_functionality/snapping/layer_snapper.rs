@@ -52,6 +52,24 @@ impl LayerSnapper {
 			}
 			self.add_layer_bounds(document, layer, SnapTarget::Board(BoardSnapTarget::Edge));
 		}
+		if document.guides.visible && document.snapping_state.target_enabled(SnapTarget::Guide) {
+			// Guides are endless, so only the stretch of them that could be within the viewport is snapped to
+			let to_document = document.metadata.document_to_viewport.inverse();
+			let viewport_size = snap_data.input.viewport_bounds.size();
+			let center = to_document.transform_point2(viewport_size / 2.);
+			let half_length = to_document.transform_vector2(viewport_size).length();
+
+			for guide in &document.guides.guides {
+				let [start, end] = guide.segment(center, half_length);
+				self.paths_to_snap.push(SnapCandidatePath {
+					document_curve: Bezier::from_linear_dvec2(start, end),
+					layer: LayerNodeIdentifier::ROOT,
+					start: ManipulatorGroupId::new(),
+					target: SnapTarget::Guide,
+					bounds: None,
+				});
+			}
+		}
 		for &layer in snap_data.get_candidates() {
 			let transform = document.metadata.transform_to_document(layer);
 			if !transform.is_finite() {
@@ -44,10 +44,10 @@ pub enum ToolMessage {
 
 	#[child]
 	Brush(BrushToolMessage),
-	// 	// #[child]
-	// Heal(HealToolMessage),
-	// 	// #[child]
-	// Clone(CloneToolMessage),
+	#[child]
+	Heal(HealToolMessage),
+	#[child]
+	Clone(CloneToolMessage),
 	// 	// #[child]
 	// Patch(PatchToolMessage),
 	// 	// #[child]
@@ -76,6 +76,8 @@ pub enum ToolMessage {
 	ActivateToolPolygon,
 
 	ActivateToolBrush,
+	ActivateToolHeal,
+	ActivateToolClone,
 	ActivateToolImaginate,
 
 	ActivateTool {
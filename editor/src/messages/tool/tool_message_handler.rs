@@ -59,6 +59,8 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 			ToolMessage::ActivateToolPolygon => responses.add_front(ToolMessage::ActivateTool { tool_type: ToolType::Polygon }),
 
 			ToolMessage::ActivateToolBrush => responses.add_front(ToolMessage::ActivateTool { tool_type: ToolType::Brush }),
+			ToolMessage::ActivateToolHeal => responses.add_front(ToolMessage::ActivateTool { tool_type: ToolType::Heal }),
+			ToolMessage::ActivateToolClone => responses.add_front(ToolMessage::ActivateTool { tool_type: ToolType::Clone }),
 			ToolMessage::ActivateToolImaginate => responses.add_front(ToolMessage::ActivateTool { tool_type: ToolType::Imaginate }),
 
 			ToolMessage::ActivateTool { tool_type } => {
@@ -284,6 +286,8 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 			ActivateToolPolygon,
 
 			ActivateToolBrush,
+			ActivateToolHeal,
+			ActivateToolClone,
 			ActivateToolImaginate,
 
 			SelectRandomPrimaryColor,
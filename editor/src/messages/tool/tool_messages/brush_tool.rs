@@ -1,17 +1,23 @@
 use super::tool_prelude::*;
+use crate::messages::input_preprocessor::input_preprocessor_message_handler::InputPreprocessorMessageHandler;
 use crate::messages::portfolio::document::graph_operation::transform_utils::{get_current_normalized_pivot, get_current_transform};
 use crate::messages::portfolio::document::node_graph::document_node_types::resolve_document_node_type;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
+use crate::messages::tool::common_functionality::graph_modification_utils;
 
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{DocumentNodeMetadata, NodeId, NodeInput};
 use graphene_core::raster::BlendMode;
 use graphene_core::uuid::generate_uuid;
-use graphene_core::vector::brush_stroke::{BrushInputSample, BrushStroke, BrushStyle};
+use graphene_core::vector::brush_stroke::{BrushDynamics, BrushDynamicsInput, BrushInputSample, BrushStroke, BrushStyle};
 use graphene_core::Color;
 
-const BRUSH_MAX_SIZE: f64 = 5000.;
+use std::time::Duration;
+
+pub(super) const BRUSH_MAX_SIZE: f64 = 5000.;
+/// How much of the previously measured pointer velocity is kept with each new measurement, which smooths out jitter in the pointer events.
+const VELOCITY_SMOOTHING: f64 = 0.6;
 
 #[derive(PartialEq, Copy, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum DrawMode {
@@ -35,6 +41,7 @@ pub struct BrushOptions {
 	color: ToolColorOptions,
 	blend_mode: BlendMode,
 	draw_mode: DrawMode,
+	dynamics: BrushDynamics,
 }
 
 impl Default for BrushOptions {
@@ -47,6 +54,7 @@ impl Default for BrushOptions {
 			color: ToolColorOptions::default(),
 			blend_mode: BlendMode::Normal,
 			draw_mode: DrawMode::Draw,
+			dynamics: BrushDynamics::default(),
 		}
 	}
 }
@@ -72,9 +80,12 @@ pub enum BrushToolMessageOptionsUpdate {
 	Color(Option<Color>),
 	ColorType(ToolColorType),
 	Diameter(f64),
+	DiameterDynamics(BrushDynamicsInput),
 	DrawMode(DrawMode),
 	Flow(f64),
+	FlowDynamics(BrushDynamicsInput),
 	Hardness(f64),
+	HardnessDynamics(BrushDynamicsInput),
 	Spacing(f64),
 	WorkingColors(Option<Color>, Option<Color>),
 }
@@ -139,6 +150,14 @@ impl LayoutHolder for BrushTool {
 
 		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
 
+		widgets.push(dynamics_widget("Size", self.options.dynamics.diameter, BrushToolMessageOptionsUpdate::DiameterDynamics));
+		widgets.push(Separator::new(SeparatorType::Related).widget_holder());
+		widgets.push(dynamics_widget("Flow", self.options.dynamics.flow, BrushToolMessageOptionsUpdate::FlowDynamics));
+		widgets.push(Separator::new(SeparatorType::Related).widget_holder());
+		widgets.push(dynamics_widget("Hardness", self.options.dynamics.hardness, BrushToolMessageOptionsUpdate::HardnessDynamics));
+
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+
 		let draw_mode_entries: Vec<_> = [DrawMode::Draw, DrawMode::Erase, DrawMode::Restore]
 			.into_iter()
 			.map(|draw_mode| RadioEntryData::new(format!("{draw_mode:?}")).on_update(move |_| BrushToolMessage::UpdateOptions(BrushToolMessageOptionsUpdate::DrawMode(draw_mode)).into()))
@@ -182,6 +201,35 @@ impl LayoutHolder for BrushTool {
 	}
 }
 
+fn dynamics_widget(parameter: &str, selected: BrushDynamicsInput, update: fn(BrushDynamicsInput) -> BrushToolMessageOptionsUpdate) -> WidgetHolder {
+	let entries = BrushDynamicsInput::list()
+		.into_iter()
+		.map(|input| {
+			MenuListEntry::new(format!("{input:?}"))
+				.label(format!("{parameter}: {input:?}"))
+				.on_commit(move |_| BrushToolMessage::UpdateOptions(update(input)).into())
+		})
+		.collect();
+
+	DropdownInput::new(vec![entries])
+		.selected_index(BrushDynamicsInput::list().iter().position(|&input| input == selected).map(|index| index as u32))
+		.tooltip(format!("The stylus or pointer input which varies the brush {} along the stroke", parameter.to_lowercase()))
+		.widget_holder()
+}
+
+/// Grows or shrinks a brush diameter by the change, first snapping it to a multiple of the change if it's between them.
+pub(super) fn change_diameter(diameter: f64, change: f64) -> f64 {
+	let needs_rounding = ((diameter + change.abs() / 2.) % change.abs() - change.abs() / 2.).abs() > 0.5;
+	let diameter = if needs_rounding && change > 0. {
+		(diameter / change.abs()).ceil() * change.abs()
+	} else if needs_rounding && change < 0. {
+		(diameter / change.abs()).floor() * change.abs()
+	} else {
+		(diameter / change.abs()).round() * change.abs() + change
+	};
+	diameter.max(1.)
+}
+
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for BrushTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
 		let ToolMessage::Brush(BrushToolMessage::UpdateOptions(action)) = message else {
@@ -191,21 +239,16 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for BrushTo
 		match action {
 			BrushToolMessageOptionsUpdate::BlendMode(blend_mode) => self.options.blend_mode = blend_mode,
 			BrushToolMessageOptionsUpdate::ChangeDiameter(change) => {
-				let needs_rounding = ((self.options.diameter + change.abs() / 2.) % change.abs() - change.abs() / 2.).abs() > 0.5;
-				if needs_rounding && change > 0. {
-					self.options.diameter = (self.options.diameter / change.abs()).ceil() * change.abs();
-				} else if needs_rounding && change < 0. {
-					self.options.diameter = (self.options.diameter / change.abs()).floor() * change.abs();
-				} else {
-					self.options.diameter = (self.options.diameter / change.abs()).round() * change.abs() + change;
-				}
-				self.options.diameter = self.options.diameter.max(1.);
+				self.options.diameter = change_diameter(self.options.diameter, change);
 				self.send_layout(responses, LayoutTarget::ToolOptions);
 			}
 			BrushToolMessageOptionsUpdate::Diameter(diameter) => self.options.diameter = diameter,
+			BrushToolMessageOptionsUpdate::DiameterDynamics(input) => self.options.dynamics.diameter = input,
 			BrushToolMessageOptionsUpdate::DrawMode(draw_mode) => self.options.draw_mode = draw_mode,
 			BrushToolMessageOptionsUpdate::Hardness(hardness) => self.options.hardness = hardness,
+			BrushToolMessageOptionsUpdate::HardnessDynamics(input) => self.options.dynamics.hardness = input,
 			BrushToolMessageOptionsUpdate::Flow(flow) => self.options.flow = flow,
+			BrushToolMessageOptionsUpdate::FlowDynamics(input) => self.options.dynamics.flow = input,
 			BrushToolMessageOptionsUpdate::Spacing(spacing) => self.options.spacing = spacing,
 			BrushToolMessageOptionsUpdate::Color(color) => {
 				self.options.color.custom_color = color;
@@ -249,15 +292,20 @@ impl ToolTransition for BrushTool {
 }
 
 #[derive(Clone, Debug, Default)]
-struct BrushToolData {
-	strokes: Vec<BrushStroke>,
-	layer: Option<LayerNodeIdentifier>,
-	transform: DAffine2,
+pub(super) struct BrushToolData {
+	pub(super) strokes: Vec<BrushStroke>,
+	pub(super) layer: Option<LayerNodeIdentifier>,
+	pub(super) transform: DAffine2,
+	/// The viewport position of the pointer at the last frame in which it was sampled, used to measure its velocity
+	last_pointer: Option<(Duration, DVec2)>,
+	velocity: f64,
 }
 
 impl BrushToolData {
-	fn load_existing_strokes(&mut self, document: &DocumentMessageHandler) -> Option<LayerNodeIdentifier> {
+	pub(super) fn load_existing_strokes(&mut self, document: &DocumentMessageHandler) -> Option<LayerNodeIdentifier> {
 		self.transform = DAffine2::IDENTITY;
+		self.last_pointer = None;
+		self.velocity = 0.;
 
 		if document.selected_nodes.selected_layers(document.metadata()).count() != 1 {
 			return None;
@@ -291,11 +339,80 @@ impl BrushToolData {
 		None
 	}
 
-	fn update_strokes(&self, responses: &mut VecDeque<Message>) {
+	/// Loads the strokes of the single selected layer for a tool which samples the pixels it paints on, so the layer must be painted with the Brush tool or made from an image.
+	/// An image layer without a Brush node starts with no strokes, and the node is added to it along with the first stroke.
+	pub(super) fn load_sampled_layer(&mut self, document: &DocumentMessageHandler) -> Option<LayerNodeIdentifier> {
+		let mut selected_layers = document.selected_nodes.selected_layers(document.metadata());
+		let (Some(layer), None) = (selected_layers.next(), selected_layers.next()) else {
+			return None;
+		};
+
+		if self.load_existing_strokes(document).is_none() {
+			if !graph_modification_utils::is_layer_fed_by_node_of_name(layer, document.network(), "Image") {
+				return None;
+			}
+			self.strokes.clear();
+		}
+
+		self.layer = Some(layer);
+		Some(layer)
+	}
+
+	pub(super) fn update_strokes(&self, responses: &mut VecDeque<Message>) {
 		let Some(layer) = self.layer else { return };
 		let strokes = self.strokes.clone();
 		responses.add(GraphOperationMessage::Brush { layer, strokes });
 	}
+
+	/// Converts a viewport position into the space of the brush strokes in the layer.
+	pub(super) fn layer_position(&self, document: &DocumentMessageHandler, layer: LayerNodeIdentifier, viewport_position: DVec2) -> DVec2 {
+		let parent = layer.parent(document.metadata()).unwrap_or_default();
+		let parent_position = document.metadata().transform_to_viewport(parent).inverse().transform_point2(viewport_position);
+		self.transform.inverse().transform_point2(parent_position)
+	}
+
+	/// The outline of the area selected in Select Mode as polygons in the space of the brush strokes, which confine a new stroke to the selection.
+	pub(super) fn selection_clip(&self, document: &DocumentMessageHandler, layer: LayerNodeIdentifier) -> Vec<Vec<DVec2>> {
+		let parent = layer.parent(document.metadata()).unwrap_or_default();
+		let document_to_layer = (document.metadata().transform_to_document(parent) * self.transform).inverse();
+		let mut clip = document.selection_mask.polygons();
+		clip.iter_mut().flatten().for_each(|point| *point = document_to_layer.transform_point2(*point));
+		clip
+	}
+
+	/// Samples the stylus and the pointer velocity at the current pointer position, given in the space of the brush strokes.
+	pub(super) fn input_sample(&mut self, layer_position: DVec2, input: &InputPreprocessorMessageHandler) -> BrushInputSample {
+		// Several pointer events may arrive within a single frame, so the velocity is only measured once the frame time advances
+		let timestamp = input.frame_time.timestamp();
+		let pointer = input.mouse.position;
+		match self.last_pointer {
+			Some((last_timestamp, last_pointer)) if timestamp > last_timestamp => {
+				let speed = pointer.distance(last_pointer) / (timestamp - last_timestamp).as_secs_f64();
+				self.velocity = self.velocity * VELOCITY_SMOOTHING + speed * (1. - VELOCITY_SMOOTHING);
+				self.last_pointer = Some((timestamp, pointer));
+			}
+			Some(_) => {}
+			None => self.last_pointer = Some((timestamp, pointer)),
+		}
+
+		BrushInputSample {
+			position: layer_position,
+			pressure: input.mouse.stylus.pressure,
+			tilt: input.mouse.stylus.tilt,
+			velocity: self.velocity,
+		}
+	}
+
+	/// The scale from the space of the brush strokes to document space, used to keep the chosen brush diameter in document pixels.
+	pub(super) fn layer_scale(&self, document: &DocumentMessageHandler, layer: LayerNodeIdentifier) -> f64 {
+		let parent = layer.parent(document.metadata()).unwrap_or_default();
+		let layer_document_scale = document.metadata().transform_to_document(parent) * self.transform;
+		// TODO: Also scale it based on the input image ('Background' parameter).
+		// TODO: Resizing the input image results in a different brush size from the chosen diameter.
+		0.0001_f64 // Safety against division by zero
+			.max((layer_document_scale.matrix2 * glam::DVec2::X).length())
+			.max((layer_document_scale.matrix2 * glam::DVec2::Y).length())
+	}
 }
 
 impl Fsm for BrushToolFsmState {
@@ -318,16 +435,8 @@ impl Fsm for BrushToolFsmState {
 				let layer = loaded_layer.unwrap_or_else(|| new_brush_layer(document, responses));
 				tool_data.layer = Some(layer);
 
-				let parent = layer.parent(document.metadata()).unwrap_or_default();
-				let parent_transform = document.metadata().transform_to_viewport(parent).inverse().transform_point2(input.mouse.position);
-				let layer_position = tool_data.transform.inverse().transform_point2(parent_transform);
-
-				let layer_document_scale = document.metadata().transform_to_document(parent) * tool_data.transform;
-				// TODO: Also scale it based on the input image ('Background' parameter).
-				// TODO: Resizing the input image results in a different brush size from the chosen diameter.
-				let layer_scale = 0.0001_f64 // Safety against division by zero
-					.max((layer_document_scale.matrix2 * glam::DVec2::X).length())
-					.max((layer_document_scale.matrix2 * glam::DVec2::Y).length());
+				let layer_position = tool_data.layer_position(document, layer, input.mouse.position);
+				let layer_scale = tool_data.layer_scale(document, layer);
 
 				// Start a new stroke with a single sample
 				let blend_mode = match tool_options.draw_mode {
@@ -335,8 +444,10 @@ impl Fsm for BrushToolFsmState {
 					DrawMode::Erase => BlendMode::Erase,
 					DrawMode::Restore => BlendMode::Restore,
 				};
+				let sample = tool_data.input_sample(layer_position, input);
+				let clip = tool_data.selection_clip(document, layer);
 				tool_data.strokes.push(BrushStroke {
-					trace: vec![BrushInputSample { position: layer_position }],
+					trace: vec![sample],
 					style: BrushStyle {
 						color: tool_options.color.active_color().unwrap_or_default(),
 						diameter: tool_options.diameter / layer_scale,
@@ -344,7 +455,11 @@ impl Fsm for BrushToolFsmState {
 						flow: tool_options.flow,
 						spacing: tool_options.spacing,
 						blend_mode,
+						dynamics: tool_options.dynamics,
 					},
+					clone_offset: None,
+					healing: false,
+					clip,
 				});
 
 				tool_data.update_strokes(responses);
@@ -354,12 +469,10 @@ impl Fsm for BrushToolFsmState {
 
 			(BrushToolFsmState::Drawing, BrushToolMessage::PointerMove) => {
 				if let Some(layer) = tool_data.layer {
+					let layer_position = tool_data.layer_position(document, layer, input.mouse.position);
+					let sample = tool_data.input_sample(layer_position, input);
 					if let Some(stroke) = tool_data.strokes.last_mut() {
-						let parent = layer.parent(document.metadata()).unwrap_or_default();
-						let parent_position = document.metadata().transform_to_viewport(parent).inverse().transform_point2(input.mouse.position);
-						let layer_position = tool_data.transform.inverse().transform_point2(parent_position);
-
-						stroke.trace.push(BrushInputSample { position: layer_position })
+						stroke.trace.push(sample)
 					}
 				}
 				tool_data.update_strokes(responses);
@@ -428,3 +541,320 @@ fn new_brush_layer(document: &DocumentMessageHandler, responses: &mut VecDeque<M
 
 	LayerNodeIdentifier::new_unchecked(id)
 }
+
+/// Which of the tools painting with pixels sampled from the layer, rather than with a color, is using the implementation they share below.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum SamplingTool {
+	/// Copies the pixels at an offset from the brush
+	Clone,
+	/// Blends the texture of nearby or sampled pixels into the colors around the brush
+	Heal,
+}
+
+impl SamplingTool {
+	fn name(self) -> &'static str {
+		match self {
+			Self::Clone => "Clone",
+			Self::Heal => "Heal",
+		}
+	}
+
+	fn message(self, update: SamplingToolOptionsUpdate) -> Message {
+		match self {
+			Self::Clone => CloneToolMessage::UpdateOptions(update).into(),
+			Self::Heal => HealToolMessage::UpdateOptions(update).into(),
+		}
+	}
+}
+
+/// Where the texture used for healing comes from.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum HealSourceMode {
+	/// A smooth area near the stroke is found automatically
+	Proximity = 0,
+	/// The area at an Alt-clicked point, like the Clone tool
+	Sampled,
+}
+
+pub(super) struct SamplingToolOptions {
+	diameter: f64,
+	hardness: f64,
+	flow: f64,
+	spacing: f64,
+	/// Only offered by the Clone tool, since the Heal tool keeps the offset to its sampled source
+	aligned: bool,
+	/// Only offered by the Heal tool, since the Clone tool always samples from its source
+	source_mode: HealSourceMode,
+}
+
+impl Default for SamplingToolOptions {
+	fn default() -> Self {
+		Self {
+			diameter: 40.,
+			hardness: 0.,
+			flow: 100.,
+			spacing: 20.,
+			aligned: true,
+			source_mode: HealSourceMode::Proximity,
+		}
+	}
+}
+
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum SamplingToolOptionsUpdate {
+	Aligned(bool),
+	ChangeDiameter(f64),
+	Diameter(f64),
+	Flow(f64),
+	Hardness(f64),
+	SourceMode(HealSourceMode),
+	Spacing(f64),
+}
+
+impl SamplingToolOptions {
+	pub(super) fn update(&mut self, update: SamplingToolOptionsUpdate) {
+		match update {
+			SamplingToolOptionsUpdate::Aligned(aligned) => self.aligned = aligned,
+			SamplingToolOptionsUpdate::ChangeDiameter(change) => self.diameter = change_diameter(self.diameter, change),
+			SamplingToolOptionsUpdate::Diameter(diameter) => self.diameter = diameter,
+			SamplingToolOptionsUpdate::Flow(flow) => self.flow = flow,
+			SamplingToolOptionsUpdate::Hardness(hardness) => self.hardness = hardness,
+			SamplingToolOptionsUpdate::SourceMode(source_mode) => self.source_mode = source_mode,
+			SamplingToolOptionsUpdate::Spacing(spacing) => self.spacing = spacing,
+		}
+	}
+
+	pub(super) fn layout(&self, tool: SamplingTool) -> Layout {
+		let mut widgets = vec![
+			NumberInput::new(Some(self.diameter))
+				.label("Diameter")
+				.min(1.)
+				.max(BRUSH_MAX_SIZE) /* Anything bigger would cause the application to be unresponsive and eventually die */
+				.unit(" px")
+				.on_update(move |number_input: &NumberInput| tool.message(SamplingToolOptionsUpdate::Diameter(number_input.value.unwrap())))
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(self.hardness))
+				.label("Hardness")
+				.min(0.)
+				.max(100.)
+				.mode_range()
+				.unit("%")
+				.on_update(move |number_input: &NumberInput| tool.message(SamplingToolOptionsUpdate::Hardness(number_input.value.unwrap())))
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(self.flow))
+				.label("Flow")
+				.min(1.)
+				.max(100.)
+				.mode_range()
+				.unit("%")
+				.on_update(move |number_input: &NumberInput| tool.message(SamplingToolOptionsUpdate::Flow(number_input.value.unwrap())))
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(self.spacing))
+				.label("Spacing")
+				.min(1.)
+				.max(100.)
+				.mode_range()
+				.unit("%")
+				.on_update(move |number_input: &NumberInput| tool.message(SamplingToolOptionsUpdate::Spacing(number_input.value.unwrap())))
+				.widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+		];
+
+		match tool {
+			SamplingTool::Clone => {
+				let aligned_tooltip = "Keep the same offset between the source and the brush for every stroke, instead of sampling from the source point again with each new stroke";
+				widgets.extend([
+					CheckboxInput::new(self.aligned)
+						.on_update(move |&CheckboxInput { checked, .. }| tool.message(SamplingToolOptionsUpdate::Aligned(checked)))
+						.tooltip(aligned_tooltip)
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					TextLabel::new("Aligned").tooltip(aligned_tooltip).widget_holder(),
+				]);
+			}
+			SamplingTool::Heal => {
+				let source_mode_entries = vec![
+					RadioEntryData::new("Proximity")
+						.label("Proximity")
+						.tooltip("Heal with the texture of a smooth area near the stroke, which is found automatically")
+						.on_update(move |_| tool.message(SamplingToolOptionsUpdate::SourceMode(HealSourceMode::Proximity))),
+					RadioEntryData::new("Sampled")
+						.label("Sampled")
+						.tooltip("Heal with the texture around a point chosen with Alt+click, like the Clone tool")
+						.on_update(move |_| tool.message(SamplingToolOptionsUpdate::SourceMode(HealSourceMode::Sampled))),
+				];
+				widgets.push(RadioInput::new(source_mode_entries).selected_index(Some(self.source_mode as u32)).widget_holder());
+			}
+		}
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+/// The events of the Clone and Heal tools, whose messages share these variants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SamplingToolEvent {
+	Abort,
+	DragStart,
+	DragStop,
+	PointerMove,
+	SetSource,
+}
+
+impl SamplingToolEvent {
+	fn from_message(message: &ToolMessage) -> Option<Self> {
+		let event = match message {
+			ToolMessage::Clone(CloneToolMessage::Abort) | ToolMessage::Heal(HealToolMessage::Abort) => Self::Abort,
+			ToolMessage::Clone(CloneToolMessage::DragStart) | ToolMessage::Heal(HealToolMessage::DragStart) => Self::DragStart,
+			ToolMessage::Clone(CloneToolMessage::DragStop) | ToolMessage::Heal(HealToolMessage::DragStop) => Self::DragStop,
+			ToolMessage::Clone(CloneToolMessage::PointerMove) | ToolMessage::Heal(HealToolMessage::PointerMove) => Self::PointerMove,
+			ToolMessage::Clone(CloneToolMessage::SetSource) | ToolMessage::Heal(HealToolMessage::SetSource) => Self::SetSource,
+			_ => return None,
+		};
+		Some(event)
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum SamplingToolFsmState {
+	Ready(SamplingTool),
+	Drawing(SamplingTool),
+}
+
+#[derive(Clone, Debug, Default)]
+pub(super) struct SamplingToolData {
+	brush: BrushToolData,
+	/// The point to sample from, in document space, as chosen with Alt+click
+	source: Option<DVec2>,
+	/// The offset from the brush to the source used by the strokes on the layer since the source was chosen, kept while the strokes are aligned
+	aligned_offset: Option<(LayerNodeIdentifier, DVec2)>,
+}
+
+impl Fsm for SamplingToolFsmState {
+	type ToolData = SamplingToolData;
+	type ToolOptions = SamplingToolOptions;
+
+	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
+		let ToolActionHandlerData { document, input, .. } = tool_action_data;
+
+		let Some(event) = SamplingToolEvent::from_message(&event) else {
+			return self;
+		};
+		let (SamplingToolFsmState::Ready(tool) | SamplingToolFsmState::Drawing(tool)) = self;
+		let healing = tool == SamplingTool::Heal;
+
+		match (self, event) {
+			(SamplingToolFsmState::Ready(_), SamplingToolEvent::SetSource) => {
+				tool_data.source = Some(document.metadata().document_to_viewport.inverse().transform_point2(input.mouse.position));
+				tool_data.aligned_offset = None;
+
+				self
+			}
+			(SamplingToolFsmState::Ready(_), SamplingToolEvent::DragStart) => {
+				// The Clone tool, and the Heal tool in Sampled mode, paint with the pixels at an offset to the source which was Alt-clicked
+				let source = if healing && tool_options.source_mode == HealSourceMode::Proximity {
+					None
+				} else {
+					let Some(source) = tool_data.source else {
+						warn!("Alt+click to choose the point to sample from before painting with the {} tool", tool.name());
+						return self;
+					};
+					Some(source)
+				};
+
+				// The pixels are sampled from the layer being painted on, so there must be exactly one layer selected which has pixels
+				let Some(layer) = tool_data.brush.load_sampled_layer(document) else {
+					warn!("Select a single image layer, or one painted with the Brush tool, to paint on with the {} tool", tool.name());
+					return self;
+				};
+
+				responses.add(DocumentMessage::StartTransaction);
+
+				// The Heal tool always keeps the offset to its source, while the Clone tool keeps it only while its strokes are aligned
+				let layer_position = tool_data.brush.layer_position(document, layer, input.mouse.position);
+				let offset = source.map(|source| {
+					let offset = match tool_data.aligned_offset {
+						Some((aligned_layer, offset)) if (healing || tool_options.aligned) && aligned_layer == layer => offset,
+						_ => tool_data.brush.layer_position(document, layer, document.metadata().document_to_viewport.transform_point2(source)) - layer_position,
+					};
+					tool_data.aligned_offset = Some((layer, offset));
+					offset
+				});
+
+				let layer_scale = tool_data.brush.layer_scale(document, layer);
+				let sample = tool_data.brush.input_sample(layer_position, input);
+				let clip = tool_data.brush.selection_clip(document, layer);
+				tool_data.brush.strokes.push(BrushStroke {
+					trace: vec![sample],
+					style: BrushStyle {
+						// The brush only provides the coverage of the stroke, its color comes from the sampled pixels
+						color: Color::WHITE,
+						diameter: tool_options.diameter / layer_scale,
+						hardness: tool_options.hardness,
+						flow: tool_options.flow,
+						spacing: tool_options.spacing,
+						blend_mode: BlendMode::Normal,
+						dynamics: Default::default(),
+					},
+					clone_offset: offset,
+					healing,
+					clip,
+				});
+
+				tool_data.brush.update_strokes(responses);
+
+				SamplingToolFsmState::Drawing(tool)
+			}
+			(SamplingToolFsmState::Drawing(_), SamplingToolEvent::PointerMove) => {
+				if let Some(layer) = tool_data.brush.layer {
+					let layer_position = tool_data.brush.layer_position(document, layer, input.mouse.position);
+					let sample = tool_data.brush.input_sample(layer_position, input);
+					if let Some(stroke) = tool_data.brush.strokes.last_mut() {
+						stroke.trace.push(sample)
+					}
+				}
+				tool_data.brush.update_strokes(responses);
+
+				self
+			}
+			(SamplingToolFsmState::Drawing(_), SamplingToolEvent::DragStop) => {
+				if !tool_data.brush.strokes.is_empty() {
+					responses.add(DocumentMessage::CommitTransaction);
+				}
+				tool_data.brush.strokes.clear();
+
+				SamplingToolFsmState::Ready(tool)
+			}
+			(SamplingToolFsmState::Drawing(_), SamplingToolEvent::Abort) => {
+				responses.add(DocumentMessage::AbortTransaction);
+				tool_data.brush.strokes.clear();
+
+				SamplingToolFsmState::Ready(tool)
+			}
+			_ => self,
+		}
+	}
+
+	fn update_hints(&self, responses: &mut VecDeque<Message>) {
+		let hint_data = match *self {
+			SamplingToolFsmState::Ready(tool) => {
+				let set_source = if tool == SamplingTool::Heal { "Set Sampled Source" } else { "Set Source" };
+				HintData(vec![
+					HintGroup(vec![HintInfo::keys_and_mouse([Key::Alt], MouseMotion::Lmb, set_source)]),
+					HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, tool.name())]),
+					HintGroup(vec![HintInfo::keys([Key::BracketLeft, Key::BracketRight], "Shrink/Grow Brush")]),
+				])
+			}
+			SamplingToolFsmState::Drawing(_) => HintData(vec![HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()])]),
+		};
+
+		responses.add(FrontendMessage::UpdateInputHints { hint_data });
+	}
+
+	fn update_cursor(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Crosshair });
+	}
+}
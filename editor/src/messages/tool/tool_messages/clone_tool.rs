@@ -0,0 +1,87 @@
+use super::brush_tool::{SamplingTool, SamplingToolData, SamplingToolFsmState, SamplingToolOptions, SamplingToolOptionsUpdate};
+use super::tool_prelude::*;
+
+pub struct CloneTool {
+	fsm_state: SamplingToolFsmState,
+	data: SamplingToolData,
+	options: SamplingToolOptions,
+}
+
+impl Default for CloneTool {
+	fn default() -> Self {
+		Self {
+			fsm_state: SamplingToolFsmState::Ready(SamplingTool::Clone),
+			data: SamplingToolData::default(),
+			options: SamplingToolOptions::default(),
+		}
+	}
+}
+
+#[impl_message(Message, ToolMessage, Clone)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum CloneToolMessage {
+	// Standard messages
+	Abort,
+
+	// Tool-specific messages
+	DragStart,
+	DragStop,
+	PointerMove,
+	SetSource,
+	UpdateOptions(SamplingToolOptionsUpdate),
+}
+
+impl ToolMetadata for CloneTool {
+	fn icon_name(&self) -> String {
+		"RasterCloneTool".into()
+	}
+	fn tooltip(&self) -> String {
+		"Clone Tool".into()
+	}
+	fn tool_type(&self) -> crate::messages::tool::utility_types::ToolType {
+		ToolType::Clone
+	}
+}
+
+impl LayoutHolder for CloneTool {
+	fn layout(&self) -> Layout {
+		self.options.layout(SamplingTool::Clone)
+	}
+}
+
+impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for CloneTool {
+	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
+		let ToolMessage::Clone(CloneToolMessage::UpdateOptions(action)) = message else {
+			self.fsm_state.process_event(message, &mut self.data, tool_data, &self.options, responses, true);
+			return;
+		};
+		self.options.update(action);
+
+		self.send_layout(responses, LayoutTarget::ToolOptions);
+	}
+
+	fn actions(&self) -> ActionList {
+		match self.fsm_state {
+			SamplingToolFsmState::Ready(_) => actions!(CloneToolMessageDiscriminant;
+				DragStart,
+				SetSource,
+				UpdateOptions,
+			),
+			SamplingToolFsmState::Drawing(_) => actions!(CloneToolMessageDiscriminant;
+				DragStop,
+				PointerMove,
+				Abort,
+				UpdateOptions,
+			),
+		}
+	}
+}
+
+impl ToolTransition for CloneTool {
+	fn event_to_message_map(&self) -> EventToMessageMap {
+		EventToMessageMap {
+			tool_abort: Some(CloneToolMessage::Abort.into()),
+			..Default::default()
+		}
+	}
+}
@@ -1,14 +1,33 @@
 use super::tool_prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils::{get_upstream_raster_transform, is_layer_fed_by_node_of_name};
 
 use graphene_core::vector::style::Fill;
 
 #[derive(Default)]
 pub struct FillTool {
 	fsm_state: FillToolFsmState,
+	options: FillOptions,
+}
+
+/// Options for filling raster layers, whose pixels are flood filled outwards from the one clicked on.
+pub struct FillOptions {
+	tolerance: f64,
+	contiguous: bool,
+	antialias: bool,
+}
+
+impl Default for FillOptions {
+	fn default() -> Self {
+		Self {
+			tolerance: 10.,
+			contiguous: true,
+			antialias: true,
+		}
+	}
 }
 
 #[impl_message(Message, ToolMessage, Fill)]
-#[derive(PartialEq, Eq, Clone, Debug, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum FillToolMessage {
 	// Standard messages
 	Abort,
@@ -17,6 +36,14 @@ pub enum FillToolMessage {
 	PointerUp,
 	FillPrimaryColor,
 	FillSecondaryColor,
+	UpdateOptions(FillOptionsUpdate),
+}
+
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum FillOptionsUpdate {
+	Antialias(bool),
+	Contiguous(bool),
+	Tolerance(f64),
 }
 
 impl ToolMetadata for FillTool {
@@ -33,23 +60,64 @@ impl ToolMetadata for FillTool {
 
 impl LayoutHolder for FillTool {
 	fn layout(&self) -> Layout {
-		Layout::WidgetLayout(WidgetLayout::default())
+		let tolerance_tooltip = "How different the colors of the pixels filled on raster layers may be from the one clicked on";
+		let contiguous_tooltip = "Only fill pixels connected to the one clicked on, instead of every matching pixel of a raster layer";
+		let antialias_tooltip = "Smooth the edges of fills on raster layers by partially filling the pixels bordering them";
+		let widgets = vec![
+			NumberInput::new(Some(self.options.tolerance))
+				.label("Tolerance")
+				.min(0.)
+				.max(100.)
+				.mode_range()
+				.unit("%")
+				.tooltip(tolerance_tooltip)
+				.on_update(|number_input: &NumberInput| FillToolMessage::UpdateOptions(FillOptionsUpdate::Tolerance(number_input.value.unwrap())).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.options.contiguous)
+				.on_update(|&CheckboxInput { checked, .. }| FillToolMessage::UpdateOptions(FillOptionsUpdate::Contiguous(checked)).into())
+				.tooltip(contiguous_tooltip)
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextLabel::new("Contiguous").tooltip(contiguous_tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.options.antialias)
+				.on_update(|&CheckboxInput { checked, .. }| FillToolMessage::UpdateOptions(FillOptionsUpdate::Antialias(checked)).into())
+				.tooltip(antialias_tooltip)
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextLabel::new("Anti-aliasing").tooltip(antialias_tooltip).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
 	}
 }
 
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for FillTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
-		self.fsm_state.process_event(message, &mut (), tool_data, &(), responses, true);
+		let ToolMessage::Fill(FillToolMessage::UpdateOptions(action)) = message else {
+			self.fsm_state.process_event(message, &mut (), tool_data, &self.options, responses, true);
+			return;
+		};
+		match action {
+			FillOptionsUpdate::Antialias(antialias) => self.options.antialias = antialias,
+			FillOptionsUpdate::Contiguous(contiguous) => self.options.contiguous = contiguous,
+			FillOptionsUpdate::Tolerance(tolerance) => self.options.tolerance = tolerance,
+		}
+
+		self.send_layout(responses, LayoutTarget::ToolOptions);
 	}
 	fn actions(&self) -> ActionList {
 		match self.fsm_state {
 			FillToolFsmState::Ready => actions!(FillToolMessageDiscriminant;
 				FillPrimaryColor,
 				FillSecondaryColor,
+				UpdateOptions,
 			),
 			FillToolFsmState::Filling => actions!(FillToolMessageDiscriminant;
 				PointerUp,
 				Abort,
+				UpdateOptions,
 			),
 		}
 	}
@@ -74,9 +142,9 @@ enum FillToolFsmState {
 
 impl Fsm for FillToolFsmState {
 	type ToolData = ();
-	type ToolOptions = ();
+	type ToolOptions = FillOptions;
 
-	fn transition(self, event: ToolMessage, _tool_data: &mut Self::ToolData, handler_data: &mut ToolActionHandlerData, _tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
+	fn transition(self, event: ToolMessage, _tool_data: &mut Self::ToolData, handler_data: &mut ToolActionHandlerData, tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
 		let ToolActionHandlerData {
 			document, global_tool_data, input, ..
 		} = handler_data;
@@ -90,14 +158,31 @@ impl Fsm for FillToolFsmState {
 				let Some(layer_identifier) = document.click(input.mouse.position, &document.network) else {
 					return self;
 				};
-				let fill = match color_event {
-					FillToolMessage::FillPrimaryColor => Fill::Solid(global_tool_data.primary_color),
-					FillToolMessage::FillSecondaryColor => Fill::Solid(global_tool_data.secondary_color),
+				let color = match color_event {
+					FillToolMessage::FillPrimaryColor => global_tool_data.primary_color,
+					FillToolMessage::FillSecondaryColor => global_tool_data.secondary_color,
 					_ => return self,
 				};
 
 				responses.add(DocumentMessage::StartTransaction);
-				responses.add(GraphOperationMessage::FillSet { layer: layer_identifier, fill });
+				if is_layer_fed_by_node_of_name(layer_identifier, &document.network, "Image") || is_layer_fed_by_node_of_name(layer_identifier, &document.network, "Brush") {
+					// The seed is given in the space of the raster data before the layer's Transform node, where the Flood Fill node is inserted
+					let parent = layer_identifier.parent(document.metadata()).unwrap_or_default();
+					let raster_to_viewport = document.metadata().transform_to_viewport(parent) * get_upstream_raster_transform(layer_identifier, &document.network, document.metadata());
+					responses.add(GraphOperationMessage::FloodFill {
+						layer: layer_identifier,
+						seed: raster_to_viewport.inverse().transform_point2(input.mouse.position),
+						color,
+						tolerance: tool_options.tolerance,
+						contiguous: tool_options.contiguous,
+						antialias: tool_options.antialias,
+					});
+				} else {
+					responses.add(GraphOperationMessage::FillSet {
+						layer: layer_identifier,
+						fill: Fill::Solid(color),
+					});
+				}
 				responses.add(DocumentMessage::CommitTransaction);
 
 				FillToolFsmState::Filling
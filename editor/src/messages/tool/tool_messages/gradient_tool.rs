@@ -6,7 +6,7 @@ use crate::messages::tool::common_functionality::auto_panning::AutoPanning;
 use crate::messages::tool::common_functionality::graph_modification_utils::get_gradient;
 use crate::messages::tool::common_functionality::snapping::SnapManager;
 
-use graphene_core::vector::style::{Fill, Gradient, GradientType};
+use graphene_core::vector::style::{Fill, Gradient, GradientSpreadMethod, GradientType};
 
 #[derive(Default)]
 pub struct GradientTool {
@@ -18,6 +18,7 @@ pub struct GradientTool {
 #[derive(Default)]
 pub struct GradientOptions {
 	gradient_type: GradientType,
+	spread_method: GradientSpreadMethod,
 }
 
 #[impl_message(Message, ToolMessage, Gradient)]
@@ -39,6 +40,7 @@ pub enum GradientToolMessage {
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum GradientOptionsUpdate {
+	SpreadMethod(GradientSpreadMethod),
 	Type(GradientType),
 }
 
@@ -61,6 +63,13 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Gradien
 			return;
 		};
 		match action {
+			GradientOptionsUpdate::SpreadMethod(spread_method) => {
+				self.options.spread_method = spread_method;
+				if let Some(selected_gradient) = &mut self.data.selected_gradient {
+					selected_gradient.gradient.spread_method = spread_method;
+					selected_gradient.render_gradient(responses);
+				}
+			}
 			GradientOptionsUpdate::Type(gradient_type) => {
 				self.options.gradient_type = gradient_type;
 				if let Some(selected_gradient) = &mut self.data.selected_gradient {
@@ -83,20 +92,33 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Gradien
 
 impl LayoutHolder for GradientTool {
 	fn layout(&self) -> Layout {
-		let gradient_type = RadioInput::new(vec![
-			RadioEntryData::new("linear")
-				.label("Linear")
-				.tooltip("Linear Gradient")
-				.on_update(move |_| GradientToolMessage::UpdateOptions(GradientOptionsUpdate::Type(GradientType::Linear)).into()),
-			RadioEntryData::new("radial")
-				.label("Radial")
-				.tooltip("Radial Gradient")
-				.on_update(move |_| GradientToolMessage::UpdateOptions(GradientOptionsUpdate::Type(GradientType::Radial)).into()),
-		])
-		.selected_index(Some((self.selected_gradient().unwrap_or(self.options.gradient_type) == GradientType::Radial) as u32))
-		.widget_holder();
-
-		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets: vec![gradient_type] }]))
+		let gradient_type_entries = GradientType::list()
+			.into_iter()
+			.map(|gradient_type| {
+				RadioEntryData::new(format!("{gradient_type:?}").to_lowercase())
+					.label(format!("{gradient_type:?}"))
+					.tooltip(format!("{gradient_type:?} Gradient"))
+					.on_update(move |_| GradientToolMessage::UpdateOptions(GradientOptionsUpdate::Type(gradient_type)).into())
+			})
+			.collect();
+		let gradient_type = RadioInput::new(gradient_type_entries)
+			.selected_index(Some(self.selected_gradient().unwrap_or(self.options.gradient_type) as u32))
+			.widget_holder();
+
+		let spread_method_entries = GradientSpreadMethod::list()
+			.into_iter()
+			.map(|spread_method| {
+				RadioEntryData::new(format!("{spread_method:?}").to_lowercase())
+					.label(format!("{spread_method:?}"))
+					.tooltip(format!("{spread_method:?} Spread Method"))
+					.on_update(move |_| GradientToolMessage::UpdateOptions(GradientOptionsUpdate::SpreadMethod(spread_method)).into())
+			})
+			.collect();
+		let selected_spread_method = self.data.selected_gradient.as_ref().map_or(self.options.spread_method, |selected| selected.gradient.spread_method);
+		let spread_method = RadioInput::new(spread_method_entries).selected_index(Some(selected_spread_method as u32)).widget_holder();
+
+		let widgets = vec![gradient_type, Separator::new(SeparatorType::Unrelated).widget_holder(), spread_method];
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
 	}
 }
 
@@ -410,14 +432,17 @@ impl Fsm for GradientToolFsmState {
 							gradient.clone()
 						} else {
 							// Generate a new gradient
-							Gradient::new(
-								DVec2::ZERO,
-								global_tool_data.secondary_color,
-								DVec2::ONE,
-								global_tool_data.primary_color,
-								DAffine2::IDENTITY,
-								tool_options.gradient_type,
-							)
+							Gradient {
+								spread_method: tool_options.spread_method,
+								..Gradient::new(
+									DVec2::ZERO,
+									global_tool_data.secondary_color,
+									DVec2::ONE,
+									global_tool_data.primary_color,
+									DAffine2::IDENTITY,
+									tool_options.gradient_type,
+								)
+							}
 						};
 						let selected_gradient = SelectedGradient::new(gradient, layer, document).with_gradient_start(input.mouse.position);
 
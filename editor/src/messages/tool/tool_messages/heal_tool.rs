@@ -0,0 +1,87 @@
+use super::brush_tool::{SamplingTool, SamplingToolData, SamplingToolFsmState, SamplingToolOptions, SamplingToolOptionsUpdate};
+use super::tool_prelude::*;
+
+pub struct HealTool {
+	fsm_state: SamplingToolFsmState,
+	data: SamplingToolData,
+	options: SamplingToolOptions,
+}
+
+impl Default for HealTool {
+	fn default() -> Self {
+		Self {
+			fsm_state: SamplingToolFsmState::Ready(SamplingTool::Heal),
+			data: SamplingToolData::default(),
+			options: SamplingToolOptions::default(),
+		}
+	}
+}
+
+#[impl_message(Message, ToolMessage, Heal)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum HealToolMessage {
+	// Standard messages
+	Abort,
+
+	// Tool-specific messages
+	DragStart,
+	DragStop,
+	PointerMove,
+	SetSource,
+	UpdateOptions(SamplingToolOptionsUpdate),
+}
+
+impl ToolMetadata for HealTool {
+	fn icon_name(&self) -> String {
+		"RasterHealTool".into()
+	}
+	fn tooltip(&self) -> String {
+		"Heal Tool".into()
+	}
+	fn tool_type(&self) -> crate::messages::tool::utility_types::ToolType {
+		ToolType::Heal
+	}
+}
+
+impl LayoutHolder for HealTool {
+	fn layout(&self) -> Layout {
+		self.options.layout(SamplingTool::Heal)
+	}
+}
+
+impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for HealTool {
+	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
+		let ToolMessage::Heal(HealToolMessage::UpdateOptions(action)) = message else {
+			self.fsm_state.process_event(message, &mut self.data, tool_data, &self.options, responses, true);
+			return;
+		};
+		self.options.update(action);
+
+		self.send_layout(responses, LayoutTarget::ToolOptions);
+	}
+
+	fn actions(&self) -> ActionList {
+		match self.fsm_state {
+			SamplingToolFsmState::Ready(_) => actions!(HealToolMessageDiscriminant;
+				DragStart,
+				SetSource,
+				UpdateOptions,
+			),
+			SamplingToolFsmState::Drawing(_) => actions!(HealToolMessageDiscriminant;
+				DragStop,
+				PointerMove,
+				Abort,
+				UpdateOptions,
+			),
+		}
+	}
+}
+
+impl ToolTransition for HealTool {
+	fn event_to_message_map(&self) -> EventToMessageMap {
+		EventToMessageMap {
+			tool_abort: Some(HealToolMessage::Abort.into()),
+			..Default::default()
+		}
+	}
+}
@@ -1,10 +1,12 @@
 pub mod artboard_tool;
 pub mod brush_tool;
+pub mod clone_tool;
 pub mod ellipse_tool;
 pub mod eyedropper_tool;
 pub mod fill_tool;
 pub mod freehand_tool;
 pub mod gradient_tool;
+pub mod heal_tool;
 pub mod imaginate_tool;
 pub mod line_tool;
 pub mod navigate_tool;
@@ -10,11 +10,12 @@ use crate::messages::portfolio::document::utility_types::document_metadata::Laye
 use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis};
 use crate::messages::portfolio::document::utility_types::transformation::Selected;
 use crate::messages::tool::common_functionality::auto_panning::AutoPanning;
-use crate::messages::tool::common_functionality::graph_modification_utils::is_layer_fed_by_node_of_name;
+use crate::messages::tool::common_functionality::graph_modification_utils::{is_layer_fed_by_node_of_name, is_text_layer};
 use crate::messages::tool::common_functionality::pivot::Pivot;
 use crate::messages::tool::common_functionality::snapping::{self, SnapCandidatePoint, SnapConstraint, SnapData, SnapManager, SnappedPoint};
 use crate::messages::tool::common_functionality::transformation_cage::*;
 
+use bezier_rs::BooleanOperation;
 use graph_craft::document::{DocumentNode, NodeId, NodeNetwork};
 use graphene_core::renderer::Quad;
 
@@ -147,10 +148,18 @@ impl SelectTool {
 	}
 
 	fn boolean_widgets(&self) -> impl Iterator<Item = WidgetHolder> {
-		["Union", "Subtract Front", "Subtract Back", "Intersect", "Difference"].into_iter().map(|name| {
+		[
+			("Union", BooleanOperation::Union),
+			("Subtract Front", BooleanOperation::SubtractFront),
+			("Subtract Back", BooleanOperation::SubtractBack),
+			("Intersect", BooleanOperation::Intersect),
+			("Difference", BooleanOperation::Difference),
+		]
+		.into_iter()
+		.map(|(name, operation)| {
 			IconButton::new(format!("Boolean{}", name.replace(' ', "")), 24)
-				.tooltip(format!("Boolean {name} (coming soon)"))
-				.on_update(|_| DialogMessage::RequestComingSoonDialog { issue: Some(1091) }.into())
+				.tooltip(format!("Boolean {name}"))
+				.on_update(move |_| DocumentMessage::BooleanOperationSelectedLayers { operation }.into())
 				.widget_holder()
 		})
 	}
@@ -965,7 +974,7 @@ impl Fsm for SelectToolFsmState {
 
 				if let Some(layer) = selected_layers.next() {
 					// Check that only one layer is selected
-					if selected_layers.next().is_none() && is_layer_fed_by_node_of_name(layer, &document.network, "Text") {
+					if selected_layers.next().is_none() && is_text_layer(layer, &document.network) {
 						responses.add_front(ToolMessage::ActivateTool { tool_type: ToolType::Text });
 						responses.add(TextToolMessage::EditSelected);
 					}
@@ -1142,7 +1151,7 @@ fn edit_layer_shallowest_manipulation(document: &DocumentMessageHandler, layer:
 }
 
 fn edit_layer_deepest_manipulation(layer: LayerNodeIdentifier, document_network: &NodeNetwork, responses: &mut VecDeque<Message>) {
-	if is_layer_fed_by_node_of_name(layer, document_network, "Text") {
+	if is_text_layer(layer, document_network) {
 		responses.add_front(ToolMessage::ActivateTool { tool_type: ToolType::Text });
 		responses.add(TextToolMessage::EditSelected);
 	} else if is_layer_fed_by_node_of_name(layer, document_network, "Shape") {
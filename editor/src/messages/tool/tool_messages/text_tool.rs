@@ -7,13 +7,12 @@ use crate::messages::portfolio::document::graph_operation::utility_types::Transf
 use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
-use crate::messages::tool::common_functionality::graph_modification_utils::{self, is_layer_fed_by_node_of_name};
+use crate::messages::tool::common_functionality::graph_modification_utils::{self, is_text_layer};
 
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::NodeId;
 use graphene_core::renderer::Quad;
-use graphene_core::text::{load_face, Font, FontCache};
-use graphene_core::vector::style::Fill;
+use graphene_core::text::{load_face, Font, FontCache, TextAlignment, TextRun, Typography};
 use graphene_core::Color;
 
 #[derive(Default)]
@@ -202,6 +201,25 @@ pub struct EditingText {
 	font_size: f64,
 	color: Option<Color>,
 	transform: DAffine2,
+	/// The formatting of a layer made by a Typography node, or `None` for a layer of outlines made by a Text node in an older document
+	typography: Option<Typography>,
+}
+
+impl EditingText {
+	/// Lays out the given text with the formatting of the layer, returning its extent
+	fn size(&self, text: &str, font_cache: &FontCache) -> DVec2 {
+		if let Some(typography) = &self.typography {
+			let mut typography = typography.clone();
+			if let Some(run) = typography.runs.first_mut() {
+				run.text = text.to_string();
+			}
+			typography.layout(font_cache);
+			return typography.size;
+		}
+
+		let buzz_face = font_cache.get(&self.font).map(|data| load_face(data));
+		graphene_core::text::bounding_box(text, buzz_face, self.font_size, None)
+	}
 }
 
 #[derive(Clone, Debug, Default)]
@@ -213,15 +231,16 @@ struct TextToolData {
 
 impl TextToolData {
 	/// Set the editing state of the currently modifying layer
-	fn set_editing(&self, editable: bool, font_cache: &FontCache, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
-		if let Some(node_id) = graph_modification_utils::get_fill_id(self.layer, &document.network) {
-			responses.add(NodeGraphMessage::SetVisibility { node_id, visible: !editable });
-		}
+	fn set_editing(&self, editable: bool, font_cache: &FontCache, responses: &mut VecDeque<Message>) {
+		// Hide the rendered text while the textbox shows it
+		responses.add(DocumentMessage::SetTextBeingEdited {
+			layer: editable.then_some(self.layer),
+		});
 
 		if let Some(editing_text) = self.editing_text.as_ref().filter(|_| editable) {
 			responses.add(FrontendMessage::DisplayEditableTextbox {
 				text: editing_text.text.clone(),
-				line_width: None,
+				line_width: editing_text.typography.as_ref().and_then(|typography| typography.box_width),
 				font_size: editing_text.font_size,
 				color: editing_text.color.unwrap_or(Color::BLACK),
 				url: font_cache.get_preview_url(&editing_text.font).cloned().unwrap_or_default(),
@@ -234,14 +253,19 @@ impl TextToolData {
 
 	fn load_layer_text_node(&mut self, document: &DocumentMessageHandler) -> Option<()> {
 		let transform = document.metadata().transform_to_viewport(self.layer);
-		let color = graph_modification_utils::get_fill_color(self.layer, &document.network).unwrap_or(Color::BLACK);
+		let typography = graph_modification_utils::get_typography(self.layer, &document.network);
+		let color = match &typography {
+			Some(typography) => typography.runs.first().map(|run| run.color),
+			None => graph_modification_utils::get_fill_color(self.layer, &document.network),
+		};
 		let (text, font, font_size) = graph_modification_utils::get_text(self.layer, &document.network)?;
 		self.editing_text = Some(EditingText {
 			text: text.clone(),
 			font: font.clone(),
 			font_size,
-			color: Some(color),
+			color: Some(color.unwrap_or(Color::BLACK)),
 			transform,
+			typography,
 		});
 		self.new_text = text.clone();
 		Some(())
@@ -249,7 +273,7 @@ impl TextToolData {
 
 	fn start_editing_layer(&mut self, layer: LayerNodeIdentifier, tool_state: TextToolFsmState, document: &DocumentMessageHandler, font_cache: &FontCache, responses: &mut VecDeque<Message>) {
 		if tool_state == TextToolFsmState::Editing {
-			self.set_editing(false, font_cache, document, responses);
+			self.set_editing(false, font_cache, responses);
 		}
 
 		self.layer = layer;
@@ -257,17 +281,14 @@ impl TextToolData {
 
 		responses.add(DocumentMessage::StartTransaction);
 
-		self.set_editing(true, font_cache, document, responses);
+		self.set_editing(true, font_cache, responses);
 
 		responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![self.layer.to_node()] });
 	}
 
 	fn interact(&mut self, state: TextToolFsmState, mouse: DVec2, document: &DocumentMessageHandler, font_cache: &FontCache, responses: &mut VecDeque<Message>) -> TextToolFsmState {
 		// Check if the user has selected an existing text layer
-		if let Some(clicked_text_layer_path) = document
-			.click(mouse, document.network())
-			.filter(|&layer| is_layer_fed_by_node_of_name(layer, &document.network, "Text"))
-		{
+		if let Some(clicked_text_layer_path) = document.click(mouse, document.network()).filter(|&layer| is_text_layer(layer, &document.network)) {
 			self.start_editing_layer(clicked_text_layer_path, state, document, font_cache, responses);
 
 			TextToolFsmState::Editing
@@ -283,13 +304,10 @@ impl TextToolData {
 				text: String::new(),
 				font: editing_text.font.clone(),
 				size: editing_text.font_size,
+				color: editing_text.color.unwrap_or(Color::TRANSPARENT),
 				parent: document.new_layer_parent(true),
 				insert_index: -1,
 			});
-			responses.add(GraphOperationMessage::FillSet {
-				layer: self.layer,
-				fill: if editing_text.color.is_some() { Fill::Solid(editing_text.color.unwrap()) } else { Fill::None },
-			});
 			responses.add(GraphOperationMessage::TransformSet {
 				layer: self.layer,
 				transform: editing_text.transform,
@@ -297,14 +315,14 @@ impl TextToolData {
 				skip_rerender: true,
 			});
 
-			self.set_editing(true, font_cache, document, responses);
+			self.set_editing(true, font_cache, responses);
 
 			responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![self.layer.to_node()] });
 
 			TextToolFsmState::Editing
 		} else {
 			// Removing old text as editable
-			self.set_editing(false, font_cache, document, responses);
+			self.set_editing(false, font_cache, responses);
 
 			TextToolFsmState::Ready
 		}
@@ -312,6 +330,10 @@ impl TextToolData {
 
 	fn get_bounds(&self, text: &str, font_cache: &FontCache) -> Option<[DVec2; 2]> {
 		let editing_text = self.editing_text.as_ref()?;
+		if editing_text.typography.is_some() {
+			return Some([DVec2::ZERO, editing_text.size(text, font_cache)]);
+		}
+
 		let buzz_face = font_cache.get(&editing_text.font).map(|data| load_face(data));
 		let subpaths = graphene_core::text::to_path(text, buzz_face, editing_text.font_size, None);
 		let bounds = subpaths.iter().filter_map(|subpath| subpath.bounding_box());
@@ -339,7 +361,7 @@ fn can_edit_selected(document: &DocumentMessageHandler) -> Option<LayerNodeIdent
 		return None;
 	}
 
-	if !is_layer_fed_by_node_of_name(layer, &document.network, "Text") {
+	if !is_text_layer(layer, &document.network) {
 		return None;
 	}
 
@@ -367,8 +389,7 @@ impl Fsm for TextToolFsmState {
 					transform: document.metadata().transform_to_viewport(tool_data.layer).to_cols_array(),
 				});
 				if let Some(editing_text) = tool_data.editing_text.as_ref() {
-					let buzz_face = font_cache.get(&editing_text.font).map(|data| load_face(data));
-					let far = graphene_core::text::bounding_box(&tool_data.new_text, buzz_face, editing_text.font_size, None);
+					let far = editing_text.size(&tool_data.new_text, font_cache);
 					if far.x != 0. && far.y != 0. {
 						let quad = Quad::from_box([DVec2::ZERO, far]);
 						let transformed_quad = document.metadata().transform_to_viewport(tool_data.layer) * quad;
@@ -380,11 +401,15 @@ impl Fsm for TextToolFsmState {
 			}
 			(_, TextToolMessage::Overlays(mut overlay_context)) => {
 				for layer in document.selected_nodes.selected_layers(document.metadata()) {
-					let Some((text, font, font_size)) = graph_modification_utils::get_text(layer, &document.network) else {
+					let far = if let Some(mut typography) = graph_modification_utils::get_typography(layer, &document.network) {
+						typography.layout(font_cache);
+						typography.size
+					} else if let Some((text, font, font_size)) = graph_modification_utils::get_text(layer, &document.network) {
+						let buzz_face = font_cache.get(font).map(|data| load_face(data));
+						graphene_core::text::bounding_box(text, buzz_face, font_size, None)
+					} else {
 						continue;
 					};
-					let buzz_face = font_cache.get(font).map(|data| load_face(data));
-					let far = graphene_core::text::bounding_box(text, buzz_face, font_size, None);
 					let quad = Quad::from_box([DVec2::ZERO, far]);
 					let multiplied = document.metadata().transform_to_viewport(layer) * quad;
 					overlay_context.quad(multiplied);
@@ -393,12 +418,19 @@ impl Fsm for TextToolFsmState {
 				self
 			}
 			(state, TextToolMessage::Interact) => {
+				let font = Font::new(tool_options.font_name.clone(), tool_options.font_style.clone());
+				let font_size = tool_options.font_size as f64;
+				let color = tool_options.fill.active_color();
+				let mut run = TextRun::new(String::new(), font.clone(), font_size);
+				run.color = color.unwrap_or(Color::TRANSPARENT);
+
 				tool_data.editing_text = Some(EditingText {
 					text: String::new(),
 					transform: DAffine2::from_translation(input.mouse.position),
-					font_size: tool_options.font_size as f64,
-					font: Font::new(tool_options.font_name.clone(), tool_options.font_style.clone()),
-					color: tool_options.fill.active_color(),
+					font_size,
+					font,
+					color,
+					typography: Some(Typography::new(vec![run], TextAlignment::Left, None)),
 				});
 				tool_data.new_text = String::new();
 
@@ -414,7 +446,7 @@ impl Fsm for TextToolFsmState {
 			}
 			(state, TextToolMessage::Abort) => {
 				if state == TextToolFsmState::Editing {
-					tool_data.set_editing(false, font_cache, document, responses);
+					tool_data.set_editing(false, font_cache, responses);
 				}
 
 				TextToolFsmState::Ready
@@ -432,7 +464,7 @@ impl Fsm for TextToolFsmState {
 					value: TaggedValue::String(new_text),
 				});
 
-				tool_data.set_editing(false, font_cache, document, responses);
+				tool_data.set_editing(false, font_cache, responses);
 
 				TextToolFsmState::Ready
 			}
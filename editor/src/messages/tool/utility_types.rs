@@ -399,11 +399,11 @@ fn list_tools_in_groups() -> Vec<Vec<ToolAvailability>> {
 			// Raster tool group
 			// ToolAvailability::Available(Box::<imaginate_tool::ImaginateTool>::default()), // TODO: Fix and reenable ASAP
 			ToolAvailability::ComingSoon(
-				ToolEntry::new(ToolType::Heal, "RasterImaginateTool").tooltip("Coming Soon: Imaginate Tool - Temporarily disabled, please use Imaginate node directly from graph"),
+				ToolEntry::new(ToolType::Imaginate, "RasterImaginateTool").tooltip("Coming Soon: Imaginate Tool - Temporarily disabled, please use Imaginate node directly from graph"),
 			),
 			ToolAvailability::Available(Box::<brush_tool::BrushTool>::default()),
-			ToolAvailability::ComingSoon(ToolEntry::new(ToolType::Heal, "RasterHealTool").tooltip("Coming Soon: Heal Tool (J)")),
-			ToolAvailability::ComingSoon(ToolEntry::new(ToolType::Clone, "RasterCloneTool").tooltip("Coming Soon: Clone Tool (C)")),
+			ToolAvailability::Available(Box::<heal_tool::HealTool>::default()),
+			ToolAvailability::Available(Box::<clone_tool::CloneTool>::default()),
 			ToolAvailability::ComingSoon(ToolEntry::new(ToolType::Patch, "RasterPatchTool").tooltip("Coming Soon: Patch Tool")),
 			ToolAvailability::ComingSoon(ToolEntry::new(ToolType::Detail, "RasterDetailTool").tooltip("Coming Soon: Detail Tool (D)")),
 			ToolAvailability::ComingSoon(ToolEntry::new(ToolType::Relight, "RasterRelightTool").tooltip("Coming Soon: Relight Tool (O)")),
@@ -434,8 +434,8 @@ pub fn tool_message_to_tool_type(tool_message: &ToolMessage) -> ToolType {
 
 		// Raster tool group
 		ToolMessage::Brush(_) => ToolType::Brush,
-		// ToolMessage::Heal(_) => ToolType::Heal,
-		// ToolMessage::Clone(_) => ToolType::Clone,
+		ToolMessage::Heal(_) => ToolType::Heal,
+		ToolMessage::Clone(_) => ToolType::Clone,
 		// ToolMessage::Patch(_) => ToolType::Patch,
 		// ToolMessage::Detail(_) => ToolType::Detail,
 		// ToolMessage::Relight(_) => ToolType::Relight,
@@ -467,8 +467,8 @@ pub fn tool_type_to_activate_tool_message(tool_type: ToolType) -> ToolMessageDis
 
 		// Raster tool group
 		ToolType::Brush => ToolMessageDiscriminant::ActivateToolBrush,
-		// ToolType::Heal => ToolMessageDiscriminant::ActivateToolHeal,
-		// ToolType::Clone => ToolMessageDiscriminant::ActivateToolClone,
+		ToolType::Heal => ToolMessageDiscriminant::ActivateToolHeal,
+		ToolType::Clone => ToolMessageDiscriminant::ActivateToolClone,
 		// ToolType::Patch => ToolMessageDiscriminant::ActivateToolPatch,
 		// ToolType::Detail => ToolMessageDiscriminant::ActivateToolDetail,
 		// ToolType::Relight => ToolMessageDiscriminant::ActivateToolRelight,
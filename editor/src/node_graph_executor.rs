@@ -1,5 +1,5 @@
 use crate::consts::FILE_SAVE_SUFFIX;
-use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::frontend::utility_types::ExportBounds;
 use crate::messages::portfolio::document::node_graph::document_node_types::wrap_network_in_scope;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::prelude::*;
@@ -15,12 +15,12 @@ use graphene_core::memo::IORecord;
 use graphene_core::raster::ImageFrame;
 use graphene_core::renderer::{ClickTarget, GraphicElementRendered, ImageRenderMode, RenderParams, SvgRender};
 use graphene_core::renderer::{RenderSvgSegmentList, SvgSegment};
-use graphene_core::text::FontCache;
+use graphene_core::text::{FontCache, Typography};
 use graphene_core::transform::{Footprint, Transform};
 use graphene_core::vector::style::ViewMode;
 use graphene_core::vector::VectorData;
 use graphene_core::{Color, GraphicElement, SurfaceFrame};
-use graphene_std::wasm_application_io::{WasmApplicationIo, WasmEditorApi};
+use graphene_std::wasm_application_io::{RenderOutput, WasmApplicationIo, WasmEditorApi};
 use interpreted_executor::dynamic_executor::{DynamicExecutor, ResolvedDocumentNodeTypes};
 
 use glam::{DAffine2, DVec2, UVec2};
@@ -84,6 +84,7 @@ pub(crate) struct ExecutionRequest {
 	render_config: RenderConfig,
 }
 
+#[derive(Debug)]
 pub(crate) struct ExecutionResponse {
 	execution_id: u64,
 	result: Result<TaggedValue, String>,
@@ -95,6 +96,7 @@ pub(crate) struct ExecutionResponse {
 	transform: DAffine2,
 }
 
+#[derive(Debug)]
 enum NodeGraphUpdate {
 	ExecutionResponse(ExecutionResponse),
 	NodeGraphUpdateMessage(NodeGraphUpdateMessage),
@@ -121,7 +123,7 @@ thread_local! {
 impl NodeRuntime {
 	fn new(receiver: Receiver<NodeRuntimeMessage>, sender: Sender<NodeGraphUpdate>) -> Self {
 		Self {
-			executor: DynamicExecutor::default(),
+			executor: Self::new_executor(),
 			receiver,
 			sender: InternalNodeGraphUpdateSender(sender),
 
@@ -140,6 +142,22 @@ impl NodeRuntime {
 		}
 	}
 
+	/// On native builds, the outputs of expensive nodes are kept in the user's cache directory so reopening a document doesn't compute them again.
+	/// Browsers have no file system to keep them in.
+	fn new_executor() -> DynamicExecutor {
+		#[allow(unused_mut)]
+		let mut executor = DynamicExecutor::default();
+
+		#[cfg(all(not(target_arch = "wasm32"), not(test)))]
+		{
+			use graph_craft::render_cache::{RenderCache, DEFAULT_MAX_SIZE};
+			let render_cache = RenderCache::default_directory().and_then(|directory| RenderCache::new(directory, DEFAULT_MAX_SIZE).ok());
+			executor.set_render_cache(render_cache);
+		}
+
+		executor
+	}
+
 	pub async fn run(&mut self) {
 		let mut requests = self.receiver.try_iter().collect::<Vec<_>>();
 		// TODO: Currently we still render the document after we submit the node graph execution request.
@@ -328,7 +346,7 @@ impl NodeRuntime {
 				}
 			}
 
-			// If this is `VectorData`, `ImageFrame`, or `GraphicElement` data:
+			// If this is `VectorData`, `ImageFrame`, `Typography`, or `GraphicElement` data:
 			// Update the stored upstream transforms for this layer/node.
 			if let Some(transform) = {
 				fn try_downcast<T: Transform + 'static>(value: &dyn std::any::Any) -> Option<(Footprint, DAffine2)> {
@@ -338,6 +356,7 @@ impl NodeRuntime {
 				}
 				None.or_else(|| try_downcast::<VectorData>(introspected_data.as_ref()))
 					.or_else(|| try_downcast::<ImageFrame<Color>>(introspected_data.as_ref()))
+					.or_else(|| try_downcast::<Typography>(introspected_data.as_ref()))
 					.or_else(|| try_downcast::<GraphicElement>(introspected_data.as_ref()))
 			} {
 				self.upstream_transforms.insert(parent_network_node_id, transform);
@@ -381,6 +400,8 @@ pub async fn run_node_graph() {
 pub struct NodeGraphExecutor {
 	sender: Sender<NodeRuntimeMessage>,
 	receiver: Receiver<NodeGraphUpdate>,
+	/// Updates received while waiting for a blocking evaluation, which are processed the next time the evaluation is polled
+	pending_updates: Vec<NodeGraphUpdate>,
 	futures: HashMap<u64, ExecutionContext>,
 }
 
@@ -401,6 +422,7 @@ impl Default for NodeGraphExecutor {
 			futures: Default::default(),
 			sender: request_sender,
 			receiver: response_receiver,
+			pending_updates: Vec::new(),
 		}
 	}
 }
@@ -456,7 +478,7 @@ impl NodeGraphExecutor {
 	/// Evaluates a node graph, computing the entire graph
 	pub fn submit_node_graph_evaluation(&mut self, document: &mut DocumentMessageHandler, viewport_resolution: UVec2) -> Result<(), String> {
 		// Get the node graph layer
-		let network = document.network().clone();
+		let network = document.network_for_viewport();
 
 		let render_config = RenderConfig {
 			viewport: Footprint {
@@ -484,12 +506,89 @@ impl NodeGraphExecutor {
 	/// Evaluates a node graph for export
 	pub fn submit_document_export(&mut self, document: &mut DocumentMessageHandler, mut export_config: ExportConfig) -> Result<(), String> {
 		let network = document.network().clone();
+		let render_config = Self::export_render_config(document, &mut export_config)?;
 
+		// Execute the node graph
+		let execution_id = self.queue_execution(network, render_config);
+		let execution_context = ExecutionContext { export_config: Some(export_config) };
+		self.futures.insert(execution_id, execution_context);
+
+		Ok(())
+	}
+
+	/// Renders the document right away with the same output an export would produce, returning the SVG instead of downloading it.
+	/// This doesn't involve the frontend, so it can be used by tests and tooling running natively.
+	pub fn render_document_svg(&mut self, document: &mut DocumentMessageHandler, mut export_config: ExportConfig) -> Result<String, String> {
+		export_config.file_type = FileType::Svg;
+
+		// The export bounds come from the click targets, which are only known after the graph has been evaluated
+		let render_config = RenderConfig {
+			view_mode: document.view_mode,
+			for_export: true,
+			..Default::default()
+		};
+		let response = self.execute_blocking(document.network().clone(), render_config)?;
+		document.metadata.update_transforms(response.new_upstream_transforms);
+		document.metadata.update_click_targets(response.new_click_targets);
+		response.result?;
+
+		let render_config = Self::export_render_config(document, &mut export_config)?;
+		match self.execute_blocking(document.network().clone(), render_config)?.result? {
+			TaggedValue::RenderOutput(RenderOutput::Svg(svg)) => Ok(svg),
+			_ => Err("Incorrect render type for rendering the document (expected RenderOutput::Svg)".to_string()),
+		}
+	}
+
+	/// Renders the document right away like [`Self::render_document_svg`], then rasterizes the result into unpremultiplied RGBA pixels.
+	pub fn render_document_pixels(&mut self, document: &mut DocumentMessageHandler, export_config: ExportConfig) -> Result<image::RgbaImage, String> {
+		let svg = self.render_document_svg(document, export_config)?;
+
+		let mut tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).map_err(|error| format!("Failed to parse the rendered SVG: {error}"))?;
+		let mut font_database = usvg::fontdb::Database::new();
+		font_database.load_system_fonts();
+		tree.postprocess(usvg::PostProcessingSteps::default(), &font_database);
+
+		let size = tree.size.to_int_size();
+		let mut pixmap = resvg::tiny_skia::Pixmap::new(size.width(), size.height()).ok_or_else(|| "Invalid render resolution".to_string())?;
+		resvg::render(&tree, resvg::tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+		let pixels = pixmap
+			.pixels()
+			.iter()
+			.flat_map(|pixel| {
+				let pixel = pixel.demultiply();
+				[pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+			})
+			.collect();
+		image::RgbaImage::from_raw(size.width(), size.height(), pixels).ok_or_else(|| "Rendered pixels don't match the render resolution".to_string())
+	}
+
+	/// Evaluates the node graph on the current thread and waits for it to finish, rather than leaving the result to be picked up by [`Self::poll_node_graph_evaluation`].
+	fn execute_blocking(&mut self, network: NodeNetwork, render_config: RenderConfig) -> Result<ExecutionResponse, String> {
+		// Evaluate the requests which are already queued first, since the runtime only evaluates the last of several queued requests
+		futures::executor::block_on(run_node_graph());
+
+		let execution_id = self.queue_execution(network, render_config);
+		futures::executor::block_on(run_node_graph());
+
+		let mut response = None;
+		for update in self.receiver.try_iter() {
+			match update {
+				NodeGraphUpdate::ExecutionResponse(execution_response) if execution_response.execution_id == execution_id => response = Some(execution_response),
+				update => self.pending_updates.push(update),
+			}
+		}
+		response.ok_or_else(|| "The node graph was not evaluated".to_string())
+	}
+
+	/// Computes the region and resolution to be rendered for an export, recording the size of the region in the export config.
+	fn export_render_config(document: &DocumentMessageHandler, export_config: &mut ExportConfig) -> Result<RenderConfig, String> {
 		// Calculate the bounding box of the region to be exported
 		let bounds = match export_config.bounds {
 			ExportBounds::AllArtwork => document.metadata().document_bounds_document_space(!export_config.transparent_background),
 			ExportBounds::Selection => document.metadata().selected_bounds_document_space(!export_config.transparent_background, &document.selected_nodes),
 			ExportBounds::Artboard(id) => document.metadata().bounding_box_document(id),
+			ExportBounds::AllArtboards => return Err("Each artboard must be exported with its own bounds".to_string()),
 		}
 		.ok_or_else(|| "No bounding box".to_string())?;
 		let size = bounds[1] - bounds[0];
@@ -501,24 +600,32 @@ impl NodeGraphExecutor {
 				resolution: (size * export_config.scale_factor).as_uvec2(),
 				..Default::default()
 			},
-			export_format: graphene_core::application_io::ExportFormat::Svg,
+			export_format: match export_config.file_type {
+				FileType::Pdf => graphene_core::application_io::ExportFormat::Pdf,
+				_ => graphene_core::application_io::ExportFormat::Svg,
+			},
 			view_mode: document.view_mode,
 			hide_artboards: export_config.transparent_background,
 			for_export: true,
 		};
 		export_config.size = size;
 
-		// Execute the node graph
-		let execution_id = self.queue_execution(network, render_config);
-		let execution_context = ExecutionContext { export_config: Some(export_config) };
-		self.futures.insert(execution_id, execution_context);
-
-		Ok(())
+		Ok(render_config)
 	}
 
 	fn export(&self, node_graph_output: TaggedValue, export_config: ExportConfig, responses: &mut VecDeque<Message>) -> Result<(), String> {
-		let TaggedValue::RenderOutput(graphene_std::wasm_application_io::RenderOutput::Svg(svg)) = node_graph_output else {
-			return Err("Incorrect render type for exportign (expected RenderOutput::Svg)".to_string());
+		// PDF files are rendered by the node graph, while all other formats start out as an SVG
+		let document = match (node_graph_output, export_config.file_type) {
+			(TaggedValue::RenderOutput(RenderOutput::Error(description)), _) => {
+				responses.add(DialogMessage::DisplayDialogError {
+					title: "Failed to export".to_string(),
+					description,
+				});
+				return Ok(());
+			}
+			(TaggedValue::RenderOutput(RenderOutput::Pdf(pdf)), FileType::Pdf) => pdf,
+			(TaggedValue::RenderOutput(RenderOutput::Svg(svg)), file_type) if file_type != FileType::Pdf => svg,
+			_ => return Err("Incorrect render type for exporting (expected RenderOutput::Pdf for PDF files and RenderOutput::Svg otherwise)".to_string()),
 		};
 
 		let ExportConfig {
@@ -535,18 +642,18 @@ impl NodeGraphExecutor {
 			false => file_name + file_suffix,
 		};
 
-		if file_type == FileType::Svg {
-			responses.add(FrontendMessage::TriggerDownloadTextFile { document: svg, name });
+		if file_type == FileType::Svg || file_type == FileType::Pdf {
+			responses.add(FrontendMessage::TriggerDownloadTextFile { document, name });
 		} else {
 			let mime = file_type.to_mime().to_string();
 			let size = (size * scale_factor).into();
-			responses.add(FrontendMessage::TriggerDownloadImage { svg, name, mime, size });
+			responses.add(FrontendMessage::TriggerDownloadImage { svg: document, name, mime, size });
 		}
 		Ok(())
 	}
 
 	pub fn poll_node_graph_evaluation(&mut self, document: &mut DocumentMessageHandler, responses: &mut VecDeque<Message>) -> Result<(), String> {
-		let results = self.receiver.try_iter().collect::<Vec<_>>();
+		let results = std::mem::take(&mut self.pending_updates).into_iter().chain(self.receiver.try_iter()).collect::<Vec<_>>();
 		for response in results {
 			match response {
 				NodeGraphUpdate::ExecutionResponse(execution_response) => {
@@ -652,3 +759,108 @@ impl NodeGraphExecutor {
 		Ok(())
 	}
 }
+
+/// Encodes unpremultiplied RGBA pixels as a lossless WebP image for raster exports, since not every browser can encode WebP from a canvas.
+pub fn encode_webp(pixels: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
+	if pixels.len() as u64 != u64::from(width) * u64::from(height) * 4 {
+		return Err(format!("{} bytes don't make up a {width}x{height} RGBA image", pixels.len()));
+	}
+
+	let mut webp = Vec::new();
+	image::codecs::webp::WebPEncoder::new_lossless(&mut webp)
+		.encode(pixels, width, height, image::ColorType::Rgba8)
+		.map_err(|error| format!("The image couldn't be encoded as WebP: {error}"))?;
+	Ok(webp)
+}
+
+#[cfg(test)]
+mod test {
+	use super::ExportConfig;
+	use crate::application::Editor;
+	use crate::messages::frontend::utility_types::ExportBounds;
+	use crate::messages::prelude::*;
+	use crate::messages::tool::utility_types::ToolType;
+	use crate::test_utils::EditorTestUtils;
+
+	use graphene_core::raster::color::Color;
+
+	#[test]
+	#[cfg_attr(miri, ignore)]
+	fn render_document_without_frontend() {
+		let mut editor = Editor::create();
+		editor.new_document();
+		editor.select_primary_color(Color::RED);
+		editor.draw_rect(100., 200., 300., 400.);
+
+		let mut document = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap().clone();
+		let export_config = ExportConfig {
+			scale_factor: 0.5,
+			bounds: ExportBounds::AllArtwork,
+			transparent_background: true,
+			..Default::default()
+		};
+
+		let svg = editor.render_document_svg(&mut document, export_config.clone()).unwrap();
+		assert!(svg.contains("<svg"));
+
+		let pixels = editor.render_document_pixels(&mut document, export_config).unwrap();
+		assert!(pixels.width() > 0 && pixels.height() > 0);
+		let center = pixels.get_pixel(pixels.width() / 2, pixels.height() / 2);
+		assert_eq!(center.0, [255, 0, 0, 255]);
+	}
+
+	#[test]
+	#[cfg_attr(miri, ignore)]
+	fn keep_updates_received_while_rendering() {
+		let mut editor = Editor::create();
+		editor.new_document();
+		editor.draw_rect(100., 200., 300., 400.);
+
+		let mut document = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap().clone();
+		let executor = &mut editor.dispatcher.message_handlers.portfolio_message_handler.executor;
+		executor.submit_node_graph_evaluation(&mut document, glam::UVec2::new(800, 600)).unwrap();
+
+		let export_config = ExportConfig {
+			bounds: ExportBounds::AllArtwork,
+			..Default::default()
+		};
+		executor.render_document_svg(&mut document, export_config).unwrap();
+
+		// The evaluation submitted before rendering still reaches the viewport
+		let mut responses = VecDeque::new();
+		executor.poll_node_graph_evaluation(&mut document, &mut responses).unwrap();
+		assert!(responses.iter().any(|message| matches!(message, Message::Frontend(FrontendMessage::UpdateDocumentArtwork { .. }))));
+	}
+
+	#[test]
+	fn encode_webp_losslessly() {
+		let pixels = [255, 0, 0, 255, 0, 0, 255, 128];
+		let webp = super::encode_webp(&pixels, 2, 1).unwrap();
+		assert_eq!(&webp[0..4], b"RIFF");
+		assert_eq!(&webp[8..12], b"WEBP");
+
+		let decoded = image::load_from_memory_with_format(&webp, image::ImageFormat::WebP).unwrap().to_rgba8();
+		assert_eq!(decoded.dimensions(), (2, 1));
+		assert_eq!(decoded.into_raw(), pixels);
+
+		assert!(super::encode_webp(&pixels, 3, 1).is_err());
+	}
+
+	#[test]
+	#[cfg_attr(miri, ignore)]
+	fn render_text_layer_as_svg_text() {
+		let mut editor = Editor::create();
+		editor.new_document();
+		editor.drag_tool(ToolType::Text, 100., 200., 100., 200.);
+		editor.handle_message(TextToolMessage::TextChange { new_text: "Fish & chips".to_string() });
+
+		let mut document = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap().clone();
+		let export_config = ExportConfig {
+			bounds: ExportBounds::AllArtwork,
+			..Default::default()
+		};
+		let svg = editor.render_document_svg(&mut document, export_config).unwrap();
+		assert!(svg.contains("<text"));
+		assert!(svg.contains(">Fish &amp; chips</tspan>"));
+	}
+}
@@ -0,0 +1,345 @@
+//! A local HTTP server for scripting a headless editor, which other programs use to send it messages and inspect the active document.
+//!
+//! The editor it drives is the one the app's backend keeps in `EDITOR`, which is separate from the editor the app's window runs in its
+//! own WebAssembly module. Changes made through this server don't appear in the window, and edits made in the window aren't seen here.
+//!
+//! It only accepts connections from this machine, on port 3001, and turns away requests made by web pages (which carry an `Origin` header,
+//! or a `Host` header naming another site) so a page open in a browser can't drive the editor. Request bodies are JSON or RON, as named by
+//! their `Content-Type` header (`application/json` or `application/ron`), and responses are JSON unless the `Accept` header asks for RON.
+//! RON is the format the editor's messages round-trip through most reliably.
+//!
+//! - `POST /messages` takes a list of editor [`Message`]s, dispatches them in order, and returns the list of [`FrontendMessage`]s they
+//!   produced. The node graph is evaluated once the batch is done, so the state queries below reflect the changes.
+//!   For example, `[Portfolio(NewDocumentWithName(name: "Automated"))]` in RON, or `[{"Portfolio": {"NewDocumentWithName": {"name": "Automated"}}}]`
+//!   in JSON, opens a new document.
+//! - `GET /document/layers` returns the layer tree of the active document, with each layer's ID, name, bounds and visibility.
+//! - `GET /document/graph` returns the active document's node graph, as saved in the document format.
+//! - `GET /document/selection` returns the IDs of the selected nodes and layers of the active document.
+
+use crate::EDITOR;
+
+use graphite_editor::application::Editor;
+use graphite_editor::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use graphite_editor::messages::prelude::*;
+use graphite_editor::node_graph_executor::run_node_graph;
+
+use axum::extract::State;
+use axum::http::header::{ACCEPT, CONTENT_TYPE, HOST, ORIGIN};
+use axum::http::{HeaderMap, Request, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::VecDeque;
+use tauri::AppHandle;
+use tokio::sync::oneshot;
+
+const ADDRESS: &str = "127.0.0.1:3001";
+/// The `Host` headers of requests addressed to this server, rather than to a site whose domain has been pointed at this machine.
+const HOSTS: &[&str] = &[ADDRESS, "localhost:3001"];
+const USAGE: &str = "Graphite automation endpoints (JSON or RON, chosen by an application/json or application/ron Content-Type or Accept header):
+POST /messages            Dispatch a list of editor messages and return the resulting frontend messages
+GET  /document/layers     The layer tree of the active document
+GET  /document/graph      The node graph of the active document
+GET  /document/selection  The selected nodes and layers of the active document
+";
+
+type AutomationResult<T> = Result<T, (StatusCode, String)>;
+
+/// The text format of a request or response body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+	Json,
+	Ron,
+}
+
+impl Format {
+	/// The format of a media type such as `application/json; charset=utf-8`, if it's JSON or RON.
+	fn from_media_type(media_type: &str) -> Option<Self> {
+		let essence = media_type.split(';').next().unwrap_or_default().trim();
+		if essence.eq_ignore_ascii_case("application/json") {
+			Some(Self::Json)
+		} else if essence.eq_ignore_ascii_case("application/ron") {
+			Some(Self::Ron)
+		} else {
+			None
+		}
+	}
+
+	/// The format of a request body, which must be named by its `Content-Type` header.
+	fn from_content_type(headers: &HeaderMap) -> AutomationResult<Self> {
+		let content_type = headers.get(CONTENT_TYPE).and_then(|value| value.to_str().ok()).unwrap_or_default();
+		Self::from_media_type(content_type).ok_or_else(|| {
+			let error = format!("Unsupported Content-Type \"{content_type}\", which must be application/json or application/ron");
+			(StatusCode::UNSUPPORTED_MEDIA_TYPE, error)
+		})
+	}
+
+	/// The format of a response body, which is the first of JSON or RON named by the request's `Accept` header, or JSON if it accepts anything.
+	fn from_accept(headers: &HeaderMap) -> AutomationResult<Self> {
+		let Some(accept) = headers.get(ACCEPT) else { return Ok(Self::Json) };
+		let accept = accept.to_str().unwrap_or_default();
+
+		for media_type in accept.split(',') {
+			if let Some(format) = Self::from_media_type(media_type) {
+				return Ok(format);
+			}
+			let essence = media_type.split(';').next().unwrap_or_default().trim();
+			if essence == "*/*" || essence.eq_ignore_ascii_case("application/*") {
+				return Ok(Self::Json);
+			}
+		}
+
+		Err((
+			StatusCode::NOT_ACCEPTABLE,
+			format!("Unsupported Accept \"{accept}\", which must include application/json or application/ron"),
+		))
+	}
+
+	fn content_type(self) -> &'static str {
+		match self {
+			Self::Json => "application/json",
+			Self::Ron => "application/ron",
+		}
+	}
+
+	fn parse<T: DeserializeOwned>(self, text: &str) -> AutomationResult<T> {
+		let result = match self {
+			Self::Json => serde_json::from_str(text).map_err(|error| error.to_string()),
+			Self::Ron => ron::from_str(text).map_err(|error| error.to_string()),
+		};
+		result.map_err(|error| (StatusCode::BAD_REQUEST, format!("Invalid request body: {error}")))
+	}
+
+	fn serialize<T: Serialize>(self, value: &T) -> AutomationResult<String> {
+		let result = match self {
+			Self::Json => serde_json::to_string(value).map_err(|error| error.to_string()),
+			Self::Ron => ron::to_string(value).map_err(|error| error.to_string()),
+		};
+		result.map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to serialize the response: {error}")))
+	}
+
+	fn respond(self, body: String) -> Response {
+		([(CONTENT_TYPE, self.content_type())], body).into_response()
+	}
+}
+
+/// A layer of the document and the layers nested within it.
+#[derive(Debug, Serialize)]
+struct LayerState {
+	id: u64,
+	name: String,
+	folder: bool,
+	artboard: bool,
+	visible: bool,
+	locked: bool,
+	selected: bool,
+	/// The bounding box of the layer's content in document space, as its top left and bottom right corners.
+	bounds: Option<[[f64; 2]; 2]>,
+	children: Vec<LayerState>,
+}
+
+#[derive(Debug, Serialize)]
+struct SelectionState {
+	nodes: Vec<u64>,
+	layers: Vec<u64>,
+}
+
+/// Serves the automation endpoints until the app exits.
+pub async fn serve(app: AppHandle) {
+	let router = Router::new()
+		.route("/", get(|| async { USAGE }))
+		.route("/messages", post(messages))
+		.route("/document/layers", get(layers))
+		.route("/document/graph", get(graph))
+		.route("/document/selection", get(selection))
+		.layer(middleware::from_fn(reject_web_pages))
+		.with_state(app);
+
+	log::info!("Serving the automation endpoints at http://{ADDRESS}");
+	if let Err(error) = axum::Server::bind(&ADDRESS.parse().unwrap()).serve(router.into_make_service()).await {
+		log::error!("The automation server stopped: {error}");
+	}
+}
+
+/// Turns away requests made by web pages, since any site open in a browser on this machine can send requests to it.
+/// Browsers name the page's site in the `Origin` header of the requests it makes to other sites, and can't be made to leave it out of `POST`s.
+/// A page on a domain pointed at this machine makes requests to its own site, which don't all carry an `Origin`, but do name that domain as the `Host`.
+async fn reject_web_pages<B>(request: Request<B>, next: Next<B>) -> Response {
+	let headers = request.headers();
+
+	if let Some(origin) = headers.get(ORIGIN) {
+		let origin = origin.to_str().unwrap_or_default();
+		return (StatusCode::FORBIDDEN, format!("Requests from web pages ({origin}) aren't accepted")).into_response();
+	}
+	if !headers.get(HOST).and_then(|host| host.to_str().ok()).map_or(false, |host| HOSTS.contains(&host)) {
+		return (StatusCode::FORBIDDEN, format!("Requests must be addressed to http://{ADDRESS}")).into_response();
+	}
+
+	next.run(request).await
+}
+
+/// Runs the closure on the main thread, which owns the editor, and waits for its result.
+/// Only text is passed between the threads, since the editor's messages can't be sent across them.
+async fn with_editor(app: &AppHandle, f: impl FnOnce(&mut Editor) -> AutomationResult<String> + Send + 'static) -> AutomationResult<String> {
+	let (sender, receiver) = oneshot::channel();
+	app.run_on_main_thread(move || {
+		let result = EDITOR.with(|editor| editor.borrow_mut().as_mut().map(f));
+		let _ = sender.send(result);
+	})
+	.map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to reach the editor: {error}")))?;
+
+	receiver.await.ok().flatten().ok_or_else(|| (StatusCode::SERVICE_UNAVAILABLE, "The editor isn't running".to_string()))?
+}
+
+async fn messages(State(app): State<AppHandle>, headers: HeaderMap, body: String) -> AutomationResult<Response> {
+	let format = Format::from_content_type(&headers)?;
+
+	let responses = with_editor(&app, move |editor| {
+		let messages: Vec<Message> = format.parse(&body)?;
+
+		let mut responses: Vec<FrontendMessage> = messages.into_iter().flat_map(|message| editor.handle_message(message)).collect();
+
+		// Evaluate the graph so its results (such as the layers' bounds) are up to date for the next request
+		futures::executor::block_on(run_node_graph());
+		let mut evaluation_messages = VecDeque::new();
+		editor.poll_node_graph_evaluation(&mut evaluation_messages);
+		responses.extend(evaluation_messages.into_iter().flat_map(|message| editor.handle_message(message)));
+
+		format.serialize(&responses)
+	})
+	.await?;
+
+	Ok(format.respond(responses))
+}
+
+async fn layers(State(app): State<AppHandle>, headers: HeaderMap) -> AutomationResult<Response> {
+	let format = Format::from_accept(&headers)?;
+
+	let layers = with_editor(&app, move |editor| {
+		let document = active_document(editor)?;
+		let layers: Vec<_> = LayerNodeIdentifier::ROOT.children(document.metadata()).map(|layer| layer_state(layer, document)).collect();
+
+		format.serialize(&layers)
+	})
+	.await?;
+
+	Ok(format.respond(layers))
+}
+
+async fn graph(State(app): State<AppHandle>, headers: HeaderMap) -> AutomationResult<Response> {
+	let format = Format::from_accept(&headers)?;
+
+	let network = with_editor(&app, move |editor| format.serialize(active_document(editor)?.network())).await?;
+
+	Ok(format.respond(network))
+}
+
+async fn selection(State(app): State<AppHandle>, headers: HeaderMap) -> AutomationResult<Response> {
+	let format = Format::from_accept(&headers)?;
+
+	let selection = with_editor(&app, move |editor| {
+		let document = active_document(editor)?;
+		let selection = SelectionState {
+			nodes: document.selected_nodes.selected_nodes().map(|node| node.0).collect(),
+			layers: document.selected_nodes.selected_layers(document.metadata()).map(|layer| layer.to_node().0).collect(),
+		};
+
+		format.serialize(&selection)
+	})
+	.await?;
+
+	Ok(format.respond(selection))
+}
+
+/// Describes the layer and (recursively) its children.
+fn layer_state(layer: LayerNodeIdentifier, document: &DocumentMessageHandler) -> LayerState {
+	let metadata = document.metadata();
+	let id = layer.to_node();
+	LayerState {
+		id: id.0,
+		name: document.network().nodes.get(&id).map(|node| node.alias.clone()).unwrap_or_default(),
+		folder: metadata.is_folder(layer),
+		artboard: metadata.is_artboard(layer),
+		visible: metadata.node_is_visible(id),
+		locked: metadata.node_is_locked(id),
+		selected: document.selected_nodes.selected_layers_contains(layer, metadata),
+		bounds: metadata.bounding_box_document(layer).map(|[min, max]| [min.to_array(), max.to_array()]),
+		children: layer.children(metadata).map(|child| layer_state(child, document)).collect(),
+	}
+}
+
+fn active_document(editor: &Editor) -> AutomationResult<&DocumentMessageHandler> {
+	editor
+		.dispatcher
+		.message_handlers
+		.portfolio_message_handler
+		.active_document()
+		.ok_or_else(|| (StatusCode::NOT_FOUND, "No document is open".to_string()))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use axum::http::header::{HeaderName, HeaderValue};
+	use graphite_editor::messages::tool::utility_types::ToolType;
+	use graphite_editor::test_utils::EditorTestUtils;
+
+	fn headers(name: HeaderName, value: &'static str) -> HeaderMap {
+		HeaderMap::from_iter([(name, HeaderValue::from_static(value))])
+	}
+
+	#[test]
+	fn choose_format_from_headers() {
+		assert_eq!(Format::from_content_type(&headers(CONTENT_TYPE, "application/json")), Ok(Format::Json));
+		assert_eq!(Format::from_content_type(&headers(CONTENT_TYPE, "application/ron; charset=utf-8")), Ok(Format::Ron));
+		assert_eq!(Format::from_content_type(&headers(CONTENT_TYPE, "text/plain")).unwrap_err().0, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+		assert_eq!(Format::from_content_type(&HeaderMap::new()).unwrap_err().0, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+		assert_eq!(Format::from_accept(&HeaderMap::new()), Ok(Format::Json));
+		assert_eq!(Format::from_accept(&headers(ACCEPT, "*/*")), Ok(Format::Json));
+		assert_eq!(Format::from_accept(&headers(ACCEPT, "text/html, application/ron;q=0.9")), Ok(Format::Ron));
+		assert_eq!(Format::from_accept(&headers(ACCEPT, "text/html")).unwrap_err().0, StatusCode::NOT_ACCEPTABLE);
+	}
+
+	#[test]
+	fn round_trip_messages() {
+		let messages: Vec<Message> = vec![
+			PortfolioMessage::NewDocumentWithName { name: "Automated".to_string() }.into(),
+			ToolMessage::ActivateTool { tool_type: ToolType::Rectangle }.into(),
+			DocumentMessage::SelectAllLayers.into(),
+		];
+
+		for format in [Format::Json, Format::Ron] {
+			let serialized = format.serialize(&messages).unwrap();
+			let parsed: Vec<Message> = format.parse(&serialized).unwrap();
+			assert_eq!(format.serialize(&parsed).unwrap(), serialized);
+		}
+
+		let parsed: Vec<Message> = Format::Ron.parse(r#"[Portfolio(NewDocumentWithName(name: "Automated"))]"#).unwrap();
+		assert_eq!(Format::Json.serialize(&parsed).unwrap(), r#"[{"Portfolio":{"NewDocumentWithName":{"name":"Automated"}}}]"#);
+		assert_eq!(Format::Json.parse::<Vec<Message>>("[{").unwrap_err().0, StatusCode::BAD_REQUEST);
+	}
+
+	#[test]
+	fn describe_layers() {
+		let mut editor = Editor::create();
+		editor.new_document();
+		editor.draw_rect(100., 200., 300., 400.);
+
+		let document = active_document(&editor).unwrap();
+		let layers: Vec<_> = LayerNodeIdentifier::ROOT.children(document.metadata()).map(|layer| layer_state(layer, document)).collect();
+		let [rectangle] = layers.as_slice() else { panic!("Expected a single layer, got {layers:?}") };
+
+		let layer = LayerNodeIdentifier::ROOT.first_child(document.metadata()).unwrap();
+		assert_eq!(rectangle.id, layer.to_node().0);
+		assert!(!rectangle.folder && !rectangle.artboard && !rectangle.locked);
+		assert!(rectangle.visible && rectangle.selected);
+		assert!(rectangle.children.is_empty());
+
+		let serialized = Format::Json.serialize(&layers).unwrap();
+		assert!(serialized.starts_with(&format!(r#"[{{"id":{},"name":"#, rectangle.id)));
+	}
+}
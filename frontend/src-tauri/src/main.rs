@@ -3,12 +3,12 @@
 use graphite_editor::application::Editor;
 use graphite_editor::messages::prelude::*;
 
+mod automation;
+
 // use axum::body::StreamBody;
 // use axum::extract::Path;
 // use axum::http;
 // use axum::response::IntoResponse;
-use axum::routing::get;
-use axum::Router;
 use fern::colors::{Color, ColoredLevelConfig};
 // use http::{Response, StatusCode};
 use std::cell::RefCell;
@@ -57,18 +57,15 @@ async fn main() {
 	// *(IMAGES.lock().unwrap()) = Some(HashMap::new());
 	graphite_editor::application::set_uuid_seed(0);
 	EDITOR.with(|editor| editor.borrow_mut().replace(Editor::new()));
-	let app = Router::new().route("/", get(|| async { "Hello, World!" }))/*.route("/image/:id", get(respond_to))*/;
-
-	// run it with hyper on localhost:3000
-	tauri::async_runtime::spawn(async {
-		axum::Server::bind(&"0.0.0.0:3001".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
-	});
 
 	tauri::Builder::default()
 		.invoke_handler(tauri::generate_handler![set_random_seed, handle_message])
-		.setup(|_app| {
+		.setup(|app| {
 			use tauri::Manager;
-			_app.get_window("main").unwrap().open_devtools();
+			app.get_window("main").unwrap().open_devtools();
+
+			// Scripts and other tools drive the headless editor in `EDITOR` through this local server
+			tauri::async_runtime::spawn(automation::serve(app.handle()));
 			Ok(())
 		})
 		.run(tauri::generate_context!())
@@ -129,6 +129,11 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 
 	// Pointer events
 
+	// Mice and touch report a pressure of 0.5 while a button is held, so only pens are treated as pressure sensitive
+	function stylusPressure(e: PointerEvent): number {
+		return e.pointerType === "pen" ? e.pressure : 1;
+	}
+
 	// While any pointer button is already down, additional button down events are not reported, but they are sent as `pointermove` events and these are handled in the backend
 	function onPointerMove(e: PointerEvent) {
 		if (!e.buttons) viewportPointerInteractionOngoing = false;
@@ -149,7 +154,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		}
 
 		const modifiers = makeKeyboardModifiersBitfield(e);
-		editor.handle.onMouseMove(e.clientX, e.clientY, e.buttons, modifiers);
+		editor.handle.onMouseMove(e.clientX, e.clientY, e.buttons, modifiers, stylusPressure(e), e.tiltX, e.tiltY);
 	}
 
 	function onMouseDown(e: MouseEvent) {
@@ -176,7 +181,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 
 		if (viewportPointerInteractionOngoing) {
 			const modifiers = makeKeyboardModifiersBitfield(e);
-			editor.handle.onMouseDown(e.clientX, e.clientY, e.buttons, modifiers);
+			editor.handle.onMouseDown(e.clientX, e.clientY, e.buttons, modifiers, stylusPressure(e), e.tiltX, e.tiltY);
 		}
 	}
 
@@ -186,7 +191,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		if (textToolInteractiveInputElement) return;
 
 		const modifiers = makeKeyboardModifiersBitfield(e);
-		editor.handle.onMouseUp(e.clientX, e.clientY, e.buttons, modifiers);
+		editor.handle.onMouseUp(e.clientX, e.clientY, e.buttons, modifiers, stylusPressure(e), e.tiltX, e.tiltY);
 	}
 
 	function onPotentialDoubleClick(e: MouseEvent) {
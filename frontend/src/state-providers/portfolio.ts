@@ -4,7 +4,7 @@ import { writable } from "svelte/store";
 
 import { copyToClipboardFileURL } from "@graphite/io-managers/clipboard";
 import { downloadFileText, downloadFileBlob, upload } from "@graphite/utility-functions/files";
-import { extractPixelData, imageToPNG, rasterizeSVG } from "@graphite/utility-functions/rasterization";
+import { extractPixelData, imageToPNG, rasterizeSVG, rasterizeSVGCanvas } from "@graphite/utility-functions/rasterization";
 import { type Editor } from "@graphite/wasm-communication/editor";
 import {
 	type FrontendDocumentDetails,
@@ -15,6 +15,7 @@ import {
 	TriggerDownloadTextFile,
 	TriggerImport,
 	TriggerOpenDocument,
+	TriggerOpenMacro,
 	TriggerRevokeBlobUrl,
 	UpdateActiveDocument,
 	UpdateOpenDocumentsList,
@@ -63,6 +64,11 @@ export function createPortfolioState(editor: Editor) {
 		const data = await upload(extension, "text");
 		editor.handle.openDocumentFile(data.filename, data.content);
 	});
+	editor.subscriptions.subscribeJsMessage(TriggerOpenMacro, async () => {
+		const extension = editor.handle.macroSaveSuffix();
+		const data = await upload(extension, "text");
+		editor.handle.replayMacro(data.content);
+	});
 	editor.subscriptions.subscribeJsMessage(TriggerImport, async () => {
 		const data = await upload("image/*", "data");
 
@@ -91,6 +97,18 @@ export function createPortfolioState(editor: Editor) {
 	editor.subscriptions.subscribeJsMessage(TriggerCopyToClipboardBlobUrl, (triggerDownloadBlobUrl) => {
 		copyToClipboardFileURL(triggerDownloadBlobUrl.blobUrl);
 	});
+
+	// Some browsers can't encode WebP from a canvas and silently fall back to PNG, so the editor encodes the canvas pixels instead
+	async function rasterizeSVGToWebP(svg: string, width: number, height: number): Promise<Blob> {
+		const canvas = await rasterizeSVGCanvas(svg, width, height);
+		const context = canvas.getContext("2d", { willReadFrequently: true });
+		if (!context) throw new Error("Can't read the pixels of the rasterized SVG");
+
+		const imageData = context.getImageData(0, 0, canvas.width, canvas.height);
+		const webp = editor.handle.encodeWebp(new Uint8Array(imageData.data.buffer), imageData.width, imageData.height);
+		return new Blob([webp], { type: "image/webp" });
+	}
+
 	editor.subscriptions.subscribeJsMessage(TriggerDownloadImage, async (triggerDownloadImage) => {
 		const { svg, name, mime, size } = triggerDownloadImage;
 
@@ -99,7 +117,7 @@ export function createPortfolioState(editor: Editor) {
 
 		// Rasterize the SVG to an image file
 		try {
-			const blob = await rasterizeSVG(svg, size.x, size.y, mime, backgroundColor);
+			const blob = mime === "image/webp" ? await rasterizeSVGToWebP(svg, size.x, size.y) : await rasterizeSVG(svg, size.x, size.y, mime, backgroundColor);
 
 			// Have the browser download the file to the user's disk
 			downloadFileBlob(name, blob);
@@ -16,7 +16,7 @@ export function downloadFileBlob(filename: string, blob: Blob) {
 }
 
 export function downloadFileText(filename: string, text: string) {
-	const type = filename.endsWith(".svg") ? "image/svg+xml;charset=utf-8" : "text/plain;charset=utf-8";
+	const type = filename.endsWith(".svg") ? "image/svg+xml;charset=utf-8" : filename.endsWith(".pdf") ? "application/pdf" : "text/plain;charset=utf-8";
 
 	const blob = new Blob([text], { type });
 	downloadFileBlob(filename, blob);
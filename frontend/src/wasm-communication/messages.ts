@@ -531,6 +531,8 @@ export class TriggerFetchAndOpenDocument extends JsMessage {
 
 export class TriggerOpenDocument extends JsMessage {}
 
+export class TriggerOpenMacro extends JsMessage {}
+
 export class TriggerImport extends JsMessage {}
 
 export class TriggerPaste extends JsMessage {}
@@ -1234,6 +1236,8 @@ export class UpdateDocumentBarLayout extends WidgetDiffUpdate {}
 
 export class UpdateDocumentModeLayout extends WidgetDiffUpdate {}
 
+export class UpdateHistoryPanelLayout extends WidgetDiffUpdate {}
+
 export class UpdateLayersPanelOptionsLayout extends WidgetDiffUpdate {}
 
 // Extends JsMessage instead of WidgetDiffUpdate because the menu bar isn't diffed
@@ -1303,6 +1307,7 @@ export const messageMakers: Record<string, MessageMaker> = {
 	TriggerLoadAutoSaveDocuments,
 	TriggerLoadPreferences,
 	TriggerOpenDocument,
+	TriggerOpenMacro,
 	TriggerPaste,
 	TriggerRefreshBoundsOfViewports,
 	TriggerRevokeBlobUrl,
@@ -1323,6 +1328,7 @@ export const messageMakers: Record<string, MessageMaker> = {
 	UpdateDocumentRulers,
 	UpdateDocumentScrollbars,
 	UpdateEyedropperSamplingState,
+	UpdateHistoryPanelLayout,
 	UpdateInputHints,
 	UpdateLayersPanelOptionsLayout,
 	UpdateMenuBarLayout,
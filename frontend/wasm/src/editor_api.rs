@@ -9,9 +9,10 @@ use crate::{Error, EDITOR, EDITOR_HANDLE, EDITOR_HAS_CRASHED};
 
 use editor::application::generate_uuid;
 use editor::application::Editor;
-use editor::consts::FILE_SAVE_SUFFIX;
+use editor::consts::{FILE_SAVE_SUFFIX, MACRO_SAVE_SUFFIX};
 use editor::messages::input_mapper::utility_types::input_keyboard::ModifierKeys;
-use editor::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, ScrollDelta, ViewportBounds};
+use editor::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, ScrollDelta, StylusState, ViewportBounds};
+use editor::messages::portfolio::document::guides::utility_types::GuideDirection;
 use editor::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use editor::messages::portfolio::utility_types::Platform;
 use editor::messages::prelude::*;
@@ -219,6 +220,12 @@ impl EditorHandle {
 		FILE_SAVE_SUFFIX.into()
 	}
 
+	/// Get the constant `MACRO_SAVE_SUFFIX`
+	#[wasm_bindgen(js_name = macroSaveSuffix)]
+	pub fn macro_save_suffix(&self) -> String {
+		MACRO_SAVE_SUFFIX.into()
+	}
+
 	/// Update the value of a given UI widget, but don't commit it to the history (unless `commit_layout()` is called, which handles that)
 	#[wasm_bindgen(js_name = widgetValueUpdate)]
 	pub fn widget_value_update(&self, layout_target: JsValue, widget_id: u64, value: JsValue) -> Result<(), JsValue> {
@@ -296,6 +303,13 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Replay a macro saved by the macro recorder against the active document, once the user has given values for its parameters
+	#[wasm_bindgen(js_name = replayMacro)]
+	pub fn replay_macro(&self, serialized_macro: String) {
+		let message = DialogMessage::RequestMacroParametersDialog { serialized_macro };
+		self.dispatch(message);
+	}
+
 	#[wasm_bindgen(js_name = openAutoSavedDocument)]
 	pub fn open_auto_saved_document(&self, document_id: u64, document_name: String, document_is_saved: bool, document_serialized_content: String) {
 		let document_id = DocumentId(document_id);
@@ -344,8 +358,12 @@ impl EditorHandle {
 
 	/// Mouse movement within the screenspace bounds of the viewport
 	#[wasm_bindgen(js_name = onMouseMove)]
-	pub fn on_mouse_move(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
-		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+	pub fn on_mouse_move(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8, pressure: f64, tilt_x: f64, tilt_y: f64) {
+		let mut editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+		editor_mouse_state.stylus = StylusState {
+			pressure,
+			tilt: (tilt_x, tilt_y).into(),
+		};
 
 		let modifier_keys = ModifierKeys::from_bits(modifiers).expect("Invalid modifier keys");
 
@@ -367,8 +385,12 @@ impl EditorHandle {
 
 	/// A mouse button depressed within screenspace the bounds of the viewport
 	#[wasm_bindgen(js_name = onMouseDown)]
-	pub fn on_mouse_down(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
-		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+	pub fn on_mouse_down(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8, pressure: f64, tilt_x: f64, tilt_y: f64) {
+		let mut editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+		editor_mouse_state.stylus = StylusState {
+			pressure,
+			tilt: (tilt_x, tilt_y).into(),
+		};
 
 		let modifier_keys = ModifierKeys::from_bits(modifiers).expect("Invalid modifier keys");
 
@@ -378,8 +400,12 @@ impl EditorHandle {
 
 	/// A mouse button released
 	#[wasm_bindgen(js_name = onMouseUp)]
-	pub fn on_mouse_up(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
-		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+	pub fn on_mouse_up(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8, pressure: f64, tilt_x: f64, tilt_y: f64) {
+		let mut editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+		editor_mouse_state.stylus = StylusState {
+			pressure,
+			tilt: (tilt_x, tilt_y).into(),
+		};
 
 		let modifier_keys = ModifierKeys::from_bits(modifiers).expect("Invalid modifier keys");
 
@@ -551,6 +577,21 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Starts dragging a new guide out of the ruler along the top (if `horizontal`) or left of the viewport
+	#[wasm_bindgen(js_name = beginGuideDrag)]
+	pub fn begin_guide_drag(&self, horizontal: bool) {
+		let direction = if horizontal { GuideDirection::Horizontal } else { GuideDirection::Vertical };
+		let message = GuideMessage::BeginDragFromRuler { direction };
+		self.dispatch(message);
+	}
+
+	/// Drops the guide being dragged out of a ruler, since the backend doesn't see the release of a button which was pressed outside the viewport
+	#[wasm_bindgen(js_name = endGuideDrag)]
+	pub fn end_guide_drag(&self) {
+		let message = GuideMessage::EndDrag;
+		self.dispatch(message);
+	}
+
 	/// Notifies the backend that the user connected a node's primary output to one of another node's inputs
 	#[wasm_bindgen(js_name = connectNodesByLink)]
 	pub fn connect_nodes_by_link(&self, output_node: u64, output_node_connector_index: usize, input_node: u64, input_node_connector_index: usize) {
@@ -667,6 +708,12 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Encodes the RGBA pixels of a raster export as a WebP file
+	#[wasm_bindgen(js_name = encodeWebp)]
+	pub fn encode_webp(&self, image_data: Vec<u8>, width: u32, height: u32) -> Result<Vec<u8>, JsValue> {
+		editor::node_graph_executor::encode_webp(&image_data, width, height).map_err(|error| Error::new(&error).into())
+	}
+
 	/// Pastes an image
 	#[wasm_bindgen(js_name = pasteImage)]
 	pub fn paste_image(&self, image_data: Vec<u8>, width: u32, height: u32, mouse_x: Option<f64>, mouse_y: Option<f64>) {
@@ -13,4 +13,4 @@ mod utils;
 pub use bezier::*;
 pub use subpath::*;
 pub use symmetrical_basis::*;
-pub use utils::{Cap, Join, SubpathTValue, TValue, TValueType};
+pub use utils::{BooleanOperation, Cap, Join, SubpathTValue, TValue, TValueType};
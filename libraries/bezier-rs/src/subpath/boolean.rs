@@ -0,0 +1,331 @@
+use super::*;
+use crate::consts::{MAX_ABSOLUTE_DIFFERENCE, STRICT_MAX_ABSOLUTE_DIFFERENCE};
+use crate::utils::{do_rectangles_overlap, BooleanOperation};
+use crate::{BezierHandles, TValue};
+
+use glam::DVec2;
+
+/// Distance from a curve piece at which points are sampled to determine which side of the piece belongs to the result.
+const SIDE_SAMPLE_DISTANCE: f64 = 1e-4;
+/// Size of the bounding boxes at which curve subdivision stops when searching for intersections between curved segments.
+const INTERSECTION_ERROR: f64 = 1e-5;
+
+impl BooleanOperation {
+	/// Returns whether a point is part of the result given whether it is inside the lower and the upper shape.
+	pub fn includes(&self, inside_lower: bool, inside_upper: bool) -> bool {
+		match self {
+			BooleanOperation::Union => inside_lower || inside_upper,
+			BooleanOperation::SubtractFront => inside_lower && !inside_upper,
+			BooleanOperation::SubtractBack => inside_upper && !inside_lower,
+			BooleanOperation::Intersect => inside_lower && inside_upper,
+			BooleanOperation::Difference => inside_lower != inside_upper,
+		}
+	}
+}
+
+/// Converts curves whose handles sit on top of their anchors into lines, which are intersected exactly rather than by subdivision.
+fn straighten(bezier: Bezier) -> Bezier {
+	let on_anchor = |handle: DVec2| handle.abs_diff_eq(bezier.start, STRICT_MAX_ABSOLUTE_DIFFERENCE) || handle.abs_diff_eq(bezier.end, STRICT_MAX_ABSOLUTE_DIFFERENCE);
+	let straight = match bezier.handles {
+		BezierHandles::Linear => false,
+		BezierHandles::Quadratic { handle } => on_anchor(handle),
+		BezierHandles::Cubic { handle_start, handle_end } => on_anchor(handle_start) && on_anchor(handle_end),
+	};
+	if straight {
+		bezier.to_linear()
+	} else {
+		bezier
+	}
+}
+
+/// A segment of one of the input shapes along with the points where it has to be split.
+struct BooleanSegment {
+	bezier: Bezier,
+	bounding_box: [DVec2; 2],
+	splits: Vec<(f64, DVec2)>,
+}
+
+impl BooleanSegment {
+	fn add_split(&mut self, t: f64, point: DVec2) {
+		if t > STRICT_MAX_ABSOLUTE_DIFFERENCE && t < 1. - STRICT_MAX_ABSOLUTE_DIFFERENCE {
+			self.splits.push((t, point));
+		}
+	}
+
+	/// Cuts the segment at each of its split points, snapping the ends of each piece to the exact intersection positions so pieces from different segments line up.
+	fn pieces(mut self) -> impl Iterator<Item = Bezier> {
+		self.splits.sort_by(|a, b| a.0.total_cmp(&b.0));
+		self.splits.dedup_by(|a, b| a.0 - b.0 < STRICT_MAX_ABSOLUTE_DIFFERENCE);
+
+		let starts = std::iter::once((0., self.bezier.start())).chain(self.splits.clone());
+		let ends = self.splits.into_iter().chain(std::iter::once((1., self.bezier.end())));
+		let bezier = self.bezier;
+		starts.zip(ends).filter_map(move |((start_t, start_point), (end_t, end_point))| {
+			if start_point.abs_diff_eq(end_point, STRICT_MAX_ABSOLUTE_DIFFERENCE) {
+				return None;
+			}
+			let mut piece = bezier.trim(TValue::Parametric(start_t), TValue::Parametric(end_t));
+			piece.set_start(start_point);
+			piece.set_end(end_point);
+			Some(piece)
+		})
+	}
+}
+
+/// Functionality for combining the areas enclosed by multiple subpaths.
+impl<ManipulatorGroupId: crate::Identifier> Subpath<ManipulatorGroupId> {
+	/// Combines the area enclosed by the `lower` shape with the area enclosed by the `upper` shape according to the given [BooleanOperation], returning the closed subpaths which outline the result.
+	/// Each shape is made of any number of subpaths, all of which are treated as closed, and the inside of a shape is determined using the `nonzero` fill rule: <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/fill-rule>.
+	/// The resulting outer boundaries all wind in the same direction and holes wind in the opposite direction, so the result can be filled with either fill rule.
+	pub fn boolean_operation(lower: &[Subpath<ManipulatorGroupId>], upper: &[Subpath<ManipulatorGroupId>], operation: BooleanOperation) -> Vec<Subpath<ManipulatorGroupId>> {
+		let lower = Self::closed_subpaths(lower);
+		let upper = Self::closed_subpaths(upper);
+
+		let is_inside = |shape: &[Subpath<ManipulatorGroupId>], point: DVec2| shape.iter().map(|subpath| subpath.winding_order(point)).sum::<i32>() != 0;
+		Self::trace_area(lower.iter().chain(upper.iter()), |point| operation.includes(is_inside(&lower, point), is_inside(&upper, point)))
+	}
+
+	/// Returns the closed subpaths which outline the area of the shape with a positive winding number, which is the area to the left of subpaths winding anticlockwise (with +y as up).
+	/// This cleans up the self-intersections and overlaps of shapes whose subpaths all keep their area on the same side, such as the results of [Subpath::boolean_operation]
+	/// after they have been offset, removing the loops which were turned inside out. The result winds like the results of [Subpath::boolean_operation].
+	pub fn positive_area(shape: &[Subpath<ManipulatorGroupId>]) -> Vec<Subpath<ManipulatorGroupId>> {
+		let shape = Self::closed_subpaths(shape);
+		Self::trace_area(shape.iter(), |point| shape.iter().map(|subpath| subpath.winding_order(point)).sum::<i32>() > 0)
+	}
+
+	fn closed_subpaths(shape: &[Subpath<ManipulatorGroupId>]) -> Vec<Subpath<ManipulatorGroupId>> {
+		shape
+			.iter()
+			.filter(|subpath| subpath.len() > 1)
+			.map(|subpath| {
+				let mut subpath = subpath.clone();
+				subpath.set_closed(true);
+				subpath
+			})
+			.collect()
+	}
+
+	/// Outlines the area made up of the points for which `includes` is true, where that can only change across the segments of the subpaths.
+	fn trace_area<'a>(subpaths: impl Iterator<Item = &'a Subpath<ManipulatorGroupId>>, includes: impl Fn(DVec2) -> bool) -> Vec<Subpath<ManipulatorGroupId>>
+	where
+		ManipulatorGroupId: 'a,
+	{
+		// Split every segment wherever it crosses any other segment, including segments of the same shape
+		let mut segments: Vec<BooleanSegment> = subpaths
+			.flat_map(|subpath| subpath.iter())
+			.filter(|bezier| !bezier.is_point())
+			.map(straighten)
+			.map(|bezier| BooleanSegment {
+				bezier,
+				bounding_box: bezier.bounding_box(),
+				splits: Vec::new(),
+			})
+			.collect();
+
+		for index in 0..segments.len() {
+			let bezier = segments[index].bezier;
+			for other_index in index..segments.len() {
+				let intersections = if other_index == index {
+					if !bezier.handles.is_cubic() {
+						continue;
+					}
+					bezier.self_intersections(Some(INTERSECTION_ERROR), None)
+				} else {
+					if !do_rectangles_overlap(segments[index].bounding_box, segments[other_index].bounding_box) {
+						continue;
+					}
+					bezier.all_intersections(&segments[other_index].bezier, Some(INTERSECTION_ERROR), None)
+				};
+
+				for [t, other_t] in intersections {
+					let point = bezier.evaluate(TValue::Parametric(t));
+					segments[index].add_split(t, point);
+					segments[other_index].add_split(other_t, point);
+				}
+			}
+		}
+
+		// Where subpaths share an edge, their pieces along it coincide. Only one of them is kept, since the points sampled on either side of it already account for every subpath.
+		let mut unique_pieces: Vec<Bezier> = Vec::new();
+		for piece in segments.into_iter().flat_map(BooleanSegment::pieces) {
+			let middle = piece.evaluate(TValue::Parametric(0.5));
+			let coincides = |other: &Bezier| {
+				let same_ends = (other.start().abs_diff_eq(piece.start(), MAX_ABSOLUTE_DIFFERENCE) && other.end().abs_diff_eq(piece.end(), MAX_ABSOLUTE_DIFFERENCE))
+					|| (other.start().abs_diff_eq(piece.end(), MAX_ABSOLUTE_DIFFERENCE) && other.end().abs_diff_eq(piece.start(), MAX_ABSOLUTE_DIFFERENCE));
+				same_ends && other.evaluate(TValue::Parametric(0.5)).abs_diff_eq(middle, MAX_ABSOLUTE_DIFFERENCE)
+			};
+			if !unique_pieces.iter().any(coincides) {
+				unique_pieces.push(piece);
+			}
+		}
+
+		// Keep the pieces which separate the result from the rest of the plane, oriented so the result is always on the same side
+		let mut pieces: Vec<Bezier> = unique_pieces
+			.into_iter()
+			.filter_map(|piece| {
+				let middle = piece.evaluate(TValue::Parametric(0.5));
+				let normal = piece.normal(TValue::Parametric(0.5));
+				if normal == DVec2::ZERO {
+					return None;
+				}
+
+				let left_included = includes(middle + normal * SIDE_SAMPLE_DISTANCE);
+				let right_included = includes(middle - normal * SIDE_SAMPLE_DISTANCE);
+				match (left_included, right_included) {
+					(true, false) => Some(piece),
+					(false, true) => Some(piece.reverse()),
+					_ => None,
+				}
+			})
+			.collect();
+
+		// Chain the pieces end to start into closed loops
+		let mut result = Vec::new();
+		while let Some(first) = pieces.pop() {
+			let mut chain = vec![first];
+			loop {
+				let end = chain.last().unwrap().end();
+				if end.abs_diff_eq(first.start(), MAX_ABSOLUTE_DIFFERENCE) {
+					break;
+				}
+				let next = pieces
+					.iter()
+					.enumerate()
+					.map(|(index, piece)| (index, piece.start().distance_squared(end)))
+					.filter(|&(_, distance_squared)| distance_squared < MAX_ABSOLUTE_DIFFERENCE * MAX_ABSOLUTE_DIFFERENCE)
+					.min_by(|a, b| a.1.total_cmp(&b.1));
+				let Some((index, _)) = next else { break };
+				chain.push(pieces.swap_remove(index));
+			}
+
+			// A closed subpath needs at least two manipulator groups
+			if chain.len() == 1 {
+				let [start, end] = chain[0].split(TValue::Parametric(0.5));
+				chain = vec![start, end];
+			}
+			result.push(Subpath::from_beziers(&chain, true));
+		}
+
+		result
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::utils::BooleanOperation;
+	use crate::EmptyId;
+
+	use glam::DVec2;
+
+	/// Adds up the signed areas of a flattened version of each subpath, so holes winding opposite to their outer boundary are subtracted.
+	fn area(shape: &[Subpath<EmptyId>]) -> f64 {
+		let signed_area = |subpath: &Subpath<EmptyId>| {
+			let points: Vec<DVec2> = subpath.iter_closed().flat_map(|bezier| bezier.compute_lookup_table(Some(64), None).collect::<Vec<_>>()).collect();
+			points.iter().zip(points.iter().cycle().skip(1)).map(|(a, b)| a.perp_dot(*b)).sum::<f64>() / 2.
+		};
+		shape.iter().map(signed_area).sum::<f64>().abs()
+	}
+
+	fn contains(shape: &[Subpath<EmptyId>], point: DVec2) -> bool {
+		shape.iter().map(|subpath| subpath.winding_order(point)).sum::<i32>() != 0
+	}
+
+	#[test]
+	fn overlapping_squares() {
+		let lower = [Subpath::new_rect(DVec2::new(0., 0.), DVec2::new(10., 10.))];
+		let upper = [Subpath::new_rect(DVec2::new(5., 5.), DVec2::new(15., 15.))];
+		let (lower_only, both, upper_only) = (DVec2::new(2., 2.), DVec2::new(7., 7.), DVec2::new(12., 12.));
+
+		let union = Subpath::boolean_operation(&lower, &upper, BooleanOperation::Union);
+		assert_eq!(union.len(), 1);
+		assert!((area(&union) - 175.).abs() < 1e-6);
+		assert!(contains(&union, lower_only) && contains(&union, both) && contains(&union, upper_only));
+
+		let subtract_front = Subpath::boolean_operation(&lower, &upper, BooleanOperation::SubtractFront);
+		assert_eq!(subtract_front.len(), 1);
+		assert!((area(&subtract_front) - 75.).abs() < 1e-6);
+		assert!(contains(&subtract_front, lower_only) && !contains(&subtract_front, both) && !contains(&subtract_front, upper_only));
+
+		let subtract_back = Subpath::boolean_operation(&lower, &upper, BooleanOperation::SubtractBack);
+		assert_eq!(subtract_back.len(), 1);
+		assert!((area(&subtract_back) - 75.).abs() < 1e-6);
+		assert!(!contains(&subtract_back, lower_only) && !contains(&subtract_back, both) && contains(&subtract_back, upper_only));
+
+		let intersect = Subpath::boolean_operation(&lower, &upper, BooleanOperation::Intersect);
+		assert_eq!(intersect.len(), 1);
+		assert!((area(&intersect) - 25.).abs() < 1e-6);
+		assert!(!contains(&intersect, lower_only) && contains(&intersect, both) && !contains(&intersect, upper_only));
+
+		let difference = Subpath::boolean_operation(&lower, &upper, BooleanOperation::Difference);
+		assert!((area(&difference) - 150.).abs() < 1e-6);
+		assert!(contains(&difference, lower_only) && !contains(&difference, both) && contains(&difference, upper_only));
+	}
+
+	#[test]
+	fn disjoint_and_nested_shapes() {
+		let outer = [Subpath::new_rect(DVec2::new(0., 0.), DVec2::new(10., 10.))];
+		let inner = [Subpath::new_rect(DVec2::new(2., 2.), DVec2::new(8., 8.))];
+		let far = [Subpath::new_rect(DVec2::new(20., 20.), DVec2::new(30., 30.))];
+
+		assert_eq!(Subpath::boolean_operation(&outer, &far, BooleanOperation::Union).len(), 2);
+		assert!(Subpath::boolean_operation(&outer, &far, BooleanOperation::Intersect).is_empty());
+
+		// Cutting the inner square out of the outer one leaves a hole which winds opposite to the outer boundary
+		let hole = Subpath::boolean_operation(&outer, &inner, BooleanOperation::SubtractFront);
+		assert_eq!(hole.len(), 2);
+		assert!((area(&hole) - 64.).abs() < 1e-6);
+		assert!(contains(&hole, DVec2::new(1., 1.)) && !contains(&hole, DVec2::new(5., 5.)));
+	}
+
+	#[test]
+	fn coincident_edges() {
+		// The squares share part of an edge, and the union and intersection of a square with itself share all of them
+		let lower = [Subpath::new_rect(DVec2::new(0., 0.), DVec2::new(10., 10.))];
+		let upper = [Subpath::new_rect(DVec2::new(10., 5.), DVec2::new(20., 20.))];
+
+		let union = Subpath::boolean_operation(&lower, &upper, BooleanOperation::Union);
+		assert_eq!(union.len(), 1);
+		assert!((area(&union) - 250.).abs() < 1e-6);
+		assert!(Subpath::boolean_operation(&lower, &upper, BooleanOperation::Intersect).is_empty());
+
+		for operation in [BooleanOperation::Union, BooleanOperation::Intersect] {
+			let same = Subpath::boolean_operation(&lower, &lower, operation);
+			assert_eq!(same.len(), 1);
+			assert!((area(&same) - 100.).abs() < 1e-6);
+		}
+		assert!(Subpath::boolean_operation(&lower, &lower, BooleanOperation::SubtractFront).is_empty());
+	}
+
+	#[test]
+	fn overlapping_circles() {
+		let lower = [Subpath::new_ellipse(DVec2::new(0., 0.), DVec2::new(10., 10.))];
+		let upper = [Subpath::new_ellipse(DVec2::new(5., 0.), DVec2::new(15., 10.))];
+
+		let union = Subpath::boolean_operation(&lower, &upper, BooleanOperation::Union);
+		assert_eq!(union.len(), 1);
+		let intersect = Subpath::boolean_operation(&lower, &upper, BooleanOperation::Intersect);
+		assert_eq!(intersect.len(), 1);
+		assert!(contains(&intersect, DVec2::new(7.5, 5.)) && !contains(&intersect, DVec2::new(2., 5.)));
+
+		// The union and the intersection together cover as much area as both circles
+		assert!((area(&union) + area(&intersect) - area(&lower) - area(&upper)).abs() < 1e-2);
+	}
+
+	#[test]
+	fn positive_area_of_overlapping_and_inverted_loops() {
+		// Both squares wind anticlockwise, while the far one winds clockwise so its area is negative
+		let square = Subpath::new_rect(DVec2::new(0., 0.), DVec2::new(10., 10.));
+		let overlapping = Subpath::new_rect(DVec2::new(5., 0.), DVec2::new(15., 10.));
+		let inverted = Subpath::new_rect(DVec2::new(20., 0.), DVec2::new(30., 10.)).reverse();
+
+		let result = Subpath::positive_area(&[square.clone(), overlapping, inverted]);
+		assert_eq!(result.len(), 1);
+		assert!((area(&result) - 150.).abs() < 1e-6);
+		assert!(contains(&result, DVec2::new(12., 5.)) && !contains(&result, DVec2::new(25., 5.)));
+
+		// An anticlockwise loop inside a clockwise one cancels out
+		let enclosing = Subpath::new_rect(DVec2::new(-5., -5.), DVec2::new(15., 15.)).reverse();
+		assert!(Subpath::positive_area(&[enclosing, square]).is_empty());
+	}
+}
@@ -1,3 +1,4 @@
+mod boolean;
 mod core;
 mod lookup;
 mod manipulators;
@@ -60,6 +60,27 @@ pub enum Cap {
 	Square,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Represents the way two shapes are combined by [crate::Subpath::boolean_operation], where the lower shape is the one behind and the upper shape is the one in front.
+pub enum BooleanOperation {
+	/// The area covered by either shape.
+	Union,
+	/// The area of the lower shape which is not covered by the upper shape.
+	SubtractFront,
+	/// The area of the upper shape which is not covered by the lower shape.
+	SubtractBack,
+	/// The area covered by both shapes.
+	Intersect,
+	/// The area covered by exactly one of the two shapes.
+	Difference,
+}
+
+#[cfg(feature = "dyn-any")]
+unsafe impl dyn_any::StaticType for BooleanOperation {
+	type Static = BooleanOperation;
+}
+
 /// Helper to perform the computation of a and c, where b is the provided point on the curve.
 /// Given the correct power of `t` and `(1-t)`, the computation is the same for quadratic and cubic cases.
 /// Relevant derivation and the definitions of a, b, and c can be found in [the projection identity section](https://pomax.github.io/bezierinfo/#abc) of Pomax's bezier curve primer.
@@ -145,6 +145,8 @@ pub enum ExportFormat {
 		transparent: bool,
 	},
 	Jpeg,
+	Webp,
+	Pdf,
 	Canvas,
 }
 
@@ -1,7 +1,9 @@
 use crate::raster::{BlendMode, ImageFrame};
+use crate::text::Typography;
 use crate::transform::Footprint;
 use crate::vector::VectorData;
 use crate::{Color, Node};
+use effects::LayerEffect;
 
 use bezier_rs::BezierHandles;
 use dyn_any::{DynAny, StaticType};
@@ -11,6 +13,7 @@ use core::future::Future;
 use core::ops::{Deref, DerefMut};
 use glam::{DAffine2, DVec2, IVec2, UVec2};
 
+pub mod effects;
 pub mod renderer;
 
 #[derive(Copy, Clone, Debug, PartialEq, DynAny, specta::Type)]
@@ -46,6 +49,9 @@ pub struct GraphicGroup {
 	elements: Vec<GraphicElement>,
 	pub transform: DAffine2,
 	pub alpha_blending: AlphaBlending,
+	/// Shadows, glows and strokes drawn around the elements, in the order they were added.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub effects: Vec<LayerEffect>,
 }
 
 impl core::hash::Hash for GraphicGroup {
@@ -53,11 +59,12 @@ impl core::hash::Hash for GraphicGroup {
 		self.transform.to_cols_array().iter().for_each(|element| element.to_bits().hash(state));
 		self.elements.hash(state);
 		self.alpha_blending.hash(state);
+		self.effects.hash(state);
 	}
 }
 
 /// The possible forms of graphical content held in a Vec by the `elements` field of [`GraphicElement`].
-/// Can be another recursively nested [`GraphicGroup`], [`VectorData`], an [`ImageFrame`], text (not yet implemented), or an [`Artboard`].
+/// Can be another recursively nested [`GraphicGroup`], [`VectorData`], an [`ImageFrame`], [`Typography`], or an [`Artboard`].
 #[derive(Clone, Debug, Hash, PartialEq, DynAny)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GraphicElement {
@@ -67,10 +74,8 @@ pub enum GraphicElement {
 	VectorData(Box<VectorData>),
 	/// A bitmap image with a finite position and extent, equivalent to the SVG <image> tag: https://developer.mozilla.org/en-US/docs/Web/SVG/Element/image
 	ImageFrame(ImageFrame<Color>),
-	// TODO: Switch from `String` to a proper formatted typography type
-	/// Text, equivalent to the SVG <text> tag: https://developer.mozilla.org/en-US/docs/Web/SVG/Element/text
-	/// (Not yet implemented.)
-	Text(String),
+	/// Formatted text, equivalent to the SVG <text> tag: https://developer.mozilla.org/en-US/docs/Web/SVG/Element/text
+	Text(Typography),
 	/// The bounds for displaying a page of contained content
 	Artboard(Artboard),
 }
@@ -233,6 +238,11 @@ impl From<VectorData> for GraphicElement {
 		GraphicElement::VectorData(Box::new(vector_data))
 	}
 }
+impl From<Typography> for GraphicElement {
+	fn from(typography: Typography) -> Self {
+		GraphicElement::Text(typography)
+	}
+}
 impl From<GraphicGroup> for GraphicElement {
 	fn from(graphic_group: GraphicGroup) -> Self {
 		GraphicElement::GraphicGroup(graphic_group)
@@ -263,6 +273,7 @@ trait ToGraphicElement: Into<GraphicElement> {}
 
 impl ToGraphicElement for VectorData {}
 impl ToGraphicElement for ImageFrame<Color> {}
+impl ToGraphicElement for Typography {}
 impl ToGraphicElement for Artboard {}
 
 impl<T> From<T> for GraphicGroup
@@ -274,6 +285,7 @@ where
 			elements: (vec![value.into()]),
 			transform: DAffine2::IDENTITY,
 			alpha_blending: AlphaBlending::default(),
+			effects: Vec::new(),
 		}
 	}
 }
@@ -283,6 +295,7 @@ impl GraphicGroup {
 		elements: Vec::new(),
 		transform: DAffine2::IDENTITY,
 		alpha_blending: AlphaBlending::new(),
+		effects: Vec::new(),
 	};
 
 	pub fn to_usvg_tree(&self, resolution: UVec2, viewbox: [DVec2; 2]) -> usvg::Tree {
@@ -360,28 +373,7 @@ impl GraphicElement {
 					bounding_box: None,
 				}))
 			}
-			GraphicElement::Text(text) => usvg::Node::Text(Box::new(usvg::Text {
-				id: String::new(),
-				abs_transform: usvg::Transform::identity(),
-				rendering_mode: usvg::TextRendering::OptimizeSpeed,
-				writing_mode: usvg::WritingMode::LeftToRight,
-				chunks: vec![usvg::TextChunk {
-					text: text.clone(),
-					x: None,
-					y: None,
-					anchor: usvg::TextAnchor::Start,
-					spans: vec![],
-					text_flow: usvg::TextFlow::Linear,
-				}],
-				dx: Vec::new(),
-				dy: Vec::new(),
-				rotate: Vec::new(),
-				bounding_box: None,
-				abs_bounding_box: None,
-				stroke_bounding_box: None,
-				abs_stroke_bounding_box: None,
-				flattened: None,
-			})),
+			GraphicElement::Text(typography) => renderer::GraphicElementRendered::to_usvg_node(typography),
 			GraphicElement::GraphicGroup(group) => {
 				let mut group_element = usvg::Group::default();
 
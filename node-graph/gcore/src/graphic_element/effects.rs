@@ -0,0 +1,241 @@
+use crate::raster::BlendMode;
+use crate::uuid::generate_uuid;
+use crate::{Color, GraphicGroup, Node};
+
+use core::fmt::Write;
+use glam::DVec2;
+
+/// A shadow, glow or stroke drawn from the alpha of a [`crate::GraphicGroup`], so it follows the shape of any content (vector, raster or nested groups).
+///
+/// Effects are rendered as SVG filters. Distances are in the coordinate space of the group's contents.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayerEffect {
+	/// The alpha of the content, offset and blurred, beneath the content.
+	DropShadow { color: Color, offset: DVec2, blur: f64, blend_mode: BlendMode },
+	/// The inverse of the offset and blurred alpha of the content, within the content.
+	InnerShadow { color: Color, offset: DVec2, blur: f64, blend_mode: BlendMode },
+	/// The blurred alpha of the content spreading out to about the size, beneath the content.
+	OuterGlow { color: Color, size: f64, blend_mode: BlendMode },
+	/// A solid outline of the width around the outside edge of the content.
+	Stroke { color: Color, width: f64 },
+}
+
+impl core::hash::Hash for LayerEffect {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		core::mem::discriminant(self).hash(state);
+		match self {
+			Self::DropShadow { color, offset, blur, blend_mode } | Self::InnerShadow { color, offset, blur, blend_mode } => {
+				color.hash(state);
+				offset.to_array().iter().for_each(|component| component.to_bits().hash(state));
+				blur.to_bits().hash(state);
+				blend_mode.hash(state);
+			}
+			Self::OuterGlow { color, size, blend_mode } => {
+				color.hash(state);
+				size.to_bits().hash(state);
+				blend_mode.hash(state);
+			}
+			Self::Stroke { color, width } => {
+				color.hash(state);
+				width.to_bits().hash(state);
+			}
+		}
+	}
+}
+
+impl LayerEffect {
+	/// Whether the effect is drawn over the content, as opposed to beneath it.
+	pub fn in_front(&self) -> bool {
+		matches!(self, Self::InnerShadow { .. } | Self::Stroke { .. })
+	}
+
+	pub fn blend_mode(&self) -> BlendMode {
+		match *self {
+			Self::DropShadow { blend_mode, .. } | Self::InnerShadow { blend_mode, .. } | Self::OuterGlow { blend_mode, .. } => blend_mode,
+			Self::Stroke { .. } => BlendMode::Normal,
+		}
+	}
+
+	/// How far beyond the bounds of the content the effect can reach.
+	pub fn extent(&self) -> f64 {
+		match *self {
+			// A Gaussian blur fades out within three standard deviations
+			Self::DropShadow { offset, blur, .. } => offset.abs().max_element() + blur.max(0.) * 3.,
+			Self::InnerShadow { .. } => 0.,
+			Self::OuterGlow { size, .. } => size.max(0.),
+			Self::Stroke { width, .. } => width.max(0.),
+		}
+	}
+
+	/// Writes the filter primitives which draw the effect by itself, ending with a primitive whose result is named `result`.
+	fn write_primitives(&self, svg: &mut String, result: &str) {
+		let (color, shape) = match *self {
+			Self::DropShadow { color, offset, blur, .. } => {
+				let _ = write!(
+					svg,
+					r#"<feGaussianBlur in="SourceAlpha" stdDeviation="{}"/><feOffset dx="{}" dy="{}" result="{result}-shape"/>"#,
+					blur.max(0.),
+					offset.x,
+					offset.y
+				);
+				(color, format!("{result}-shape"))
+			}
+			Self::InnerShadow { color, offset, blur, .. } => {
+				// The parts of the content left uncovered when its own (blurred) alpha is shifted by the offset
+				let _ = write!(
+					svg,
+					r#"<feOffset in="SourceAlpha" dx="{}" dy="{}"/><feGaussianBlur stdDeviation="{}" result="{result}-cover"/><feComposite in="SourceAlpha" in2="{result}-cover" operator="out" result="{result}-shape"/>"#,
+					offset.x,
+					offset.y,
+					blur.max(0.)
+				);
+				(color, format!("{result}-shape"))
+			}
+			Self::OuterGlow { color, size, .. } => {
+				let _ = write!(svg, r#"<feGaussianBlur in="SourceAlpha" stdDeviation="{}" result="{result}-shape"/>"#, size.max(0.) / 3.);
+				(color, format!("{result}-shape"))
+			}
+			Self::Stroke { color, width } => {
+				let _ = write!(
+					svg,
+					r#"<feMorphology in="SourceAlpha" operator="dilate" radius="{}"/><feComposite in2="SourceAlpha" operator="out" result="{result}-shape"/>"#,
+					width.max(0.)
+				);
+				(color, format!("{result}-shape"))
+			}
+		};
+
+		let _ = write!(
+			svg,
+			r##"<feFlood flood-color="#{}" flood-opacity="{}"/><feComposite in2="{shape}" operator="in" result="{result}"/>"##,
+			color.rgb_hex(),
+			color.a()
+		);
+	}
+}
+
+fn with_effect(graphic_group: impl Into<GraphicGroup>, effect: LayerEffect) -> GraphicGroup {
+	let mut graphic_group = graphic_group.into();
+	graphic_group.effects.push(effect);
+	graphic_group
+}
+
+pub struct DropShadowNode<Color, Offset, Blur, BlendMode> {
+	color: Color,
+	offset: Offset,
+	blur: Blur,
+	blend_mode: BlendMode,
+}
+
+#[node_macro::node_fn(DropShadowNode)]
+fn drop_shadow<Data: Into<GraphicGroup>>(data: Data, color: Color, offset: DVec2, blur: f64, blend_mode: BlendMode) -> GraphicGroup {
+	with_effect(data, LayerEffect::DropShadow { color, offset, blur, blend_mode })
+}
+
+pub struct InnerShadowNode<Color, Offset, Blur, BlendMode> {
+	color: Color,
+	offset: Offset,
+	blur: Blur,
+	blend_mode: BlendMode,
+}
+
+#[node_macro::node_fn(InnerShadowNode)]
+fn inner_shadow<Data: Into<GraphicGroup>>(data: Data, color: Color, offset: DVec2, blur: f64, blend_mode: BlendMode) -> GraphicGroup {
+	with_effect(data, LayerEffect::InnerShadow { color, offset, blur, blend_mode })
+}
+
+pub struct OuterGlowNode<Color, Size, BlendMode> {
+	color: Color,
+	size: Size,
+	blend_mode: BlendMode,
+}
+
+#[node_macro::node_fn(OuterGlowNode)]
+fn outer_glow<Data: Into<GraphicGroup>>(data: Data, color: Color, size: f64, blend_mode: BlendMode) -> GraphicGroup {
+	with_effect(data, LayerEffect::OuterGlow { color, size, blend_mode })
+}
+
+pub struct StrokeEffectNode<Color, Width> {
+	color: Color,
+	width: Width,
+}
+
+#[node_macro::node_fn(StrokeEffectNode)]
+fn stroke_effect<Data: Into<GraphicGroup>>(data: Data, color: Color, width: f64) -> GraphicGroup {
+	with_effect(data, LayerEffect::Stroke { color, width })
+}
+
+fn filter_tag(svg_defs: &mut String, bounds: [DVec2; 2], primitives: impl FnOnce(&mut String)) -> String {
+	let id = format!("effect-{}", generate_uuid());
+	let [min, max] = bounds;
+	let _ = write!(
+		svg_defs,
+		r#"<filter id="{id}" filterUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}" color-interpolation-filters="sRGB">"#,
+		min.x,
+		min.y,
+		max.x - min.x,
+		max.y - min.y
+	);
+	primitives(svg_defs);
+	svg_defs.push_str("</filter>");
+	id
+}
+
+/// Adds a filter drawing only the effect to the SVG defs, returning its ID. The filtered content is placed beneath the content itself,
+/// so the effect can be blended with whatever lies behind the layer.
+pub fn write_behind_filter(svg_defs: &mut String, effect: &LayerEffect, bounds: [DVec2; 2]) -> String {
+	filter_tag(svg_defs, bounds, |svg| effect.write_primitives(svg, "effect"))
+}
+
+/// Adds a filter drawing the content with the effects over it to the SVG defs, returning its ID. Each effect is blended onto the result of the ones before it.
+pub fn write_front_filter(svg_defs: &mut String, effects: &[LayerEffect], bounds: [DVec2; 2]) -> String {
+	filter_tag(svg_defs, bounds, |svg| {
+		let mut composite = "SourceGraphic".to_string();
+		for (index, effect) in effects.iter().enumerate() {
+			let result = format!("effect{index}");
+			effect.write_primitives(svg, &result);
+
+			let mode = effect.blend_mode().to_svg_style_name().unwrap_or("normal");
+			let _ = write!(svg, r#"<feBlend in="{result}" in2="{composite}" mode="{mode}" result="{result}-composite"/>"#);
+			composite = format!("{result}-composite");
+		}
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn filters_reference_their_primitives() {
+		let shadow = LayerEffect::DropShadow {
+			color: Color::BLACK,
+			offset: DVec2::new(4., 6.),
+			blur: 2.,
+			blend_mode: BlendMode::Multiply,
+		};
+		assert!(!shadow.in_front());
+		assert_eq!(shadow.extent(), 12.);
+
+		let mut defs = String::new();
+		let id = write_behind_filter(&mut defs, &shadow, [DVec2::ZERO, DVec2::splat(10.)]);
+		assert!(defs.starts_with(&format!(r#"<filter id="{id}" filterUnits="userSpaceOnUse" x="0" y="0" width="10" height="10""#)));
+		assert!(defs.contains(r#"<feOffset dx="4" dy="6" result="effect-shape"/>"#));
+		assert!(defs.ends_with(r#"result="effect"/></filter>"#));
+
+		let effects = [
+			LayerEffect::InnerShadow {
+				color: Color::BLACK,
+				offset: DVec2::ONE,
+				blur: 1.,
+				blend_mode: BlendMode::Multiply,
+			},
+			LayerEffect::Stroke { color: Color::RED, width: 2. },
+		];
+		let mut defs = String::new();
+		write_front_filter(&mut defs, &effects, [DVec2::ZERO, DVec2::splat(10.)]);
+		assert!(defs.contains(r#"<feBlend in="effect0" in2="SourceGraphic" mode="multiply" result="effect0-composite"/>"#));
+		assert!(defs.contains(r#"<feBlend in="effect1" in2="effect0-composite" mode="normal" result="effect1-composite"/>"#));
+	}
+}
@@ -1,6 +1,9 @@
 mod quad;
 
+use crate::consts::{LAYER_OUTLINE_STROKE_COLOR, LAYER_OUTLINE_STROKE_WEIGHT};
+use crate::graphic_element::effects::{self, LayerEffect};
 use crate::raster::{BlendMode, Image, ImageFrame};
+use crate::text::Typography;
 use crate::transform::Transform;
 use crate::uuid::generate_uuid;
 use crate::vector::PointId;
@@ -124,6 +127,20 @@ impl SvgRender {
 		self.svg.push(content.into());
 	}
 
+	/// Adds a tag with its content on the same line, for elements like `<tspan>` where indentation whitespace would become part of the content
+	pub fn inline_tag(&mut self, name: impl Into<SvgSegment>, attributes: impl FnOnce(&mut SvgRenderAttrs), content: impl Into<SvgSegment>) {
+		let name = name.into();
+		self.indent();
+		self.svg.push("<".into());
+		self.svg.push(name.clone());
+		attributes(&mut SvgRenderAttrs(self));
+		self.svg.push(">".into());
+		self.svg.push(content.into());
+		self.svg.push("</".into());
+		self.svg.push(name);
+		self.svg.push(">".into());
+	}
+
 	pub fn parent_tag(&mut self, name: impl Into<SvgSegment>, attributes: impl FnOnce(&mut SvgRenderAttrs), inner: impl FnOnce(&mut Self)) {
 		let name = name.into();
 		self.indent();
@@ -202,21 +219,26 @@ fn to_transform(transform: DAffine2) -> usvg::Transform {
 	usvg::Transform::from_row(cols[0] as f32, cols[1] as f32, cols[2] as f32, cols[3] as f32, cols[4] as f32, cols[5] as f32)
 }
 
+/// Converts the element by rendering it to an SVG string which is then parsed by `usvg`.
+fn svg_to_usvg_node(element: &(impl GraphicElementRendered + ?Sized)) -> usvg::Node {
+	let mut render = SvgRender::new();
+	let render_params = RenderParams::new(crate::vector::style::ViewMode::Normal, ImageRenderMode::Base64, None, false, false, false);
+	element.render_svg(&mut render, &render_params);
+	render.format_svg(DVec2::ZERO, DVec2::ONE);
+	let svg = render.svg.to_svg_string();
+
+	let opt = usvg::Options::default();
+
+	let tree = usvg::Tree::from_str(&svg, &opt).expect("Failed to parse SVG");
+	usvg::Node::Group(Box::new(tree.root.clone()))
+}
+
 pub trait GraphicElementRendered {
 	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams);
 	fn bounding_box(&self, transform: DAffine2) -> Option<[DVec2; 2]>;
 	fn add_click_targets(&self, click_targets: &mut Vec<ClickTarget>);
 	fn to_usvg_node(&self) -> usvg::Node {
-		let mut render = SvgRender::new();
-		let render_params = RenderParams::new(crate::vector::style::ViewMode::Normal, ImageRenderMode::Base64, None, false, false, false);
-		self.render_svg(&mut render, &render_params);
-		render.format_svg(DVec2::ZERO, DVec2::ONE);
-		let svg = render.svg.to_svg_string();
-
-		let opt = usvg::Options::default();
-
-		let tree = usvg::Tree::from_str(&svg, &opt).expect("Failed to parse SVG");
-		usvg::Node::Group(Box::new(tree.root.clone()))
+		svg_to_usvg_node(self)
 	}
 
 	fn to_usvg_tree(&self, resolution: glam::UVec2, viewbox: [DVec2; 2]) -> usvg::Tree {
@@ -255,8 +277,41 @@ impl GraphicElementRendered for GraphicGroup {
 				}
 			},
 			|render| {
-				for element in self.iter() {
-					element.render_svg(render, render_params);
+				let render_elements = |render: &mut SvgRender| {
+					for element in self.iter() {
+						element.render_svg(render, render_params);
+					}
+				};
+
+				let content_bounds = self.iter().filter_map(|element| element.bounding_box(render.transform)).reduce(Quad::combine_bounds);
+				let Some([min, max]) = content_bounds.filter(|_| !self.effects.is_empty()) else {
+					render_elements(render);
+					return;
+				};
+				let extent = DVec2::splat(self.effects.iter().map(LayerEffect::extent).fold(0., f64::max));
+				let bounds = [min - extent, max + extent];
+
+				// Effects beneath the content each get their own copy of the content, so they can blend with what's behind the group
+				for effect in self.effects.iter().filter(|effect| !effect.in_front()) {
+					let id = effects::write_behind_filter(&mut render.svg_defs, effect, bounds);
+					render.parent_tag(
+						"g",
+						|attributes| {
+							attributes.push("filter", format!("url(#{id})"));
+							if effect.blend_mode() != BlendMode::default() {
+								attributes.push("style", effect.blend_mode().render());
+							}
+						},
+						render_elements,
+					);
+				}
+
+				let in_front: Vec<_> = self.effects.iter().filter(|effect| effect.in_front()).copied().collect();
+				if in_front.is_empty() {
+					render_elements(render);
+				} else {
+					let id = effects::write_front_filter(&mut render.svg_defs, &in_front, bounds);
+					render.parent_tag("g", |attributes| attributes.push("filter", format!("url(#{id})")), render_elements);
 				}
 			},
 		);
@@ -278,6 +333,11 @@ impl GraphicElementRendered for GraphicGroup {
 	}
 
 	fn to_usvg_node(&self) -> usvg::Node {
+		// Effects only exist as SVG filters, so they need to go through the SVG
+		if !self.effects.is_empty() {
+			return svg_to_usvg_node(self);
+		}
+
 		let mut root_node = usvg::Group::default();
 		for element in self.iter() {
 			root_node.children.push(element.to_usvg_node());
@@ -333,40 +393,6 @@ impl GraphicElementRendered for VectorData {
 		click_targets.extend(self.region_bezier_paths().map(|(_, subpath)| ClickTarget { stroke_width, subpath }));
 		click_targets.extend(self.stroke_bezier_paths().map(|subpath| ClickTarget { stroke_width, subpath }));
 	}
-
-	fn to_usvg_node(&self) -> usvg::Node {
-		use bezier_rs::BezierHandles;
-		use usvg::tiny_skia_path::PathBuilder;
-		let mut builder = PathBuilder::new();
-		let vector_data = self;
-
-		let transform = to_transform(vector_data.transform);
-		for subpath in vector_data.stroke_bezier_paths() {
-			let start = vector_data.transform.transform_point2(subpath[0].anchor);
-			builder.move_to(start.x as f32, start.y as f32);
-			for bezier in subpath.iter() {
-				bezier.apply_transformation(|pos| vector_data.transform.transform_point2(pos));
-				let end = bezier.end;
-				match bezier.handles {
-					BezierHandles::Linear => builder.line_to(end.x as f32, end.y as f32),
-					BezierHandles::Quadratic { handle } => builder.quad_to(handle.x as f32, handle.y as f32, end.x as f32, end.y as f32),
-					BezierHandles::Cubic { handle_start, handle_end } => {
-						builder.cubic_to(handle_start.x as f32, handle_start.y as f32, handle_end.x as f32, handle_end.y as f32, end.x as f32, end.y as f32)
-					}
-				}
-			}
-			if subpath.closed {
-				builder.close()
-			}
-		}
-		let path = builder.finish().unwrap();
-		let mut path = usvg::Path::new(path.into());
-		path.abs_transform = transform;
-		// TODO: use proper style
-		path.fill = None;
-		path.stroke = Some(usvg::Stroke::default());
-		usvg::Node::Path(Box::new(path))
-	}
 }
 
 impl GraphicElementRendered for Artboard {
@@ -537,12 +563,87 @@ impl GraphicElementRendered for ImageFrame<Color> {
 	}
 }
 
+fn escape_xml(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+	for character in text.chars() {
+		match character {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			_ => escaped.push(character),
+		}
+	}
+	escaped
+}
+
+impl GraphicElementRendered for Typography {
+	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams) {
+		let multiplied_transform = render.transform * self.transform;
+		render.parent_tag(
+			"text",
+			|attributes| {
+				attributes.push("class", "typography");
+				if multiplied_transform != DAffine2::IDENTITY {
+					attributes.push("transform", format_transform_matrix(multiplied_transform));
+				}
+				if render_params.view_mode == crate::vector::style::ViewMode::Outline {
+					attributes.push("fill", "none");
+					attributes.push("stroke", format!("#{}", LAYER_OUTLINE_STROKE_COLOR.rgb_hex()));
+					attributes.push("stroke-width", LAYER_OUTLINE_STROKE_WEIGHT.to_string());
+					attributes.push("vector-effect", "non-scaling-stroke");
+				}
+			},
+			|render| {
+				for span in &self.spans {
+					let run = &self.runs[span.run];
+					render.inline_tag(
+						"tspan",
+						|attributes| {
+							attributes.push("x", span.position.x.to_string());
+							attributes.push("y", span.position.y.to_string());
+							attributes.push("font-family", escape_xml(&run.font.font_family));
+							attributes.push("font-size", run.font_size.to_string());
+							if let Some(weight) = run.font_weight() {
+								attributes.push("font-weight", weight.to_string());
+							}
+							if run.is_italic() {
+								attributes.push("font-style", "italic");
+							}
+							if run.tracking != 0. {
+								attributes.push("letter-spacing", run.tracking.to_string());
+							}
+							if render_params.view_mode != crate::vector::style::ViewMode::Outline {
+								attributes.push("fill", format!("#{}", run.color.rgb_hex()));
+								if run.color.a() < 1. {
+									attributes.push("fill-opacity", run.color.a().to_string());
+								}
+							}
+						},
+						escape_xml(&span.text),
+					);
+				}
+			},
+		);
+	}
+
+	fn bounding_box(&self, transform: DAffine2) -> Option<[DVec2; 2]> {
+		let transform = transform * self.transform;
+		(self.size != DVec2::ZERO).then(|| (transform * Quad::from_box([DVec2::ZERO, self.size])).bounding_box())
+	}
+
+	fn add_click_targets(&self, click_targets: &mut Vec<ClickTarget>) {
+		let subpath = Subpath::new_rect(DVec2::ZERO, self.size);
+		click_targets.push(ClickTarget { subpath, stroke_width: 0. });
+	}
+}
+
 impl GraphicElementRendered for GraphicElement {
 	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams) {
 		match self {
 			GraphicElement::VectorData(vector_data) => vector_data.render_svg(render, render_params),
 			GraphicElement::ImageFrame(image_frame) => image_frame.render_svg(render, render_params),
-			GraphicElement::Text(_) => todo!("Render a text GraphicElement"),
+			GraphicElement::Text(typography) => typography.render_svg(render, render_params),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.render_svg(render, render_params),
 			GraphicElement::Artboard(artboard) => artboard.render_svg(render, render_params),
 		}
@@ -552,7 +653,7 @@ impl GraphicElementRendered for GraphicElement {
 		match self {
 			GraphicElement::VectorData(vector_data) => GraphicElementRendered::bounding_box(&**vector_data, transform),
 			GraphicElement::ImageFrame(image_frame) => image_frame.bounding_box(transform),
-			GraphicElement::Text(_) => todo!("Bounds of a text GraphicElement"),
+			GraphicElement::Text(typography) => typography.bounding_box(transform),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.bounding_box(transform),
 			GraphicElement::Artboard(artboard) => artboard.bounding_box(transform),
 		}
@@ -562,7 +663,7 @@ impl GraphicElementRendered for GraphicElement {
 		match self {
 			GraphicElement::VectorData(vector_data) => vector_data.add_click_targets(click_targets),
 			GraphicElement::ImageFrame(image_frame) => image_frame.add_click_targets(click_targets),
-			GraphicElement::Text(_) => todo!("click target for text GraphicElement"),
+			GraphicElement::Text(typography) => typography.add_click_targets(click_targets),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.add_click_targets(click_targets),
 			GraphicElement::Artboard(artboard) => artboard.add_click_targets(click_targets),
 		}
@@ -572,7 +673,7 @@ impl GraphicElementRendered for GraphicElement {
 		match self {
 			GraphicElement::VectorData(vector_data) => vector_data.to_usvg_node(),
 			GraphicElement::ImageFrame(image_frame) => image_frame.to_usvg_node(),
-			GraphicElement::Text(text) => text.to_usvg_node(),
+			GraphicElement::Text(typography) => typography.to_usvg_node(),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.to_usvg_node(),
 			GraphicElement::Artboard(artboard) => artboard.to_usvg_node(),
 		}
@@ -582,7 +683,7 @@ impl GraphicElementRendered for GraphicElement {
 		match self {
 			GraphicElement::VectorData(vector_data) => vector_data.contains_artboard(),
 			GraphicElement::ImageFrame(image_frame) => image_frame.contains_artboard(),
-			GraphicElement::Text(text) => text.contains_artboard(),
+			GraphicElement::Text(typography) => typography.contains_artboard(),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.contains_artboard(),
 			GraphicElement::Artboard(artboard) => artboard.contains_artboard(),
 		}
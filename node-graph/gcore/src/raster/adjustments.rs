@@ -798,6 +798,34 @@ impl DomainWarpType {
 	}
 }
 
+/// The reconstruction filter used when resampling an image to a new resolution, from fastest and blockiest to slowest and sharpest.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny)]
+pub enum ResampleFilter {
+	Nearest,
+	Bilinear,
+	Bicubic,
+	Lanczos,
+}
+
+impl core::fmt::Display for ResampleFilter {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			ResampleFilter::Nearest => write!(f, "Nearest Neighbor"),
+			ResampleFilter::Bilinear => write!(f, "Bilinear"),
+			ResampleFilter::Bicubic => write!(f, "Bicubic"),
+			ResampleFilter::Lanczos => write!(f, "Lanczos"),
+		}
+	}
+}
+
+impl ResampleFilter {
+	pub fn list() -> &'static [ResampleFilter; 4] {
+		&[ResampleFilter::Nearest, ResampleFilter::Bilinear, ResampleFilter::Bicubic, ResampleFilter::Lanczos]
+	}
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ChannelMixerNode<Monochrome, MonochromeR, MonochromeG, MonochromeB, MonochromeC, RedR, RedG, RedB, RedC, GreenR, GreenG, GreenB, GreenC, BlueR, BlueG, BlueB, BlueC> {
 	monochrome: Monochrome,
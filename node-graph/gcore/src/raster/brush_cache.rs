@@ -11,6 +11,9 @@ use crate::vector::brush_stroke::BrushStroke;
 use crate::vector::brush_stroke::BrushStyle;
 use crate::Color;
 
+/// The most memory the cached brush textures may take up before the least recently used ones are dropped.
+const BRUSH_TEXTURE_CACHE_BYTES: usize = 256 * 1024 * 1024;
+
 #[derive(Clone, Debug, PartialEq, DynAny, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct BrushCacheImpl {
@@ -22,9 +25,13 @@ struct BrushCacheImpl {
 	blended_image: ImageFrame<Color>,
 	last_stroke_texture: ImageFrame<Color>,
 
-	// A cache for brush textures.
+	// A cache for brush textures, shared with the strokes stamping them, along with when each was last used.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	brush_texture_cache: HashMap<BrushStyle, (Arc<Image<Color>>, u64)>,
 	#[cfg_attr(feature = "serde", serde(skip))]
-	brush_texture_cache: HashMap<BrushStyle, Image<Color>>,
+	brush_texture_uses: u64,
+	#[cfg_attr(feature = "serde", serde(skip))]
+	brush_texture_bytes: usize,
 }
 
 impl BrushCacheImpl {
@@ -61,8 +68,8 @@ impl BrushCacheImpl {
 		if !strokes.is_empty() && self.prev_input.len() > num_blended_strokes {
 			let last_stroke = &self.prev_input[num_blended_strokes];
 			let same_style = strokes[0].style == last_stroke.style;
-			let prev_points = last_stroke.compute_blit_points();
-			let new_points = strokes[0].compute_blit_points();
+			let prev_points = last_stroke.compute_blit_samples();
+			let new_points = strokes[0].compute_blit_samples();
 			let is_point_prefix = new_points.get(..prev_points.len()) == Some(&prev_points);
 			if same_style && is_point_prefix {
 				first_stroke_texture = core::mem::take(&mut self.last_stroke_texture);
@@ -84,6 +91,35 @@ impl BrushCacheImpl {
 		self.blended_image = blended_image;
 		self.last_stroke_texture = last_stroke_texture;
 	}
+
+	fn get_cached_brush(&mut self, style: &BrushStyle) -> Option<Arc<Image<Color>>> {
+		self.brush_texture_uses += 1;
+		let (brush, last_use) = self.brush_texture_cache.get_mut(style)?;
+		*last_use = self.brush_texture_uses;
+		Some(brush.clone())
+	}
+
+	fn store_brush(&mut self, style: BrushStyle, brush: Arc<Image<Color>>) {
+		self.brush_texture_uses += 1;
+		self.brush_texture_bytes += texture_bytes(&brush);
+		if let Some((replaced, _)) = self.brush_texture_cache.insert(style, (brush, self.brush_texture_uses)) {
+			self.brush_texture_bytes -= texture_bytes(&replaced);
+		}
+
+		// Drop the least recently used textures until the rest fit, but always keep the one just stored
+		while self.brush_texture_bytes > BRUSH_TEXTURE_CACHE_BYTES && self.brush_texture_cache.len() > 1 {
+			let Some(least_recent) = self.brush_texture_cache.iter().min_by_key(|(_, (_, last_use))| *last_use).map(|(style, _)| style.clone()) else {
+				break;
+			};
+			if let Some((evicted, _)) = self.brush_texture_cache.remove(&least_recent) {
+				self.brush_texture_bytes -= texture_bytes(&evicted);
+			}
+		}
+	}
+}
+
+fn texture_bytes(texture: &Image<Color>) -> usize {
+	texture.data.len() * core::mem::size_of::<Color>()
 }
 
 impl Hash for BrushCacheImpl {
@@ -163,13 +199,14 @@ impl BrushCache {
 		inner.cache_results(input, blended_image, last_stroke_texture)
 	}
 
-	pub fn get_cached_brush(&self, style: &BrushStyle) -> Option<Image<Color>> {
-		let inner = self.inner.lock().unwrap();
-		inner.brush_texture_cache.get(style).cloned()
+	pub fn get_cached_brush(&self, style: &BrushStyle) -> Option<Arc<Image<Color>>> {
+		let mut inner = self.inner.lock().unwrap();
+		inner.get_cached_brush(style)
 	}
 
-	pub fn store_brush(&self, style: BrushStyle, brush: Image<Color>) {
+	/// Caches the brush texture, dropping the least recently used textures if the cache grows too large.
+	pub fn store_brush(&self, style: BrushStyle, brush: Arc<Image<Color>>) {
 		let mut inner = self.inner.lock().unwrap();
-		inner.brush_texture_cache.insert(style, brush);
+		inner.store_brush(style, brush)
 	}
 }
@@ -1,10 +1,12 @@
 mod font_cache;
 mod to_path;
+mod typography;
 
 use crate::application_io::EditorApi;
 pub use font_cache::*;
 use node_macro::node_fn;
 pub use to_path::*;
+pub use typography::*;
 
 use crate::Node;
 
@@ -19,3 +21,63 @@ fn generate_text<'a: 'input, T>(editor: EditorApi<'a, T>, text: String, font_nam
 	let buzz_face = editor.font_cache.get(&font_name).map(|data| load_face(data));
 	crate::vector::VectorData::from_subpaths(to_path(&text, buzz_face, font_size, None))
 }
+
+pub struct TypographyNode<Text, FontName, Size, Tracking, Leading, Alignment, BoxWidth, FillColor> {
+	text: Text,
+	font_name: FontName,
+	font_size: Size,
+	tracking: Tracking,
+	leading: Leading,
+	alignment: Alignment,
+	box_width: BoxWidth,
+	color: FillColor,
+}
+
+#[node_fn(TypographyNode)]
+fn generate_typography<'a: 'input, T>(
+	editor: EditorApi<'a, T>,
+	text: String,
+	font_name: Font,
+	font_size: f64,
+	tracking: f64,
+	leading: f64,
+	alignment: TextAlignment,
+	box_width: f64,
+	color: crate::Color,
+) -> Typography {
+	let run = TextRun {
+		text,
+		font: font_name,
+		font_size,
+		tracking,
+		leading,
+		color,
+	};
+	// A box width of zero means the text is only broken at explicit line breaks
+	let box_width = (box_width > 0.).then_some(box_width);
+
+	let mut typography = Typography::new(vec![run], alignment, box_width);
+	typography.layout(editor.font_cache);
+	typography
+}
+
+pub struct AppendTextNode<TypographyInput, Appended> {
+	typography: TypographyInput,
+	appended: Appended,
+}
+
+#[node_fn(AppendTextNode)]
+fn append_text<'a: 'input, T>(editor: EditorApi<'a, T>, typography: Typography, appended: Typography) -> Typography {
+	let mut typography = typography;
+	typography.append(appended, editor.font_cache);
+	typography
+}
+
+pub struct OutlineTextNode<TypographyInput> {
+	typography: TypographyInput,
+}
+
+#[node_fn(OutlineTextNode)]
+fn outline_text<'a: 'input, T>(editor: EditorApi<'a, T>, typography: Typography) -> crate::vector::VectorData {
+	typography.to_vector_data(editor.font_cache)
+}
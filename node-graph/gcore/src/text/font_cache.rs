@@ -41,6 +41,11 @@ impl FontCache {
 		self.resolve_font(font).and_then(|font| self.font_file_data.get(font))
 	}
 
+	/// Iterate over the bytes of every loaded font
+	pub fn font_files(&self) -> impl Iterator<Item = &Vec<u8>> {
+		self.font_file_data.values()
+	}
+
 	/// Check if the font is already loaded
 	pub fn loaded_font(&self, font: &Font) -> bool {
 		self.font_file_data.contains_key(font)
@@ -131,6 +131,36 @@ pub fn to_path(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, li
 	builder.other_subpaths
 }
 
+/// Outlines a single line of text whose baseline starts at `position`, adding `tracking` after every glyph.
+pub fn glyph_outlines(str: &str, buzz_face: &rustybuzz::Face, font_size: f64, tracking: f64, position: DVec2, id: &mut ManipulatorGroupId) -> Vec<Subpath<ManipulatorGroupId>> {
+	let (scale, _, mut buffer) = font_properties(buzz_face, font_size);
+	buffer.push_str(str);
+	let glyph_buffer = rustybuzz::shape(buzz_face, &[], buffer);
+
+	let mut builder = Builder {
+		current_subpath: Subpath::new(Vec::new(), false),
+		other_subpaths: Vec::new(),
+		pos: position,
+		offset: DVec2::ZERO,
+		ascender: 0.,
+		scale,
+		id: *id,
+	};
+
+	for (glyph_position, glyph_info) in glyph_buffer.glyph_positions().iter().zip(glyph_buffer.glyph_infos()) {
+		builder.offset = DVec2::new(glyph_position.x_offset as f64, glyph_position.y_offset as f64) * builder.scale;
+		buzz_face.outline_glyph(GlyphId(glyph_info.glyph_id as u16), &mut builder);
+		if !builder.current_subpath.is_empty() {
+			builder.other_subpaths.push(core::mem::replace(&mut builder.current_subpath, Subpath::new(Vec::new(), false)));
+		}
+
+		builder.pos += DVec2::new(glyph_position.x_advance as f64 * builder.scale + tracking, glyph_position.y_advance as f64 * builder.scale);
+	}
+
+	*id = builder.id;
+	builder.other_subpaths
+}
+
 pub fn bounding_box(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, line_width: Option<f64>) -> DVec2 {
 	let buzz_face = match buzz_face {
 		Some(face) => face,
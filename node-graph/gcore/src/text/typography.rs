@@ -0,0 +1,354 @@
+use super::{glyph_outlines, load_face, Font, FontCache};
+use crate::uuid::ManipulatorGroupId;
+use crate::vector::style::Fill;
+use crate::vector::VectorData;
+use crate::Color;
+
+use dyn_any::{DynAny, StaticType};
+use glam::{DAffine2, DVec2};
+use rustybuzz::UnicodeBuffer;
+
+/// Horizontal placement of each line within the width of its text block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum TextAlignment {
+	#[default]
+	Left,
+	Center,
+	Right,
+}
+
+impl TextAlignment {
+	fn offset_factor(self) -> f64 {
+		match self {
+			TextAlignment::Left => 0.,
+			TextAlignment::Center => 0.5,
+			TextAlignment::Right => 1.,
+		}
+	}
+}
+
+/// A stretch of text sharing the same character formatting.
+#[derive(Debug, Clone, PartialEq, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextRun {
+	pub text: String,
+	pub font: Font,
+	pub font_size: f64,
+	/// Additional space added after every character, in pixels
+	pub tracking: f64,
+	/// Distance between the baselines of consecutive lines, as a multiple of the font size
+	pub leading: f64,
+	pub color: Color,
+}
+
+impl core::hash::Hash for TextRun {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.text.hash(state);
+		self.font.hash(state);
+		self.font_size.to_bits().hash(state);
+		self.tracking.to_bits().hash(state);
+		self.leading.to_bits().hash(state);
+		self.color.hash(state);
+	}
+}
+
+impl TextRun {
+	pub fn new(text: String, font: Font, font_size: f64) -> Self {
+		Self {
+			text,
+			font,
+			font_size,
+			tracking: 0.,
+			leading: 1.2,
+			color: Color::BLACK,
+		}
+	}
+
+	/// The CSS `font-weight` encoded in the font style name, such as the `700` in "Italic (700)"
+	pub fn font_weight(&self) -> Option<&str> {
+		let style = &self.font.font_style;
+		let weight = style.get(style.find('(')? + 1..style.find(')')?)?;
+		(!weight.is_empty() && weight.chars().all(|character| character.is_ascii_digit())).then_some(weight)
+	}
+
+	pub fn is_italic(&self) -> bool {
+		self.font.font_style.starts_with("Italic")
+	}
+}
+
+/// The part of a run's text which was placed on a single line by [`Typography::layout`].
+#[derive(Debug, Clone, PartialEq, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionedSpan {
+	/// Index into [`Typography::runs`]
+	pub run: usize,
+	pub text: String,
+	/// The start of the span on its baseline, in the text block's local space
+	pub position: DVec2,
+	pub width: f64,
+}
+
+impl core::hash::Hash for PositionedSpan {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.run.hash(state);
+		self.text.hash(state);
+		self.position.to_array().iter().for_each(|component| component.to_bits().hash(state));
+		self.width.to_bits().hash(state);
+	}
+}
+
+/// Formatted text built from runs, kept as text through the graph so it renders as an SVG `<text>` element and is only converted into outlines on demand.
+#[derive(Debug, Clone, PartialEq, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Typography {
+	pub runs: Vec<TextRun>,
+	pub alignment: TextAlignment,
+	/// When set, lines are wrapped at word boundaries to fit within this width
+	pub box_width: Option<f64>,
+	pub transform: DAffine2,
+	/// The lines computed by the last call to [`Typography::layout`]
+	pub spans: Vec<PositionedSpan>,
+	/// The extent of the laid out text block, starting at the origin of its local space
+	pub size: DVec2,
+}
+
+impl Default for Typography {
+	fn default() -> Self {
+		Self {
+			runs: Vec::new(),
+			alignment: TextAlignment::default(),
+			box_width: None,
+			transform: DAffine2::IDENTITY,
+			spans: Vec::new(),
+			size: DVec2::ZERO,
+		}
+	}
+}
+
+impl core::hash::Hash for Typography {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.runs.hash(state);
+		self.alignment.hash(state);
+		self.box_width.map(f64::to_bits).hash(state);
+		self.transform.to_cols_array().iter().for_each(|element| element.to_bits().hash(state));
+		self.spans.hash(state);
+		self.size.to_array().iter().for_each(|component| component.to_bits().hash(state));
+	}
+}
+
+/// A word (including its trailing spaces) measured with the font of its run.
+struct Word<'a> {
+	run: usize,
+	text: &'a str,
+	width: f64,
+}
+
+#[derive(Default)]
+struct Line<'a> {
+	words: Vec<(Word<'a>, f64)>,
+	width: f64,
+	ascent: f64,
+	height: f64,
+}
+
+impl<'a> Line<'a> {
+	fn include_run(&mut self, run: &TextRun, face: Option<&rustybuzz::Face>) {
+		self.ascent = self.ascent.max(ascent(run, face));
+		self.height = self.height.max(run.font_size * run.leading);
+	}
+}
+
+fn ascent(run: &TextRun, face: Option<&rustybuzz::Face>) -> f64 {
+	match face {
+		Some(face) => face.ascender() as f64 / face.units_per_em() as f64 * run.font_size,
+		None => run.font_size * 0.8,
+	}
+}
+
+/// The advance width of the text, including the tracking after each character.
+fn measure(text: &str, run: &TextRun, face: Option<&rustybuzz::Face>) -> f64 {
+	let tracking = run.tracking * text.chars().count() as f64;
+	let Some(face) = face else {
+		// Estimate until the font has loaded
+		return text.chars().count() as f64 * run.font_size * 0.5 + tracking;
+	};
+
+	let mut buffer = UnicodeBuffer::new();
+	buffer.push_str(text);
+	let glyph_buffer = rustybuzz::shape(face, &[], buffer);
+	let scale = run.font_size / face.units_per_em() as f64;
+	let advance: i32 = glyph_buffer.glyph_positions().iter().map(|position| position.x_advance).sum();
+	advance as f64 * scale + tracking
+}
+
+impl Typography {
+	pub fn new(runs: Vec<TextRun>, alignment: TextAlignment, box_width: Option<f64>) -> Self {
+		Self {
+			runs,
+			alignment,
+			box_width,
+			..Default::default()
+		}
+	}
+
+	/// Breaks the runs into lines, wrapping them to the box width if there is one, and computes the position of every span.
+	pub fn layout(&mut self, font_cache: &FontCache) {
+		let faces: Vec<_> = self.runs.iter().map(|run| font_cache.get(&run.font).map(|data| load_face(data))).collect();
+
+		let mut lines = Vec::new();
+		let mut line = Line::default();
+		let mut x = 0.;
+		for (run_index, run) in self.runs.iter().enumerate() {
+			let face = faces[run_index].as_ref();
+			line.include_run(run, face);
+
+			for (paragraph_index, paragraph) in run.text.split('\n').enumerate() {
+				if paragraph_index > 0 {
+					lines.push(core::mem::take(&mut line));
+					line.include_run(run, face);
+					x = 0.;
+				}
+
+				for text in paragraph.split_inclusive(' ') {
+					let width = measure(text, run, face);
+					let trimmed = text.trim_end_matches(' ');
+					let trailing_space_width = if trimmed.len() == text.len() { 0. } else { width - measure(trimmed, run, face) };
+
+					let wraps = self.box_width.is_some_and(|box_width| !line.words.is_empty() && x + width - trailing_space_width > box_width);
+					if wraps {
+						lines.push(core::mem::take(&mut line));
+						line.include_run(run, face);
+						x = 0.;
+					}
+
+					line.words.push((Word { run: run_index, text, width }, x));
+					x += width;
+					line.width = x - trailing_space_width;
+				}
+			}
+		}
+		lines.push(line);
+
+		let block_width = self.box_width.unwrap_or_else(|| lines.iter().map(|line| line.width).fold(0., f64::max));
+		let mut spans: Vec<PositionedSpan> = Vec::new();
+		let mut top = 0.;
+		for line in lines {
+			let baseline = top + line.ascent;
+			let offset = (block_width - line.width) * self.alignment.offset_factor();
+			let line_start = spans.len();
+
+			for (word, x) in line.words {
+				let continues_line = spans.len() > line_start;
+				match spans.last_mut() {
+					Some(span) if continues_line && span.run == word.run => {
+						span.text.push_str(word.text);
+						span.width += word.width;
+					}
+					_ => spans.push(PositionedSpan {
+						run: word.run,
+						text: word.text.to_string(),
+						position: DVec2::new(offset + x, baseline),
+						width: word.width,
+					}),
+				}
+			}
+
+			// Spaces at the end of a line take up no room
+			if let Some(span) = spans.get_mut(line_start..).and_then(|spans| spans.last_mut()) {
+				let trimmed_length = span.text.trim_end_matches(' ').len();
+				if trimmed_length != span.text.len() {
+					let face = faces[span.run].as_ref();
+					span.width = measure(&span.text[..trimmed_length], &self.runs[span.run], face);
+					span.text.truncate(trimmed_length);
+				}
+			}
+			spans.retain(|span| !span.text.is_empty());
+
+			top += line.height;
+		}
+
+		self.spans = spans;
+		self.size = DVec2::new(block_width, top);
+	}
+
+	/// Continues the text with the runs of another block, keeping the alignment, box width and transform of this one, and lays out the result.
+	pub fn append(&mut self, appended: Typography, font_cache: &FontCache) {
+		self.runs.extend(appended.runs);
+		self.layout(font_cache);
+	}
+
+	/// Converts the laid out text into glyph outlines filled with the color of the first run.
+	pub fn to_vector_data(&self, font_cache: &FontCache) -> VectorData {
+		let mut id = ManipulatorGroupId::ZERO;
+		let mut subpaths = Vec::new();
+		for span in &self.spans {
+			let run = &self.runs[span.run];
+			let Some(face) = font_cache.get(&run.font).map(|data| load_face(data)) else { continue };
+			subpaths.extend(glyph_outlines(&span.text, &face, run.font_size, run.tracking, span.position, &mut id));
+		}
+
+		let mut vector_data = VectorData::from_subpaths(subpaths);
+		vector_data.transform = self.transform;
+		if let Some(run) = self.runs.first() {
+			vector_data.style.set_fill(Fill::Solid(run.color));
+		}
+		vector_data
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn font() -> Font {
+		Font::new("Source Sans Pro".into(), "Normal (400)".into())
+	}
+
+	#[test]
+	fn wraps_words_to_box_width() {
+		// Without a loaded font every character is estimated at half the font size, so each word here is 50 px wide
+		let mut typography = Typography::new(vec![TextRun::new("abcde abcde abcde".into(), font(), 20.)], TextAlignment::Left, Some(120.));
+		typography.layout(&FontCache::default());
+
+		let texts: Vec<_> = typography.spans.iter().map(|span| span.text.as_str()).collect();
+		assert_eq!(texts, ["abcde abcde", "abcde"]);
+		assert_eq!(typography.spans[0].position, DVec2::new(0., 16.));
+		assert_eq!(typography.spans[1].position, DVec2::new(0., 16. + 24.));
+		assert_eq!(typography.size, DVec2::new(120., 48.));
+	}
+
+	#[test]
+	fn aligns_lines_and_keeps_runs_separate() {
+		let mut bold = TextRun::new("ab\nabcd".into(), Font::new("Source Sans Pro".into(), "Normal (700)".into()), 20.);
+		bold.tracking = 2.;
+		let runs = vec![TextRun::new("ab ".into(), font(), 20.), bold];
+		let mut typography = Typography::new(runs, TextAlignment::Right, None);
+		typography.layout(&FontCache::default());
+
+		assert_eq!(typography.spans.len(), 3);
+		let [first, second, third] = [&typography.spans[0], &typography.spans[1], &typography.spans[2]];
+		assert_eq!((first.run, first.text.as_str(), first.position.x), (0, "ab ", 0.));
+		assert_eq!((second.run, second.text.as_str(), second.position.x), (1, "ab", 30.));
+		assert_eq!((third.run, third.text.as_str(), third.position.x), (1, "abcd", 6.));
+		assert_eq!(typography.size.x, 54.);
+		assert_eq!(typography.runs[1].font_weight(), Some("700"));
+	}
+
+	#[test]
+	fn appends_runs_of_another_block() {
+		let font_cache = FontCache::default();
+		let mut typography = Typography::new(vec![TextRun::new("ab ".into(), font(), 20.)], TextAlignment::Left, Some(150.));
+		typography.layout(&font_cache);
+		let mut appended = Typography::new(vec![TextRun::new("abcd abcd".into(), font(), 40.)], TextAlignment::Right, None);
+		appended.transform = DAffine2::from_translation(DVec2::splat(10.));
+		typography.append(appended, &font_cache);
+
+		assert_eq!(typography.runs.len(), 2);
+		assert_eq!(
+			(typography.alignment, typography.box_width, typography.transform),
+			(TextAlignment::Left, Some(150.), DAffine2::IDENTITY)
+		);
+		let spans: Vec<_> = typography.spans.iter().map(|span| (span.run, span.text.as_str(), span.position.x)).collect();
+		assert_eq!(spans, [(0, "ab ", 0.), (1, "abcd", 30.), (1, "abcd", 0.)]);
+	}
+}
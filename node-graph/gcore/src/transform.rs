@@ -8,6 +8,7 @@ use glam::DVec2;
 use crate::raster::bbox::AxisAlignedBbox;
 use crate::raster::ImageFrame;
 use crate::raster::Pixel;
+use crate::text::Typography;
 use crate::vector::VectorData;
 use crate::Artboard;
 use crate::GraphicElement;
@@ -75,7 +76,7 @@ impl Transform for GraphicElement {
 		match self {
 			GraphicElement::VectorData(vector_shape) => vector_shape.transform(),
 			GraphicElement::ImageFrame(image_frame) => image_frame.transform(),
-			GraphicElement::Text(_) => todo!("Transform of text"),
+			GraphicElement::Text(typography) => typography.transform(),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.transform(),
 			GraphicElement::Artboard(artboard) => artboard.transform(),
 		}
@@ -84,7 +85,7 @@ impl Transform for GraphicElement {
 		match self {
 			GraphicElement::VectorData(vector_shape) => vector_shape.local_pivot(pivot),
 			GraphicElement::ImageFrame(image_frame) => image_frame.local_pivot(pivot),
-			GraphicElement::Text(_) => todo!("Transform of text"),
+			GraphicElement::Text(typography) => typography.local_pivot(pivot),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.local_pivot(pivot),
 			GraphicElement::Artboard(artboard) => artboard.local_pivot(pivot),
 		}
@@ -93,7 +94,7 @@ impl Transform for GraphicElement {
 		match self {
 			GraphicElement::VectorData(vector_shape) => vector_shape.decompose_scale(),
 			GraphicElement::ImageFrame(image_frame) => image_frame.decompose_scale(),
-			GraphicElement::Text(_) => todo!("Transform of text"),
+			GraphicElement::Text(typography) => typography.decompose_scale(),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.decompose_scale(),
 			GraphicElement::Artboard(artboard) => artboard.decompose_scale(),
 		}
@@ -104,7 +105,7 @@ impl TransformMut for GraphicElement {
 		match self {
 			GraphicElement::VectorData(vector_shape) => vector_shape.transform_mut(),
 			GraphicElement::ImageFrame(image_frame) => image_frame.transform_mut(),
-			GraphicElement::Text(_) => todo!("Transform of text"),
+			GraphicElement::Text(typography) => typography.transform_mut(),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.transform_mut(),
 			GraphicElement::Artboard(_) => todo!("Transform of artboard"),
 		}
@@ -125,6 +126,20 @@ impl TransformMut for VectorData {
 	}
 }
 
+impl Transform for Typography {
+	fn transform(&self) -> DAffine2 {
+		self.transform
+	}
+	fn local_pivot(&self, pivot: DVec2) -> DVec2 {
+		self.transform.transform_point2(self.size * pivot)
+	}
+}
+impl TransformMut for Typography {
+	fn transform_mut(&mut self) -> &mut DAffine2 {
+		&mut self.transform
+	}
+}
+
 impl Transform for Artboard {
 	fn transform(&self) -> DAffine2 {
 		DAffine2::from_translation(self.location.as_dvec2())
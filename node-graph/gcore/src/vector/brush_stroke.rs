@@ -3,7 +3,7 @@ use crate::raster::BlendMode;
 use crate::Color;
 
 use dyn_any::{DynAny, StaticType};
-use glam::DVec2;
+use glam::{DAffine2, DVec2};
 use std::hash::{Hash, Hasher};
 
 /// The style of a brush.
@@ -16,6 +16,8 @@ pub struct BrushStyle {
 	pub flow: f64,
 	pub spacing: f64, // Spacing as a fraction of the diameter.
 	pub blend_mode: BlendMode,
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub dynamics: BrushDynamics,
 }
 
 impl Default for BrushStyle {
@@ -27,6 +29,7 @@ impl Default for BrushStyle {
 			flow: 100.,
 			spacing: 50., // Percentage of diameter.
 			blend_mode: BlendMode::Normal,
+			dynamics: BrushDynamics::default(),
 		}
 	}
 }
@@ -38,6 +41,7 @@ impl Hash for BrushStyle {
 		self.hardness.to_bits().hash(state);
 		self.flow.to_bits().hash(state);
 		self.spacing.to_bits().hash(state);
+		self.dynamics.hash(state);
 	}
 }
 
@@ -51,6 +55,75 @@ impl PartialEq for BrushStyle {
 			&& self.flow.to_bits() == other.flow.to_bits()
 			&& self.spacing.to_bits() == other.spacing.to_bits()
 			&& self.blend_mode == other.blend_mode
+			&& self.dynamics == other.dynamics
+	}
+}
+
+impl BrushStyle {
+	/// The style of the single stamp placed at the sample, with the dynamics of the brush applied.
+	pub fn at_sample(&self, sample: &BrushInputSample) -> Self {
+		// Rounding lets stamps with nearly the same size, flow and hardness share a brush texture in the cache
+		Self {
+			color: self.color,
+			diameter: (self.diameter * self.dynamics.diameter.factor(sample)).round().max(1.),
+			hardness: (self.hardness * self.dynamics.hardness.factor(sample)).round(),
+			flow: (self.flow * self.dynamics.flow.factor(sample)).round(),
+			spacing: self.spacing,
+			blend_mode: self.blend_mode,
+			dynamics: BrushDynamics::default(),
+		}
+	}
+}
+
+/// The pointer speed, in viewport pixels per second, at which a brush parameter driven by velocity reaches its minimum.
+const MINIMUM_VELOCITY: f64 = 3000.;
+/// The fraction of the full value that a parameter driven by velocity keeps at the fastest speeds.
+const MINIMUM_VELOCITY_FACTOR: f64 = 0.2;
+
+/// An input from the stylus or pointer which can vary a brush parameter along the stroke.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+pub enum BrushDynamicsInput {
+	/// The parameter stays at its full value
+	#[default]
+	Fixed,
+	/// Pressing harder increases the parameter up to its full value
+	Pressure,
+	/// Tilting the stylus away from upright increases the parameter up to its full value
+	Tilt,
+	/// Moving the pointer faster reduces the parameter
+	Velocity,
+}
+
+impl BrushDynamicsInput {
+	pub fn list() -> [Self; 4] {
+		[Self::Fixed, Self::Pressure, Self::Tilt, Self::Velocity]
+	}
+
+	/// The fraction, from 0 to 1, of the full value of the parameter given by this input at the sample.
+	pub fn factor(self, sample: &BrushInputSample) -> f64 {
+		match self {
+			Self::Fixed => 1.,
+			Self::Pressure => sample.pressure.clamp(0., 1.),
+			Self::Tilt => (sample.tilt.length() / 90.).clamp(0., 1.),
+			Self::Velocity => 1. - (sample.velocity / MINIMUM_VELOCITY).clamp(0., 1.) * (1. - MINIMUM_VELOCITY_FACTOR),
+		}
+	}
+}
+
+/// Which input varies each of the parameters of the brush.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BrushDynamics {
+	pub diameter: BrushDynamicsInput,
+	pub flow: BrushDynamicsInput,
+	pub hardness: BrushDynamicsInput,
+}
+
+impl BrushDynamics {
+	pub fn is_fixed(&self) -> bool {
+		*self == Self::default()
 	}
 }
 
@@ -61,22 +134,85 @@ pub struct BrushInputSample {
 	// The position of the sample in layer space, in pixels.
 	// The origin of layer space is not specified.
 	pub position: DVec2,
-	// Future work: pressure, stylus angle, etc.
+	/// Stylus pressure from 0 to 1, which is always 1 for devices without pressure sensitivity
+	#[cfg_attr(feature = "serde", serde(default = "full_pressure"))]
+	pub pressure: f64,
+	/// Stylus tilt along the X and Y axes in degrees, from -90 to 90
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub tilt: DVec2,
+	/// Speed of the pointer in viewport pixels per second
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub velocity: f64,
+}
+
+#[cfg(feature = "serde")]
+fn full_pressure() -> f64 {
+	1.
+}
+
+impl BrushInputSample {
+	/// A sample without any input from a stylus.
+	pub fn new(position: DVec2) -> Self {
+		Self {
+			position,
+			pressure: 1.,
+			tilt: DVec2::ZERO,
+			velocity: 0.,
+		}
+	}
+
+	fn lerp(&self, other: &Self, t: f64) -> Self {
+		Self {
+			position: self.position.lerp(other.position, t),
+			pressure: self.pressure + (other.pressure - self.pressure) * t,
+			tilt: self.tilt.lerp(other.tilt, t),
+			velocity: self.velocity + (other.velocity - self.velocity) * t,
+		}
+	}
 }
 
 impl Hash for BrushInputSample {
 	fn hash<H: Hasher>(&self, state: &mut H) {
 		self.position.x.to_bits().hash(state);
 		self.position.y.to_bits().hash(state);
+		self.pressure.to_bits().hash(state);
+		self.tilt.x.to_bits().hash(state);
+		self.tilt.y.to_bits().hash(state);
+		self.velocity.to_bits().hash(state);
 	}
 }
 
 /// The parameters for a single stroke brush.
-#[derive(Clone, Debug, PartialEq, Hash, Default, DynAny)]
+#[derive(Clone, Debug, PartialEq, Default, DynAny)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BrushStroke {
 	pub style: BrushStyle,
 	pub trace: Vec<BrushInputSample>,
+	/// When set, the stroke paints with the layer's own pixels found at this offset from each point of the stroke (in layer space) instead of the brush color.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub clone_offset: Option<DVec2>,
+	/// Heals the stroke area by blending in texture from elsewhere in the layer while keeping the surrounding color.
+	/// The texture comes from the clone offset, or from a nearby smooth area when there isn't one.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub healing: bool,
+	/// Closed polygons in layer space which confine the stroke to the selection it was painted within, filled by the nonzero rule.
+	/// The stroke is unconfined when there are none.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub clip: Vec<Vec<DVec2>>,
+}
+
+impl Hash for BrushStroke {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.style.hash(state);
+		self.trace.hash(state);
+		self.clone_offset.map(|offset| [offset.x.to_bits(), offset.y.to_bits()]).hash(state);
+		self.healing.hash(state);
+		self.clip.len().hash(state);
+		for polygon in &self.clip {
+			polygon.len().hash(state);
+			polygon.iter().for_each(|point| [point.x.to_bits(), point.y.to_bits()].hash(state));
+		}
+	}
 }
 
 impl BrushStroke {
@@ -93,6 +229,11 @@ impl BrushStroke {
 	}
 
 	pub fn compute_blit_points(&self) -> Vec<DVec2> {
+		self.compute_blit_samples().into_iter().map(|sample| sample.position).collect()
+	}
+
+	/// The samples where a stamp of the brush is placed, with the stylus input interpolated between the samples of the trace.
+	pub fn compute_blit_samples(&self) -> Vec<BrushInputSample> {
 		// We always travel in a straight line towards the next user input,
 		// placing a blit point every time we travelled our spacing distance.
 		let spacing_dist = self.style.spacing / 100. * self.style.diameter;
@@ -101,30 +242,81 @@ impl BrushStroke {
 			return Vec::new();
 		};
 
-		let mut cur_pos = first_sample.position;
-		let mut result = vec![cur_pos];
+		let mut previous_sample = first_sample;
+		let mut result = vec![first_sample.clone()];
 		let mut dist_until_next_blit = spacing_dist;
 		for sample in &self.trace[1..] {
 			// Travel to the next sample.
-			let delta = sample.position - cur_pos;
-			let mut dist_left = delta.length();
-			let unit_step = delta / dist_left;
+			let segment_length = (sample.position - previous_sample.position).length();
+			let mut dist_travelled = 0.;
 
-			while dist_left >= dist_until_next_blit {
+			while segment_length - dist_travelled >= dist_until_next_blit {
 				// Take a step to the next blit point.
-				cur_pos += dist_until_next_blit * unit_step;
-				dist_left -= dist_until_next_blit;
+				dist_travelled += dist_until_next_blit;
 
 				// Blit.
-				result.push(cur_pos);
+				result.push(previous_sample.lerp(sample, dist_travelled / segment_length));
 				dist_until_next_blit = spacing_dist;
 			}
 
 			// Take the partial step to land at the sample.
-			dist_until_next_blit -= dist_left;
-			cur_pos = sample.position;
+			dist_until_next_blit -= segment_length - dist_travelled;
+			previous_sample = sample;
 		}
 
 		result
 	}
 }
+
+/// The fraction of each pixel of a `width` by `height` image which lies within the closed polygons by the nonzero rule, in rows from the top.
+/// Each pixel is sampled on a four by four grid so the edges are anti-aliased. The transform maps pixel coordinates into the space of the polygons.
+pub fn polygon_coverage(polygons: &[Vec<DVec2>], width: usize, height: usize, pixel_to_polygon: DAffine2) -> Vec<f32> {
+	const SAMPLES: usize = 4;
+	const SAMPLE_WEIGHT: f32 = 1. / (SAMPLES * SAMPLES) as f32;
+
+	let mut coverage = vec![0.; width * height];
+	if pixel_to_polygon.matrix2.determinant() == 0. {
+		return coverage;
+	}
+
+	let polygon_to_pixel = pixel_to_polygon.inverse();
+	let edges: Vec<(DVec2, DVec2)> = polygons
+		.iter()
+		.flat_map(|polygon| polygon.iter().zip(polygon.iter().cycle().skip(1)))
+		.map(|(&start, &end)| (polygon_to_pixel.transform_point2(start), polygon_to_pixel.transform_point2(end)))
+		.collect();
+
+	let mut crossings = Vec::new();
+	for row in 0..height * SAMPLES {
+		let y = (row as f64 + 0.5) / SAMPLES as f64;
+
+		crossings.clear();
+		for &(start, end) in &edges {
+			if (start.y <= y) == (end.y <= y) {
+				continue;
+			}
+			let x = start.x + (y - start.y) / (end.y - start.y) * (end.x - start.x);
+			crossings.push((x, if end.y > start.y { 1 } else { -1 }));
+		}
+		if crossings.is_empty() {
+			continue;
+		}
+		crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+		// Walk along the row, keeping count of how many times the edges crossed so far wind around the samples
+		let pixels = &mut coverage[row / SAMPLES * width..][..width];
+		let (mut winding, mut next_crossing) = (0, 0);
+		for column in 0..width * SAMPLES {
+			let x = (column as f64 + 0.5) / SAMPLES as f64;
+			while next_crossing < crossings.len() && crossings[next_crossing].0 < x {
+				winding += crossings[next_crossing].1;
+				next_crossing += 1;
+			}
+			if winding != 0 {
+				pixels[column / SAMPLES] += SAMPLE_WEIGHT;
+			}
+		}
+	}
+
+	coverage
+}
@@ -8,6 +8,9 @@ use glam::{DAffine2, DVec2};
 
 use std::fmt::{self, Display, Write};
 
+/// Number of solid wedges used to approximate a conic gradient in SVG, which has no native conic gradient.
+const CONIC_GRADIENT_SEGMENTS: usize = 180;
+
 /// Precision of the opacity value in digits after the decimal point.
 /// A value of 3 would correspond to a precision of 10^-3.
 const OPACITY_PRECISION: usize = 3;
@@ -25,6 +28,51 @@ pub enum GradientType {
 	#[default]
 	Linear,
 	Radial,
+	/// Sweeps the colors around the start point, beginning in the direction of the end point (also called an angular gradient)
+	Conic,
+	/// Spreads the colors out from the start point in the shape of a diamond with one of its corners at the end point
+	Diamond,
+}
+
+impl GradientType {
+	pub fn list() -> [Self; 4] {
+		[Self::Linear, Self::Radial, Self::Conic, Self::Diamond]
+	}
+}
+
+/// How a gradient continues past the first and last stops.
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug, Hash, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub enum GradientSpreadMethod {
+	/// Extends the colors of the first and last stops
+	#[default]
+	Pad,
+	/// Repeats the gradient, mirroring every other repetition
+	Reflect,
+	/// Repeats the gradient from its start
+	Repeat,
+}
+
+impl GradientSpreadMethod {
+	pub fn list() -> [Self; 3] {
+		[Self::Pad, Self::Reflect, Self::Repeat]
+	}
+
+	/// Maps a position along the gradient into the range from 0 to 1.
+	pub fn apply(self, position: f64) -> f64 {
+		match self {
+			Self::Pad => position.clamp(0., 1.),
+			Self::Reflect => 1. - ((position.rem_euclid(2.)) - 1.).abs(),
+			Self::Repeat => position.rem_euclid(1.),
+		}
+	}
+
+	fn svg_attribute(self) -> &'static str {
+		match self {
+			Self::Pad => "",
+			Self::Reflect => r#" spreadMethod="reflect""#,
+			Self::Repeat => r#" spreadMethod="repeat""#,
+		}
+	}
 }
 
 /// A gradient fill.
@@ -38,6 +86,8 @@ pub struct Gradient {
 	pub transform: DAffine2,
 	pub positions: Vec<(f64, Color)>,
 	pub gradient_type: GradientType,
+	#[serde(default)]
+	pub spread_method: GradientSpreadMethod,
 }
 
 impl core::hash::Hash for Gradient {
@@ -51,6 +101,7 @@ impl core::hash::Hash for Gradient {
 			.for_each(|x| x.to_bits().hash(state));
 		self.positions.iter().for_each(|(_, color)| color.hash(state));
 		self.gradient_type.hash(state);
+		self.spread_method.hash(state);
 	}
 }
 
@@ -63,6 +114,7 @@ impl Gradient {
 			positions: vec![(0., start_color), (1., end_color)],
 			transform,
 			gradient_type,
+			spread_method: GradientSpreadMethod::Pad,
 		}
 	}
 
@@ -81,6 +133,7 @@ impl Gradient {
 			})
 			.collect::<Vec<_>>();
 		let gradient_type = if time < 0.5 { self.gradient_type } else { other.gradient_type };
+		let spread_method = if time < 0.5 { self.spread_method } else { other.spread_method };
 
 		Self {
 			start,
@@ -88,9 +141,30 @@ impl Gradient {
 			transform,
 			positions,
 			gradient_type,
+			spread_method,
 		}
 	}
 
+	/// Evaluates the color at a position along the gradient, where the first and last stops are usually at 0 and 1.
+	pub fn sample(&self, position: f64) -> Color {
+		let position = self.spread_method.apply(position);
+		let Some(&(first_position, first_color)) = self.positions.first() else {
+			return Color::TRANSPARENT;
+		};
+		if position <= first_position {
+			return first_color;
+		}
+
+		for pair in self.positions.windows(2) {
+			let [(start, start_color), (end, end_color)] = [pair[0], pair[1]];
+			if position <= end {
+				let time = if end > start { (position - start) / (end - start) } else { 1. };
+				return start_color.lerp(&end_color, time as f32);
+			}
+		}
+		self.positions[self.positions.len() - 1].1
+	}
+
 	/// Adds the gradient def through mutating the first argument, returning the gradient ID.
 	fn render_defs(&self, svg_defs: &mut String, multiplied_transform: DAffine2, bounds: [DVec2; 2], transformed_bounds: [DVec2; 2]) -> u64 {
 		let bound_transform = DAffine2::from_scale_angle_translation(bounds[1] - bounds[0], 0., bounds[0]);
@@ -116,27 +190,116 @@ impl Gradient {
 			.collect::<String>();
 
 		let gradient_id = crate::uuid::generate_uuid();
+		let spread_method = self.spread_method.svg_attribute();
 		match self.gradient_type {
 			GradientType::Linear => {
 				let _ = write!(
 					svg_defs,
-					r#"<linearGradient id="{}" x1="{}" x2="{}" y1="{}" y2="{}" gradientTransform="matrix({})">{}</linearGradient>"#,
-					gradient_id, start.x, end.x, start.y, end.y, transform, positions
+					r#"<linearGradient id="{}" x1="{}" x2="{}" y1="{}" y2="{}" gradientTransform="matrix({})"{}>{}</linearGradient>"#,
+					gradient_id, start.x, end.x, start.y, end.y, transform, spread_method, positions
 				);
 			}
 			GradientType::Radial => {
 				let radius = (f64::powi(start.x - end.x, 2) + f64::powi(start.y - end.y, 2)).sqrt();
 				let _ = write!(
 					svg_defs,
-					r#"<radialGradient id="{}" cx="{}" cy="{}" r="{}" gradientTransform="matrix({})">{}</radialGradient>"#,
-					gradient_id, start.x, start.y, radius, transform, positions
+					r#"<radialGradient id="{}" cx="{}" cy="{}" r="{}" gradientTransform="matrix({})"{}>{}</radialGradient>"#,
+					gradient_id, start.x, start.y, radius, transform, spread_method, positions
 				);
 			}
+			GradientType::Conic | GradientType::Diamond => {
+				// The start and end are already in the user space of the painted element, which the pattern's contents are drawn in
+				self.render_pattern_defs(svg_defs, gradient_id, start, end, transformed_bounds, &positions);
+			}
 		}
 
 		gradient_id
 	}
 
+	/// SVG has no conic or diamond gradients, so they are drawn as shapes within a `<pattern>` covering the painted element, which is used as its paint.
+	///
+	/// - A conic gradient is approximated by [`CONIC_GRADIENT_SEGMENTS`] wedges around the start point, each filled with the gradient color at its middle.
+	/// - A diamond gradient is exact: in each quadrant around the axis from the start to the end point, the position along the gradient
+	///   grows linearly, so each quadrant is filled with a linear gradient (which also takes care of the spread method).
+	fn render_pattern_defs(&self, svg_defs: &mut String, gradient_id: u64, start: DVec2, end: DVec2, transformed_bounds: [DVec2; 2], positions: &str) {
+		// Leave room around the bounds for strokes, which extend outside of them
+		let margin = (transformed_bounds[1] - transformed_bounds[0]).length().max(1.);
+		let region = [transformed_bounds[0] - margin, transformed_bounds[1] + margin];
+		let size = region[1] - region[0];
+
+		// The contents of a pattern are positioned relative to the corner of its tile
+		let center = start - region[0];
+		let axis = end - start;
+		let corners = [region[0], DVec2::new(region[1].x, region[0].y), region[1], DVec2::new(region[0].x, region[1].y)];
+		let reach = corners.iter().map(|&corner| (corner - region[0]).distance(center)).fold(0., f64::max) + 1.;
+
+		let mut shapes = String::new();
+		match self.gradient_type {
+			GradientType::Conic => {
+				let start_angle = axis.y.atan2(axis.x);
+				let step = std::f64::consts::TAU / CONIC_GRADIENT_SEGMENTS as f64;
+				for segment in 0..CONIC_GRADIENT_SEGMENTS {
+					// Each wedge overlaps the next one by half a segment to hide the antialiased seams between them
+					let from = center + DVec2::from_angle(start_angle + segment as f64 * step) * reach;
+					let to = center + DVec2::from_angle(start_angle + (segment as f64 + 1.5) * step) * reach;
+					let color = self.sample((segment as f64 + 0.5) / CONIC_GRADIENT_SEGMENTS as f64);
+					let _ = write!(
+						shapes,
+						r##"<path d="M{},{} L{},{} L{},{} Z" fill="#{}"{} />"##,
+						center.x,
+						center.y,
+						from.x,
+						from.y,
+						to.x,
+						to.y,
+						color.rgb_hex(),
+						format_opacity("fill-opacity", color.a())
+					);
+				}
+			}
+			GradientType::Diamond if axis.length_squared() > 0. => {
+				let spread_method = self.spread_method.svg_attribute();
+				let perpendicular = axis.perp();
+				for (quadrant, (along, across)) in [(1., 1.), (-1., 1.), (-1., -1.), (1., -1.)].into_iter().enumerate() {
+					// Within the quadrant, the position along the gradient reaches 1 on the line between the end point and its perpendicular counterpart,
+					// which is closest to the center at its midpoint
+					let gradient_end = center + (axis * along + perpendicular * across) / 2.;
+					let _ = write!(
+						svg_defs,
+						r#"<linearGradient id="{gradient_id}-{quadrant}" gradientUnits="userSpaceOnUse" x1="{}" y1="{}" x2="{}" y2="{}"{spread_method}>{positions}</linearGradient>"#,
+						center.x, center.y, gradient_end.x, gradient_end.y
+					);
+
+					let [side, other_side] = [axis * along / axis.length() * reach, perpendicular * across / axis.length() * reach];
+					let [a, b, c] = [center + side, center + side + other_side, center + other_side];
+					let _ = write!(
+						shapes,
+						r#"<path d="M{},{} L{},{} L{},{} L{},{} Z" fill="url('#{gradient_id}-{quadrant}')" />"#,
+						center.x, center.y, a.x, a.y, b.x, b.y, c.x, c.y
+					);
+				}
+			}
+			_ => {
+				// A diamond without any size is entirely past its end
+				let color = self.sample(1.);
+				let _ = write!(
+					shapes,
+					r##"<rect width="{}" height="{}" fill="#{}"{} />"##,
+					size.x,
+					size.y,
+					color.rgb_hex(),
+					format_opacity("fill-opacity", color.a())
+				);
+			}
+		}
+
+		let _ = write!(
+			svg_defs,
+			r#"<pattern id="{gradient_id}" patternUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}">{shapes}</pattern>"#,
+			region[0].x, region[0].y, size.x, size.y
+		);
+	}
+
 	/// Insert a stop into the gradient, the index if successful
 	pub fn insert_stop(&mut self, mouse: DVec2, transform: DAffine2) -> Option<usize> {
 		// Transform the start and end positions to the same coordinate space as the mouse.
@@ -313,6 +476,9 @@ impl Display for LineJoin {
 pub struct Stroke {
 	/// Stroke color
 	pub color: Option<Color>,
+	/// Gradient paint, which takes the place of the color when present
+	#[serde(default)]
+	pub gradient: Option<Gradient>,
 	/// Line thickness
 	pub weight: f64,
 	pub dash_lengths: Vec<f64>,
@@ -325,6 +491,7 @@ pub struct Stroke {
 impl core::hash::Hash for Stroke {
 	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
 		self.color.hash(state);
+		self.gradient.hash(state);
 		self.weight.to_bits().hash(state);
 		self.dash_lengths.len().hash(state);
 		self.dash_lengths.iter().for_each(|length| length.to_bits().hash(state));
@@ -339,6 +506,7 @@ impl Stroke {
 	pub const fn new(color: Option<Color>, weight: f64) -> Self {
 		Self {
 			color,
+			gradient: None,
 			weight,
 			dash_lengths: Vec::new(),
 			dash_offset: 0.,
@@ -351,6 +519,10 @@ impl Stroke {
 	pub fn lerp(&self, other: &Self, time: f64) -> Self {
 		Self {
 			color: self.color.map(|color| color.lerp(&other.color.unwrap_or(color), time as f32)),
+			gradient: match (&self.gradient, &other.gradient) {
+				(Some(a), Some(b)) => Some(a.lerp(b, time)),
+				(gradient, _) => gradient.clone(),
+			},
 			weight: self.weight + (other.weight - self.weight) * time,
 			dash_lengths: self.dash_lengths.iter().zip(other.dash_lengths.iter()).map(|(a, b)| a + (b - a) * time).collect(),
 			dash_offset: self.dash_offset + (other.dash_offset - self.dash_offset) * time,
@@ -365,6 +537,11 @@ impl Stroke {
 		self.color
 	}
 
+	/// Get the current stroke gradient, if the stroke is painted with one.
+	pub fn gradient(&self) -> Option<&Gradient> {
+		self.gradient.as_ref()
+	}
+
 	/// Get the current stroke weight.
 	pub fn weight(&self) -> f64 {
 		self.weight
@@ -394,23 +571,25 @@ impl Stroke {
 		self.line_join_miter_limit as f32
 	}
 
-	/// Provide the SVG attributes for the stroke.
-	pub fn render(&self) -> String {
-		if let Some(color) = self.color {
-			format!(
-				r##" stroke="#{}"{} stroke-width="{}" stroke-dasharray="{}" stroke-dashoffset="{}" stroke-linecap="{}" stroke-linejoin="{}" stroke-miterlimit="{}" "##,
-				color.rgb_hex(),
-				format_opacity("stroke-opacity", color.a()),
-				self.weight,
-				self.dash_lengths(),
-				self.dash_offset,
-				self.line_cap,
-				self.line_join,
-				self.line_join_miter_limit
-			)
-		} else {
-			String::new()
-		}
+	/// Provide the SVG attributes for the stroke, adding the defs of a gradient paint through mutating the first argument.
+	pub fn render(&self, svg_defs: &mut String, multiplied_transform: DAffine2, bounds: [DVec2; 2], transformed_bounds: [DVec2; 2]) -> String {
+		let paint = match (&self.gradient, self.color) {
+			(Some(gradient), _) => {
+				let gradient_id = gradient.render_defs(svg_defs, multiplied_transform, bounds, transformed_bounds);
+				format!(r##" stroke="url('#{gradient_id}')""##)
+			}
+			(None, Some(color)) => format!(r##" stroke="#{}"{}"##, color.rgb_hex(), format_opacity("stroke-opacity", color.a())),
+			(None, None) => return String::new(),
+		};
+		format!(
+			r#"{paint} stroke-width="{}" stroke-dasharray="{}" stroke-dashoffset="{}" stroke-linecap="{}" stroke-linejoin="{}" stroke-miterlimit="{}" "#,
+			self.weight,
+			self.dash_lengths(),
+			self.dash_offset,
+			self.line_cap,
+			self.line_join,
+			self.line_join_miter_limit
+		)
 	}
 
 	pub fn with_color(mut self, color: &Option<Color>) -> Option<Self> {
@@ -419,6 +598,11 @@ impl Stroke {
 		Some(self)
 	}
 
+	pub fn with_gradient(mut self, gradient: Option<Gradient>) -> Self {
+		self.gradient = gradient;
+		self
+	}
+
 	pub fn with_weight(mut self, weight: f64) -> Self {
 		self.weight = weight;
 		self
@@ -464,6 +648,7 @@ impl Default for Stroke {
 		Self {
 			weight: 0.,
 			color: Some(Color::from_rgba8_srgb(0, 0, 0, 255)),
+			gradient: None,
 			dash_lengths: Vec::new(),
 			dash_offset: 0.,
 			line_cap: LineCap::Butt,
@@ -625,12 +810,16 @@ impl PathStyle {
 		match view_mode {
 			ViewMode::Outline => {
 				let fill_attribute = Fill::None.render(svg_defs, multiplied_transform, bounds, transformed_bounds);
-				let stroke_attribute = Stroke::new(Some(LAYER_OUTLINE_STROKE_COLOR), LAYER_OUTLINE_STROKE_WEIGHT).render();
+				let stroke_attribute = Stroke::new(Some(LAYER_OUTLINE_STROKE_COLOR), LAYER_OUTLINE_STROKE_WEIGHT).render(svg_defs, multiplied_transform, bounds, transformed_bounds);
 				format!("{fill_attribute}{stroke_attribute}")
 			}
 			_ => {
 				let fill_attribute = self.fill.render(svg_defs, multiplied_transform, bounds, transformed_bounds);
-				let stroke_attribute = self.stroke.as_ref().map(|stroke| stroke.render()).unwrap_or_default();
+				let stroke_attribute = self
+					.stroke
+					.as_ref()
+					.map(|stroke| stroke.render(svg_defs, multiplied_transform, bounds, transformed_bounds))
+					.unwrap_or_default();
 				format!("{fill_attribute}{stroke_attribute}")
 			}
 		}
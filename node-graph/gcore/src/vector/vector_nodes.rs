@@ -1,16 +1,16 @@
-use super::style::{Fill, FillType, Gradient, GradientType, Stroke};
+use super::style::{Fill, FillType, Gradient, GradientSpreadMethod, GradientType, LineJoin, Stroke};
 use super::{PointId, SegmentId, StrokeId, VectorData};
 use crate::renderer::GraphicElementRendered;
 use crate::transform::{Footprint, Transform, TransformMut};
 use crate::{Color, GraphicGroup, Node};
 use core::future::Future;
 
-use bezier_rs::{Cap, Join, Subpath, SubpathTValue, TValue};
+use bezier_rs::{BooleanOperation, Cap, Join, Subpath, SubpathTValue, TValue};
 use glam::{DAffine2, DVec2};
 use rand::{Rng, SeedableRng};
 
 #[derive(Debug, Clone, Copy)]
-pub struct SetFillNode<FillType, SolidColor, GradientType, Start, End, Transform, Positions> {
+pub struct SetFillNode<FillType, SolidColor, GradientType, Start, End, Transform, Positions, SpreadMethod> {
 	fill_type: FillType,
 	solid_color: SolidColor,
 	gradient_type: GradientType,
@@ -18,6 +18,7 @@ pub struct SetFillNode<FillType, SolidColor, GradientType, Start, End, Transform
 	end: End,
 	transform: Transform,
 	positions: Positions,
+	spread_method: SpreadMethod,
 }
 
 #[node_macro::node_fn(SetFillNode)]
@@ -30,6 +31,7 @@ fn set_vector_data_fill(
 	end: DVec2,
 	transform: DAffine2,
 	positions: Vec<(f64, Color)>,
+	spread_method: GradientSpreadMethod,
 ) -> VectorData {
 	vector_data.style.set_fill(match fill_type {
 		FillType::Solid => solid_color.map_or(Fill::None, Fill::Solid),
@@ -39,13 +41,14 @@ fn set_vector_data_fill(
 			transform,
 			positions,
 			gradient_type,
+			spread_method,
 		}),
 	});
 	vector_data
 }
 
 #[derive(Debug, Clone, Copy)]
-pub struct SetStrokeNode<Color, Weight, DashLengths, DashOffset, LineCap, LineJoin, MiterLimit> {
+pub struct SetStrokeNode<Color, Weight, DashLengths, DashOffset, LineCap, LineJoin, MiterLimit, Gradient> {
 	color: Color,
 	weight: Weight,
 	dash_lengths: DashLengths,
@@ -53,6 +56,7 @@ pub struct SetStrokeNode<Color, Weight, DashLengths, DashOffset, LineCap, LineJo
 	line_cap: LineCap,
 	line_join: LineJoin,
 	miter_limit: MiterLimit,
+	gradient: Gradient,
 }
 
 #[node_macro::node_fn(SetStrokeNode)]
@@ -65,9 +69,11 @@ fn set_vector_data_stroke(
 	line_cap: super::style::LineCap,
 	line_join: super::style::LineJoin,
 	miter_limit: f64,
+	gradient: Option<Gradient>,
 ) -> VectorData {
 	vector_data.style.set_stroke(Stroke {
 		color,
+		gradient,
 		weight,
 		dash_lengths,
 		dash_offset,
@@ -201,13 +207,59 @@ fn solidify_stroke(vector_data: VectorData) -> VectorData {
 
 	// We set our fill to our stroke's color, then clear our stroke.
 	if let Some(stroke) = vector_data.style.stroke() {
-		result.style.set_fill(Fill::solid_or_none(stroke.color));
+		result.style.set_fill(match stroke.gradient {
+			Some(gradient) => Fill::Gradient(gradient),
+			None => Fill::solid_or_none(stroke.color),
+		});
 		result.style.set_stroke(Stroke::default());
 	}
 
 	result
 }
 
+/// Grows the area of the shape outwards by the distance, or shrinks it inwards when the distance is negative.
+/// Open subpaths are closed first, overlapping subpaths are merged, and holes shrink as the shape grows (and the other way around).
+#[derive(Debug, Clone, Copy)]
+pub struct OffsetPathNode<Distance, LineJoin, MiterLimit> {
+	distance: Distance,
+	line_join: LineJoin,
+	miter_limit: MiterLimit,
+}
+
+#[node_macro::node_fn(OffsetPathNode)]
+fn offset_path(vector_data: VectorData, distance: f64, line_join: LineJoin, miter_limit: f64) -> VectorData {
+	let subpaths = vector_data
+		.stroke_bezier_paths()
+		.map(|mut subpath| {
+			subpath.apply_transform(vector_data.transform);
+			subpath
+		})
+		.collect::<Vec<_>>();
+
+	// The union winds every outline with the area to its left, where the offset would move it, so outer boundaries and holes are both pushed out of the area by offsetting backwards
+	let outlines = Subpath::boolean_operation(&subpaths, &[], BooleanOperation::Union);
+	let join = match line_join {
+		LineJoin::Miter => Join::Miter(Some(miter_limit)),
+		LineJoin::Bevel => Join::Bevel,
+		LineJoin::Round => Join::Round,
+	};
+	let offset = outlines
+		.iter()
+		.filter(|subpath| subpath.len_segments() > 1)
+		.map(|subpath| subpath.offset(-distance, join))
+		.collect::<Vec<_>>();
+
+	// Offsetting leaves overlaps where parts of the shape grow into each other, and loops turned inside out where they shrink past nothing
+	let mut result = VectorData::empty();
+	for subpath in Subpath::positive_area(&offset) {
+		result.append_subpath(subpath);
+	}
+	result.style = vector_data.style.clone();
+	result.alpha_blending = vector_data.alpha_blending;
+
+	result
+}
+
 pub trait ConcatElement {
 	fn concat(&mut self, other: &Self, transform: DAffine2);
 }
@@ -530,6 +582,47 @@ async fn morph<SourceFuture: Future<Output = VectorData>, TargetFuture: Future<O
 	result
 }
 
+pub struct BooleanOperationNode<LowerVectorData, UpperVectorData, Operation> {
+	lower_vector_data: LowerVectorData,
+	upper_vector_data: UpperVectorData,
+	operation: Operation,
+}
+
+#[node_macro::node_fn(BooleanOperationNode)]
+async fn boolean_operation_node<LowerFuture: Future<Output = VectorData>, UpperFuture: Future<Output = VectorData>>(
+	footprint: Footprint,
+	lower_vector_data: impl Node<Footprint, Output = LowerFuture>,
+	upper_vector_data: impl Node<Footprint, Output = UpperFuture>,
+	operation: BooleanOperation,
+) -> VectorData {
+	let lower_vector_data = self.lower_vector_data.eval(footprint).await;
+	let upper_vector_data = self.upper_vector_data.eval(footprint).await;
+
+	// Both shapes are combined in the same space, so their transforms are applied to their subpaths
+	let transformed_subpaths = |vector_data: &VectorData| {
+		vector_data
+			.stroke_bezier_paths()
+			.map(|mut subpath| {
+				subpath.apply_transform(vector_data.transform);
+				subpath
+			})
+			.collect::<Vec<_>>()
+	};
+	let subpaths = Subpath::boolean_operation(&transformed_subpaths(&lower_vector_data), &transformed_subpaths(&upper_vector_data), operation);
+
+	let mut result = VectorData::empty();
+	for subpath in subpaths {
+		result.append_subpath(subpath);
+	}
+
+	// The shape which remains visible in the result determines its appearance
+	let style_source = if operation == BooleanOperation::SubtractFront { &lower_vector_data } else { &upper_vector_data };
+	result.style = style_source.style.clone();
+	result.alpha_blending = style_source.alpha_blending;
+
+	result
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -606,6 +699,67 @@ mod test {
 		let subpath = bounding_box.region_bezier_paths().next().unwrap().1;
 		assert_eq!(&subpath.anchors()[..4], &[DVec2::NEG_ONE, DVec2::new(1., -1.), DVec2::ONE, DVec2::new(-1., 1.),]);
 	}
+	#[test]
+	fn gradient_stroke() {
+		let gradient = Gradient::new(DVec2::ZERO, Color::BLACK, DVec2::X, Color::WHITE, DAffine2::IDENTITY, GradientType::Linear);
+		let stroked = SetStrokeNode {
+			color: ClonedNode::new(Some(Color::BLACK)),
+			weight: ClonedNode::new(2.),
+			dash_lengths: ClonedNode::new(Vec::new()),
+			dash_offset: ClonedNode::new(0.),
+			line_cap: ClonedNode::new(super::super::style::LineCap::Butt),
+			line_join: ClonedNode::new(super::super::style::LineJoin::Miter),
+			miter_limit: ClonedNode::new(4.),
+			gradient: ClonedNode::new(Some(gradient)),
+		}
+		.eval(VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::ONE)));
+
+		let mut svg_defs = String::new();
+		let bounds = [DVec2::ZERO, DVec2::ONE];
+		let attributes = stroked.style.render(super::super::style::ViewMode::Normal, &mut svg_defs, DAffine2::IDENTITY, bounds, bounds);
+		assert!(svg_defs.starts_with("<linearGradient"));
+		assert!(attributes.contains(r#" stroke="url('#"#));
+		assert!(!attributes.contains("stroke-opacity"));
+	}
+	#[test]
+	fn conic_and_diamond_fill() {
+		let fill = |gradient_type| {
+			SetFillNode {
+				fill_type: ClonedNode::new(FillType::Gradient),
+				solid_color: ClonedNode::new(None),
+				gradient_type: ClonedNode::new(gradient_type),
+				start: ClonedNode::new(DVec2::splat(0.5)),
+				end: ClonedNode::new(DVec2::new(1., 0.5)),
+				transform: ClonedNode::new(DAffine2::IDENTITY),
+				positions: ClonedNode::new(vec![(0., Color::BLACK), (1., Color::WHITE)]),
+				spread_method: ClonedNode::new(GradientSpreadMethod::Reflect),
+			}
+			.eval(VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::splat(10.))))
+		};
+		let bounds = [DVec2::ZERO, DVec2::splat(10.)];
+
+		// SVG has neither, so both are drawn within a pattern
+		let mut svg_defs = String::new();
+		let attributes = fill(GradientType::Conic)
+			.style
+			.render(super::super::style::ViewMode::Normal, &mut svg_defs, DAffine2::IDENTITY, bounds, bounds);
+		assert!(svg_defs.starts_with("<pattern"));
+		assert!(attributes.contains(r#" fill="url('#"#));
+
+		let mut svg_defs = String::new();
+		fill(GradientType::Diamond)
+			.style
+			.render(super::super::style::ViewMode::Normal, &mut svg_defs, DAffine2::IDENTITY, bounds, bounds);
+		assert_eq!(svg_defs.matches(r#"<linearGradient"#).count(), 4);
+		assert_eq!(svg_defs.matches(r#"spreadMethod="reflect""#).count(), 4);
+		assert!(svg_defs.contains("<pattern"));
+
+		let Fill::Gradient(gradient) = fill(GradientType::Linear).style.fill().clone() else {
+			panic!("Expected a gradient fill");
+		};
+		assert_eq!(gradient.sample(0.25), Color::BLACK.lerp(&Color::WHITE, 0.25));
+		assert_eq!(gradient.sample(1.25), Color::BLACK.lerp(&Color::WHITE, 0.75));
+	}
 	#[tokio::test]
 	async fn copy_to_points() {
 		let points = VectorData::from_subpath(Subpath::new_rect(DVec2::NEG_ONE * 10., DVec2::ONE * 10.));
@@ -710,4 +864,58 @@ mod test {
 			vec![DVec2::new(-25., -50.), DVec2::new(50., -25.), DVec2::new(25., 50.), DVec2::new(-50., 25.)]
 		);
 	}
+
+	#[tokio::test]
+	async fn boolean_operation() {
+		let lower = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::ONE * 100.));
+		let mut upper = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::ONE * 100.));
+		upper.transform = DAffine2::from_translation(DVec2::ONE * 50.);
+		let boolean_operation = |operation| BooleanOperationNode {
+			lower_vector_data: CullNode::new(FutureWrapperNode(ClonedNode(lower.clone()))),
+			upper_vector_data: CullNode::new(FutureWrapperNode(ClonedNode(upper.clone()))),
+			operation: FutureWrapperNode(ClonedNode(operation)),
+		};
+
+		let union = boolean_operation(BooleanOperation::Union).eval(Footprint::default()).await;
+		assert_eq!(union.stroke_bezier_paths().count(), 1);
+		assert_eq!(union.point_domain.positions().len(), 8);
+
+		let intersect = boolean_operation(BooleanOperation::Intersect).eval(Footprint::default()).await;
+		let [min, max] = intersect.bounding_box().unwrap();
+		assert!(
+			min.abs_diff_eq(DVec2::ONE * 50., 1e-6) && max.abs_diff_eq(DVec2::ONE * 100., 1e-6),
+			"Expected the overlapping square, found {min} to {max}"
+		);
+	}
+
+	#[test]
+	fn offset_path() {
+		// A square with a square hole in the middle
+		let mut square = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::ONE * 30.));
+		square.append_subpath(Subpath::new_rect(DVec2::ONE * 10., DVec2::ONE * 20.).reverse());
+		let offset = |distance: f64| {
+			OffsetPathNode {
+				distance: ClonedNode(distance),
+				line_join: ClonedNode(LineJoin::Miter),
+				miter_limit: ClonedNode(4.),
+			}
+			.eval(square.clone())
+		};
+		let bounds = |vector_data: &VectorData| vector_data.stroke_bezier_paths().map(|subpath| subpath.bounding_box().unwrap()).collect::<Vec<_>>();
+		let has_bounds = |all_bounds: &[[DVec2; 2]], min: f64, max: f64| all_bounds.iter().any(|[a, b]| a.abs_diff_eq(DVec2::splat(min), 1e-6) && b.abs_diff_eq(DVec2::splat(max), 1e-6));
+
+		// Growing moves the outside out and the hole in, with mitered corners
+		let grown = bounds(&offset(2.));
+		assert_eq!(grown.len(), 2);
+		assert!(has_bounds(&grown, -2., 32.) && has_bounds(&grown, 12., 18.), "{grown:?}");
+
+		let shrunk = bounds(&offset(-2.));
+		assert_eq!(shrunk.len(), 2);
+		assert!(has_bounds(&shrunk, 2., 28.) && has_bounds(&shrunk, 8., 22.), "{shrunk:?}");
+
+		// The hole closes up entirely
+		assert_eq!(bounds(&offset(6.)).len(), 1);
+		// The walls around the hole are too thin to survive
+		assert!(bounds(&offset(-6.)).is_empty());
+	}
 }
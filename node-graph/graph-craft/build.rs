@@ -0,0 +1,55 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The source of the nodes whose outputs the render cache keeps, relative to this crate, along with the versions of their dependencies.
+const NODE_SOURCES: &[&str] = &["src", "../gcore/src", "../gstd/src", "../../Cargo.lock"];
+
+fn main() {
+	// Execute a Git command for its trimmed stdout, or `None` if it fails for any of the possible reasons.
+	let git_command = |args: &[&str]| -> Option<String> {
+		let output = Command::new("git").args(args).output().ok()?;
+		let output = String::from_utf8(output.stdout).ok()?;
+		let output = output.trim();
+		(!output.is_empty()).then(|| output.to_string())
+	};
+
+	// The render cache keeps the outputs computed by each commit apart, since its nodes might compute different outputs than those of another.
+	// These set environment variables in the Cargo toolchain, which are accessed with the `env!("...")` macro in the codebase.
+	let commit_hash = git_command(&["rev-parse", "HEAD"]).unwrap_or_else(|| String::from("unknown"));
+	println!("cargo:rustc-env=GRAPHITE_GIT_COMMIT_HASH={commit_hash}");
+
+	// Rerun when a commit is checked out or made, which changes `HEAD` or the branch it points to
+	for git_path in ["HEAD", "packed-refs"] {
+		if let Some(path) = git_command(&["rev-parse", "--git-path", git_path]) {
+			println!("cargo:rerun-if-changed={path}");
+		}
+	}
+	if let Some(path) = git_command(&["symbolic-ref", "-q", "HEAD"]).and_then(|branch| git_command(&["rev-parse", "--git-path", &branch])) {
+		println!("cargo:rerun-if-changed={path}");
+	}
+
+	// Edits which aren't committed yet also change what the nodes compute, so the node source is hashed as well
+	let mut hasher = DefaultHasher::new();
+	for source in NODE_SOURCES {
+		println!("cargo:rerun-if-changed={source}");
+		hash_files(Path::new(source), &mut hasher);
+	}
+	println!("cargo:rustc-env=GRAPHITE_NODE_SOURCE_HASH={:016x}", hasher.finish());
+}
+
+/// Hashes the paths and contents of the file, or of the files within the directory in a stable order.
+fn hash_files(path: &Path, hasher: &mut DefaultHasher) {
+	if path.is_dir() {
+		let Ok(entries) = std::fs::read_dir(path) else { return };
+		let mut paths: Vec<PathBuf> = entries.filter_map(|entry| Some(entry.ok()?.path())).collect();
+		paths.sort();
+		for path in paths {
+			hash_files(&path, hasher);
+		}
+	} else if let Ok(contents) = std::fs::read(path) {
+		path.hash(hasher);
+		contents.hash(hasher);
+	}
+}
@@ -293,7 +293,7 @@ impl DocumentNode {
 					(node, ConstructionArgs::Nodes(vec![]))
 				}
 				NodeInput::Network(ty) => (ProtoNodeInput::ManualComposition(ty), ConstructionArgs::Nodes(vec![])),
-				NodeInput::Inline(inline) => (ProtoNodeInput::None, ConstructionArgs::Inline(inline)),
+				NodeInput::Inline(inline) => (ProtoNodeInput::None, ConstructionArgs::Inline(inline, vec![])),
 			}
 		};
 		assert!(!self.inputs.iter().any(|input| matches!(input, NodeInput::Network(_))), "received non resolved parameter");
@@ -306,13 +306,19 @@ impl DocumentNode {
 
 		// If we have one parameter of the type inline, set it as the construction args
 		if let &[NodeInput::Inline(ref inline)] = self.inputs.as_slice() {
-			args = ConstructionArgs::Inline(inline.clone());
+			args = ConstructionArgs::Inline(inline.clone(), vec![]);
 		}
-		if let ConstructionArgs::Nodes(nodes) = &mut args {
-			nodes.extend(self.inputs.iter().map(|input| match input {
+		match &mut args {
+			ConstructionArgs::Nodes(nodes) => nodes.extend(self.inputs.iter().map(|input| match input {
 				NodeInput::Node { node_id, lambda, .. } => (*node_id, *lambda),
 				_ => unreachable!(),
-			}));
+			})),
+			// The remaining inputs of an inline expression node are the inputs referenced by the expression
+			ConstructionArgs::Inline(_, nodes) => nodes.extend(self.inputs.iter().filter_map(|input| match input {
+				NodeInput::Node { node_id, .. } => Some(*node_id),
+				_ => None,
+			})),
+			_ => (),
 		}
 		ProtoNode {
 			identifier: fqn,
@@ -1004,6 +1010,39 @@ impl NodeNetwork {
 			}
 		}
 
+		// Replace inline expressions written on one of the node's inputs with nodes evaluating them, which are given the other inputs referenced by the expression
+		if node.inputs.len() > 1 {
+			for index in 0..node.inputs.len() {
+				let NodeInput::Inline(inline) = &node.inputs[index] else { continue };
+
+				// Inputs which can't be referenced are left out, which the executor reports as an error
+				let references = crate::inline_expression::Expression::parse(&inline.expr).map(|expression| expression.references()).unwrap_or_default();
+				let referenced_inputs = references
+					.into_iter()
+					.map(|reference| node.inputs.get(reference).filter(|input| matches!(input, NodeInput::Node { .. })).cloned())
+					.collect::<Option<Vec<_>>>()
+					.unwrap_or_default();
+
+				let inline_node_id = gen_id();
+				let merged_node_id = map_ids(id, inline_node_id);
+				let mut original_location = node.original_location.clone();
+				if let Some(path) = &mut original_location.path {
+					path.push(inline_node_id);
+				}
+				self.nodes.insert(
+					merged_node_id,
+					DocumentNode {
+						name: "Inline Expression".into(),
+						inputs: std::iter::once(NodeInput::Inline(inline.clone())).chain(referenced_inputs).collect(),
+						implementation: DocumentNodeImplementation::ProtoNode("graph_craft::inline_expression::InlineExpressionNode".into()),
+						original_location,
+						..Default::default()
+					},
+				);
+				node.inputs[index] = NodeInput::node(merged_node_id, 0);
+			}
+		}
+
 		if let DocumentNodeImplementation::Network(mut inner_network) = node.implementation {
 			// Connect all network inputs to either the parent network nodes, or newly created value nodes.
 			inner_network.map_ids(|inner_id| map_ids(id, inner_id));
@@ -1423,6 +1462,37 @@ mod test {
 		assert_eq!(proto_node, reference);
 	}
 
+	#[test]
+	fn flatten_inline_expression() {
+		let inline = InlineRust::new("i0 * 3".into(), concrete!(f64));
+		let mut network = NodeNetwork {
+			exports: vec![NodeOutput::new(NodeId(1), 0)],
+			nodes: [(
+				NodeId(1),
+				DocumentNode {
+					name: "Multiply".into(),
+					inputs: vec![NodeInput::value(TaggedValue::F64(2.), false), NodeInput::Inline(inline.clone())],
+					implementation: DocumentNodeImplementation::ProtoNode("graphene_core::ops::MultiplyNode".into()),
+					..Default::default()
+				},
+			)]
+			.into_iter()
+			.collect(),
+			..Default::default()
+		};
+		network.flatten_with_fns(NodeId(1), |self_id, inner_id| NodeId(self_id.0 * 10 + inner_id.0), gen_node_id);
+
+		let multiply_node = &network.nodes[&NodeId(1)];
+		let (NodeInput::Node { node_id: value_id, .. }, NodeInput::Node { node_id: inline_id, .. }) = (&multiply_node.inputs[0], &multiply_node.inputs[1]) else {
+			panic!("Expected the value and the expression to be replaced by nodes, found {:?}", multiply_node.inputs);
+		};
+		let inline_node = network.nodes[inline_id].clone();
+		assert_eq!(inline_node.name, "Inline Expression");
+
+		let proto_node = inline_node.resolve_proto_node();
+		assert_eq!(proto_node.construction_args, ConstructionArgs::Inline(inline, vec![*value_id]));
+	}
+
 	#[test]
 	fn resolve_flatten_add_as_proto_network() {
 		let construction_network = ProtoNetwork {
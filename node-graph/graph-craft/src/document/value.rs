@@ -52,13 +52,18 @@ pub enum TaggedValue {
 	CellularDistanceFunction(graphene_core::raster::CellularDistanceFunction),
 	CellularReturnType(graphene_core::raster::CellularReturnType),
 	DomainWarpType(graphene_core::raster::DomainWarpType),
+	ResampleFilter(graphene_core::raster::ResampleFilter),
 	RelativeAbsolute(graphene_core::raster::RelativeAbsolute),
 	SelectiveColorChoice(graphene_core::raster::SelectiveColorChoice),
 	LineCap(graphene_core::vector::style::LineCap),
 	LineJoin(graphene_core::vector::style::LineJoin),
+	BooleanOperation(bezier_rs::BooleanOperation),
+	TextAlignment(graphene_core::text::TextAlignment),
 	FillType(graphene_core::vector::style::FillType),
 	GradientType(graphene_core::vector::style::GradientType),
+	GradientSpreadMethod(graphene_core::vector::style::GradientSpreadMethod),
 	GradientPositions(Vec<(f64, graphene_core::Color)>),
+	OptionalGradient(Option<graphene_core::vector::style::Gradient>),
 	Quantization(graphene_core::quantization::QuantizationChannels),
 	OptionalColor(Option<graphene_core::raster::color::Color>),
 	ManipulatorGroupIds(Vec<graphene_core::uuid::ManipulatorGroupId>),
@@ -122,12 +127,16 @@ impl Hash for TaggedValue {
 			Self::CellularDistanceFunction(x) => x.hash(state),
 			Self::CellularReturnType(x) => x.hash(state),
 			Self::DomainWarpType(x) => x.hash(state),
+			Self::ResampleFilter(x) => x.hash(state),
 			Self::RelativeAbsolute(x) => x.hash(state),
 			Self::SelectiveColorChoice(x) => x.hash(state),
 			Self::LineCap(x) => x.hash(state),
 			Self::LineJoin(x) => x.hash(state),
+			Self::BooleanOperation(x) => x.hash(state),
+			Self::TextAlignment(x) => x.hash(state),
 			Self::FillType(x) => x.hash(state),
 			Self::GradientType(x) => x.hash(state),
+			Self::GradientSpreadMethod(x) => x.hash(state),
 			Self::GradientPositions(x) => {
 				x.len().hash(state);
 				for (position, color) in x {
@@ -136,6 +145,7 @@ impl Hash for TaggedValue {
 				}
 			}
 			Self::Quantization(x) => x.hash(state),
+			Self::OptionalGradient(x) => x.hash(state),
 			Self::OptionalColor(x) => x.hash(state),
 			Self::ManipulatorGroupIds(x) => x.hash(state),
 			Self::Font(x) => x.hash(state),
@@ -199,14 +209,19 @@ impl<'a> TaggedValue {
 			TaggedValue::CellularDistanceFunction(x) => Box::new(x),
 			TaggedValue::CellularReturnType(x) => Box::new(x),
 			TaggedValue::DomainWarpType(x) => Box::new(x),
+			TaggedValue::ResampleFilter(x) => Box::new(x),
 			TaggedValue::RelativeAbsolute(x) => Box::new(x),
 			TaggedValue::SelectiveColorChoice(x) => Box::new(x),
 			TaggedValue::LineCap(x) => Box::new(x),
 			TaggedValue::LineJoin(x) => Box::new(x),
+			TaggedValue::BooleanOperation(x) => Box::new(x),
+			TaggedValue::TextAlignment(x) => Box::new(x),
 			TaggedValue::FillType(x) => Box::new(x),
 			TaggedValue::GradientType(x) => Box::new(x),
+			TaggedValue::GradientSpreadMethod(x) => Box::new(x),
 			TaggedValue::GradientPositions(x) => Box::new(x),
 			TaggedValue::Quantization(x) => Box::new(x),
+			TaggedValue::OptionalGradient(x) => Box::new(x),
 			TaggedValue::OptionalColor(x) => Box::new(x),
 			TaggedValue::ManipulatorGroupIds(x) => Box::new(x),
 			TaggedValue::Font(x) => Box::new(x),
@@ -278,14 +293,19 @@ impl<'a> TaggedValue {
 			TaggedValue::CellularDistanceFunction(_) => concrete!(graphene_core::raster::CellularDistanceFunction),
 			TaggedValue::CellularReturnType(_) => concrete!(graphene_core::raster::CellularReturnType),
 			TaggedValue::DomainWarpType(_) => concrete!(graphene_core::raster::DomainWarpType),
+			TaggedValue::ResampleFilter(_) => concrete!(graphene_core::raster::ResampleFilter),
 			TaggedValue::RelativeAbsolute(_) => concrete!(graphene_core::raster::RelativeAbsolute),
 			TaggedValue::SelectiveColorChoice(_) => concrete!(graphene_core::raster::SelectiveColorChoice),
 			TaggedValue::LineCap(_) => concrete!(graphene_core::vector::style::LineCap),
 			TaggedValue::LineJoin(_) => concrete!(graphene_core::vector::style::LineJoin),
+			TaggedValue::BooleanOperation(_) => concrete!(bezier_rs::BooleanOperation),
+			TaggedValue::TextAlignment(_) => concrete!(graphene_core::text::TextAlignment),
 			TaggedValue::FillType(_) => concrete!(graphene_core::vector::style::FillType),
 			TaggedValue::GradientType(_) => concrete!(graphene_core::vector::style::GradientType),
+			TaggedValue::GradientSpreadMethod(_) => concrete!(graphene_core::vector::style::GradientSpreadMethod),
 			TaggedValue::GradientPositions(_) => concrete!(Vec<(f64, graphene_core::Color)>),
 			TaggedValue::Quantization(_) => concrete!(graphene_core::quantization::QuantizationChannels),
+			TaggedValue::OptionalGradient(_) => concrete!(Option<graphene_core::vector::style::Gradient>),
 			TaggedValue::OptionalColor(_) => concrete!(Option<graphene_core::Color>),
 			TaggedValue::ManipulatorGroupIds(_) => concrete!(Vec<graphene_core::uuid::ManipulatorGroupId>),
 			TaggedValue::Font(_) => concrete!(graphene_core::text::Font),
@@ -344,14 +364,19 @@ impl<'a> TaggedValue {
 			x if x == TypeId::of::<graphene_core::raster::CellularDistanceFunction>() => Ok(TaggedValue::CellularDistanceFunction(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::CellularReturnType>() => Ok(TaggedValue::CellularReturnType(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::DomainWarpType>() => Ok(TaggedValue::DomainWarpType(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::raster::ResampleFilter>() => Ok(TaggedValue::ResampleFilter(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::RelativeAbsolute>() => Ok(TaggedValue::RelativeAbsolute(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::SelectiveColorChoice>() => Ok(TaggedValue::SelectiveColorChoice(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::style::LineCap>() => Ok(TaggedValue::LineCap(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::style::LineJoin>() => Ok(TaggedValue::LineJoin(*downcast(input).unwrap())),
+			x if x == TypeId::of::<bezier_rs::BooleanOperation>() => Ok(TaggedValue::BooleanOperation(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::text::TextAlignment>() => Ok(TaggedValue::TextAlignment(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::style::FillType>() => Ok(TaggedValue::FillType(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::style::GradientType>() => Ok(TaggedValue::GradientType(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::vector::style::GradientSpreadMethod>() => Ok(TaggedValue::GradientSpreadMethod(*downcast(input).unwrap())),
 			x if x == TypeId::of::<Vec<(f64, graphene_core::Color)>>() => Ok(TaggedValue::GradientPositions(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::quantization::QuantizationChannels>() => Ok(TaggedValue::Quantization(*downcast(input).unwrap())),
+			x if x == TypeId::of::<Option<graphene_core::vector::style::Gradient>>() => Ok(TaggedValue::OptionalGradient(*downcast(input).unwrap())),
 			x if x == TypeId::of::<Option<graphene_core::Color>>() => Ok(TaggedValue::OptionalColor(*downcast(input).unwrap())),
 			x if x == TypeId::of::<Vec<graphene_core::uuid::ManipulatorGroupId>>() => Ok(TaggedValue::ManipulatorGroupIds(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::text::Font>() => Ok(TaggedValue::Font(*downcast(input).unwrap())),
@@ -397,4 +422,8 @@ pub enum RenderOutput {
 	CanvasFrame(graphene_core::SurfaceFrame),
 	Svg(String),
 	Image(Vec<u8>),
+	/// A PDF document, which is written as ASCII so it can be handled like the SVG output
+	Pdf(String),
+	/// Why the output couldn't be rendered in the requested format, such as artwork a PDF can't represent
+	Error(String),
 }
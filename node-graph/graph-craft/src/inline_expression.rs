@@ -0,0 +1,675 @@
+//! A small expression language for formulas written inline on a node's input, so simple math doesn't need chains of math nodes.
+//!
+//! Expressions work with numbers, 2D vectors and booleans:
+//! - Arithmetic with `+`, `-`, `*`, `/`, `%` and `^`, which applies per component to vectors (mixing a vector with a number applies the number to both components)
+//! - Comparisons with `==`, `!=`, `<`, `<=`, `>` and `>=`, combined with `&&`, `||` and `!`
+//! - The `x` and `y` components of a vector, such as `i1.x`
+//! - The constants `pi`, `tau`, `true` and `false`
+//! - The functions listed in [`Function`], such as `vec2(x, y)`, `length(v)` or `if(condition, a, b)`
+//! - References to the values of the node's other inputs by their index, where `i0` is the primary input
+
+use crate::document::value::TaggedValue;
+use crate::document::InlineRust;
+use crate::proto::{Any as DAny, FutureAny, SharedNodeContainer};
+
+use graphene_core::{Node, Type};
+
+use glam::{DVec2, IVec2, UVec2};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+	Number(f64),
+	Vector(DVec2),
+	Bool(bool),
+}
+
+impl Value {
+	pub fn from_tagged_value(value: &TaggedValue) -> Option<Self> {
+		Some(match *value {
+			TaggedValue::U32(x) => Value::Number(x as f64),
+			TaggedValue::U64(x) => Value::Number(x as f64),
+			TaggedValue::F32(x) => Value::Number(x as f64),
+			TaggedValue::F64(x) => Value::Number(x),
+			TaggedValue::Bool(x) => Value::Bool(x),
+			TaggedValue::DVec2(x) => Value::Vector(x),
+			TaggedValue::UVec2(x) => Value::Vector(x.as_dvec2()),
+			TaggedValue::IVec2(x) => Value::Vector(x.as_dvec2()),
+			_ => return None,
+		})
+	}
+
+	/// Converts the value into the type of the input it is written on, rounding numbers for integer types.
+	pub fn to_tagged_value(self, ty: &Type) -> Result<TaggedValue, String> {
+		Ok(match self {
+			Value::Number(x) if *ty == concrete!(f64) => TaggedValue::F64(x),
+			Value::Number(x) if *ty == concrete!(f32) => TaggedValue::F32(x as f32),
+			Value::Number(x) if *ty == concrete!(u32) => TaggedValue::U32(x.round() as u32),
+			Value::Number(x) if *ty == concrete!(u64) => TaggedValue::U64(x.round() as u64),
+			Value::Vector(x) if *ty == concrete!(DVec2) => TaggedValue::DVec2(x),
+			Value::Vector(x) if *ty == concrete!(UVec2) => TaggedValue::UVec2(x.round().as_uvec2()),
+			Value::Vector(x) if *ty == concrete!(IVec2) => TaggedValue::IVec2(x.round().as_ivec2()),
+			Value::Bool(x) if *ty == concrete!(bool) => TaggedValue::Bool(x),
+			_ => return Err(format!("The expression evaluates to a {} but the input expects {ty}", self.type_name())),
+		})
+	}
+
+	/// The value used in place of the result when an expression of the given type fails to evaluate.
+	pub fn default_for(ty: &Type) -> Option<Self> {
+		let defaults = [Value::Number(0.), Value::Vector(DVec2::ZERO), Value::Bool(false)];
+		defaults.into_iter().find(|value| value.to_tagged_value(ty).is_ok())
+	}
+
+	fn type_name(&self) -> &'static str {
+		match self {
+			Value::Number(_) => "number",
+			Value::Vector(_) => "vector",
+			Value::Bool(_) => "bool",
+		}
+	}
+
+	fn as_number(self, context: &str) -> Result<f64, String> {
+		match self {
+			Value::Number(x) => Ok(x),
+			_ => Err(format!("{context} expects a number but found a {}", self.type_name())),
+		}
+	}
+
+	fn as_vector(self, context: &str) -> Result<DVec2, String> {
+		match self {
+			Value::Vector(x) => Ok(x),
+			_ => Err(format!("{context} expects a vector but found a {}", self.type_name())),
+		}
+	}
+
+	fn as_bool(self, context: &str) -> Result<bool, String> {
+		match self {
+			Value::Bool(x) => Ok(x),
+			_ => Err(format!("{context} expects a bool but found a {}", self.type_name())),
+		}
+	}
+
+	/// Applies the function to a number or to each component of a vector.
+	fn map(self, context: &str, f: impl Fn(f64) -> f64) -> Result<Value, String> {
+		match self {
+			Value::Number(x) => Ok(Value::Number(f(x))),
+			Value::Vector(x) => Ok(Value::Vector(DVec2::new(f(x.x), f(x.y)))),
+			Value::Bool(_) => Err(format!("{context} can't be applied to a bool")),
+		}
+	}
+
+	/// Combines two numbers, or two vectors per component, using the number on both components when it is paired with a vector.
+	fn zip(self, other: Value, context: &str, f: impl Fn(f64, f64) -> f64) -> Result<Value, String> {
+		match (self, other) {
+			(Value::Number(a), Value::Number(b)) => Ok(Value::Number(f(a, b))),
+			(Value::Vector(a), Value::Vector(b)) => Ok(Value::Vector(DVec2::new(f(a.x, b.x), f(a.y, b.y)))),
+			(Value::Vector(a), Value::Number(b)) => Ok(Value::Vector(DVec2::new(f(a.x, b), f(a.y, b)))),
+			(Value::Number(a), Value::Vector(b)) => Ok(Value::Vector(DVec2::new(f(a, b.x), f(a, b.y)))),
+			_ => Err(format!("{context} can't be applied to a {} and a {}", self.type_name(), other.type_name())),
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum UnaryOperator {
+	Negate,
+	Not,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BinaryOperator {
+	Add,
+	Subtract,
+	Multiply,
+	Divide,
+	Modulo,
+	Power,
+	Equal,
+	NotEqual,
+	Less,
+	LessOrEqual,
+	Greater,
+	GreaterOrEqual,
+	And,
+	Or,
+}
+
+impl BinaryOperator {
+	fn symbol(self) -> &'static str {
+		match self {
+			BinaryOperator::Add => "+",
+			BinaryOperator::Subtract => "-",
+			BinaryOperator::Multiply => "*",
+			BinaryOperator::Divide => "/",
+			BinaryOperator::Modulo => "%",
+			BinaryOperator::Power => "^",
+			BinaryOperator::Equal => "==",
+			BinaryOperator::NotEqual => "!=",
+			BinaryOperator::Less => "<",
+			BinaryOperator::LessOrEqual => "<=",
+			BinaryOperator::Greater => ">",
+			BinaryOperator::GreaterOrEqual => ">=",
+			BinaryOperator::And => "&&",
+			BinaryOperator::Or => "||",
+		}
+	}
+
+	fn apply(self, a: Value, b: Value) -> Result<Value, String> {
+		let context = format!("`{}`", self.symbol());
+		let compare = |ordering: fn(f64, f64) -> bool| -> Result<Value, String> { Ok(Value::Bool(ordering(a.as_number(&context)?, b.as_number(&context)?))) };
+		match self {
+			BinaryOperator::Add => a.zip(b, &context, |a, b| a + b),
+			BinaryOperator::Subtract => a.zip(b, &context, |a, b| a - b),
+			BinaryOperator::Multiply => a.zip(b, &context, |a, b| a * b),
+			BinaryOperator::Divide => a.zip(b, &context, |a, b| a / b),
+			BinaryOperator::Modulo => a.zip(b, &context, |a, b| a % b),
+			BinaryOperator::Power => a.zip(b, &context, f64::powf),
+			BinaryOperator::Equal | BinaryOperator::NotEqual => {
+				if core::mem::discriminant(&a) != core::mem::discriminant(&b) {
+					return Err(format!("{context} can't compare a {} with a {}", a.type_name(), b.type_name()));
+				}
+				Ok(Value::Bool((a == b) == (self == BinaryOperator::Equal)))
+			}
+			BinaryOperator::Less => compare(|a, b| a < b),
+			BinaryOperator::LessOrEqual => compare(|a, b| a <= b),
+			BinaryOperator::Greater => compare(|a, b| a > b),
+			BinaryOperator::GreaterOrEqual => compare(|a, b| a >= b),
+			BinaryOperator::And => Ok(Value::Bool(a.as_bool(&context)? && b.as_bool(&context)?)),
+			BinaryOperator::Or => Ok(Value::Bool(a.as_bool(&context)? || b.as_bool(&context)?)),
+		}
+	}
+}
+
+/// The functions which can be called from an expression, such as `clamp(i1, 0, 1)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Function {
+	/// `vec2(x, y)` builds a vector from two numbers
+	Vec2,
+	Abs,
+	Floor,
+	Ceil,
+	Round,
+	Sqrt,
+	Sin,
+	Cos,
+	Tan,
+	/// `atan2(y, x)` is the angle of the point from the positive x axis, in radians
+	Atan2,
+	Min,
+	Max,
+	/// `clamp(value, min, max)`
+	Clamp,
+	/// `lerp(a, b, t)` blends from `a` at `t = 0` to `b` at `t = 1`
+	Lerp,
+	Length,
+	Normalize,
+	Dot,
+	Distance,
+	/// `if(condition, a, b)` is `a` when the condition is true and `b` otherwise
+	If,
+}
+
+impl Function {
+	fn from_name(name: &str) -> Option<Self> {
+		Some(match name {
+			"vec2" => Function::Vec2,
+			"abs" => Function::Abs,
+			"floor" => Function::Floor,
+			"ceil" => Function::Ceil,
+			"round" => Function::Round,
+			"sqrt" => Function::Sqrt,
+			"sin" => Function::Sin,
+			"cos" => Function::Cos,
+			"tan" => Function::Tan,
+			"atan2" => Function::Atan2,
+			"min" => Function::Min,
+			"max" => Function::Max,
+			"clamp" => Function::Clamp,
+			"lerp" => Function::Lerp,
+			"length" => Function::Length,
+			"normalize" => Function::Normalize,
+			"dot" => Function::Dot,
+			"distance" => Function::Distance,
+			"if" => Function::If,
+			_ => return None,
+		})
+	}
+
+	fn arity(self) -> usize {
+		match self {
+			Function::Abs | Function::Floor | Function::Ceil | Function::Round | Function::Sqrt | Function::Sin | Function::Cos | Function::Tan | Function::Length | Function::Normalize => 1,
+			Function::Vec2 | Function::Atan2 | Function::Min | Function::Max | Function::Dot | Function::Distance => 2,
+			Function::Clamp | Function::Lerp | Function::If => 3,
+		}
+	}
+
+	fn call(self, name: &str, arguments: &[Value]) -> Result<Value, String> {
+		let context = format!("`{name}`");
+		let context = context.as_str();
+		Ok(match (self, arguments) {
+			(Function::Vec2, &[x, y]) => Value::Vector(DVec2::new(x.as_number(context)?, y.as_number(context)?)),
+			(Function::Abs, &[x]) => x.map(context, f64::abs)?,
+			(Function::Floor, &[x]) => x.map(context, f64::floor)?,
+			(Function::Ceil, &[x]) => x.map(context, f64::ceil)?,
+			(Function::Round, &[x]) => x.map(context, f64::round)?,
+			(Function::Sqrt, &[x]) => x.map(context, f64::sqrt)?,
+			(Function::Sin, &[x]) => x.map(context, f64::sin)?,
+			(Function::Cos, &[x]) => x.map(context, f64::cos)?,
+			(Function::Tan, &[x]) => x.map(context, f64::tan)?,
+			(Function::Atan2, &[y, x]) => Value::Number(y.as_number(context)?.atan2(x.as_number(context)?)),
+			(Function::Min, &[a, b]) => a.zip(b, context, f64::min)?,
+			(Function::Max, &[a, b]) => a.zip(b, context, f64::max)?,
+			(Function::Clamp, &[x, min, max]) => x.zip(min, context, f64::max)?.zip(max, context, f64::min)?,
+			(Function::Lerp, &[a, b, t]) => {
+				let difference = b.zip(a, context, |b, a| b - a)?;
+				a.zip(difference.zip(t, context, |difference, t| difference * t)?, context, |a, offset| a + offset)?
+			}
+			(Function::Length, &[v]) => Value::Number(v.as_vector(context)?.length()),
+			(Function::Normalize, &[v]) => Value::Vector(v.as_vector(context)?.normalize_or_zero()),
+			(Function::Dot, &[a, b]) => Value::Number(a.as_vector(context)?.dot(b.as_vector(context)?)),
+			(Function::Distance, &[a, b]) => Value::Number(a.as_vector(context)?.distance(b.as_vector(context)?)),
+			(Function::If, &[condition, a, b]) => {
+				if condition.as_bool(context)? {
+					a
+				} else {
+					b
+				}
+			}
+			_ => return Err(format!("{context} takes {} arguments but {} were given", self.arity(), arguments.len())),
+		})
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+	Literal(Value),
+	/// The value of the node input with this index
+	Input(usize),
+	Unary(UnaryOperator, Box<Expr>),
+	Binary(BinaryOperator, Box<Expr>, Box<Expr>),
+	/// The `x` (`false`) or `y` (`true`) component of a vector
+	Component(Box<Expr>, bool),
+	Call(Function, String, Vec<Expr>),
+}
+
+impl Expr {
+	fn collect_references(&self, references: &mut Vec<usize>) {
+		match self {
+			Expr::Literal(_) => {}
+			Expr::Input(index) => references.push(*index),
+			Expr::Unary(_, operand) | Expr::Component(operand, _) => operand.collect_references(references),
+			Expr::Binary(_, a, b) => {
+				a.collect_references(references);
+				b.collect_references(references);
+			}
+			Expr::Call(_, _, arguments) => arguments.iter().for_each(|argument| argument.collect_references(references)),
+		}
+	}
+
+	fn evaluate(&self, inputs: &HashMap<usize, Value>) -> Result<Value, String> {
+		match self {
+			Expr::Literal(value) => Ok(*value),
+			Expr::Input(index) => inputs.get(index).copied().ok_or_else(|| format!("Input `i{index}` doesn't provide a number, vector or bool")),
+			Expr::Unary(UnaryOperator::Negate, operand) => operand.evaluate(inputs)?.map("`-`", |x| -x),
+			Expr::Unary(UnaryOperator::Not, operand) => Ok(Value::Bool(!operand.evaluate(inputs)?.as_bool("`!`")?)),
+			Expr::Binary(operator, a, b) => operator.apply(a.evaluate(inputs)?, b.evaluate(inputs)?),
+			Expr::Component(operand, y) => {
+				let vector = operand.evaluate(inputs)?.as_vector(if *y { "`.y`" } else { "`.x`" })?;
+				Ok(Value::Number(if *y { vector.y } else { vector.x }))
+			}
+			Expr::Call(function, name, arguments) => {
+				let arguments = arguments.iter().map(|argument| argument.evaluate(inputs)).collect::<Result<Vec<_>, _>>()?;
+				function.call(name, &arguments)
+			}
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+	Number(f64),
+	Identifier(String),
+	Symbol(&'static str),
+}
+
+const SYMBOLS: [&str; 22] = ["==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "%", "^", "<", ">", "!", "(", ")", ",", ".", "=", "&", "|"];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+	let mut tokens = Vec::new();
+	let mut rest = source.trim_start();
+	while let Some(character) = rest.chars().next() {
+		let starts_number = character.is_ascii_digit() || (character == '.' && rest[1..].starts_with(|c: char| c.is_ascii_digit()));
+		let length = if starts_number {
+			let mut length = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+			// Scientific notation, such as `1e-3`
+			if rest[length..].starts_with(['e', 'E']) {
+				let exponent = rest[length + 1..].strip_prefix(['+', '-']).unwrap_or(&rest[length + 1..]);
+				let digits = exponent.find(|c: char| !c.is_ascii_digit()).unwrap_or(exponent.len());
+				if digits > 0 {
+					length = rest.len() - exponent.len() + digits;
+				}
+			}
+			let number = rest[..length].parse().map_err(|_| format!("`{}` is not a valid number", &rest[..length]))?;
+			tokens.push(Token::Number(number));
+			length
+		} else if character.is_alphabetic() || character == '_' {
+			let length = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+			tokens.push(Token::Identifier(rest[..length].to_string()));
+			length
+		} else {
+			let Some(symbol) = SYMBOLS.into_iter().find(|symbol| rest.starts_with(symbol)) else {
+				return Err(format!("Unexpected character `{character}`"));
+			};
+			if symbol == "=" || symbol == "&" || symbol == "|" {
+				return Err(format!("Unexpected `{symbol}`, did you mean `{symbol}{symbol}`?"));
+			}
+			tokens.push(Token::Symbol(symbol));
+			symbol.len()
+		};
+		rest = rest[length..].trim_start();
+	}
+	Ok(tokens)
+}
+
+/// A recursive descent parser, with one method per level of operator precedence from the loosest to the tightest binding.
+struct Parser {
+	tokens: Vec<Token>,
+	position: usize,
+}
+
+impl Parser {
+	fn peek(&self) -> Option<&Token> {
+		self.tokens.get(self.position)
+	}
+
+	fn eat(&mut self, symbol: &str) -> bool {
+		let matches = matches!(self.peek(), Some(Token::Symbol(next)) if *next == symbol);
+		if matches {
+			self.position += 1;
+		}
+		matches
+	}
+
+	fn expect(&mut self, symbol: &str) -> Result<(), String> {
+		if self.eat(symbol) {
+			return Ok(());
+		}
+		match self.peek() {
+			Some(token) => Err(format!("Expected `{symbol}` but found {}", describe(token))),
+			None => Err(format!("Expected `{symbol}` but the expression ended")),
+		}
+	}
+
+	/// Parses a chain of left-associative binary operators, with operands parsed by the next level of precedence.
+	fn binary(&mut self, operators: &[(&str, BinaryOperator)], operand: fn(&mut Self) -> Result<Expr, String>) -> Result<Expr, String> {
+		let mut expression = operand(self)?;
+		'chain: loop {
+			for &(symbol, operator) in operators {
+				if self.eat(symbol) {
+					expression = Expr::Binary(operator, Box::new(expression), Box::new(operand(self)?));
+					continue 'chain;
+				}
+			}
+			return Ok(expression);
+		}
+	}
+
+	fn or(&mut self) -> Result<Expr, String> {
+		self.binary(&[("||", BinaryOperator::Or)], Self::and)
+	}
+
+	fn and(&mut self) -> Result<Expr, String> {
+		self.binary(&[("&&", BinaryOperator::And)], Self::equality)
+	}
+
+	fn equality(&mut self) -> Result<Expr, String> {
+		self.binary(&[("==", BinaryOperator::Equal), ("!=", BinaryOperator::NotEqual)], Self::comparison)
+	}
+
+	fn comparison(&mut self) -> Result<Expr, String> {
+		let operators = [
+			("<=", BinaryOperator::LessOrEqual),
+			(">=", BinaryOperator::GreaterOrEqual),
+			("<", BinaryOperator::Less),
+			(">", BinaryOperator::Greater),
+		];
+		self.binary(&operators, Self::sum)
+	}
+
+	fn sum(&mut self) -> Result<Expr, String> {
+		self.binary(&[("+", BinaryOperator::Add), ("-", BinaryOperator::Subtract)], Self::product)
+	}
+
+	fn product(&mut self) -> Result<Expr, String> {
+		self.binary(&[("*", BinaryOperator::Multiply), ("/", BinaryOperator::Divide), ("%", BinaryOperator::Modulo)], Self::unary)
+	}
+
+	fn unary(&mut self) -> Result<Expr, String> {
+		if self.eat("-") {
+			return Ok(Expr::Unary(UnaryOperator::Negate, Box::new(self.unary()?)));
+		}
+		if self.eat("!") {
+			return Ok(Expr::Unary(UnaryOperator::Not, Box::new(self.unary()?)));
+		}
+		self.power()
+	}
+
+	/// Exponentiation binds tighter than negation (so `-2^2` is `-4`) and is right-associative.
+	fn power(&mut self) -> Result<Expr, String> {
+		let base = self.postfix()?;
+		if self.eat("^") {
+			return Ok(Expr::Binary(BinaryOperator::Power, Box::new(base), Box::new(self.unary()?)));
+		}
+		Ok(base)
+	}
+
+	fn postfix(&mut self) -> Result<Expr, String> {
+		let mut expression = self.primary()?;
+		while self.eat(".") {
+			let y = match self.peek() {
+				Some(Token::Identifier(name)) if name == "x" => false,
+				Some(Token::Identifier(name)) if name == "y" => true,
+				_ => return Err("Only the `.x` and `.y` components of a vector can be accessed".to_string()),
+			};
+			self.position += 1;
+			expression = Expr::Component(Box::new(expression), y);
+		}
+		Ok(expression)
+	}
+
+	fn primary(&mut self) -> Result<Expr, String> {
+		let Some(token) = self.peek().cloned() else {
+			return Err("The expression ended unexpectedly".to_string());
+		};
+		self.position += 1;
+		match token {
+			Token::Number(number) => Ok(Expr::Literal(Value::Number(number))),
+			Token::Symbol("(") => {
+				let expression = self.or()?;
+				self.expect(")")?;
+				Ok(expression)
+			}
+			Token::Symbol(_) => Err(format!("Unexpected {}", describe(&token))),
+			Token::Identifier(name) => {
+				if let Some(index) = name.strip_prefix('i').filter(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit())) {
+					let index = index.parse().map_err(|_| format!("`{name}` is not a valid input"))?;
+					return Ok(Expr::Input(index));
+				}
+				match name.as_str() {
+					"pi" => return Ok(Expr::Literal(Value::Number(core::f64::consts::PI))),
+					"tau" => return Ok(Expr::Literal(Value::Number(core::f64::consts::TAU))),
+					"true" => return Ok(Expr::Literal(Value::Bool(true))),
+					"false" => return Ok(Expr::Literal(Value::Bool(false))),
+					_ => {}
+				}
+				let function = Function::from_name(&name).ok_or_else(|| format!("Unknown name `{name}`"))?;
+				self.expect("(")?;
+				let mut arguments = Vec::new();
+				if !self.eat(")") {
+					loop {
+						arguments.push(self.or()?);
+						if self.eat(")") {
+							break;
+						}
+						self.expect(",")?;
+					}
+				}
+				if arguments.len() != function.arity() {
+					return Err(format!("`{name}` takes {} arguments but {} were given", function.arity(), arguments.len()));
+				}
+				Ok(Expr::Call(function, name, arguments))
+			}
+		}
+	}
+}
+
+fn describe(token: &Token) -> String {
+	match token {
+		Token::Number(number) => format!("the number `{number}`"),
+		Token::Identifier(name) => format!("`{name}`"),
+		Token::Symbol(symbol) => format!("`{symbol}`"),
+	}
+}
+
+/// A parsed inline expression, ready to be evaluated with the values of the inputs it references.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+	root: Expr,
+}
+
+impl Expression {
+	pub fn parse(source: &str) -> Result<Self, String> {
+		let mut parser = Parser {
+			tokens: tokenize(source)?,
+			position: 0,
+		};
+		let root = parser.or()?;
+		if let Some(token) = parser.peek() {
+			return Err(format!("Unexpected {} after the end of the expression", describe(token)));
+		}
+		Ok(Self { root })
+	}
+
+	/// The indices of the inputs referenced by the expression, in ascending order and without duplicates.
+	pub fn references(&self) -> Vec<usize> {
+		let mut references = Vec::new();
+		self.root.collect_references(&mut references);
+		references.sort_unstable();
+		references.dedup();
+		references
+	}
+
+	pub fn evaluate(&self, inputs: &HashMap<usize, Value>) -> Result<Value, String> {
+		self.root.evaluate(inputs)
+	}
+}
+
+/// Evaluates an inline expression, reading the inputs it references from the nodes they are connected to.
+pub struct InlineExpressionNode {
+	expression: Expression,
+	ty: Type,
+	/// The index of each referenced input along with the node providing its value
+	inputs: Vec<(usize, SharedNodeContainer)>,
+}
+
+impl InlineExpressionNode {
+	/// The `inputs` are the nodes connected to the inputs referenced by the expression, in the order of [`Expression::references`].
+	pub fn new(inline: &InlineRust, inputs: Vec<SharedNodeContainer>) -> Result<Self, String> {
+		let expression = Expression::parse(&inline.expr)?;
+		if Value::default_for(&inline.ty).is_none() {
+			return Err(format!("Expressions can't be written on an input of type {}", inline.ty));
+		}
+
+		let references = expression.references();
+		if references.len() != inputs.len() {
+			let references = references.iter().map(|index| format!("`i{index}`")).collect::<Vec<_>>().join(", ");
+			return Err(format!("The expression references inputs ({references}) which aren't all connected to a value"));
+		}
+
+		Ok(Self {
+			expression,
+			ty: inline.ty.clone(),
+			inputs: references.into_iter().zip(inputs).collect(),
+		})
+	}
+}
+
+impl<'input> Node<'input, DAny<'input>> for InlineExpressionNode {
+	type Output = FutureAny<'input>;
+
+	fn eval(&'input self, _: DAny<'input>) -> Self::Output {
+		Box::pin(async move {
+			let mut inputs = HashMap::new();
+			for (index, node) in &self.inputs {
+				let output = node.eval(Box::new(())).await;
+				if let Some(value) = TaggedValue::try_from_any(output).ok().as_ref().and_then(Value::from_tagged_value) {
+					inputs.insert(*index, value);
+				}
+			}
+
+			let result = self.expression.evaluate(&inputs).and_then(|value| value.to_tagged_value(&self.ty));
+			let value = result.unwrap_or_else(|error| {
+				warn!("Failed to evaluate the inline expression: {error}");
+				let default = Value::default_for(&self.ty).expect("The type of the expression is checked when the node is constructed");
+				default.to_tagged_value(&self.ty).unwrap()
+			});
+			value.to_any()
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn evaluate(source: &str, inputs: &[(usize, Value)]) -> Result<Value, String> {
+		Expression::parse(source)?.evaluate(&inputs.iter().copied().collect())
+	}
+
+	#[test]
+	fn arithmetic_and_precedence() {
+		assert_eq!(evaluate("1 + 2 * 3", &[]), Ok(Value::Number(7.)));
+		assert_eq!(evaluate("(1 + 2) * 3 % 4", &[]), Ok(Value::Number(1.)));
+		assert_eq!(evaluate("-2^2 + 2^3^2", &[]), Ok(Value::Number(-4. + 512.)));
+		assert_eq!(evaluate("1.5e1 / .5", &[]), Ok(Value::Number(30.)));
+		assert_eq!(evaluate("round(tau / pi)", &[]), Ok(Value::Number(2.)));
+	}
+
+	#[test]
+	fn vectors_and_inputs() {
+		let inputs = [(1, Value::Vector(DVec2::new(3., 4.))), (2, Value::Number(2.))];
+		assert_eq!(evaluate("i1 * i2 + vec2(1, 0)", &inputs), Ok(Value::Vector(DVec2::new(7., 8.))));
+		assert_eq!(evaluate("length(i1) + i1.y", &inputs), Ok(Value::Number(9.)));
+		assert_eq!(evaluate("lerp(vec2(0, 0), i1, 0.5)", &inputs), Ok(Value::Vector(DVec2::new(1.5, 2.))));
+		assert_eq!(evaluate("clamp(i1, 0, i2 + 1.5)", &inputs), Ok(Value::Vector(DVec2::new(3., 3.5))));
+		assert_eq!(Expression::parse("i2 * i1.x + i1").unwrap().references(), [1, 2]);
+	}
+
+	#[test]
+	fn comparisons_and_conditions() {
+		let inputs = [(0, Value::Number(5.))];
+		assert_eq!(evaluate("i0 >= 5 && !(i0 == 6)", &inputs), Ok(Value::Bool(true)));
+		assert_eq!(evaluate("if(i0 < 3 || false, 1, 2)", &inputs), Ok(Value::Number(2.)));
+		assert!(evaluate("i0 == true", &inputs).is_err());
+	}
+
+	#[test]
+	fn reports_errors() {
+		assert!(Expression::parse("1 +").is_err());
+		assert!(Expression::parse("(1 + 2").is_err());
+		assert!(Expression::parse("1 = 2").is_err());
+		assert!(Expression::parse("min(1)").is_err());
+		assert!(Expression::parse("foo(1)").is_err());
+		assert!(Expression::parse("1 2").is_err());
+		assert!(evaluate("i3", &[]).is_err());
+		assert!(evaluate("true + 1", &[]).is_err());
+	}
+
+	#[test]
+	fn converts_to_the_input_type() {
+		assert_eq!(Value::Number(2.6).to_tagged_value(&concrete!(u32)), Ok(TaggedValue::U32(3)));
+		assert_eq!(Value::Vector(DVec2::new(1., 2.)).to_tagged_value(&concrete!(DVec2)), Ok(TaggedValue::DVec2(DVec2::new(1., 2.))));
+		assert!(Value::Bool(true).to_tagged_value(&concrete!(f64)).is_err());
+		assert_eq!(Value::default_for(&concrete!(bool)), Some(Value::Bool(false)));
+	}
+}
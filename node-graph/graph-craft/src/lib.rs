@@ -10,3 +10,6 @@ pub mod proto;
 
 pub mod graphene_compiler;
 pub mod imaginate_input;
+pub mod inline_expression;
+#[cfg(feature = "serde")]
+pub mod render_cache;
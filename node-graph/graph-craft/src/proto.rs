@@ -123,9 +123,12 @@ impl core::fmt::Display for ProtoNetwork {
 						write_node(f, network, id.0, indent + 1)?;
 					}
 				}
-				ConstructionArgs::Inline(inline) => {
+				ConstructionArgs::Inline(inline, nodes) => {
 					f.write_str(&"\t".repeat(indent + 1))?;
-					f.write_fmt(format_args!("Inline construction argument: {inline:?}"))?
+					f.write_fmt(format_args!("Inline construction argument: {inline:?}\n"))?;
+					for id in nodes {
+						write_node(f, network, *id, indent + 1)?;
+					}
 				}
 			}
 			f.write_str(&"\t".repeat(indent))?;
@@ -148,8 +151,9 @@ pub enum ConstructionArgs {
 	/// A list of nodes used as inputs to the constructor function in `node_registry.rs`.
 	/// The bool indicates whether to treat the node as lambda node.
 	Nodes(Vec<(NodeId, bool)>),
-	// TODO: What?
-	Inline(InlineRust),
+	/// An expression written inline on a node input, along with the nodes providing the values of the inputs it references.
+	/// See [`crate::inline_expression`] for the language, and [`InlineRust`] for how the GPU compiler instead treats the expression as Rust source code.
+	Inline(InlineRust, Vec<NodeId>),
 }
 
 impl Eq for ConstructionArgs {}
@@ -182,7 +186,10 @@ impl Hash for ConstructionArgs {
 				}
 			}
 			Self::Value(value) => value.hash(state),
-			Self::Inline(inline) => inline.hash(state),
+			Self::Inline(inline, nodes) => {
+				inline.hash(state);
+				nodes.hash(state);
+			}
 		}
 	}
 }
@@ -193,7 +200,16 @@ impl ConstructionArgs {
 		match self {
 			ConstructionArgs::Nodes(nodes) => nodes.iter().map(|(n, _)| format!("n{:0x}", n.0)).collect(),
 			ConstructionArgs::Value(value) => vec![value.to_primitive_string()],
-			ConstructionArgs::Inline(inline) => vec![inline.expr.clone()],
+			ConstructionArgs::Inline(inline, _) => vec![inline.expr.clone()],
+		}
+	}
+
+	/// The IDs of the nodes used as construction arguments.
+	pub fn node_ids(&self) -> Vec<NodeId> {
+		match self {
+			ConstructionArgs::Nodes(nodes) => nodes.iter().map(|(id, _)| *id).collect(),
+			ConstructionArgs::Inline(_, nodes) => nodes.clone(),
+			ConstructionArgs::Value(_) => Vec::new(),
 		}
 	}
 }
@@ -288,6 +304,7 @@ impl ProtoNode {
 	pub fn value(value: ConstructionArgs, path: Vec<NodeId>) -> Self {
 		let inputs_exposed = match &value {
 			ConstructionArgs::Nodes(nodes) => nodes.len() + 1,
+			ConstructionArgs::Inline(_, nodes) => nodes.len() + 1,
 			_ => 2,
 		};
 		Self {
@@ -317,8 +334,10 @@ impl ProtoNode {
 			_ => (),
 		}
 
-		if let ConstructionArgs::Nodes(ids) = &mut self.construction_args {
-			ids.iter_mut().filter(|(_, lambda)| !(skip_lambdas && *lambda)).for_each(|(id, _)| *id = f(*id));
+		match &mut self.construction_args {
+			ConstructionArgs::Nodes(ids) => ids.iter_mut().filter(|(_, lambda)| !(skip_lambdas && *lambda)).for_each(|(id, _)| *id = f(*id)),
+			ConstructionArgs::Inline(_, ids) => ids.iter_mut().for_each(|id| *id = f(*id)),
+			ConstructionArgs::Value(_) => (),
 		}
 	}
 
@@ -350,11 +369,9 @@ impl ProtoNetwork {
 				_ => (),
 			}
 
-			if let ConstructionArgs::Nodes(ref_nodes) = &node.construction_args {
-				for (ref_id, _) in ref_nodes {
-					self.check_ref(ref_id, id);
-					edges.entry(*ref_id).or_default().push(*id)
-				}
+			for ref_id in node.construction_args.node_ids() {
+				self.check_ref(&ref_id, id);
+				edges.entry(ref_id).or_default().push(*id)
 			}
 		}
 		edges
@@ -387,11 +404,9 @@ impl ProtoNetwork {
 				_ => (),
 			}
 
-			if let ConstructionArgs::Nodes(ref_nodes) = &node.construction_args {
-				for (ref_id, _) in ref_nodes {
-					self.check_ref(ref_id, id);
-					edges.entry(*id).or_default().push(*ref_id)
-				}
+			for ref_id in node.construction_args.node_ids() {
+				self.check_ref(&ref_id, id);
+				edges.entry(*id).or_default().push(ref_id)
 			}
 		}
 		edges
@@ -580,6 +595,7 @@ pub enum GraphErrorType {
 	NoConstructor,
 	InvalidImplementations { parameters: String, error_inputs: Vec<Vec<(usize, (Type, Type))>> },
 	MultipleImplementations { parameters: String, valid: Vec<NodeIOTypes> },
+	InvalidInlineExpression(String),
 }
 impl core::fmt::Debug for GraphErrorType {
 	// TODO: format with the document graph context so the input index is the same as in the graph UI.
@@ -615,6 +631,7 @@ impl core::fmt::Debug for GraphErrorType {
 				)
 			}
 			GraphErrorType::MultipleImplementations { parameters, valid } => write!(f, "Multiple implementations found ({parameters}):\n{valid:#?}"),
+			GraphErrorType::InvalidInlineExpression(error) => write!(f, "Invalid inline expression: {error}"),
 		}
 	}
 }
@@ -706,16 +723,17 @@ impl TypingContext {
 				return Ok(types);
 			}
 			// If the node has nodes as parameters we can infer the types from the node outputs
-			ConstructionArgs::Nodes(ref nodes) => nodes
-				.iter()
-				.map(|(id, _)| {
+			ConstructionArgs::Nodes(_) | ConstructionArgs::Inline(..) => node
+				.construction_args
+				.node_ids()
+				.into_iter()
+				.map(|id| {
 					self.inferred
-						.get(id)
-						.ok_or_else(|| vec![GraphError::new(node, GraphErrorType::NodeNotFound(*id))])
+						.get(&id)
+						.ok_or_else(|| vec![GraphError::new(node, GraphErrorType::NodeNotFound(id))])
 						.map(|node| node.ty())
 				})
 				.collect::<Result<Vec<Type>, GraphErrors>>()?,
-			ConstructionArgs::Inline(ref inline) => vec![inline.ty.clone()],
 		};
 
 		// Get the node input type from the proto node declaration
@@ -727,6 +745,14 @@ impl TypingContext {
 				input.output.clone()
 			}
 		};
+
+		// An inline expression produces a value of the type of the input it was written on
+		if let ConstructionArgs::Inline(ref inline, _) = node.construction_args {
+			let types = NodeIOTypes::new(input, inline.ty.clone(), parameters);
+			self.inferred.insert(node_id, types.clone());
+			return Ok(types);
+		}
+
 		let impls = self.lookup.get(&node.identifier).ok_or_else(|| vec![GraphError::new(node, GraphErrorType::NoImplementations)])?;
 
 		if let Some(index) = parameters.iter().position(|p| {
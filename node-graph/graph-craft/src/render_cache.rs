@@ -0,0 +1,312 @@
+use crate::document::value::TaggedValue;
+use crate::proto::{Any as DAny, FutureAny, SharedNodeContainer};
+
+use graphene_core::raster::{Color, Image, ImageFrame};
+use graphene_core::vector::VectorData;
+use graphene_core::{Node, Type};
+
+use std::cell::Cell;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// The size the cache directory is kept under when no other limit is chosen, in bytes.
+pub const DEFAULT_MAX_SIZE: u64 = 1024 * 1024 * 1024;
+
+const EXTENSION: &str = "entry";
+
+/// The subdirectory holding the entries of this build, since the nodes of another build might compute different outputs for the same stable IDs.
+/// Builds from the same commit are told apart by a hash of the node source, in case it has uncommitted changes.
+const NAMESPACE: &str = concat!(env!("CARGO_PKG_VERSION"), "-", env!("GRAPHITE_GIT_COMMIT_HASH"), "-", env!("GRAPHITE_NODE_SOURCE_HASH"));
+
+/// Node outputs persisted as serialized [`TaggedValue`]s in a directory, so expensive results outlive the executor that computed them.
+///
+/// Entries are keyed by the stable ID of the proto node which produced them. That ID hashes the node's identity along with
+/// (recursively) the IDs of everything upstream, including the values fed in, so any change to the inputs leads to a different key.
+/// Each build of Graphite keeps its entries in a subdirectory of its own.
+/// Once the directory grows beyond its maximum size, the least recently written entries are removed.
+#[derive(Clone, Debug)]
+pub struct RenderCache {
+	directory: PathBuf,
+	max_size: u64,
+	/// The size of the entries, counting those written since the directory was last measured, shared by the clones of the cache.
+	size: Arc<AtomicU64>,
+}
+
+impl RenderCache {
+	/// Opens the cache in the given directory, creating the directory if needed and removing the oldest entries if it has outgrown its maximum size.
+	pub fn new(directory: impl Into<PathBuf>, max_size: u64) -> io::Result<Self> {
+		let directory = directory.into().join(NAMESPACE);
+		fs::create_dir_all(&directory)?;
+
+		let render_cache = Self {
+			directory,
+			max_size,
+			size: Arc::default(),
+		};
+		render_cache.evict()?;
+		Ok(render_cache)
+	}
+
+	/// The user's cache directory for Graphite render outputs, which can be overridden by setting the `GRAPHITE_RENDER_CACHE` environment variable.
+	pub fn default_directory() -> Option<PathBuf> {
+		if let Some(directory) = std::env::var_os("GRAPHITE_RENDER_CACHE") {
+			return Some(directory.into());
+		}
+
+		let home_cache = || {
+			let home = PathBuf::from(std::env::var_os("HOME")?);
+			Some(if cfg!(target_os = "macos") { home.join("Library").join("Caches") } else { home.join(".cache") })
+		};
+		let cache = std::env::var_os("XDG_CACHE_HOME").or_else(|| std::env::var_os("LOCALAPPDATA")).map(PathBuf::from).or_else(home_cache)?;
+
+		Some(cache.join("graphite").join("render-cache"))
+	}
+
+	fn path(&self, key: u64) -> PathBuf {
+		self.directory.join(format!("{key:016x}.{EXTENSION}"))
+	}
+
+	/// Whether an output is stored for the key, without reading it.
+	pub fn contains(&self, key: u64) -> bool {
+		self.path(key).exists()
+	}
+
+	/// Reads the output stored for the key, if there is one.
+	pub fn load(&self, key: u64) -> Option<TaggedValue> {
+		let path = self.path(key);
+		let data = fs::read(&path).ok()?;
+
+		match decode(&data) {
+			Ok(value) => Some(value),
+			Err(error) => {
+				// Most likely written by another version of Graphite, so it will never become readable
+				warn!("Removing the unreadable render cache entry {}: {error}", path.display());
+				let _ = fs::remove_file(&path);
+				None
+			}
+		}
+	}
+
+	/// Writes the output for the key, then removes the oldest entries if the cache has outgrown its maximum size.
+	pub fn store(&self, key: u64, value: &TaggedValue) {
+		let write = || -> io::Result<u64> {
+			let data = encode(value)?;
+			// Written to a temporary file first so an interrupted write can't leave a truncated entry behind
+			let temporary = self.path(key).with_extension("tmp");
+			fs::write(&temporary, &data)?;
+			fs::rename(&temporary, self.path(key))?;
+			Ok(data.len() as u64)
+		};
+		let length = match write() {
+			Ok(length) => length,
+			Err(error) => {
+				warn!("Failed to write the render cache entry {key:016x}: {error}");
+				return;
+			}
+		};
+
+		// The directory is only measured again once the entries written since might have taken it beyond its maximum size
+		if self.size.fetch_add(length, Ordering::Relaxed) + length <= self.max_size {
+			return;
+		}
+		if let Err(error) = self.evict() {
+			warn!("Failed to evict old render cache entries: {error}");
+		}
+	}
+
+	/// Removes the least recently written entries until the rest fit within the maximum size, and records the size of what's left.
+	fn evict(&self) -> io::Result<()> {
+		let mut entries = Vec::new();
+		for entry in fs::read_dir(&self.directory)? {
+			let path = entry?.path();
+			if path.extension().map_or(true, |extension| extension != EXTENSION) {
+				continue;
+			}
+			let metadata = fs::metadata(&path)?;
+			entries.push((metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), metadata.len(), path));
+		}
+		entries.sort_by_key(|(modified, _, _)| *modified);
+
+		let mut size: u64 = entries.iter().map(|(_, length, _)| length).sum();
+		for (_, length, path) in entries {
+			if size <= self.max_size {
+				break;
+			}
+			fs::remove_file(path)?;
+			size -= length;
+		}
+		self.size.store(size, Ordering::Relaxed);
+
+		Ok(())
+	}
+}
+
+/// Encodes the value as its JSON length, its JSON and then the raw pixels of images, which are left out of the JSON since encoding them as text is far slower and larger.
+fn encode(value: &TaggedValue) -> serde_json::Result<Vec<u8>> {
+	let without_pixels = |image: &Image<Color>| Image {
+		width: image.width,
+		height: image.height,
+		data: Vec::new(),
+		base64_string: None,
+	};
+	let (header, pixels) = match value {
+		TaggedValue::ImageFrame(image_frame) => {
+			let header = TaggedValue::ImageFrame(ImageFrame {
+				image: without_pixels(&image_frame.image),
+				transform: image_frame.transform,
+				alpha_blending: image_frame.alpha_blending,
+			});
+			(serde_json::to_vec(&header)?, image_frame.image.data.as_slice())
+		}
+		TaggedValue::Image(image) => (serde_json::to_vec(&TaggedValue::Image(without_pixels(image)))?, image.data.as_slice()),
+		value => (serde_json::to_vec(value)?, [].as_slice()),
+	};
+
+	let pixels: &[u8] = bytemuck::cast_slice(pixels);
+	let mut data = Vec::with_capacity(8 + header.len() + pixels.len());
+	data.extend_from_slice(&(header.len() as u64).to_le_bytes());
+	data.extend_from_slice(&header);
+	data.extend_from_slice(pixels);
+	Ok(data)
+}
+
+/// Decodes a value encoded by [`encode`].
+fn decode(data: &[u8]) -> Result<TaggedValue, String> {
+	let header_length = data.get(..8).and_then(|length| length.try_into().ok()).map(u64::from_le_bytes).ok_or("The entry is truncated")? as usize;
+	let header = data.get(8..).and_then(|rest| rest.get(..header_length)).ok_or("The entry is truncated")?;
+	let pixels = &data[8 + header_length..];
+
+	let mut value: TaggedValue = serde_json::from_slice(header).map_err(|error| error.to_string())?;
+	let image = match &mut value {
+		TaggedValue::ImageFrame(image_frame) => &mut image_frame.image,
+		TaggedValue::Image(image) => image,
+		_ if pixels.is_empty() => return Ok(value),
+		_ => return Err("The entry has pixels but isn't an image".to_string()),
+	};
+	if pixels.len() != image.width as usize * image.height as usize * std::mem::size_of::<Color>() {
+		return Err(format!("The entry doesn't have the pixels of a {}x{} image", image.width, image.height));
+	}
+	image.data = bytemuck::pod_collect_to_vec(pixels);
+
+	Ok(value)
+}
+
+/// Whether outputs of the type are worth persisting in the render cache, because they're expensive to compute and can be serialized.
+pub fn is_cacheable(ty: &Type) -> bool {
+	[concrete!(ImageFrame<Color>), concrete!(Image<Color>), concrete!(VectorData)].contains(ty)
+}
+
+/// Stands in for a `MemoNode`, keeping the output of the node it caches in memory and also in the [`RenderCache`].
+/// When the same node is built again, even by another executor, its output is read back from the cache instead of being evaluated.
+pub struct RenderCacheNode {
+	node: SharedNodeContainer,
+	key: u64,
+	render_cache: RenderCache,
+	value: Cell<Option<TaggedValue>>,
+}
+
+impl RenderCacheNode {
+	pub fn new(node: SharedNodeContainer, key: u64, render_cache: RenderCache) -> Self {
+		Self {
+			node,
+			key,
+			render_cache,
+			value: Cell::new(None),
+		}
+	}
+}
+
+impl<'input> Node<'input, DAny<'input>> for RenderCacheNode {
+	type Output = FutureAny<'input>;
+
+	fn eval(&'input self, _: DAny<'input>) -> Self::Output {
+		Box::pin(async move {
+			if let Some(value) = self.value.take() {
+				self.value.set(Some(value.clone()));
+				return value.to_any();
+			}
+
+			let value = match self.render_cache.load(self.key) {
+				Some(value) => value,
+				None => {
+					let output = self.node.eval(Box::new(())).await;
+					let value = TaggedValue::try_from_any(output).expect("The render cache node should only be used for cacheable types");
+					self.render_cache.store(self.key, &value);
+					value
+				}
+			};
+			self.value.set(Some(value.clone()));
+			value.to_any()
+		})
+	}
+
+	fn reset(&self) {
+		self.value.set(None);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn test_directory(name: &str) -> PathBuf {
+		let directory = std::env::temp_dir().join(format!("graphite-render-cache-{name}-{}", std::process::id()));
+		let _ = fs::remove_dir_all(&directory);
+		directory
+	}
+
+	#[test]
+	fn store_and_load() {
+		let directory = test_directory("store");
+		let cache = RenderCache::new(&directory, DEFAULT_MAX_SIZE).unwrap();
+
+		assert_eq!(cache.load(1), None);
+		cache.store(1, &TaggedValue::F64(4.5));
+		assert_eq!(cache.load(1), Some(TaggedValue::F64(4.5)));
+
+		// Unreadable entries are treated as missing and removed
+		fs::write(cache.path(2), "not a tagged value").unwrap();
+		assert_eq!(cache.load(2), None);
+		assert!(!cache.path(2).exists());
+
+		// Images keep their pixels outside the JSON
+		let image_frame = ImageFrame {
+			image: Image::new(3, 2, Color::from_rgbaf32_unchecked(0.25, 0.5, 0.75, 1.)),
+			transform: glam::DAffine2::from_scale(glam::DVec2::new(3., 2.)),
+			..Default::default()
+		};
+		cache.store(3, &TaggedValue::ImageFrame(image_frame.clone()));
+		assert_eq!(cache.load(3), Some(TaggedValue::ImageFrame(image_frame)));
+		let data = fs::read(cache.path(3)).unwrap();
+		assert_eq!(data.len(), 8 + u64::from_le_bytes(data[..8].try_into().unwrap()) as usize + 6 * std::mem::size_of::<Color>());
+
+		// Entries are kept apart from those of other builds
+		assert_eq!(cache.path(1).parent().unwrap(), directory.join(NAMESPACE));
+
+		fs::remove_dir_all(directory).unwrap();
+	}
+
+	#[test]
+	fn evicts_beyond_the_maximum_size() {
+		let directory = test_directory("evict");
+		let value = TaggedValue::String("x".repeat(100));
+		let entry_size = encode(&value).unwrap().len() as u64;
+		let cache = RenderCache::new(&directory, entry_size * 2).unwrap();
+
+		for key in 0..3 {
+			cache.store(key, &value);
+			// Keeps the modification times of the entries apart
+			std::thread::sleep(std::time::Duration::from_millis(10));
+		}
+
+		assert_eq!(cache.load(0), None);
+		assert_eq!(cache.load(1), Some(value.clone()));
+		assert_eq!(cache.load(2), Some(value));
+
+		fs::remove_dir_all(directory).unwrap();
+	}
+}
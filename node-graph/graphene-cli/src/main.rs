@@ -1,15 +1,24 @@
+use graph_craft::document::value::{RenderOutput, TaggedValue};
 use graph_craft::document::*;
-use graph_craft::graphene_compiler::Executor;
+use graph_craft::graphene_compiler::{Compiler, Executor};
 use graph_craft::imaginate_input::ImaginatePreferences;
+use graph_craft::render_cache::{self, RenderCache};
 use graph_craft::{concrete, ProtoNodeIdentifier};
-use graphene_core::application_io::{ApplicationIo, NodeGraphUpdateSender};
+use graphene_core::application_io::{ApplicationIo, ExportFormat, NodeGraphUpdateSender, RenderConfig};
+use graphene_core::memo::IORecord;
+use graphene_core::renderer::{GraphicElementRendered, Quad};
 use graphene_core::text::FontCache;
+use graphene_core::transform::Footprint;
+use graphene_core::{Artboard, GraphicElement};
 use graphene_std::wasm_application_io::{WasmApplicationIo, WasmEditorApi};
 use interpreted_executor::dynamic_executor::DynamicExecutor;
 
 use fern::colors::{Color, ColoredLevelConfig};
 use futures::executor::block_on;
-use std::{error::Error, sync::Arc};
+use glam::{DAffine2, DVec2};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 struct UpdateLogger {}
 
@@ -19,44 +28,191 @@ impl NodeGraphUpdateSender for UpdateLogger {
 	}
 }
 
+/// Command line arguments, e.g. `graphene-cli brand/logo.graphite logo.png --scale 2`.
+#[derive(Debug)]
+struct Arguments {
+	document_path: String,
+	output_path: PathBuf,
+	scale_factor: f64,
+	transparent_background: bool,
+	image_path: Option<String>,
+	/// Where the outputs of expensive nodes are persisted between runs, or `None` when caching is disabled.
+	cache_directory: Option<PathBuf>,
+	cache_size: u64,
+}
+
+impl Arguments {
+	/// Parses the arguments following the name of the program.
+	fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, Box<dyn Error>> {
+		let mut args = args.into_iter();
+		let mut positional = Vec::new();
+		let mut scale_factor = 1.;
+		let mut transparent_background = false;
+		let mut image_path = None;
+		let mut cache_directory = RenderCache::default_directory();
+		let mut cache_size = render_cache::DEFAULT_MAX_SIZE;
+
+		while let Some(arg) = args.next() {
+			match arg.as_str() {
+				"--scale" => scale_factor = args.next().ok_or("Missing value for --scale")?.parse()?,
+				"--transparent" => transparent_background = true,
+				"--image" => image_path = Some(args.next().ok_or("Missing value for --image")?),
+				"--cache" => cache_directory = Some(args.next().ok_or("Missing value for --cache")?.into()),
+				"--cache-size" => cache_size = args.next().ok_or("Missing value for --cache-size")?.parse::<u64>()? * 1024 * 1024,
+				"--no-cache" => cache_directory = None,
+				_ => positional.push(arg),
+			}
+		}
+
+		let [document_path, output_path] = <[String; 2]>::try_from(positional).map_err(|_| {
+			"Usage: graphene-cli <document.graphite> <output.svg|output.png|output.webp|output.pdf> [--scale <factor>] [--transparent] [--image <path>] [--cache <directory>] [--cache-size <megabytes>] [--no-cache]"
+		})?;
+		if scale_factor <= 0. {
+			return Err("The scale factor must be positive".into());
+		}
+
+		Ok(Self {
+			document_path,
+			output_path: PathBuf::from(output_path),
+			scale_factor,
+			transparent_background,
+			image_path,
+			cache_directory,
+			cache_size,
+		})
+	}
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
 	init_logging();
 
-	let document_path = std::env::args().nth(1).expect("No document path provided");
+	let arguments = Arguments::parse(std::env::args().skip(1))?;
 
-	let image_path = std::env::args().nth(2);
+	let document_string = std::fs::read_to_string(&arguments.document_path)?;
 
-	let document_string = std::fs::read_to_string(&document_path).expect("Failed to read document");
-
-	let executor = create_executor(document_string)?;
-	println!("creating gpu context",);
+	let render_cache = arguments.cache_directory.as_ref().and_then(|directory| match RenderCache::new(directory, arguments.cache_size) {
+		Ok(render_cache) => Some(render_cache),
+		Err(error) => {
+			log::warn!("Rendering without a cache, since the cache directory {} could not be created: {error}", directory.display());
+			None
+		}
+	});
+	let (executor, monitor_nodes) = create_executor(document_string, render_cache)?;
 	let mut application_io = block_on(WasmApplicationIo::new());
-	if let Some(image_path) = image_path {
-		application_io.resources.insert("null".to_string(), Arc::from(std::fs::read(image_path).expect("Failed to read image")));
+	if let Some(image_path) = &arguments.image_path {
+		application_io.resources.insert("null".to_string(), Arc::from(std::fs::read(image_path)?));
 	}
 
-	let device = application_io.gpu_executor().unwrap().context.device.clone();
-	std::thread::spawn(move || loop {
-		std::thread::sleep(std::time::Duration::from_nanos(10));
-		device.poll(wgpu::Maintain::Poll);
-	});
+	// Headless machines usually have no GPU, so only drive the device if one was found
+	if let Some(gpu_executor) = application_io.gpu_executor() {
+		let device = gpu_executor.context.device.clone();
+		std::thread::spawn(move || loop {
+			std::thread::sleep(std::time::Duration::from_nanos(10));
+			device.poll(wgpu::Maintain::Poll);
+		});
+	}
 
-	let editor_api = WasmEditorApi {
+	let font_cache = FontCache::default();
+	let imaginate_preferences = ImaginatePreferences::default();
+	let editor_api = |render_config| WasmEditorApi {
 		image_frame: None,
-		font_cache: &FontCache::default(),
+		font_cache: &font_cache,
 		application_io: &application_io,
 		node_graph_message_sender: &UpdateLogger {},
-		imaginate_preferences: &ImaginatePreferences::default(),
-		render_config: graphene_core::application_io::RenderConfig::default(),
+		imaginate_preferences: &imaginate_preferences,
+		render_config,
+	};
+
+	// The first execution populates the monitor nodes, from which we learn the bounds of the artwork
+	let render_config = RenderConfig {
+		export_format: ExportFormat::Svg,
+		for_export: true,
+		hide_artboards: arguments.transparent_background,
+		..Default::default()
 	};
+	(&executor).execute(editor_api(render_config)).await?;
+	let bounds = document_bounds(&executor, &monitor_nodes).ok_or("The document has no artwork to render")?;
 
-	loop {
-		//println!("executing");
-		let _result = (&executor).execute(editor_api.clone()).await?;
-		//println!("result: {result:?}");
-		std::thread::sleep(std::time::Duration::from_millis(16));
+	// The second execution renders the artwork within those bounds at the requested scale
+	let size = bounds[1] - bounds[0];
+	let resolution = (size * arguments.scale_factor).round().as_uvec2();
+	let render_config = RenderConfig {
+		viewport: Footprint {
+			transform: DAffine2::from_scale(DVec2::splat(arguments.scale_factor)) * DAffine2::from_translation(-bounds[0]),
+			resolution,
+			..Default::default()
+		},
+		..render_config
+	};
+	let TaggedValue::RenderOutput(RenderOutput::Svg(svg)) = (&executor).execute(editor_api(render_config)).await? else {
+		return Err("The document did not render to an SVG".into());
+	};
+
+	write_output(&svg, &arguments.output_path)?;
+	log::info!("Rendered {}x{} output to {}", resolution.x, resolution.y, arguments.output_path.display());
+
+	Ok(())
+}
+
+/// Writes the rendered SVG to disk, converting it first if the output file is a PNG, WebP or PDF.
+fn write_output(svg: &str, output_path: &Path) -> Result<(), Box<dyn Error>> {
+	let extension = output_path.extension().and_then(|extension| extension.to_str()).map(|extension| extension.to_lowercase());
+	match extension.as_deref() {
+		Some("svg") => std::fs::write(output_path, svg)?,
+		Some(extension @ ("png" | "webp")) => {
+			let tree = parse_svg(svg)?;
+			let size = tree.size.to_int_size();
+			let mut pixmap = resvg::tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("Invalid output resolution")?;
+			resvg::render(&tree, resvg::tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+			if extension == "png" {
+				pixmap.save_png(output_path)?;
+			} else {
+				let pixels: Vec<u8> = pixmap
+					.pixels()
+					.iter()
+					.flat_map(|pixel| {
+						let pixel = pixel.demultiply();
+						[pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+					})
+					.collect();
+				let file = std::io::BufWriter::new(std::fs::File::create(output_path)?);
+				image::codecs::webp::WebPEncoder::new_lossless(file).encode(&pixels, size.width(), size.height(), image::ColorType::Rgba8)?;
+			}
+		}
+		Some("pdf") => std::fs::write(output_path, graphene_std::pdf::tree_to_pdf(&parse_svg(svg)?)?)?,
+		_ => return Err(format!("Unsupported output file type for {}, expected .svg, .png, .webp or .pdf", output_path.display()).into()),
 	}
+	Ok(())
+}
+
+/// Parses the SVG with text converted to outlines using the system fonts.
+fn parse_svg(svg: &str) -> Result<resvg::usvg::Tree, Box<dyn Error>> {
+	let mut tree = resvg::usvg::Tree::from_str(svg, &resvg::usvg::Options::default())?;
+	let mut font_database = resvg::usvg::fontdb::Database::new();
+	font_database.load_system_fonts();
+	tree.postprocess(resvg::usvg::PostProcessingSteps::default(), &font_database);
+	Ok(tree)
+}
+
+/// Combines the bounding boxes recorded by the monitor nodes of the layers and artboards at the root of the document.
+fn document_bounds(executor: &DynamicExecutor, monitor_nodes: &[Vec<NodeId>]) -> Option<[DVec2; 2]> {
+	monitor_nodes
+		.iter()
+		// Monitor nodes of root layers and artboards are nested one level deep, directly inside the layer or artboard node
+		.filter(|path| path.len() == 2)
+		.filter_map(|path| {
+			let introspected_data = executor.introspect(path).flatten()?;
+			if let Some(io_data) = introspected_data.downcast_ref::<IORecord<Footprint, GraphicElement>>() {
+				io_data.output.bounding_box(DAffine2::IDENTITY)
+			} else if let Some(io_data) = introspected_data.downcast_ref::<IORecord<Footprint, Artboard>>() {
+				io_data.output.bounding_box(DAffine2::IDENTITY)
+			} else {
+				None
+			}
+		})
+		.reduce(Quad::combine_bounds)
 }
 
 fn init_logging() {
@@ -79,23 +235,34 @@ fn init_logging() {
 		.unwrap();
 }
 
-fn create_executor(_document_string: String) -> Result<DynamicExecutor, Box<dyn Error>> {
-	// let document: serde_json::Value = serde_json::from_str(&document_string).expect("Failed to parse document");
-	// let document = serde_json::from_value::<Document>(document["document_legacy"].clone()).expect("Failed to parse document");
-	// let Some(LegacyLayerType::Layer(ref network)) = document.root.iter().find(|layer| matches!(layer, LegacyLayerType::Layer(_))) else {
-	panic!("Failed to extract node graph from document")
-	// };
-	// let wrapped_network = wrap_network_in_scope(network.clone());
-	// let compiler = Compiler {};
-	// let protograph = compiler.compile_single(wrapped_network)?;
-	// let executor = block_on(DynamicExecutor::new(protograph))?;
-	// Ok(executor)
+/// Builds an executor for the node graph saved in a `.graphite` document, also returning the paths of its monitor nodes.
+fn create_executor(document_string: String, render_cache: Option<RenderCache>) -> Result<(DynamicExecutor, Vec<Vec<NodeId>>), Box<dyn Error>> {
+	let document: serde_json::Value = serde_json::from_str(&document_string)?;
+	let network = document
+		.get("network")
+		.ok_or("Failed to extract node graph from document, it may have been saved in an outdated format")?;
+	let network = serde_json::from_value::<NodeNetwork>(network.clone())?;
+
+	let wrapped_network = wrap_network_in_scope(network);
+	let monitor_nodes = wrapped_network
+		.recursive_nodes()
+		.filter(|(_, node)| node.implementation == DocumentNodeImplementation::proto("graphene_core::memo::MonitorNode<_, _, _>"))
+		.map(|(_, node)| node.original_location.path.clone().unwrap_or_default())
+		.collect();
+
+	let compiler = Compiler {};
+	let protograph = compiler.compile_single(wrapped_network)?;
+	let mut executor = DynamicExecutor::default();
+	executor.set_render_cache(render_cache);
+	block_on(executor.update(protograph)).map_err(|errors| format!("Failed to build the node graph: {errors:?}"))?;
+	Ok((executor, monitor_nodes))
 }
 
 pub fn wrap_network_in_scope(mut network: NodeNetwork) -> NodeNetwork {
-	let node_ids = network.nodes.keys().copied().collect::<Vec<_>>();
-
 	network.generate_node_paths(&[]);
+	network.resolve_empty_stacks();
+
+	let node_ids = network.nodes.keys().copied().collect::<Vec<_>>();
 	for id in node_ids {
 		network.flatten(id);
 	}
@@ -168,7 +335,7 @@ fn begin_scope() -> DocumentNode {
 				},
 				DocumentNode {
 					name: "RefNode".to_string(),
-					manual_composition: Some(concrete!(WasmEditorApi)),
+					manual_composition: Some(concrete!(())),
 					inputs: vec![NodeInput::lambda(NodeId(1), 0)],
 					implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::memo::RefNode<_, _>")),
 					..Default::default()
@@ -186,6 +353,62 @@ fn begin_scope() -> DocumentNode {
 	}
 }
 
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn parse(args: &str) -> Result<Arguments, Box<dyn Error>> {
+		Arguments::parse(args.split_whitespace().map(String::from))
+	}
+
+	#[test]
+	fn parse_arguments() {
+		let arguments = parse("in.graphite --scale 2.5 out.png --transparent --no-cache --image photo.jpg").unwrap();
+		assert_eq!(arguments.document_path, "in.graphite");
+		assert_eq!(arguments.output_path, PathBuf::from("out.png"));
+		assert_eq!(arguments.scale_factor, 2.5);
+		assert!(arguments.transparent_background);
+		assert_eq!(arguments.image_path.as_deref(), Some("photo.jpg"));
+		assert!(arguments.cache_directory.is_none());
+
+		let arguments = parse("in.graphite out.svg --cache cache --cache-size 3").unwrap();
+		assert_eq!(arguments.scale_factor, 1.);
+		assert_eq!(arguments.cache_directory, Some(PathBuf::from("cache")));
+		assert_eq!(arguments.cache_size, 3 * 1024 * 1024);
+
+		assert!(parse("in.graphite").is_err());
+		assert!(parse("in.graphite out.svg extra.svg").is_err());
+		assert!(parse("in.graphite out.svg --scale").is_err());
+		assert!(parse("in.graphite out.svg --scale 0").is_err());
+	}
+
+	#[test]
+	fn write_output_by_extension() {
+		let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="2"><rect width="2" height="2" fill="red" /></svg>"#;
+		let directory = std::env::temp_dir().join(format!("graphene-cli-test-{}", std::process::id()));
+		std::fs::create_dir_all(&directory).unwrap();
+
+		write_output(svg, &directory.join("output.svg")).unwrap();
+		assert_eq!(std::fs::read_to_string(directory.join("output.svg")).unwrap(), svg);
+
+		// The PNG has the size of the SVG, with the left half red and the right half transparent
+		write_output(svg, &directory.join("output.PNG")).unwrap();
+		let png = image::open(directory.join("output.PNG")).unwrap().to_rgba8();
+		assert_eq!(png.dimensions(), (4, 2));
+		assert_eq!(png.get_pixel(0, 0).0, [255, 0, 0, 255]);
+		assert_eq!(png.get_pixel(3, 1).0, [0, 0, 0, 0]);
+
+		write_output(svg, &directory.join("output.webp")).unwrap();
+		let webp = image::open(directory.join("output.webp")).unwrap().to_rgba8();
+		assert_eq!(webp, png);
+
+		assert!(write_output(svg, &directory.join("output.bmp")).is_err());
+		assert!(write_output(svg, &directory.join("output")).is_err());
+
+		std::fs::remove_dir_all(directory).unwrap();
+	}
+}
+
 // #[cfg(test)]
 // mod test {
 // 	use super::*;
@@ -194,7 +417,7 @@ fn begin_scope() -> DocumentNode {
 // 	#[cfg_attr(not(feature = "wayland"), ignore)]
 // 	async fn grays_scale() {
 // 		let document_string = include_str!("../test_files/gray.graphite");
-// 		let executor = create_executor(document_string.to_string()).unwrap();
+// 		let (executor, _) = create_executor(document_string.to_string(), None).unwrap();
 // 		let editor_api = WasmEditorApi {
 // 			image_frame: None,
 // 			font_cache: &FontCache::default(),
@@ -211,7 +434,7 @@ fn begin_scope() -> DocumentNode {
 // 	#[cfg_attr(not(feature = "wayland"), ignore)]
 // 	async fn hue() {
 // 		let document_string = include_str!("../test_files/hue.graphite");
-// 		let executor = create_executor(document_string.to_string()).unwrap();
+// 		let (executor, _) = create_executor(document_string.to_string(), None).unwrap();
 // 		let editor_api = WasmEditorApi {
 // 			image_frame: None,
 // 			font_cache: &FontCache::default(),
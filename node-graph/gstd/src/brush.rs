@@ -6,14 +6,15 @@ use graphene_core::raster::brush_cache::BrushCache;
 use graphene_core::raster::{Alpha, Color, Image, ImageFrame, Pixel, Sample};
 use graphene_core::raster::{BlendMode, BlendNode};
 use graphene_core::transform::{Transform, TransformMut};
-use graphene_core::value::{ClonedNode, CopiedNode, OnceCellNode, ValueNode};
-use graphene_core::vector::brush_stroke::{BrushStroke, BrushStyle};
+use graphene_core::value::{CopiedNode, OnceCellNode, ValueNode};
+use graphene_core::vector::brush_stroke::{polygon_coverage, BrushInputSample, BrushStroke, BrushStyle};
 use graphene_core::vector::VectorData;
 use graphene_core::Node;
 use node_macro::node_fn;
 
 use glam::{DAffine2, DVec2};
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ReduceNode<Initial, Lambda> {
@@ -167,9 +168,17 @@ pub struct BlitNode<P, Texture, Positions, BlendFn> {
 }
 
 #[node_fn(BlitNode<_P>)]
-fn blit_node<_P: Alpha + Pixel + std::fmt::Debug, BlendFn>(mut target: ImageFrame<_P>, texture: Image<_P>, positions: Vec<DVec2>, blend_mode: BlendFn) -> ImageFrame<_P>
+fn blit_node<_P: Alpha + Pixel + std::fmt::Debug, BlendFn>(target: ImageFrame<_P>, texture: Image<_P>, positions: Vec<DVec2>, blend_mode: BlendFn) -> ImageFrame<_P>
 where
 	BlendFn: for<'any_input> Node<'any_input, (_P, _P), Output = _P>,
+{
+	blit(target, &texture, &positions, &blend_mode)
+}
+
+/// Stamps the texture, centered on each of the positions, onto the target.
+fn blit<P: Alpha + Pixel + std::fmt::Debug, BlendFn>(mut target: ImageFrame<P>, texture: &Image<P>, positions: &[DVec2], blend_mode: &BlendFn) -> ImageFrame<P>
+where
+	BlendFn: for<'any_input> Node<'any_input, (P, P), Output = P>,
 {
 	if positions.is_empty() {
 		return target;
@@ -179,7 +188,7 @@ where
 	let texture_size = DVec2::new(texture.width as f64, texture.height as f64);
 	let document_to_target = DAffine2::from_translation(-texture_size / 2.) * DAffine2::from_scale(target_size) * target.transform.inverse();
 
-	for position in positions {
+	for &position in positions {
 		let start = document_to_target.transform_point2(position).round();
 		let stop = start + texture_size;
 
@@ -281,6 +290,214 @@ pub fn blend_with_mode(background: ImageFrame<Color>, foreground: ImageFrame<Col
 	)
 }
 
+/// Replaces each pixel of the stroke mask with the source image's pixel found at the given offset from it in layer space, keeping the coverage of the mask.
+pub fn clone_from_source(mut mask: ImageFrame<Color>, source: &ImageFrame<Color>, offset: DVec2) -> ImageFrame<Color> {
+	let mask_size = DVec2::new(mask.image.width as f64, mask.image.height as f64);
+	let source_size = DVec2::new(source.image.width as f64, source.image.height as f64);
+
+	let source_is_empty = source.image.data.is_empty() || source.transform.matrix2.determinant() == 0.;
+	if source_is_empty || mask_size.min_element() == 0. {
+		mask.image.data.fill(Color::TRANSPARENT);
+		return mask;
+	}
+
+	let mask_to_layer = mask.transform * DAffine2::from_scale(mask_size.recip());
+	let layer_to_source = DAffine2::from_scale(source_size) * source.transform.inverse();
+	let mask_to_source = layer_to_source * DAffine2::from_translation(offset) * mask_to_layer;
+
+	let width = mask.image.width as usize;
+	for (index, pixel) in mask.image.data.iter_mut().enumerate() {
+		if pixel.a() == 0. {
+			continue;
+		}
+
+		let pixel_center = DVec2::new((index % width) as f64, (index / width) as f64) + DVec2::splat(0.5);
+		let source_position = mask_to_source.transform_point2(pixel_center).floor();
+		let inside_source = source_position.cmpge(DVec2::ZERO).all() && source_position.cmplt(source_size).all();
+		let source_pixel = if inside_source {
+			source.image.data[source_position.y as usize * source.image.width as usize + source_position.x as usize]
+		} else {
+			Color::TRANSPARENT
+		};
+
+		*pixel = source_pixel.multiplied_alpha(pixel.a());
+	}
+
+	mask
+}
+
+/// Confines the pixels painted by a stroke to the clip polygons (in layer space), fading back to the pixels from before the stroke
+/// (or to transparency when there are none) outside of them, with anti-aliased edges.
+pub fn confine_to_clip(mut painted: ImageFrame<Color>, before: Option<&ImageFrame<Color>>, clip: &[Vec<DVec2>]) -> ImageFrame<Color> {
+	let (width, height) = (painted.image.width as usize, painted.image.height as usize);
+	if clip.is_empty() || width == 0 || height == 0 {
+		return painted;
+	}
+
+	let image_to_layer = painted.transform * DAffine2::from_scale(DVec2::new(width as f64, height as f64).recip());
+	let coverage = polygon_coverage(clip, width, height, image_to_layer);
+	for (index, (pixel, coverage)) in painted.image.data.iter_mut().zip(coverage).enumerate() {
+		if coverage == 1. {
+			continue;
+		}
+
+		let previous = before.and_then(|before| before.image.data.get(index).copied()).unwrap_or(Color::TRANSPARENT);
+		*pixel = previous.lerp(&*pixel, coverage);
+	}
+
+	painted
+}
+
+/// Summed-area table of the channels of an image, used to find the average color of any rectangle of pixels in constant time.
+struct SummedAreaTable {
+	width: usize,
+	height: usize,
+	sums: Vec<[f64; 4]>,
+}
+
+impl SummedAreaTable {
+	fn new(image: &Image<Color>) -> Self {
+		let (width, height) = (image.width as usize, image.height as usize);
+		let mut sums = vec![[0.; 4]; (width + 1) * (height + 1)];
+		for y in 0..height {
+			let mut row_sum = [0.; 4];
+			for x in 0..width {
+				for (channel, value) in channels(image.data[y * width + x]).into_iter().enumerate() {
+					row_sum[channel] += value as f64;
+				}
+				let above = sums[y * (width + 1) + x + 1];
+				sums[(y + 1) * (width + 1) + x + 1] = core::array::from_fn(|channel| above[channel] + row_sum[channel]);
+			}
+		}
+		Self { width, height, sums }
+	}
+
+	/// The average color of the pixels within `radius` pixels of the given pixel, ignoring the area outside the image.
+	fn average(&self, (x, y): (usize, usize), radius: usize) -> [f32; 4] {
+		let (start_x, start_y) = (x.saturating_sub(radius), y.saturating_sub(radius));
+		let (end_x, end_y) = ((x + radius + 1).min(self.width), (y + radius + 1).min(self.height));
+		let sum = |x: usize, y: usize| self.sums[y * (self.width + 1) + x];
+		let count = ((end_x - start_x) * (end_y - start_y)) as f64;
+
+		let [start, end, start_x_end_y, end_x_start_y] = [sum(start_x, start_y), sum(end_x, end_y), sum(start_x, end_y), sum(end_x, start_y)];
+		core::array::from_fn(|channel| ((end[channel] - start_x_end_y[channel] - end_x_start_y[channel] + start[channel]) / count) as f32)
+	}
+}
+
+fn channels(color: Color) -> [f32; 4] {
+	[color.r(), color.g(), color.b(), color.a()]
+}
+
+/// The pixel of the image containing the position given in pixel coordinates, if it's inside the image.
+fn pixel_at(image: &Image<Color>, position: DVec2) -> Option<(usize, usize)> {
+	let position = position.floor();
+	let inside = position.cmpge(DVec2::ZERO).all() && position.x < image.width as f64 && position.y < image.height as f64;
+	inside.then_some((position.x as usize, position.y as usize))
+}
+
+/// Looks in several directions around the stroke for the area with the least fine detail, which is the most likely to be free of blemishes, returning its offset in pixels.
+fn automatic_heal_offset(covered_pixels: &[(usize, DVec2)], source: &Image<Color>, averages: &SummedAreaTable, radius: usize, distance: f64) -> Option<DVec2> {
+	const DIRECTIONS: usize = 8;
+
+	(0..DIRECTIONS)
+		.map(|direction| DVec2::from_angle(direction as f64 * core::f64::consts::TAU / DIRECTIONS as f64) * distance)
+		.filter_map(|offset| {
+			let mut detail = 0.;
+			for &(_, position) in covered_pixels {
+				let pixel = pixel_at(source, position + offset)?;
+				let color = channels(source.data[pixel.1 * source.width as usize + pixel.0]);
+				let average = averages.average(pixel, radius);
+				detail += (0..4).map(|channel| (color[channel] - average[channel]).powi(2) as f64).sum::<f64>();
+			}
+			Some((offset, detail))
+		})
+		.min_by(|(_, a), (_, b)| a.total_cmp(b))
+		.map(|(offset, _)| offset)
+}
+
+/// Fills the stroke mask with texture from the source image at the offset (in layer space), shifted to match the average color around each destination pixel.
+/// This keeps the fine detail of the source while blending in with the surrounding colors. Without an offset, a nearby source area is picked automatically.
+pub fn heal_from_source(mut mask: ImageFrame<Color>, source: &ImageFrame<Color>, offset: Option<DVec2>, diameter: f64) -> ImageFrame<Color> {
+	let mask_size = DVec2::new(mask.image.width as f64, mask.image.height as f64);
+	let source_size = DVec2::new(source.image.width as f64, source.image.height as f64);
+
+	let source_is_empty = source.image.data.is_empty() || source.transform.matrix2.determinant() == 0.;
+	if source_is_empty || mask_size.min_element() == 0. {
+		mask.image.data.fill(Color::TRANSPARENT);
+		return mask;
+	}
+
+	let mask_to_layer = mask.transform * DAffine2::from_scale(mask_size.recip());
+	let layer_to_source = DAffine2::from_scale(source_size) * source.transform.inverse();
+	let mask_to_source = layer_to_source * mask_to_layer;
+
+	// The colors are matched over a quarter of the brush diameter, so detail smaller than that comes from the source texture
+	let source_pixels_per_unit = layer_to_source.matrix2.determinant().abs().sqrt();
+	let radius = ((diameter * source_pixels_per_unit / 4.).round() as usize).max(1);
+	let averages = SummedAreaTable::new(&source.image);
+
+	let width = mask.image.width as usize;
+	let covered_pixels: Vec<_> = mask
+		.image
+		.data
+		.iter()
+		.enumerate()
+		.filter(|(_, pixel)| pixel.a() > 0.)
+		.map(|(index, _)| (index, mask_to_source.transform_point2(DVec2::new((index % width) as f64, (index / width) as f64) + DVec2::splat(0.5))))
+		.collect();
+
+	let offset = match offset {
+		Some(offset) => Some(layer_to_source.transform_vector2(offset)),
+		None => automatic_heal_offset(&covered_pixels, &source.image, &averages, radius, diameter * source_pixels_per_unit * 1.25),
+	};
+	let Some(offset) = offset else {
+		mask.image.data.fill(Color::TRANSPARENT);
+		return mask;
+	};
+
+	for (index, position) in covered_pixels {
+		let coverage = mask.image.data[index].a();
+		let (Some(destination_pixel), Some(source_pixel)) = (pixel_at(&source.image, position), pixel_at(&source.image, position + offset)) else {
+			mask.image.data[index] = Color::TRANSPARENT;
+			continue;
+		};
+
+		let texture = channels(source.image.data[source_pixel.1 * source.image.width as usize + source_pixel.0]);
+		let destination_average = averages.average(destination_pixel, radius);
+		let source_average = averages.average(source_pixel, radius);
+		let [red, green, blue, alpha]: [f32; 4] = core::array::from_fn(|channel| texture[channel] + destination_average[channel] - source_average[channel]);
+
+		// Keep the result a valid color with premultiplied alpha
+		let alpha = alpha.clamp(0., 1.);
+		let healed = Color::from_rgbaf32_unchecked(red.clamp(0., alpha), green.clamp(0., alpha), blue.clamp(0., alpha), alpha);
+		mask.image.data[index] = healed.multiplied_alpha(coverage);
+	}
+
+	mask
+}
+
+fn brush_texture(style: &BrushStyle, cache: &BrushCache) -> Arc<Image<Color>> {
+	cache.get_cached_brush(style).unwrap_or_else(|| {
+		let tex = Arc::new(create_brush_texture(style));
+		cache.store_brush(style.clone(), tex.clone());
+		tex
+	})
+}
+
+/// Stamps the brush at each sample onto the target. Brushes with dynamics vary the stamp from sample to sample, so each one is blitted with its own texture.
+fn blit_stroke(target: ImageFrame<Color>, style: &BrushStyle, samples: Vec<BrushInputSample>, blend_mode: BlendMode, cache: &BrushCache) -> ImageFrame<Color> {
+	let blend_params = BlendNode::new(CopiedNode::new(blend_mode), CopiedNode::new(100.));
+
+	if style.dynamics.is_fixed() {
+		let positions: Vec<_> = samples.into_iter().map(|sample| sample.position).collect();
+		return blit(target, &brush_texture(style, cache), &positions, &blend_params);
+	}
+
+	samples.into_iter().fold(target, |target, sample| {
+		blit(target, &brush_texture(&style.at_sample(&sample), cache), &[sample.position], &blend_params)
+	})
+}
+
 pub struct BrushNode<Bounds, Strokes, Cache> {
 	bounds: Bounds,
 	strokes: Strokes,
@@ -307,18 +524,11 @@ async fn brush(image: ImageFrame<Color>, bounds: ImageFrame<Color>, strokes: Vec
 	let mut actual_image = ExtendImageToBoundsNode::new(OnceCellNode::new(background_bounds)).eval(brush_plan.background);
 	let final_stroke_idx = brush_plan.strokes.len().saturating_sub(1);
 	for (idx, stroke) in brush_plan.strokes.into_iter().enumerate() {
-		// Create brush texture.
-		// TODO: apply rotation from layer to stamp for non-rotationally-symmetric brushes.
-		let brush_texture = cache.get_cached_brush(&stroke.style).unwrap_or_else(|| {
-			let tex = create_brush_texture(&stroke.style);
-			cache.store_brush(stroke.style.clone(), tex.clone());
-			tex
-		});
-
 		// Compute transformation from stroke texture space into layer space, and create the stroke texture.
+		// TODO: apply rotation from layer to stamp for non-rotationally-symmetric brushes.
 		let skip = if idx == 0 { brush_plan.first_stroke_point_skip } else { 0 };
-		let positions: Vec<_> = stroke.compute_blit_points().into_iter().skip(skip).collect();
-		let stroke_texture = if idx == 0 && positions.is_empty() {
+		let samples: Vec<_> = stroke.compute_blit_samples().into_iter().skip(skip).collect();
+		let stroke_texture = if idx == 0 && samples.is_empty() {
 			core::mem::take(&mut brush_plan.first_stroke_texture)
 		} else {
 			let mut bbox = stroke.bounding_box();
@@ -327,19 +537,17 @@ async fn brush(image: ImageFrame<Color>, bounds: ImageFrame<Color>, strokes: Vec
 			let stroke_size = bbox.size() + DVec2::splat(stroke.style.diameter);
 			// For numerical stability we want to place the first blit point at a stable, integer offset
 			// in layer space.
-			let snap_offset = positions[0].floor() - positions[0];
+			let snap_offset = samples[0].position.floor() - samples[0].position;
 			let stroke_origin_in_layer = bbox.start - snap_offset - DVec2::splat(stroke.style.diameter / 2.0);
 			let stroke_to_layer = DAffine2::from_translation(stroke_origin_in_layer) * DAffine2::from_scale(stroke_size);
 
-			let normal_blend = BlendNode::new(CopiedNode::new(BlendMode::Normal), CopiedNode::new(100.));
-			let blit_node = BlitNode::new(ClonedNode::new(brush_texture), ClonedNode::new(positions), ClonedNode::new(normal_blend));
 			let blit_target = if idx == 0 {
 				let target = core::mem::take(&mut brush_plan.first_stroke_texture);
 				ExtendImageToBoundsNode::new(CopiedNode::new(stroke_to_layer)).eval(target)
 			} else {
 				EmptyImageNode::new(CopiedNode::new(Color::TRANSPARENT)).eval(stroke_to_layer)
 			};
-			blit_node.eval(blit_target)
+			blit_stroke(blit_target, &stroke.style, samples, BlendMode::Normal, &cache)
 		};
 
 		// Cache image before doing final blend, and store final stroke texture.
@@ -347,6 +555,14 @@ async fn brush(image: ImageFrame<Color>, bounds: ImageFrame<Color>, strokes: Vec
 			cache.cache_results(core::mem::take(&mut draw_strokes), actual_image.clone(), stroke_texture.clone());
 		}
 
+		// Clone and healing strokes use the blitted texture as a mask for pixels copied from elsewhere in the image.
+		let stroke_texture = match (stroke.clone_offset, stroke.healing) {
+			(offset, true) => heal_from_source(stroke_texture, &actual_image, offset, stroke.style.diameter),
+			(Some(offset), false) => clone_from_source(stroke_texture, &actual_image, offset),
+			(None, false) => stroke_texture,
+		};
+		let stroke_texture = confine_to_clip(stroke_texture, None, &stroke.clip);
+
 		// TODO: Is this the correct way to do opacity in blending?
 		actual_image = blend_with_mode(actual_image, stroke_texture, stroke.style.blend_mode, stroke.style.color.a() * 100.0);
 	}
@@ -361,28 +577,11 @@ async fn brush(image: ImageFrame<Color>, bounds: ImageFrame<Color>, strokes: Vec
 		let mut erase_restore_mask = opaque_image;
 
 		for stroke in erase_restore_strokes {
-			let brush_texture = cache.get_cached_brush(&stroke.style).unwrap_or_else(|| {
-				let tex = create_brush_texture(&stroke.style);
-				cache.store_brush(stroke.style.clone(), tex.clone());
-				tex
-			});
-			let positions: Vec<_> = stroke.compute_blit_points().into_iter().collect();
-
-			match stroke.style.blend_mode {
-				BlendMode::Erase => {
-					let blend_params = BlendNode::new(CopiedNode::new(BlendMode::Erase), CopiedNode::new(100.));
-					let blit_node = BlitNode::new(ClonedNode::new(brush_texture), ClonedNode::new(positions), ClonedNode::new(blend_params));
-					erase_restore_mask = blit_node.eval(erase_restore_mask);
-				}
-
-				// Yes, this is essentially the same as the above, but we duplicate to inline the blend mode.
-				BlendMode::Restore => {
-					let blend_params = BlendNode::new(CopiedNode::new(BlendMode::Restore), CopiedNode::new(100.));
-					let blit_node = BlitNode::new(ClonedNode::new(brush_texture), ClonedNode::new(positions), ClonedNode::new(blend_params));
-					erase_restore_mask = blit_node.eval(erase_restore_mask);
-				}
-
-				_ => unreachable!(),
+			let samples = stroke.compute_blit_samples();
+			let before = (!stroke.clip.is_empty()).then(|| erase_restore_mask.clone());
+			erase_restore_mask = blit_stroke(erase_restore_mask, &stroke.style, samples, stroke.style.blend_mode, &cache);
+			if let Some(before) = before {
+				erase_restore_mask = confine_to_clip(erase_restore_mask, Some(&before), &stroke.clip);
 			}
 		}
 
@@ -434,6 +633,161 @@ mod test {
 		assert_eq!(image.sample(DVec2::splat(0.), DVec2::ONE), Some(Color::BLACK));
 	}
 
+	#[test]
+	fn test_clone_from_source() {
+		// A 4x1 source image in layer space from (0, 0) to (4, 1), with a distinct color per pixel
+		let colors = [Color::RED, Color::GREEN, Color::BLUE, Color::WHITE];
+		let source = ImageFrame {
+			image: Image {
+				width: 4,
+				height: 1,
+				data: colors.to_vec(),
+				..Default::default()
+			},
+			transform: DAffine2::from_scale(DVec2::new(4., 1.)),
+			..Default::default()
+		};
+
+		// A 2x1 mask covering layer space from (2, 0) to (4, 1), with the second pixel half covered
+		let mask = ImageFrame {
+			image: Image {
+				width: 2,
+				height: 1,
+				data: vec![Color::WHITE, Color::WHITE.apply_opacity(0.5)],
+				..Default::default()
+			},
+			transform: DAffine2::from_scale_angle_translation(DVec2::new(2., 1.), 0., DVec2::new(2., 0.)),
+			..Default::default()
+		};
+
+		let cloned = clone_from_source(mask.clone(), &source, DVec2::new(-2., 0.));
+		assert_eq!(cloned.image.data, vec![Color::RED, Color::GREEN.apply_opacity(0.5)]);
+
+		// Sampling outside of the source image gives transparency
+		let cloned = clone_from_source(mask, &source, DVec2::new(1., 0.));
+		assert_eq!(cloned.image.data, vec![Color::WHITE, Color::TRANSPARENT]);
+	}
+
+	#[test]
+	fn test_confine_to_clip() {
+		// A 4x1 stroke texture covering layer space from (0, 0) to (4, 1), clipped to the left half and a quarter of the third pixel
+		let painted = ImageFrame {
+			image: Image::new(4, 1, Color::BLACK),
+			transform: DAffine2::from_scale(DVec2::new(4., 1.)),
+			..Default::default()
+		};
+		let clip = vec![vec![DVec2::new(0., 0.), DVec2::new(2.25, 0.), DVec2::new(2.25, 1.), DVec2::new(0., 1.)]];
+
+		let confined = confine_to_clip(painted.clone(), None, &clip);
+		assert_eq!(confined.image.data, vec![Color::BLACK, Color::BLACK, Color::BLACK.apply_opacity(0.25), Color::TRANSPARENT]);
+
+		// Erasing keeps the pixels from before the stroke outside of the clip
+		let before = ImageFrame {
+			image: Image::new(4, 1, Color::WHITE),
+			..painted.clone()
+		};
+		let confined = confine_to_clip(painted, Some(&before), &clip);
+		assert_eq!(confined.image.data[3], Color::WHITE);
+	}
+
+	#[test]
+	fn test_brush_dynamics() {
+		use graphene_core::vector::brush_stroke::{BrushDynamicsInput, BrushInputSample};
+
+		let mut style = BrushStyle {
+			diameter: 20.,
+			spacing: 50.,
+			..Default::default()
+		};
+		style.dynamics.diameter = BrushDynamicsInput::Pressure;
+		let light_sample = BrushInputSample {
+			pressure: 0.5,
+			..BrushInputSample::new(DVec2::new(20., 0.))
+		};
+		let stroke = BrushStroke {
+			style,
+			trace: vec![BrushInputSample::new(DVec2::ZERO), light_sample],
+			clone_offset: None,
+			healing: false,
+			clip: Vec::new(),
+		};
+
+		// The pressure is interpolated between the samples of the trace, scaling the diameter of each stamp
+		let samples = stroke.compute_blit_samples();
+		let positions: Vec<_> = samples.iter().map(|sample| sample.position.x).collect();
+		let diameters: Vec<_> = samples.iter().map(|sample| stroke.style.at_sample(sample).diameter).collect();
+		assert_eq!(positions, [0., 10., 20.]);
+		assert_eq!(diameters, [20., 15., 10.]);
+	}
+
+	fn gray(value: f32) -> Color {
+		Color::from_rgbaf32_unchecked(value, value, value, 1.)
+	}
+
+	fn row_image(values: impl IntoIterator<Item = f32>) -> ImageFrame<Color> {
+		let data: Vec<_> = values.into_iter().map(gray).collect();
+		let width = data.len() as u32;
+		ImageFrame {
+			image: Image {
+				width,
+				height: 1,
+				data,
+				..Default::default()
+			},
+			transform: DAffine2::from_scale(DVec2::new(width as f64, 1.)),
+			..Default::default()
+		}
+	}
+
+	fn single_pixel_mask(x: f64) -> ImageFrame<Color> {
+		ImageFrame {
+			image: Image::new(1, 1, Color::WHITE),
+			transform: DAffine2::from_translation(DVec2::new(x, 0.)),
+			..Default::default()
+		}
+	}
+
+	fn only_pixel(image: ImageFrame<Color>) -> Color {
+		assert_eq!(image.image.data.len(), 1);
+		image.image.data[0]
+	}
+
+	#[test]
+	fn test_heal_from_source() {
+		// A flat area on the left and a textured area alternating between 0.5 and 0.7 on the right
+		let source = row_image((0..16).map(|x| {
+			if x < 8 {
+				0.2
+			} else if x % 2 == 0 {
+				0.5
+			} else {
+				0.7
+			}
+		}));
+
+		// Taking the texture of pixel 12 into pixel 2 keeps its difference from its surroundings, but moved to the flat area's color
+		let healed = only_pixel(heal_from_source(single_pixel_mask(2.), &source, Some(DVec2::new(10., 0.)), 4.));
+		let expected = 0.5 + 0.2 - (0.7 + 0.5 + 0.7) / 3.;
+		assert!((healed.r() - expected).abs() < 1e-5);
+		assert_eq!(healed.a(), 1.);
+	}
+
+	#[test]
+	fn test_heal_with_automatic_source() {
+		// A textured area on the left, a blemish in the middle of a 0.3 area, and a flat 0.5 area on the right
+		let source = row_image((0..32).map(|x| match x {
+			0..=12 if x % 2 == 0 => 0.1,
+			0..=12 => 0.9,
+			16 => 0.,
+			13..=19 => 0.3,
+			_ => 0.5,
+		}));
+
+		// The smooth area on the right is picked, so the blemish takes on the color around it
+		let healed = only_pixel(heal_from_source(single_pixel_mask(16.), &source, None, 4.));
+		assert!((healed.r() - 0.2).abs() < 1e-5);
+	}
+
 	#[test]
 	fn test_brush() {
 		let brush_texture_node = BrushStampGeneratorNode::new(ClonedNode::new(Color::BLACK), ClonedNode::new(1.), ClonedNode::new(1.));
@@ -0,0 +1,553 @@
+use graphene_core::raster::{Image, ImageFrame, ResampleFilter};
+use graphene_core::{Color, Node};
+
+use glam::{DVec2, UVec2};
+
+/// The red, green, blue and alpha channels of a pixel, summed up and scaled freely while filtering.
+///
+/// Image data stores colors with associated (premultiplied) alpha, so averaging the channels directly gives transparent pixels no say in the color of their neighbors.
+type Channels = [f32; 4];
+
+fn add_weighted(sum: &mut Channels, pixel: Channels, weight: f32) {
+	sum.iter_mut().zip(pixel).for_each(|(sum, channel)| *sum += channel * weight);
+}
+
+fn to_color([red, green, blue, alpha]: Channels) -> Color {
+	let alpha = alpha.clamp(0., 1.);
+	if alpha == 0. {
+		return Color::TRANSPARENT;
+	}
+	// Sharpening kernels have negative weights which can overshoot below zero
+	Color::from_rgbaf32_unchecked(red.max(0.), green.max(0.), blue.max(0.), alpha)
+}
+
+/// Runs the filter over the pixels of the frame, keeping its transform and blending.
+fn filter_frame(image_frame: ImageFrame<Color>, filter: impl FnOnce(&[Channels], usize, usize) -> Vec<Channels>) -> ImageFrame<Color> {
+	let (width, height) = (image_frame.image.width as usize, image_frame.image.height as usize);
+	if width == 0 || height == 0 {
+		return image_frame;
+	}
+
+	let data: Vec<Channels> = image_frame.image.data.iter().map(|color| [color.r(), color.g(), color.b(), color.a()]).collect();
+	let data = filter(&data, width, height);
+
+	ImageFrame {
+		image: Image {
+			width: width as u32,
+			height: height as u32,
+			data: data.into_iter().map(to_color).collect(),
+			base64_string: None,
+		},
+		..image_frame
+	}
+}
+
+/// Reads the pixel at the position, where positions beyond the edges of the image repeat the nearest edge pixel.
+fn clamped_pixel(data: &[Channels], width: usize, height: usize, x: i64, y: i64) -> Channels {
+	let x = x.clamp(0, width as i64 - 1) as usize;
+	let y = y.clamp(0, height as i64 - 1) as usize;
+	data[y * width + x]
+}
+
+/// Convolves each row (or column) with the kernel, whose middle element lines up with the pixel being computed.
+fn convolve_1d(data: &[Channels], width: usize, height: usize, kernel: &[f32], horizontal: bool) -> Vec<Channels> {
+	let radius = (kernel.len() / 2) as i64;
+	let mut output = vec![[0.; 4]; data.len()];
+
+	for y in 0..height {
+		for x in 0..width {
+			let sum = &mut output[y * width + x];
+			for (index, &weight) in kernel.iter().enumerate() {
+				let offset = index as i64 - radius;
+				let (x, y) = if horizontal { (x as i64 + offset, y as i64) } else { (x as i64, y as i64 + offset) };
+				add_weighted(sum, clamped_pixel(data, width, height, x, y), weight);
+			}
+		}
+	}
+
+	output
+}
+
+/// Convolves the image with a square kernel given in row-major order.
+fn convolve_2d(data: &[Channels], width: usize, height: usize, kernel: &[f32], size: usize) -> Vec<Channels> {
+	let radius = (size / 2) as i64;
+	let mut output = vec![[0.; 4]; data.len()];
+
+	for y in 0..height {
+		for x in 0..width {
+			let sum = &mut output[y * width + x];
+			for (index, &weight) in kernel.iter().enumerate() {
+				let (offset_x, offset_y) = ((index % size) as i64 - radius, (index / size) as i64 - radius);
+				add_weighted(sum, clamped_pixel(data, width, height, x as i64 + offset_x, y as i64 + offset_y), weight);
+			}
+		}
+	}
+
+	output
+}
+
+/// Averages each pixel with the `radius` pixels to either side along the rows (or columns), using a running sum so the cost doesn't grow with the radius.
+fn box_blur_1d(data: &[Channels], width: usize, height: usize, radius: usize, horizontal: bool) -> Vec<Channels> {
+	let (length, lines) = if horizontal { (width, height) } else { (height, width) };
+	let index = |line: usize, position: usize| if horizontal { line * width + position } else { position * width + line };
+	let radius = radius as i64;
+	let scale = 1. / (2 * radius + 1) as f32;
+	let mut output = vec![[0.; 4]; data.len()];
+
+	for line in 0..lines {
+		let pixel = |position: i64| data[index(line, position.clamp(0, length as i64 - 1) as usize)];
+
+		let mut sum = [0.; 4];
+		for position in -radius..=radius {
+			add_weighted(&mut sum, pixel(position), 1.);
+		}
+		for position in 0..length {
+			output[index(line, position)] = sum.map(|channel| channel * scale);
+			add_weighted(&mut sum, pixel(position as i64 + radius + 1), 1.);
+			add_weighted(&mut sum, pixel(position as i64 - radius), -1.);
+		}
+	}
+
+	output
+}
+
+/// A normalized Gaussian kernel wide enough to cover three standard deviations to either side.
+fn gaussian_kernel(sigma: f64) -> Vec<f32> {
+	let radius = (sigma * 3.).ceil() as i64;
+	let weights: Vec<f64> = (-radius..=radius).map(|x| (-(x * x) as f64 / (2. * sigma * sigma)).exp()).collect();
+	let total: f64 = weights.iter().sum();
+	weights.into_iter().map(|weight| (weight / total) as f32).collect()
+}
+
+fn gaussian_blur_data(data: &[Channels], width: usize, height: usize, radius: f64) -> Vec<Channels> {
+	// The Gaussian is separable, so blurring the rows and then the columns is the same as the full 2D convolution
+	let kernel = gaussian_kernel(radius);
+	let data = convolve_1d(data, width, height, &kernel, true);
+	convolve_1d(&data, width, height, &kernel, false)
+}
+
+/// Splits the square kernel into a column and a row whose outer product is the kernel, if there are such vectors.
+fn separate_kernel(kernel: &[f32], size: usize) -> Option<(Vec<f32>, Vec<f32>)> {
+	let (pivot, &pivot_weight) = kernel.iter().enumerate().max_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))?;
+	if pivot_weight == 0. {
+		return None;
+	}
+
+	let (pivot_x, pivot_y) = (pivot % size, pivot / size);
+	let row: Vec<f32> = kernel[pivot_y * size..(pivot_y + 1) * size].to_vec();
+	let column: Vec<f32> = (0..size).map(|y| kernel[y * size + pivot_x] / pivot_weight).collect();
+
+	let tolerance = pivot_weight.abs() * 1e-5;
+	let separable = kernel.iter().enumerate().all(|(index, &weight)| (column[index / size] * row[index % size] - weight).abs() <= tolerance);
+	separable.then_some((column, row))
+}
+
+/// Blurs the image with a Gaussian whose standard deviation is the radius, in pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct GaussianBlurNode<Radius> {
+	radius: Radius,
+}
+
+#[node_macro::node_fn(GaussianBlurNode)]
+fn gaussian_blur(image_frame: ImageFrame<Color>, radius: f64) -> ImageFrame<Color> {
+	if radius <= 0. {
+		return image_frame;
+	}
+	filter_frame(image_frame, |data, width, height| gaussian_blur_data(data, width, height, radius))
+}
+
+/// Averages each pixel with the square of pixels within the radius around it.
+#[derive(Debug, Clone, Copy)]
+pub struct BoxBlurNode<Radius> {
+	radius: Radius,
+}
+
+#[node_macro::node_fn(BoxBlurNode)]
+fn box_blur(image_frame: ImageFrame<Color>, radius: f64) -> ImageFrame<Color> {
+	let radius = radius.round().max(0.) as usize;
+	if radius == 0 {
+		return image_frame;
+	}
+	filter_frame(image_frame, |data, width, height| {
+		let data = box_blur_1d(data, width, height, radius, true);
+		box_blur_1d(&data, width, height, radius, false)
+	})
+}
+
+/// Averages each pixel along a line through it, as if the camera moved the distance (in pixels) in the direction of the angle (in degrees) while exposing.
+#[derive(Debug, Clone, Copy)]
+pub struct MotionBlurNode<Angle, Distance> {
+	angle: Angle,
+	distance: Distance,
+}
+
+#[node_macro::node_fn(MotionBlurNode)]
+fn motion_blur(image_frame: ImageFrame<Color>, angle: f64, distance: f64) -> ImageFrame<Color> {
+	if distance <= 0. {
+		return image_frame;
+	}
+
+	filter_frame(image_frame, |data, width, height| {
+		// One sample per pixel of travel, spread evenly between both ends of the line
+		let samples = distance.ceil() as usize + 1;
+		let direction = DVec2::from_angle(angle.to_radians()) * distance;
+		let offsets: Vec<DVec2> = (0..samples).map(|sample| direction * (sample as f64 / (samples - 1) as f64 - 0.5)).collect();
+		let weight = 1. / samples as f32;
+
+		let bilinear = |position: DVec2| {
+			let floor = position.floor();
+			let fraction = (position - floor).as_vec2();
+			let (x, y) = (floor.x as i64, floor.y as i64);
+			let mut sum = [0.; 4];
+			add_weighted(&mut sum, clamped_pixel(data, width, height, x, y), (1. - fraction.x) * (1. - fraction.y));
+			add_weighted(&mut sum, clamped_pixel(data, width, height, x + 1, y), fraction.x * (1. - fraction.y));
+			add_weighted(&mut sum, clamped_pixel(data, width, height, x, y + 1), (1. - fraction.x) * fraction.y);
+			add_weighted(&mut sum, clamped_pixel(data, width, height, x + 1, y + 1), fraction.x * fraction.y);
+			sum
+		};
+
+		let mut output = vec![[0.; 4]; data.len()];
+		for y in 0..height {
+			for x in 0..width {
+				let sum = &mut output[y * width + x];
+				for &offset in &offsets {
+					add_weighted(sum, bilinear(DVec2::new(x as f64, y as f64) + offset), weight);
+				}
+			}
+		}
+		output
+	})
+}
+
+/// Sharpens the image by adding the difference between it and a Gaussian blurred copy, scaled by the amount (in percent).
+/// Pixels which differ from the blurred copy by less than the threshold (in percent) are left alone, so flat areas don't gain noise.
+#[derive(Debug, Clone, Copy)]
+pub struct UnsharpMaskNode<Radius, Amount, Threshold> {
+	radius: Radius,
+	amount: Amount,
+	threshold: Threshold,
+}
+
+#[node_macro::node_fn(UnsharpMaskNode)]
+fn unsharp_mask(image_frame: ImageFrame<Color>, radius: f64, amount: f64, threshold: f64) -> ImageFrame<Color> {
+	if radius <= 0. || amount == 0. {
+		return image_frame;
+	}
+
+	filter_frame(image_frame, |data, width, height| {
+		let blurred = gaussian_blur_data(data, width, height, radius);
+		let (amount, threshold) = (amount as f32 / 100., threshold as f32 / 100.);
+
+		data.iter()
+			.zip(blurred)
+			.map(|(&[red, green, blue, alpha], [blurred_red, blurred_green, blurred_blue, _])| {
+				let difference = [red - blurred_red, green - blurred_green, blue - blurred_blue];
+				if difference.iter().all(|channel| channel.abs() < threshold) {
+					return [red, green, blue, alpha];
+				}
+				// The result stays within the original coverage, so the edges of opaque areas don't sprout halos
+				let sharpen = |channel: f32, difference: f32| (channel + difference * amount).min(alpha);
+				[sharpen(red, difference[0]), sharpen(green, difference[1]), sharpen(blue, difference[2]), alpha]
+			})
+			.collect()
+	})
+}
+
+/// Convolves the image with a square kernel of odd width, listed row by row. When normalizing, the weights are scaled to sum to one.
+#[derive(Debug, Clone)]
+pub struct ConvolutionNode<Kernel, Normalize> {
+	kernel: Kernel,
+	normalize: Normalize,
+}
+
+#[node_macro::node_fn(ConvolutionNode)]
+fn convolution(image_frame: ImageFrame<Color>, kernel: Vec<f64>, normalize: bool) -> ImageFrame<Color> {
+	let size = (kernel.len() as f64).sqrt() as usize;
+	if size * size != kernel.len() || size % 2 == 0 {
+		warn!("The convolution kernel has {} weights, which is not the square of an odd number", kernel.len());
+		return image_frame;
+	}
+
+	let total: f64 = kernel.iter().sum();
+	let scale = if normalize && total != 0. { 1. / total } else { 1. };
+	let kernel: Vec<f32> = kernel.iter().map(|weight| (weight * scale) as f32).collect();
+
+	filter_frame(image_frame, |data, width, height| match separate_kernel(&kernel, size) {
+		Some((column, row)) => {
+			let data = convolve_1d(data, width, height, &row, true);
+			convolve_1d(&data, width, height, &column, false)
+		}
+		None => convolve_2d(data, width, height, &kernel, size),
+	})
+}
+
+fn sinc(x: f64) -> f64 {
+	if x == 0. {
+		return 1.;
+	}
+	let x = x * core::f64::consts::PI;
+	x.sin() / x
+}
+
+/// How far from the sample position (in input pixels) the filter reaches when the image isn't shrunk.
+fn resample_support(filter: ResampleFilter) -> f64 {
+	match filter {
+		ResampleFilter::Nearest => 0.5,
+		ResampleFilter::Bilinear => 1.,
+		ResampleFilter::Bicubic => 2.,
+		ResampleFilter::Lanczos => 3.,
+	}
+}
+
+/// The weight of the filter at the distance from the sample position.
+fn resample_weight(filter: ResampleFilter, x: f64) -> f64 {
+	let x = x.abs();
+	match filter {
+		ResampleFilter::Nearest => (x < 0.5) as u8 as f64,
+		ResampleFilter::Bilinear => (1. - x).max(0.),
+		// The Catmull-Rom spline, which passes through the pixels it samples
+		ResampleFilter::Bicubic => {
+			const A: f64 = -0.5;
+			if x < 1. {
+				((A + 2.) * x - (A + 3.)) * x * x + 1.
+			} else if x < 2. {
+				((A * x - 5. * A) * x + 8. * A) * x - 4. * A
+			} else {
+				0.
+			}
+		}
+		ResampleFilter::Lanczos => {
+			if x < 3. {
+				sinc(x) * sinc(x / 3.)
+			} else {
+				0.
+			}
+		}
+	}
+}
+
+/// For each output pixel along an axis, the first input pixel it reads along with the weights of that pixel and the ones following it.
+fn resample_weights(input: usize, output: usize, filter: ResampleFilter) -> Vec<(usize, Vec<f32>)> {
+	let scale = input as f64 / output as f64;
+	if filter == ResampleFilter::Nearest {
+		return (0..output).map(|position| ((((position as f64 + 0.5) * scale) as usize).min(input - 1), vec![1.])).collect();
+	}
+
+	// When shrinking, the filter is stretched over all the input pixels within each output pixel, so fine details average out instead of aliasing
+	let stretch = scale.max(1.);
+	let support = resample_support(filter) * stretch;
+
+	(0..output)
+		.map(|position| {
+			let center = (position as f64 + 0.5) * scale;
+			let start = (center - support).floor().max(0.) as usize;
+			let end = ((center + support).ceil() as usize).clamp(start + 1, input);
+
+			// Input pixels beyond the edges are left out, with the remaining weights normalized to make up for them
+			let weights: Vec<f64> = (start..end).map(|index| resample_weight(filter, (index as f64 + 0.5 - center) / stretch)).collect();
+			let total: f64 = weights.iter().sum();
+			let scale = if total != 0. { 1. / total } else { 1. };
+			(start, weights.into_iter().map(|weight| (weight * scale) as f32).collect())
+		})
+		.collect()
+}
+
+/// Resamples the rows (or columns) of the image to the number of pixels given by the weights.
+fn resample_1d(data: &[Channels], width: usize, height: usize, weights: &[(usize, Vec<f32>)], horizontal: bool) -> Vec<Channels> {
+	let (output_width, output_height) = if horizontal { (weights.len(), height) } else { (width, weights.len()) };
+	let mut output = vec![[0.; 4]; output_width * output_height];
+
+	for y in 0..output_height {
+		for x in 0..output_width {
+			let (position, line) = if horizontal { (x, y) } else { (y, x) };
+			let (start, kernel) = &weights[position];
+			let sum = &mut output[y * output_width + x];
+			for (index, &weight) in (*start..).zip(kernel) {
+				let pixel = if horizontal { data[line * width + index] } else { data[index * width + line] };
+				add_weighted(sum, pixel, weight);
+			}
+		}
+	}
+
+	output
+}
+
+/// Resamples the image to a new resolution in pixels using the filter. The new image covers the same area as before, since the transform of the frame is kept.
+#[derive(Debug, Clone, Copy)]
+pub struct ResampleNode<Resolution, Filter> {
+	resolution: Resolution,
+	filter: Filter,
+}
+
+#[node_macro::node_fn(ResampleNode)]
+fn resample(image_frame: ImageFrame<Color>, resolution: UVec2, filter: ResampleFilter) -> ImageFrame<Color> {
+	let (width, height) = (image_frame.image.width as usize, image_frame.image.height as usize);
+	let (new_width, new_height) = (resolution.x as usize, resolution.y as usize);
+	if (width, height) == (new_width, new_height) {
+		return image_frame;
+	}
+	if width == 0 || height == 0 || new_width == 0 || new_height == 0 {
+		// There's nothing to sample, but the resolution is still honored
+		return ImageFrame {
+			image: Image::new(resolution.x, resolution.y, Color::TRANSPARENT),
+			..image_frame
+		};
+	}
+
+	let data: Vec<Channels> = image_frame.image.data.iter().map(|color| [color.r(), color.g(), color.b(), color.a()]).collect();
+	let data = resample_1d(&data, width, height, &resample_weights(width, new_width, filter), true);
+	let data = resample_1d(&data, new_width, height, &resample_weights(height, new_height, filter), false);
+
+	ImageFrame {
+		image: Image {
+			width: resolution.x,
+			height: resolution.y,
+			// The negative lobes of the bicubic and Lanczos filters can overshoot, which mustn't leave a color channel above the alpha it's associated with
+			data: data
+				.into_iter()
+				.map(|[red, green, blue, alpha]| {
+					let alpha = alpha.clamp(0., 1.);
+					to_color([red.min(alpha), green.min(alpha), blue.min(alpha), alpha])
+				})
+				.collect(),
+			base64_string: None,
+		},
+		..image_frame
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graphene_core::value::{ClonedNode, CopiedNode};
+
+	fn frame(width: u32, height: u32, data: Vec<Color>) -> ImageFrame<Color> {
+		ImageFrame {
+			image: Image {
+				width,
+				height,
+				data,
+				base64_string: None,
+			},
+			..Default::default()
+		}
+	}
+
+	fn assert_close(a: Color, b: Color) {
+		let channels = |color: Color| [color.r(), color.g(), color.b(), color.a()];
+		assert!(channels(a).iter().zip(channels(b)).all(|(a, b)| (a - b).abs() < 1e-4), "{a:?} != {b:?}");
+	}
+
+	#[test]
+	fn blurs_keep_uniform_images() {
+		let color = Color::from_rgbaf32_unchecked(0.2, 0.3, 0.4, 0.5);
+		let image = frame(5, 4, vec![color; 20]);
+
+		let results = [
+			GaussianBlurNode { radius: CopiedNode(2.) }.eval(image.clone()),
+			BoxBlurNode { radius: CopiedNode(3.) }.eval(image.clone()),
+			MotionBlurNode {
+				angle: CopiedNode(30.),
+				distance: CopiedNode(4.),
+			}
+			.eval(image.clone()),
+		];
+		for result in results {
+			assert_eq!((result.image.width, result.image.height), (5, 4));
+			result.image.data.into_iter().for_each(|pixel| assert_close(pixel, color));
+		}
+	}
+
+	#[test]
+	fn transparent_pixels_do_not_darken() {
+		let image = frame(2, 1, vec![Color::RED, Color::TRANSPARENT]);
+		let result = BoxBlurNode { radius: CopiedNode(1.) }.eval(image);
+
+		// The pixels become partially transparent but remain fully red
+		for pixel in result.image.data {
+			assert_close(pixel.to_unassociated_alpha().with_alpha(1.), Color::RED);
+		}
+	}
+
+	#[test]
+	fn separable_and_full_convolution_agree() {
+		let data = (0..25).map(|i| Color::from_rgbaf32_unchecked(i as f32 / 25., 0.5, 1. - i as f32 / 25., 1.)).collect();
+		let image = frame(5, 5, data);
+
+		let separable = vec![1., 2., 1., 2., 4., 2., 1., 2., 1.];
+		assert!(separate_kernel(&separable.iter().map(|&weight| weight as f32).collect::<Vec<_>>(), 3).is_some());
+		let result = ConvolutionNode {
+			kernel: ClonedNode(separable.clone()),
+			normalize: CopiedNode(true),
+		}
+		.eval(image.clone());
+
+		let full = filter_frame(image, |data, width, height| {
+			let kernel: Vec<f32> = separable.iter().map(|&weight| weight as f32 / 16.).collect();
+			convolve_2d(data, width, height, &kernel, 3)
+		});
+		result.image.data.into_iter().zip(full.image.data).for_each(|(a, b)| assert_close(a, b));
+	}
+
+	#[test]
+	fn unsharp_mask_increases_contrast() {
+		let gray = Color::from_rgbf32_unchecked(0.5, 0.5, 0.5);
+		let image = frame(3, 1, vec![gray, Color::WHITE, gray]);
+		let result = UnsharpMaskNode {
+			radius: CopiedNode(1.),
+			amount: CopiedNode(100.),
+			threshold: CopiedNode(0.),
+		}
+		.eval(image);
+
+		assert!(result.image.data[0].r() < 0.5);
+		assert_eq!(result.image.data[1], Color::WHITE);
+	}
+
+	#[test]
+	fn resampling_keeps_uniform_images() {
+		let color = Color::from_rgbaf32_unchecked(0.2, 0.3, 0.4, 0.5);
+		let image = ImageFrame {
+			transform: glam::DAffine2::from_scale(DVec2::new(30., 20.)),
+			..frame(3, 2, vec![color; 6])
+		};
+
+		for &filter in ResampleFilter::list() {
+			for resolution in [UVec2::new(7, 5), UVec2::new(2, 1)] {
+				let result = ResampleNode {
+					resolution: CopiedNode(resolution),
+					filter: CopiedNode(filter),
+				}
+				.eval(image.clone());
+
+				assert_eq!((result.image.width, result.image.height), (resolution.x, resolution.y));
+				assert_eq!(result.transform, image.transform);
+				result.image.data.into_iter().for_each(|pixel| assert_close(pixel, color));
+			}
+		}
+	}
+
+	#[test]
+	fn resampling_filters() {
+		let image = frame(2, 1, vec![Color::RED, Color::BLUE]);
+		let resample = |resolution: UVec2, filter: ResampleFilter| {
+			ResampleNode {
+				resolution: CopiedNode(resolution),
+				filter: CopiedNode(filter),
+			}
+			.eval(image.clone())
+			.image
+			.data
+		};
+
+		assert_eq!(resample(UVec2::new(4, 1), ResampleFilter::Nearest), vec![Color::RED, Color::RED, Color::BLUE, Color::BLUE]);
+
+		// Shrinking averages all of the pixels covered by the output pixel
+		let average = resample(UVec2::new(1, 1), ResampleFilter::Bilinear);
+		assert_close(average[0], Color::from_rgbaf32_unchecked(0.5, 0., 0.5, 1.));
+
+		// An empty resolution gives an empty image rather than failing
+		assert!(resample(UVec2::new(0, 3), ResampleFilter::Lanczos).is_empty());
+	}
+}
@@ -0,0 +1,171 @@
+use graphene_core::raster::{Image, ImageFrame};
+use graphene_core::{Color, Node};
+
+use glam::{DAffine2, DVec2};
+use std::collections::VecDeque;
+
+/// How different two colors are, from 0 for identical colors to 1 for opposites such as opaque white and transparent black.
+/// It's the largest difference between any of their (premultiplied) channels, so a change in any one channel is enough to tell them apart.
+fn color_difference(a: Color, b: Color) -> f32 {
+	[a.r() - b.r(), a.g() - b.g(), a.b() - b.b(), a.a() - b.a()]
+		.into_iter()
+		.fold(0., |max, difference| max.max(difference.abs()))
+}
+
+/// Finds the pixels which match the color of the seed pixel, as the coverage of each pixel by the fill from 0 (untouched) to 1 (filled).
+///
+/// A pixel matches when its color differs from the seed's by no more than the tolerance, a percentage of the largest possible difference.
+/// A contiguous fill spreads out from the seed through its four neighbors of each matching pixel, while a global fill covers every matching pixel in the image.
+/// With anti-aliasing, pixels bordering the filled area are partially covered the closer their colors are to matching, which smooths its edges.
+pub fn flood_fill_coverage(image: &Image<Color>, seed: (u32, u32), tolerance: f64, contiguous: bool, antialias: bool) -> Vec<f32> {
+	let (width, height) = (image.width as usize, image.height as usize);
+	let mut coverage = vec![0.; width * height];
+	let (seed_x, seed_y) = (seed.0 as usize, seed.1 as usize);
+	if seed_x >= width || seed_y >= height {
+		return coverage;
+	}
+
+	let tolerance = (tolerance / 100.).clamp(0., 1.) as f32;
+	let seed_color = image.data[seed_y * width + seed_x];
+	let differences: Vec<f32> = image.data.iter().map(|&color| color_difference(color, seed_color)).collect();
+
+	if contiguous {
+		coverage[seed_y * width + seed_x] = 1.;
+		let mut queue = VecDeque::from([(seed_x, seed_y)]);
+		while let Some((x, y)) = queue.pop_front() {
+			let neighbors = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+			for (x, y) in neighbors.into_iter().filter(|&(x, y)| x < width && y < height) {
+				let index = y * width + x;
+				if coverage[index] == 0. && differences[index] <= tolerance {
+					coverage[index] = 1.;
+					queue.push_back((x, y));
+				}
+			}
+		}
+	} else {
+		coverage
+			.iter_mut()
+			.zip(&differences)
+			.filter(|(_, &difference)| difference <= tolerance)
+			.for_each(|(coverage, _)| *coverage = 1.);
+	}
+
+	if antialias {
+		let filled = coverage.clone();
+		for y in 0..height {
+			for x in 0..width {
+				let index = y * width + x;
+				let bordering = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)]
+					.into_iter()
+					.any(|(x, y)| x < width && y < height && filled[y * width + x] == 1.);
+				if filled[index] == 0. && bordering {
+					// Fades from fully covered at the tolerance to uncovered for the most different color
+					coverage[index] = ((1. - differences[index]) / (1. - tolerance).max(f32::EPSILON)).clamp(0., 1.);
+				}
+			}
+		}
+	}
+
+	coverage
+}
+
+/// The pixel of the frame under the seed, given in the space the frame's transform maps the image into.
+pub fn seed_pixel(image_frame: &ImageFrame<Color>, seed: DVec2) -> Option<(u32, u32)> {
+	let size = DVec2::new(image_frame.image.width as f64, image_frame.image.height as f64);
+	let image_to_frame = image_frame.transform * DAffine2::from_scale(size.recip());
+	if image_to_frame.matrix2.determinant() == 0. {
+		return None;
+	}
+
+	let pixel = image_to_frame.inverse().transform_point2(seed).floor();
+	(pixel.cmpge(DVec2::ZERO).all() && pixel.cmplt(size).all()).then(|| (pixel.x as u32, pixel.y as u32))
+}
+
+/// Fills the area around the seed whose color matches the seed's pixel with a color, like a paint bucket.
+/// The seed is given in the space the frame's transform maps the image into, and nothing is filled if it lies outside the image.
+#[derive(Debug, Clone, Copy)]
+pub struct FloodFillNode<Seed, FillColor, Tolerance, Contiguous, Antialias> {
+	seed: Seed,
+	color: FillColor,
+	tolerance: Tolerance,
+	contiguous: Contiguous,
+	antialias: Antialias,
+}
+
+#[node_macro::node_fn(FloodFillNode)]
+fn flood_fill(mut image_frame: ImageFrame<Color>, seed: DVec2, color: Color, tolerance: f64, contiguous: bool, antialias: bool) -> ImageFrame<Color> {
+	let Some(seed) = seed_pixel(&image_frame, seed) else { return image_frame };
+
+	let coverage = flood_fill_coverage(&image_frame.image, seed, tolerance, contiguous, antialias);
+	// Image data has premultiplied alpha
+	let color = Color::from_unassociated_alpha(color.r(), color.g(), color.b(), color.a());
+	for (pixel, coverage) in image_frame.image.data.iter_mut().zip(coverage) {
+		if coverage > 0. {
+			*pixel = pixel.lerp(&color, coverage);
+		}
+	}
+
+	image_frame
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graphene_core::value::CopiedNode;
+
+	#[test]
+	fn fills_contiguous_and_global_areas() {
+		let (black, white) = (Color::BLACK, Color::WHITE);
+		// A white column splits the black pixels into two areas
+		#[rustfmt::skip]
+		let data = vec![
+			black, white, black,
+			black, white, black,
+		];
+		let image_frame = ImageFrame {
+			image: Image {
+				width: 3,
+				height: 2,
+				data,
+				base64_string: None,
+			},
+			transform: DAffine2::from_scale(DVec2::new(3., 2.)),
+			..Default::default()
+		};
+		let red = Color::from_rgbaf32_unchecked(1., 0., 0., 1.);
+
+		let fill = |contiguous, antialias| {
+			FloodFillNode {
+				seed: CopiedNode(DVec2::new(0.5, 1.5)),
+				color: CopiedNode(red),
+				tolerance: CopiedNode(10.),
+				contiguous: CopiedNode(contiguous),
+				antialias: CopiedNode(antialias),
+			}
+			.eval(image_frame.clone())
+			.image
+			.data
+		};
+
+		assert_eq!(fill(true, false), [red, white, black, red, white, black]);
+		assert_eq!(fill(false, false), [red, white, red, red, white, red]);
+		// The white pixels border the fill but are as different from black as can be, so anti-aliasing leaves them alone
+		assert_eq!(fill(true, true), [red, white, black, red, white, black]);
+
+		let gray = Color::from_rgbaf32_unchecked(0.5, 0.5, 0.5, 1.);
+		let coverage = flood_fill_coverage(
+			&Image {
+				width: 2,
+				height: 1,
+				data: vec![black, gray],
+				base64_string: None,
+			},
+			(0, 0),
+			0.,
+			true,
+			true,
+		);
+		assert_eq!(coverage, [1., 0.5]);
+	}
+}
@@ -23,9 +23,16 @@ pub mod image_segmentation;
 
 pub mod image_color_palette;
 
+pub mod filter;
+
 pub mod brush;
 
+pub mod flood_fill;
+
 #[cfg(feature = "wasm")]
 pub mod wasm_application_io;
 
+#[cfg(feature = "resvg")]
+pub mod pdf;
+
 pub mod imaginate;
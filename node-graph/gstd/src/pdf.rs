@@ -0,0 +1,511 @@
+//! Writes a usvg tree as a vector PDF document.
+//!
+//! Every stream is hex encoded, so the whole file is plain ASCII and can be passed around as a string like an SVG export.
+//! Paths with solid colors and padded linear or radial gradients, group transforms, opacity and clip paths, embedded PNG and JPEG images,
+//! and text which was converted to outlines by [`usvg::Tree::postprocess`] are supported.
+//! Anything else, such as masks, filters and patterns, fails the export rather than being left out of it.
+
+use glam::{DAffine2, DVec2};
+use resvg::usvg;
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// PDF user space units (points) per CSS pixel
+const POINTS_PER_PIXEL: f64 = 0.75;
+
+/// Serializes the tree into a single page PDF with the size of the tree converted from pixels to points.
+/// Fails with a description of the first part of the tree which can't be written.
+pub fn tree_to_pdf(tree: &usvg::Tree) -> Result<String, String> {
+	let page_size = DVec2::new(tree.size.width() as f64, tree.size.height() as f64) * POINTS_PER_PIXEL;
+
+	// PDF's y axis points up from the bottom of the page, so flip the SVG coordinate space
+	let page_transform = DAffine2::from_cols_array(&[POINTS_PER_PIXEL, 0., 0., -POINTS_PER_PIXEL, 0., page_size.y]);
+	let view_box = tree.view_box.rect;
+	let view_box_scale = DVec2::new(tree.size.width() as f64 / view_box.width() as f64, tree.size.height() as f64 / view_box.height() as f64);
+	let view_box_transform = DAffine2::from_scale(view_box_scale) * DAffine2::from_translation(-DVec2::new(view_box.x() as f64, view_box.y() as f64));
+
+	let mut writer = PdfWriter::default();
+	// The first objects are reserved for the document structure, which refers to objects created while writing the content
+	let [catalog, pages, page, content] = [(); 4].map(|_| writer.reserve());
+
+	let transform = page_transform * view_box_transform;
+	writer.content = format!("{} cm\n", matrix(transform));
+	writer.write_group(&tree.root, transform, 1.)?;
+
+	let content_stream = stream("", &writer.content);
+	let resources = writer.resources();
+	writer.set(catalog, format!("<< /Type /Catalog /Pages {pages} 0 R >>"));
+	writer.set(pages, format!("<< /Type /Pages /Kids [{page} 0 R] /Count 1 >>"));
+	writer.set(
+		page,
+		format!(
+			"<< /Type /Page /Parent {pages} 0 R /MediaBox [0 0 {} {}] /Resources {resources} /Contents {content} 0 R >>",
+			number(page_size.x),
+			number(page_size.y)
+		),
+	);
+	writer.set(content, content_stream);
+
+	Ok(writer.finish(catalog))
+}
+
+#[derive(Default)]
+struct PdfWriter {
+	/// Object bodies, where the object number is the index plus one
+	objects: Vec<String>,
+	content: String,
+	/// Graphics states keyed by their dictionary so each combination of opacities is only added once
+	graphics_states: HashMap<String, String>,
+	patterns: Vec<(String, usize)>,
+	images: Vec<(String, usize)>,
+}
+
+impl PdfWriter {
+	fn reserve(&mut self) -> usize {
+		self.objects.push(String::new());
+		self.objects.len()
+	}
+
+	fn set(&mut self, object: usize, body: String) {
+		self.objects[object - 1] = body;
+	}
+
+	fn add(&mut self, body: String) -> usize {
+		self.objects.push(body);
+		self.objects.len()
+	}
+
+	fn resources(&self) -> String {
+		let mut resources = String::from("<< ");
+		if !self.graphics_states.is_empty() {
+			let mut states: Vec<_> = self.graphics_states.iter().map(|(dictionary, name)| (name, dictionary)).collect();
+			states.sort();
+			resources.push_str("/ExtGState << ");
+			for (name, dictionary) in states {
+				let _ = write!(resources, "/{name} {dictionary} ");
+			}
+			resources.push_str(">> ");
+		}
+		for (category, entries) in [("Pattern", &self.patterns), ("XObject", &self.images)] {
+			if entries.is_empty() {
+				continue;
+			}
+			let _ = write!(resources, "/{category} << ");
+			for (name, object) in entries {
+				let _ = write!(resources, "/{name} {object} 0 R ");
+			}
+			resources.push_str(">> ");
+		}
+		resources.push_str(">>");
+		resources
+	}
+
+	fn finish(self, catalog: usize) -> String {
+		let mut pdf = String::from("%PDF-1.4\n");
+		let mut offsets = Vec::with_capacity(self.objects.len());
+		for (index, body) in self.objects.iter().enumerate() {
+			offsets.push(pdf.len());
+			let _ = writeln!(pdf, "{} 0 obj\n{body}\nendobj", index + 1);
+		}
+
+		let xref_offset = pdf.len();
+		let _ = writeln!(pdf, "xref\n0 {}\n0000000000 65535 f ", self.objects.len() + 1);
+		for offset in offsets {
+			let _ = writeln!(pdf, "{offset:010} 00000 n ");
+		}
+		let _ = writeln!(pdf, "trailer\n<< /Size {} /Root {catalog} 0 R >>\nstartxref\n{xref_offset}\n%%EOF", self.objects.len() + 1);
+		pdf
+	}
+
+	/// Selects a graphics state with the given fill and stroke opacities.
+	fn set_opacity(&mut self, fill: f64, stroke: f64) {
+		if fill >= 1. && stroke >= 1. {
+			return;
+		}
+		let dictionary = format!("<< /ca {} /CA {} >>", number(fill), number(stroke));
+		let count = self.graphics_states.len();
+		let name = self.graphics_states.entry(dictionary).or_insert_with(|| format!("G{}", count + 1));
+		let _ = writeln!(self.content, "/{name} gs");
+	}
+
+	/// Writes the group and its children, where `transform` maps the group's parent space to the page.
+	fn write_group(&mut self, group: &usvg::Group, transform: DAffine2, opacity: f64) -> Result<(), String> {
+		if group.mask.is_some() {
+			return Err("Masks can't be exported to PDF".to_string());
+		}
+		if !group.filters.is_empty() {
+			return Err("Filters, which are used by effects such as shadows and blurs, can't be exported to PDF".to_string());
+		}
+
+		let group_transform = usvg_transform(group.transform);
+		let transform = transform * group_transform;
+		let opacity = opacity * group.opacity.get() as f64;
+
+		self.content.push_str("q\n");
+		if group_transform != DAffine2::IDENTITY {
+			let _ = writeln!(self.content, "{} cm", matrix(group_transform));
+		}
+		if let Some(clip_path) = &group.clip_path {
+			self.write_clip_path(&clip_path.borrow(), group.bounding_box)?;
+		}
+		for child in &group.children {
+			match child {
+				usvg::Node::Group(group) => self.write_group(group, transform, opacity)?,
+				usvg::Node::Path(path) => self.write_path(path, transform, opacity)?,
+				usvg::Node::Image(image) => self.write_image(image, opacity)?,
+				usvg::Node::Text(text) => self.write_group(flattened_text(text)?, transform, opacity)?,
+			}
+		}
+		self.content.push_str("Q\n");
+		Ok(())
+	}
+
+	/// Intersects the clipping region with the clip path, which is given in the space of the group it clips.
+	/// PDF clips with a single path, so the clip path has to be made of one shape.
+	fn write_clip_path(&mut self, clip_path: &usvg::ClipPath, bounding_box: Option<usvg::Rect>) -> Result<(), String> {
+		let mut clip_transform = usvg_transform(clip_path.transform);
+		if clip_path.units == usvg::Units::ObjectBoundingBox {
+			let bounds = bounding_box.ok_or_else(|| "A clip path relative to the bounds of its group can't be exported to PDF without the bounds".to_string())?;
+			clip_transform = clip_transform * rect_transform(bounds);
+		}
+
+		// A clip path which is itself clipped is the intersection of the two
+		if let Some(clip_path) = &clip_path.clip_path {
+			self.write_clip_path(&clip_path.borrow(), bounding_box)?;
+		}
+
+		let mut shapes = Vec::new();
+		clip_shapes(&clip_path.root, clip_transform, &mut shapes)?;
+		match shapes.as_slice() {
+			// Nothing is visible through an empty clip path
+			[] => self.content.push_str("0 0 0 0 re W n\n"),
+			[(path, transform)] => {
+				let operator = if path.fill.as_ref().is_some_and(|fill| fill.rule == usvg::FillRule::EvenOdd) { "W*" } else { "W" };
+				let _ = writeln!(self.content, "{}{operator} n", path_data(&path.data, *transform));
+			}
+			_ => return Err("Clip paths made of more than one shape can't be exported to PDF".to_string()),
+		}
+		Ok(())
+	}
+
+	fn write_path(&mut self, path: &usvg::Path, transform: DAffine2, opacity: f64) -> Result<(), String> {
+		if path.visibility != usvg::Visibility::Visible {
+			return Ok(());
+		}
+		let data = path_data(&path.data, DAffine2::IDENTITY);
+		let bounds = rect_transform(path.data.bounds());
+
+		if let Some(fill) = &path.fill {
+			let paint = self.paint(&fill.paint, transform, bounds, false)?;
+			self.content.push_str("q\n");
+			self.set_opacity(opacity * fill.opacity.get() as f64, 1.);
+			let operator = if fill.rule == usvg::FillRule::EvenOdd { "f*" } else { "f" };
+			let _ = writeln!(self.content, "{paint}\n{data}{operator}\nQ");
+		}
+
+		if let Some(stroke) = &path.stroke {
+			let paint = self.paint(&stroke.paint, transform, bounds, true)?;
+			self.content.push_str("q\n");
+			self.set_opacity(1., opacity * stroke.opacity.get() as f64);
+			let line_cap = match stroke.linecap {
+				usvg::LineCap::Butt => 0,
+				usvg::LineCap::Round => 1,
+				usvg::LineCap::Square => 2,
+			};
+			let line_join = match stroke.linejoin {
+				usvg::LineJoin::Miter | usvg::LineJoin::MiterClip => 0,
+				usvg::LineJoin::Round => 1,
+				usvg::LineJoin::Bevel => 2,
+			};
+			let dashes = stroke.dasharray.iter().flatten().map(|&length| number(length as f64)).collect::<Vec<_>>().join(" ");
+			let _ = writeln!(
+				self.content,
+				"{paint}\n{} w {line_cap} J {line_join} j {} M [{dashes}] {} d\n{data}S\nQ",
+				number(stroke.width.get() as f64),
+				number(stroke.miterlimit.get() as f64),
+				number(stroke.dashoffset as f64)
+			);
+		}
+		Ok(())
+	}
+
+	/// The operators selecting the paint as the fill or stroke color.
+	fn paint(&mut self, paint: &usvg::Paint, transform: DAffine2, bounds: DAffine2, stroke: bool) -> Result<String, String> {
+		let (color_operator, pattern_operators) = if stroke { ("RG", "/Pattern CS") } else { ("rg", "/Pattern cs") };
+		let pattern_operator = if stroke { "SCN" } else { "scn" };
+
+		let (base, shading) = match paint {
+			usvg::Paint::Color(color) => return Ok(format!("{} {color_operator}", rgb(*color))),
+			usvg::Paint::LinearGradient(linear) => {
+				let coordinates = [linear.x1, linear.y1, linear.x2, linear.y2].map(|value| number(value as f64)).join(" ");
+				(&linear.base, format!("/ShadingType 2 /Coords [{coordinates}]"))
+			}
+			usvg::Paint::RadialGradient(radial) => {
+				let coordinates = [radial.fx, radial.fy, 0., radial.cx, radial.cy, radial.r.get()].map(|value| number(value as f64)).join(" ");
+				(&radial.base, format!("/ShadingType 3 /Coords [{coordinates}]"))
+			}
+			usvg::Paint::Pattern(_) => return Err("Pattern fills and strokes can't be exported to PDF".to_string()),
+		};
+		// PDF shadings can only extend their end colors past the ends of the gradient
+		if base.spread_method != usvg::SpreadMethod::Pad {
+			return Err("Gradients which repeat or reflect past their ends can't be exported to PDF".to_string());
+		}
+
+		let units = if base.units == usvg::Units::ObjectBoundingBox { bounds } else { DAffine2::IDENTITY };
+		// Pattern space is relative to the page rather than the current transformation matrix
+		let pattern_transform = transform * units * usvg_transform(base.transform);
+		let function = stops_function(&base.stops);
+		let pattern = self.add(format!(
+			"<< /PatternType 2 /Matrix [{}] /Shading << {shading} /ColorSpace /DeviceRGB /Function {function} /Extend [true true] >> >>",
+			matrix(pattern_transform)
+		));
+		let name = format!("P{}", self.patterns.len() + 1);
+		self.patterns.push((name.clone(), pattern));
+
+		Ok(format!("{pattern_operators} /{name} {pattern_operator}"))
+	}
+
+	/// Draws the image stretched over its view box, which matches the `preserveAspectRatio="none"` used when rendering image frames.
+	fn write_image(&mut self, image: &usvg::Image, opacity: f64) -> Result<(), String> {
+		if image.visibility != usvg::Visibility::Visible {
+			return Ok(());
+		}
+		let (data, format) = match &image.kind {
+			usvg::ImageKind::PNG(data) => (data, image::ImageFormat::Png),
+			usvg::ImageKind::JPEG(data) => (data, image::ImageFormat::Jpeg),
+			usvg::ImageKind::GIF(_) | usvg::ImageKind::SVG(_) => return Err("Only PNG and JPEG images can be exported to PDF".to_string()),
+		};
+		let decoded = image::load_from_memory_with_format(data, format)
+			.map_err(|error| format!("An image couldn't be decoded for the PDF export: {error}"))?
+			.to_rgba8();
+		let (width, height) = decoded.dimensions();
+		let interpolate = image.rendering_mode != usvg::ImageRendering::OptimizeSpeed;
+
+		let mut color = String::with_capacity(decoded.len() / 4 * 6 + 1);
+		let mut alpha = String::with_capacity(decoded.len() / 4 * 2 + 1);
+		for pixel in decoded.pixels() {
+			let [r, g, b, a] = pixel.0;
+			let _ = write!(color, "{r:02x}{g:02x}{b:02x}");
+			let _ = write!(alpha, "{a:02x}");
+		}
+		color.push('>');
+		alpha.push('>');
+
+		let dictionary = format!("/Type /XObject /Subtype /Image /Width {width} /Height {height} /BitsPerComponent 8 /Interpolate {interpolate} /Filter /ASCIIHexDecode");
+		let mut soft_mask = String::new();
+		if decoded.pixels().any(|pixel| pixel.0[3] != 255) {
+			let mask = self.add(stream(&format!("{dictionary} /ColorSpace /DeviceGray"), &alpha));
+			soft_mask = format!(" /SMask {mask} 0 R");
+		}
+		let object = self.add(stream(&format!("{dictionary} /ColorSpace /DeviceRGB{soft_mask}"), &color));
+		let name = format!("I{}", self.images.len() + 1);
+		self.images.push((name.clone(), object));
+
+		// Image space has its first row at the top of the unit square, so flip it back into the SVG orientation
+		let rect = image.view_box.rect;
+		let placement = DAffine2::from_cols_array(&[rect.width() as f64, 0., 0., -rect.height() as f64, rect.x() as f64, (rect.y() + rect.height()) as f64]);
+		self.content.push_str("q\n");
+		self.set_opacity(opacity, opacity);
+		let _ = writeln!(self.content, "{} cm\n/{name} Do\nQ", matrix(placement));
+		Ok(())
+	}
+}
+
+/// The outlines of the text, which only exist once the tree has been postprocessed with the fonts it uses.
+fn flattened_text(text: &usvg::Text) -> Result<&usvg::Group, String> {
+	text.flattened
+		.as_deref()
+		.ok_or_else(|| "Text can't be exported to PDF unless its font is loaded, so it can be converted to outlines".to_string())
+}
+
+/// Collects the visible paths making up the clip path, along with the transforms from their space to the space of the clipped group.
+fn clip_shapes<'a>(group: &'a usvg::Group, transform: DAffine2, shapes: &mut Vec<(&'a usvg::Path, DAffine2)>) -> Result<(), String> {
+	for child in &group.children {
+		match child {
+			usvg::Node::Path(path) if path.visibility == usvg::Visibility::Visible => shapes.push((path, transform)),
+			usvg::Node::Path(_) | usvg::Node::Image(_) => {}
+			usvg::Node::Group(group) if group.clip_path.is_none() => clip_shapes(group, transform * usvg_transform(group.transform), shapes)?,
+			usvg::Node::Group(_) => return Err("Clip paths containing other clip paths can't be exported to PDF".to_string()),
+			usvg::Node::Text(text) => {
+				let outlines = flattened_text(text)?;
+				clip_shapes(outlines, transform * usvg_transform(outlines.transform), shapes)?
+			}
+		}
+	}
+	Ok(())
+}
+
+/// A PDF function interpolating between the gradient stops, padded with the end colors outside of the first and last stop.
+fn stops_function(stops: &[usvg::Stop]) -> String {
+	let mut stops: Vec<_> = stops.iter().map(|stop| (stop.offset.get() as f64, rgb(stop.color))).collect();
+	let (Some(first), Some(last)) = (stops.first().cloned(), stops.last().cloned()) else {
+		return "<< /FunctionType 2 /Domain [0 1] /C0 [0 0 0] /C1 [0 0 0] /N 1 >>".to_string();
+	};
+	if first.0 > 0. {
+		stops.insert(0, (0., first.1));
+	}
+	if last.0 < 1. || stops.len() == 1 {
+		stops.push((1., last.1));
+	}
+
+	let segments: Vec<_> = stops
+		.windows(2)
+		.map(|pair| format!("<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>", pair[0].1, pair[1].1))
+		.collect();
+	if segments.len() == 1 {
+		return segments[0].clone();
+	}
+
+	let bounds = stops[1..stops.len() - 1].iter().map(|(offset, _)| number(*offset)).collect::<Vec<_>>().join(" ");
+	let encode = vec!["0 1"; segments.len()].join(" ");
+	format!("<< /FunctionType 3 /Domain [0 1] /Functions [{}] /Bounds [{bounds}] /Encode [{encode}] >>", segments.join(" "))
+}
+
+/// The operators drawing the path, with its points mapped by the transform.
+fn path_data(path: &usvg::tiny_skia_path::Path, transform: DAffine2) -> String {
+	use usvg::tiny_skia_path::PathVerb;
+
+	let mut data = String::new();
+	let mut points = path.points().iter().map(|point| transform.transform_point2(DVec2::new(point.x as f64, point.y as f64)));
+	let mut current = DVec2::ZERO;
+	let mut start = DVec2::ZERO;
+	let write_points = |data: &mut String, points: &[DVec2], operator: &str| {
+		for point in points {
+			let _ = write!(data, "{} {} ", number(point.x), number(point.y));
+		}
+		let _ = writeln!(data, "{operator}");
+	};
+
+	for verb in path.verbs() {
+		match verb {
+			PathVerb::Move => {
+				let Some(point) = points.next() else { break };
+				write_points(&mut data, &[point], "m");
+				(current, start) = (point, point);
+			}
+			PathVerb::Line => {
+				let Some(point) = points.next() else { break };
+				write_points(&mut data, &[point], "l");
+				current = point;
+			}
+			PathVerb::Quad => {
+				let (Some(handle), Some(end)) = (points.next(), points.next()) else { break };
+				// PDF only has cubic curves, so raise the degree of the quadratic curve
+				let handles = [current + (handle - current) * (2. / 3.), end + (handle - end) * (2. / 3.)];
+				write_points(&mut data, &[handles[0], handles[1], end], "c");
+				current = end;
+			}
+			PathVerb::Cubic => {
+				let (Some(handle_start), Some(handle_end), Some(end)) = (points.next(), points.next(), points.next()) else {
+					break;
+				};
+				write_points(&mut data, &[handle_start, handle_end, end], "c");
+				current = end;
+			}
+			PathVerb::Close => {
+				data.push_str("h\n");
+				current = start;
+			}
+		}
+	}
+	data
+}
+
+fn stream(dictionary: &str, data: &str) -> String {
+	let separator = if dictionary.is_empty() { "" } else { " " };
+	format!("<< {dictionary}{separator}/Length {} >>\nstream\n{data}\nendstream", data.len())
+}
+
+fn rgb(color: usvg::Color) -> String {
+	[color.red, color.green, color.blue].map(|channel| number(channel as f64 / 255.)).join(" ")
+}
+
+fn matrix(transform: DAffine2) -> String {
+	transform.to_cols_array().map(number).join(" ")
+}
+
+/// Maps the unit square onto the rectangle, as used for content in object bounding box units.
+fn rect_transform(rect: usvg::Rect) -> DAffine2 {
+	DAffine2::from_scale_angle_translation(DVec2::new(rect.width() as f64, rect.height() as f64), 0., DVec2::new(rect.left() as f64, rect.top() as f64))
+}
+
+fn usvg_transform(transform: usvg::Transform) -> DAffine2 {
+	DAffine2::from_cols_array(&[transform.sx, transform.ky, transform.kx, transform.sy, transform.tx, transform.ty].map(|value| value as f64))
+}
+
+/// Formats the number without an exponent or superfluous trailing zeros, which PDF does not allow or need.
+fn number(value: f64) -> String {
+	let formatted = format!("{value:.4}");
+	let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+	match trimmed {
+		"-0" | "" => "0".to_string(),
+		_ => trimmed.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn object_offsets(pdf: &str) -> Vec<usize> {
+		let xref = &pdf[pdf.find("xref\n").unwrap()..];
+		xref.lines().skip(3).take_while(|line| line.ends_with(" n ")).map(|line| line[..10].parse().unwrap()).collect()
+	}
+
+	#[test]
+	fn writes_paths_gradients_and_cross_references() {
+		let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 200 100">
+			<defs><linearGradient id="gradient"><stop offset="0" stop-color="#ff0000"/><stop offset="0.5" stop-color="#00ff00"/><stop offset="1" stop-color="#0000ff"/></linearGradient></defs>
+			<rect x="10" y="10" width="80" height="40" fill="url(#gradient)" stroke="#000000" stroke-width="2" opacity="0.5"/>
+		</svg>"##;
+		let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+		let pdf = tree_to_pdf(&tree).unwrap();
+
+		assert!(pdf.is_ascii());
+		assert!(pdf.starts_with("%PDF-1.4\n"));
+		assert!(pdf.contains("/MediaBox [0 0 150 75]"));
+		assert!(pdf.contains("/ShadingType 2"));
+		assert!(pdf.contains("/FunctionType 3"));
+		assert!(pdf.contains("/Bounds [0.5]"));
+		assert!(pdf.contains("/Pattern cs /P1 scn"));
+		assert!(pdf.contains("0 0 0 RG"));
+		assert!(pdf.contains("<< /ca 0.5 /CA 1 >>"));
+
+		let offsets = object_offsets(&pdf);
+		assert_eq!(offsets.len(), pdf.matches(" 0 obj\n").count());
+		for (index, offset) in offsets.into_iter().enumerate() {
+			assert!(pdf[offset..].starts_with(&format!("{} 0 obj\n", index + 1)));
+		}
+	}
+
+	#[test]
+	fn clips_or_rejects_what_a_pdf_cannot_represent() {
+		let pdf = |content: &str| {
+			let svg = format!(r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">{content}</svg>"##);
+			tree_to_pdf(&usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap())
+		};
+
+		let clipped = pdf(r##"<clipPath id="clip"><rect x="10" y="20" width="30" height="40"/></clipPath><rect width="100" height="100" fill="#ff0000" clip-path="url(#clip)"/>"##).unwrap();
+		assert!(clipped.contains("10 20 m\n40 20 l\n40 60 l\n10 60 l\nh\nW n"));
+
+		let errors = [
+			r##"<clipPath id="clip"><rect width="10" height="10"/><rect x="20" width="10" height="10"/></clipPath><rect width="100" height="100" clip-path="url(#clip)"/>"##,
+			r##"<mask id="mask"><rect width="50" height="50" fill="#ffffff"/></mask><rect width="100" height="100" mask="url(#mask)"/>"##,
+			r##"<filter id="blur"><feGaussianBlur stdDeviation="4"/></filter><rect width="100" height="100" filter="url(#blur)"/>"##,
+			r##"<linearGradient id="gradient" spreadMethod="repeat" x2="0.5"><stop offset="0" stop-color="#ff0000"/><stop offset="1" stop-color="#0000ff"/></linearGradient><rect width="100" height="100" fill="url(#gradient)"/>"##,
+			r##"<text x="10" y="50" font-family="A font which isn't loaded">Text</text>"##,
+		];
+		for content in errors {
+			assert!(pdf(content).is_err(), "{content} should not be exported");
+		}
+	}
+
+	#[test]
+	fn formats_numbers_without_exponents() {
+		assert_eq!(number(1e-9), "0");
+		assert_eq!(number(-0.00001), "0");
+		assert_eq!(number(2.5), "2.5");
+		assert_eq!(number(1e12), "1000000000000");
+	}
+}
@@ -4,6 +4,8 @@ use graphene_core::raster::Image;
 use graphene_core::raster::{color::SRGBA8, ImageFrame};
 use graphene_core::renderer::{format_transform_matrix, GraphicElementRendered, ImageRenderMode, RenderParams, RenderSvgSegmentList, SvgRender};
 use graphene_core::transform::Footprint;
+#[cfg(feature = "resvg")]
+use graphene_core::vector::style::ViewMode;
 use graphene_core::Color;
 use graphene_core::Node;
 #[cfg(feature = "wgpu")]
@@ -311,6 +313,31 @@ fn render_svg(data: impl GraphicElementRendered, mut render: SvgRender, render_p
 	RenderOutput::Svg(render.svg.to_svg_string())
 }
 
+/// Renders the SVG output as usual and converts it into a vector PDF page of the same size, with text converted to outlines using the loaded fonts.
+#[cfg(feature = "resvg")]
+fn render_pdf(data: impl GraphicElementRendered, render: SvgRender, render_params: RenderParams, footprint: Footprint, font_cache: &graphene_core::text::FontCache) -> RenderOutput {
+	use resvg::usvg;
+
+	let RenderOutput::Svg(svg) = render_svg(data, render, render_params, footprint) else {
+		unreachable!("SVG rendering always produces an SVG")
+	};
+	let mut tree = match usvg::Tree::from_str(&svg, &usvg::Options::default()) {
+		Ok(tree) => tree,
+		Err(error) => return RenderOutput::Error(format!("The rendered SVG couldn't be parsed: {error}")),
+	};
+
+	let mut font_database = usvg::fontdb::Database::new();
+	for font_data in font_cache.font_files() {
+		font_database.load_font_data(font_data.clone());
+	}
+	tree.postprocess(usvg::PostProcessingSteps::default(), &font_database);
+
+	match crate::pdf::tree_to_pdf(&tree) {
+		Ok(pdf) => RenderOutput::Pdf(pdf),
+		Err(error) => RenderOutput::Error(error),
+	}
+}
+
 #[cfg(any(feature = "resvg", feature = "vello"))]
 fn render_canvas(
 	data: impl GraphicElementRendered,
@@ -362,6 +389,79 @@ fn render_canvas(
 	RenderOutput::CanvasFrame(frame.into())
 }
 
+/// Rasterizes the visible part of the document at its 1:1 pixel grid and displays the result scaled up with nearest-neighbor sampling.
+/// Returns `None` when there is nothing to rasterize or the viewport isn't zoomed in past 100%, in which case the regular render should be used.
+#[cfg(feature = "resvg")]
+fn render_pixels(data: &impl GraphicElementRendered, render_params: &RenderParams, footprint: Footprint) -> Option<RenderOutput> {
+	let image_frame = rasterize_visible_pixels(data, footprint)?;
+	let resolution = footprint.resolution.as_dvec2();
+	let viewport_to_document = footprint.transform.inverse();
+
+	let mut render = SvgRender::new();
+	if !data.contains_artboard() && !render_params.hide_artboards {
+		render.leaf_tag("rect", |attributes| {
+			attributes.push("x", "0");
+			attributes.push("y", "0");
+			attributes.push("width", footprint.resolution.x.to_string());
+			attributes.push("height", footprint.resolution.y.to_string());
+			attributes.push("transform", format_transform_matrix(viewport_to_document));
+			attributes.push("fill", "white");
+		});
+	}
+	render.parent_tag(
+		"g",
+		|attributes| attributes.push("style", "image-rendering: pixelated"),
+		|render| image_frame.render_svg(render, render_params),
+	);
+	render.wrap_with_transform(footprint.transform, Some(resolution));
+
+	Some(RenderOutput::Svg(render.svg.to_svg_string()))
+}
+
+/// Renders every document pixel the viewport shows any part of, at one image pixel per document unit, into an image placed over those pixels in document space.
+/// Returns `None` when the viewport isn't zoomed in past 100% or shows no whole pixels.
+#[cfg(feature = "resvg")]
+fn rasterize_visible_pixels(data: &impl GraphicElementRendered, footprint: Footprint) -> Option<ImageFrame<Color>> {
+	use graphene_core::transform::Transform;
+
+	if footprint.decompose_scale().min_element() <= 1. {
+		return None;
+	}
+
+	let resolution = footprint.resolution.as_dvec2();
+	let viewport_to_document = footprint.transform.inverse();
+	let corners = [glam::DVec2::ZERO, resolution * glam::DVec2::X, resolution * glam::DVec2::Y, resolution].map(|corner| viewport_to_document.transform_point2(corner));
+	let start = corners.into_iter().reduce(glam::DVec2::min)?.floor();
+	let end = corners.into_iter().reduce(glam::DVec2::max)?.ceil();
+	let size = (end - start).as_uvec2();
+	if size.x == 0 || size.y == 0 {
+		return None;
+	}
+
+	let usvg_tree = data.to_usvg_tree(size, [start, end]);
+	let mut pixmap = resvg::tiny_skia::Pixmap::new(size.x, size.y)?;
+	resvg::render(&usvg_tree, resvg::tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+	let pixels = pixmap
+		.pixels()
+		.iter()
+		.map(|pixel| {
+			let pixel = pixel.demultiply();
+			Color::from_rgba8_srgb(pixel.red(), pixel.green(), pixel.blue(), pixel.alpha())
+		})
+		.collect();
+	Some(ImageFrame {
+		image: Image {
+			width: size.x,
+			height: size.y,
+			data: pixels,
+			..Default::default()
+		},
+		transform: glam::DAffine2::from_translation(start) * glam::DAffine2::from_scale(size.as_dvec2()),
+		..Default::default()
+	})
+}
+
 // Render with the data node taking in Footprint.
 impl<'input, 'a: 'input, T: 'input + GraphicElementRendered, F: 'input + Future<Output = T>, Data: 'input, Surface: 'input, SurfaceFuture: 'input> Node<'input, WasmEditorApi<'a>>
 	for RenderNode<Data, Surface, Footprint>
@@ -380,11 +480,22 @@ where
 			let RenderConfig { hide_artboards, for_export, .. } = editor.render_config;
 			let render_params = RenderParams::new(editor.render_config.view_mode, ImageRenderMode::Base64, None, false, hide_artboards, for_export);
 
+			let data = self.data.eval(footprint).await;
+
+			#[cfg(feature = "resvg")]
+			if render_params.view_mode == ViewMode::Pixels && !for_export {
+				if let Some(output) = render_pixels(&data, &render_params, footprint) {
+					return output;
+				}
+			}
+
 			let output_format = editor.render_config.export_format;
 			match output_format {
-				ExportFormat::Svg => render_svg(self.data.eval(footprint).await, SvgRender::new(), render_params, footprint),
+				ExportFormat::Svg => render_svg(data, SvgRender::new(), render_params, footprint),
+				#[cfg(feature = "resvg")]
+				ExportFormat::Pdf => render_pdf(data, SvgRender::new(), render_params, footprint, editor.font_cache),
 				#[cfg(any(feature = "resvg", feature = "vello"))]
-				ExportFormat::Canvas => render_canvas(self.data.eval(footprint).await, SvgRender::new(), render_params, footprint, editor, self.surface_handle.eval(()).await),
+				ExportFormat::Canvas => render_canvas(data, SvgRender::new(), render_params, footprint, editor, self.surface_handle.eval(()).await),
 				_ => todo!("Non-SVG render output for {output_format:?}"),
 			}
 		})
@@ -408,11 +519,22 @@ where
 			let RenderConfig { hide_artboards, for_export, .. } = editor.render_config;
 			let render_params = RenderParams::new(editor.render_config.view_mode, ImageRenderMode::Base64, None, false, hide_artboards, for_export);
 
+			let data = self.data.eval(()).await;
+
+			#[cfg(feature = "resvg")]
+			if render_params.view_mode == ViewMode::Pixels && !for_export {
+				if let Some(output) = render_pixels(&data, &render_params, footprint) {
+					return output;
+				}
+			}
+
 			let output_format = editor.render_config.export_format;
 			match output_format {
-				ExportFormat::Svg => render_svg(self.data.eval(()).await, SvgRender::new(), render_params, footprint),
+				ExportFormat::Svg => render_svg(data, SvgRender::new(), render_params, footprint),
+				#[cfg(feature = "resvg")]
+				ExportFormat::Pdf => render_pdf(data, SvgRender::new(), render_params, footprint, editor.font_cache),
 				#[cfg(any(feature = "resvg", feature = "vello"))]
-				ExportFormat::Canvas => render_canvas(self.data.eval(()).await, SvgRender::new(), render_params, footprint, editor, self.surface_handle.eval(()).await),
+				ExportFormat::Canvas => render_canvas(data, SvgRender::new(), render_params, footprint, editor, self.surface_handle.eval(()).await),
 				_ => todo!("Non-SVG render output for {output_format:?}"),
 			}
 		})
@@ -431,3 +553,46 @@ impl<Data, Surface, Parameter> RenderNode<Data, Surface, Parameter> {
 		}
 	}
 }
+
+#[cfg(all(test, feature = "resvg"))]
+mod test {
+	use super::*;
+
+	use glam::{DAffine2, DVec2, UVec2};
+
+	#[test]
+	fn render_pixels_when_zoomed_in() {
+		let red = Color::from_rgbaf32_unchecked(1., 0., 0., 1.);
+		let image_frame = ImageFrame {
+			image: Image {
+				width: 2,
+				height: 1,
+				data: vec![red; 2],
+				..Default::default()
+			},
+			transform: DAffine2::from_scale(DVec2::new(2., 1.)),
+			..Default::default()
+		};
+		let footprint = |zoom: f64| Footprint {
+			transform: DAffine2::from_scale(DVec2::splat(zoom)),
+			resolution: UVec2::new(16, 8),
+			..Default::default()
+		};
+
+		// The regular render is used unless the pixels are shown larger than they are
+		assert!(rasterize_visible_pixels(&image_frame, footprint(1.)).is_none());
+
+		// At 400% the 16x8 viewport shows 4x2 document pixels, of which the image covers the top left 2x1
+		let pixels = rasterize_visible_pixels(&image_frame, footprint(4.)).unwrap();
+		assert_eq!((pixels.image.width, pixels.image.height), (4, 2));
+		assert_eq!(pixels.transform, DAffine2::from_scale(DVec2::new(4., 2.)));
+		let opaque: Vec<_> = pixels.image.data.iter().map(|pixel| pixel.a() > 0.99).collect();
+		assert_eq!(opaque, [true, true, false, false, false, false, false, false]);
+
+		let render_params = RenderParams::new(ViewMode::Pixels, ImageRenderMode::Base64, None, false, false, false);
+		let Some(RenderOutput::Svg(svg)) = render_pixels(&image_frame, &render_params, footprint(4.)) else {
+			panic!("Expected the pixels to be rendered as an SVG")
+		};
+		assert!(svg.contains("image-rendering: pixelated"));
+	}
+}
@@ -4,9 +4,11 @@ use dyn_any::StaticType;
 use graph_craft::document::value::{TaggedValue, UpcastNode};
 use graph_craft::document::{NodeId, Source};
 use graph_craft::graphene_compiler::Executor;
+use graph_craft::inline_expression::InlineExpressionNode;
 use graph_craft::proto::{ConstructionArgs, GraphError, LocalFuture, NodeContainer, ProtoNetwork, ProtoNode, SharedNodeContainer, TypeErasedBox, TypingContext};
 use graph_craft::proto::{GraphErrorType, GraphErrors};
-use graph_craft::Type;
+use graph_craft::render_cache::{is_cacheable, RenderCache, RenderCacheNode};
+use graph_craft::{concrete, Type};
 
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
@@ -75,6 +77,12 @@ impl DynamicExecutor {
 		self.tree.introspect(node_path)
 	}
 
+	/// Persists the outputs of cache nodes in the [`RenderCache`], so they are read back instead of computed again whenever a later executor builds the same nodes.
+	/// This applies to the nodes built by subsequent updates.
+	pub fn set_render_cache(&mut self, render_cache: Option<RenderCache>) {
+		self.tree.render_cache = render_cache;
+	}
+
 	pub fn input_type(&self) -> Option<Type> {
 		self.typing_context.type_of(self.output).map(|node_io| node_io.input.clone())
 	}
@@ -117,12 +125,20 @@ pub struct BorrowTree {
 	inputs_source_map: HashMap<Source, (NodeId, usize)>,
 	/// A mapping of document input sources to the (single) proto node output
 	outputs_source_map: HashMap<Source, NodeId>,
+	/// Where the outputs of cache nodes are persisted, if anywhere.
+	render_cache: Option<RenderCache>,
+	/// The nodes of the latest network which depend on the input fed into the graph, whose outputs aren't persisted in the render cache.
+	input_dependent_nodes: HashSet<NodeId>,
 }
 
 impl BorrowTree {
 	pub async fn new(proto_network: ProtoNetwork, typing_context: &TypingContext) -> Result<BorrowTree, GraphErrors> {
 		let mut nodes = BorrowTree::default();
-		for (id, node) in proto_network.nodes {
+		let mut evaluated = nodes.evaluate_independent_branches(&proto_network, typing_context);
+		for (id, mut node) in proto_network.nodes {
+			if let Some(value) = evaluated.remove(&id) {
+				node.construction_args = ConstructionArgs::Value(value);
+			}
 			nodes.push_node(id, node, typing_context).await?
 		}
 		Ok(nodes)
@@ -131,8 +147,16 @@ impl BorrowTree {
 	/// Pushes new nodes into the tree and return orphaned nodes
 	pub async fn update(&mut self, proto_network: ProtoNetwork, typing_context: &TypingContext) -> Result<Vec<NodeId>, GraphErrors> {
 		let mut old_nodes: HashSet<_> = self.nodes.keys().copied().collect();
-		for (id, node) in proto_network.nodes {
+		if self.render_cache.is_some() {
+			self.input_dependent_nodes = input_dependent_nodes(&proto_network, typing_context);
+		}
+		let mut evaluated = self.evaluate_independent_branches(&proto_network, typing_context);
+		for (id, mut node) in proto_network.nodes {
 			if !self.nodes.contains_key(&id) {
+				// Cache nodes whose branch was already evaluated on another thread simply hold on to the output
+				if let Some(value) = evaluated.remove(&id) {
+					node.construction_args = ConstructionArgs::Value(value);
+				}
 				self.push_node(id, node, typing_context).await?;
 			}
 			old_nodes.remove(&id);
@@ -177,6 +201,18 @@ impl BorrowTree {
 		TaggedValue::try_from_any(output.await)
 	}
 
+	/// Evaluates the independent branches among the new nodes concurrently, see [`crate::scheduler::evaluate_independent_branches`].
+	#[cfg(not(target_arch = "wasm32"))]
+	fn evaluate_independent_branches(&self, proto_network: &ProtoNetwork, typing_context: &TypingContext) -> HashMap<NodeId, TaggedValue> {
+		crate::scheduler::evaluate_independent_branches(proto_network, typing_context, |id| self.nodes.contains_key(&id), self.render_cache.as_ref())
+	}
+
+	/// The web has no threads to spare, so everything is evaluated on demand.
+	#[cfg(target_arch = "wasm32")]
+	fn evaluate_independent_branches(&self, _proto_network: &ProtoNetwork, _typing_context: &TypingContext) -> HashMap<NodeId, TaggedValue> {
+		HashMap::new()
+	}
+
 	pub fn free_node(&mut self, id: NodeId) {
 		self.nodes.remove(&id);
 	}
@@ -187,6 +223,7 @@ impl BorrowTree {
 
 		let params = match &proto_node.construction_args {
 			ConstructionArgs::Nodes(nodes) => nodes.len() + 1,
+			ConstructionArgs::Inline(_, nodes) => nodes.len() + 1,
 			_ => 2,
 		};
 		self.inputs_source_map
@@ -203,19 +240,50 @@ impl BorrowTree {
 				let node = NodeContainer::new(node);
 				self.store_node(node, id);
 			}
-			ConstructionArgs::Inline(_) => unimplemented!("Inline nodes are not supported yet"),
+			ConstructionArgs::Inline(inline, ids) => {
+				let inline_error = |error: String| vec![GraphError::new(&proto_node, GraphErrorType::InvalidInlineExpression(error))];
+				// The referenced inputs are evaluated without any input of their own, so they can't depend on one such as the footprint
+				for id in ids {
+					if let Some(input) = typing_context.type_of(*id).map(|types| &types.input).filter(|input| **input != concrete!(())) {
+						return Err(inline_error(format!("Inputs referenced by the expression can't depend on a {input}")));
+					}
+				}
+				let node = InlineExpressionNode::new(inline, self.node_deps(ids)).map_err(inline_error)?;
+				let node = Box::new(node) as TypeErasedBox<'_>;
+				let node = NodeContainer::new(node);
+				self.store_node(node, id);
+			}
 			ConstructionArgs::Nodes(ids) => {
 				let ids: Vec<_> = ids.iter().map(|(id, _)| *id).collect();
 				let construction_nodes = self.node_deps(&ids);
-				let constructor = typing_context.constructor(id).ok_or_else(|| vec![GraphError::new(&proto_node, GraphErrorType::NoConstructor)])?;
-				let node = constructor(construction_nodes).await;
-				let node = NodeContainer::new(node);
+				let node = match self.render_cache_node(id, &proto_node, &construction_nodes, typing_context) {
+					Some(node) => node,
+					None => {
+						let constructor = typing_context.constructor(id).ok_or_else(|| vec![GraphError::new(&proto_node, GraphErrorType::NoConstructor)])?;
+						NodeContainer::new(constructor(construction_nodes).await)
+					}
+				};
 				self.store_node(node, id);
 			}
 		};
 		Ok(())
 	}
 
+	/// Builds a [`RenderCacheNode`] in place of a `MemoNode` whose output can be persisted, when a render cache is in use.
+	/// The stable ID of the proto node is the key, since it already hashes the node together with everything upstream of it.
+	/// That excludes the input fed into the graph, which carries the editor's resources such as fonts and images, so nodes depending on it aren't persisted.
+	fn render_cache_node(&self, id: NodeId, proto_node: &ProtoNode, construction_nodes: &[SharedNodeContainer], typing_context: &TypingContext) -> Option<SharedNodeContainer> {
+		let render_cache = self.render_cache.as_ref()?;
+		let cacheable = typing_context.type_of(id).map_or(false, |types| is_cacheable(&types.output));
+		let [cached_node] = construction_nodes else { return None };
+		if !is_memo_node(proto_node) || !cacheable || self.input_dependent_nodes.contains(&id) {
+			return None;
+		}
+
+		let node = RenderCacheNode::new(cached_node.clone(), id.0, render_cache.clone());
+		Some(NodeContainer::new(Box::new(node) as TypeErasedBox<'_>))
+	}
+
 	pub fn inputs_source_map(&self) -> impl Iterator<Item = (&Source, &(NodeId, usize))> {
 		self.inputs_source_map.iter()
 	}
@@ -225,6 +293,30 @@ impl BorrowTree {
 	}
 }
 
+/// Whether the proto node is a `MemoNode`, which holds on to the output of the node it caches.
+pub(crate) fn is_memo_node(proto_node: &ProtoNode) -> bool {
+	proto_node.identifier.name.starts_with("graphene_core::memo::MemoNode")
+}
+
+/// The nodes which take an input, such as the footprint or the editor API fed into the graph, along with everything downstream of them.
+fn input_dependent_nodes(proto_network: &ProtoNetwork, typing_context: &TypingContext) -> HashSet<NodeId> {
+	let outwards_edges = proto_network.collect_outwards_edges();
+	let mut stack: Vec<NodeId> = proto_network
+		.nodes
+		.iter()
+		.map(|(id, _)| *id)
+		.filter(|id| typing_context.type_of(*id).map_or(true, |types| types.input != concrete!(())))
+		.collect();
+
+	let mut dependent = HashSet::new();
+	while let Some(id) = stack.pop() {
+		if dependent.insert(id) {
+			stack.extend(outwards_edges.get(&id).into_iter().flatten().copied());
+		}
+	}
+	dependent
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -242,4 +334,24 @@ mod test {
 		let result = futures::executor::block_on(tree.eval(NodeId(0), ()));
 		assert_eq!(result, Some(2u32));
 	}
+
+	#[test]
+	fn push_inline_node() {
+		use graph_craft::document::InlineRust;
+
+		let mut tree = BorrowTree::default();
+		let context = TypingContext::default();
+		let value_protonode = ProtoNode::value(ConstructionArgs::Value(TaggedValue::DVec2((3., 4.).into())), vec![]);
+		futures::executor::block_on(tree.push_node(NodeId(0), value_protonode, &context)).unwrap();
+
+		let inline = InlineRust::new("length(i1) * 2 + i1.x".into(), concrete!(f64));
+		let inline_protonode = ProtoNode::value(ConstructionArgs::Inline(inline, vec![NodeId(0)]), vec![]);
+		futures::executor::block_on(tree.push_node(NodeId(1), inline_protonode, &context)).unwrap();
+		let result = futures::executor::block_on(tree.eval(NodeId(1), ()));
+		assert_eq!(result, Some(13.));
+
+		let invalid = InlineRust::new("i1 +".into(), concrete!(f64));
+		let invalid_protonode = ProtoNode::value(ConstructionArgs::Inline(invalid, vec![NodeId(0)]), vec![]);
+		assert!(futures::executor::block_on(tree.push_node(NodeId(2), invalid_protonode, &context)).is_err());
+	}
 }
@@ -1,5 +1,7 @@
 pub mod dynamic_executor;
 pub mod node_registry;
+#[cfg(not(target_arch = "wasm32"))]
+mod scheduler;
 
 #[cfg(test)]
 mod tests {
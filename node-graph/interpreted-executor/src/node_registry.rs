@@ -6,6 +6,7 @@ use graphene_core::quantization::{PackedPixel, QuantizationChannels};
 use graphene_core::raster::brush_cache::BrushCache;
 use graphene_core::raster::color::Color;
 use graphene_core::structural::Then;
+use graphene_core::text::Typography;
 use graphene_core::transform::Footprint;
 use graphene_core::value::{ClonedNode, CopiedNode, ValueNode};
 use graphene_core::vector::brush_stroke::BrushStroke;
@@ -343,6 +344,7 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		async_node!(graphene_core::memo::MonitorNode<_, _, _>, input: Footprint, output: ImageFrame<Color>, fn_params: [Footprint => ImageFrame<Color>]),
 		async_node!(graphene_core::memo::MonitorNode<_, _, _>, input: (), output: ImageFrame<Color>, params: [ImageFrame<Color>]),
 		async_node!(graphene_core::memo::MonitorNode<_, _, _>, input: Footprint, output: VectorData, fn_params: [Footprint => VectorData]),
+		async_node!(graphene_core::memo::MonitorNode<_, _, _>, input: Footprint, output: Typography, fn_params: [Footprint => Typography]),
 		async_node!(graphene_core::memo::MonitorNode<_, _, _>, input: Footprint, output: graphene_core::GraphicGroup, fn_params: [Footprint => graphene_core::GraphicGroup]),
 		async_node!(graphene_core::memo::MonitorNode<_, _, _>, input: Footprint, output: graphene_core::GraphicElement, fn_params: [Footprint => graphene_core::GraphicElement]),
 		async_node!(graphene_core::memo::MonitorNode<_, _, _>, input: Footprint, output: Artboard, fn_params: [Footprint => graphene_core::Artboard]),
@@ -457,6 +459,13 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		raster_node!(graphene_core::raster::LevelsNode<_, _, _, _, _>, params: [f64, f64, f64, f64, f64]),
 		register_node!(graphene_std::image_segmentation::ImageSegmentationNode<_>, input: ImageFrame<Color>, params: [ImageFrame<Color>]),
 		register_node!(graphene_std::image_color_palette::ImageColorPaletteNode<_>, input: ImageFrame<Color>, params: [u32]),
+		register_node!(graphene_std::filter::GaussianBlurNode<_>, input: ImageFrame<Color>, params: [f64]),
+		register_node!(graphene_std::filter::BoxBlurNode<_>, input: ImageFrame<Color>, params: [f64]),
+		register_node!(graphene_std::filter::MotionBlurNode<_, _>, input: ImageFrame<Color>, params: [f64, f64]),
+		register_node!(graphene_std::filter::UnsharpMaskNode<_, _, _>, input: ImageFrame<Color>, params: [f64, f64, f64]),
+		register_node!(graphene_std::filter::ConvolutionNode<_, _>, input: ImageFrame<Color>, params: [Vec<f64>, bool]),
+		register_node!(graphene_std::filter::ResampleNode<_, _>, input: ImageFrame<Color>, params: [UVec2, ResampleFilter]),
+		register_node!(graphene_std::flood_fill::FloodFillNode<_, _, _, _, _>, input: ImageFrame<Color>, params: [DVec2, Color, f64, bool, bool]),
 		register_node!(graphene_core::raster::IndexNode<_>, input: Vec<ImageFrame<Color>>, params: [u32]),
 		register_node!(graphene_core::raster::adjustments::ColorFillNode<_>, input: ImageFrame<Color>, params: [Color]),
 		register_node!(graphene_core::raster::adjustments::ColorOverlayNode<_, _, _>, input: ImageFrame<Color>, params: [Color, BlendMode, f64]),
@@ -697,15 +706,17 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		async_node!(graphene_core::transform::TransformNode<_, _, _, _, _, _>, input: Footprint, output: WasmSurfaceHandleFrame, fn_params: [Footprint => WasmSurfaceHandleFrame, () => DVec2, () => f64, () => DVec2, () => DVec2, () => DVec2]),
 		async_node!(graphene_core::transform::TransformNode<_, _, _, _, _, _>, input: Footprint, output: ImageFrame<Color>, fn_params: [Footprint => ImageFrame<Color>, () => DVec2, () => f64, () => DVec2, () => DVec2, () => DVec2]),
 		async_node!(graphene_core::transform::TransformNode<_, _, _, _, _, _>, input: Footprint, output: GraphicGroup, fn_params: [Footprint => GraphicGroup, () => DVec2, () => f64, () => DVec2, () => DVec2, () => DVec2]),
+		async_node!(graphene_core::transform::TransformNode<_, _, _, _, _, _>, input: Footprint, output: Typography, fn_params: [Footprint => Typography, () => DVec2, () => f64, () => DVec2, () => DVec2, () => DVec2]),
 		register_node!(graphene_core::transform::SetTransformNode<_>, input: VectorData, params: [VectorData]),
 		register_node!(graphene_core::transform::SetTransformNode<_>, input: ImageFrame<Color>, params: [ImageFrame<Color>]),
 		register_node!(graphene_core::transform::SetTransformNode<_>, input: VectorData, params: [DAffine2]),
 		register_node!(graphene_core::transform::SetTransformNode<_>, input: ImageFrame<Color>, params: [DAffine2]),
-		register_node!(graphene_core::vector::SetFillNode<_, _, _, _, _, _, _>, input: VectorData, params: [graphene_core::vector::style::FillType, Option<graphene_core::Color>, graphene_core::vector::style::GradientType, DVec2, DVec2, DAffine2, Vec<(f64, graphene_core::Color)>]),
-		register_node!(graphene_core::vector::SetStrokeNode<_, _, _, _, _, _, _>, input: VectorData, params: [Option<graphene_core::Color>, f64, Vec<f64>, f64, graphene_core::vector::style::LineCap, graphene_core::vector::style::LineJoin, f64]),
+		register_node!(graphene_core::vector::SetFillNode<_, _, _, _, _, _, _, _>, input: VectorData, params: [graphene_core::vector::style::FillType, Option<graphene_core::Color>, graphene_core::vector::style::GradientType, DVec2, DVec2, DAffine2, Vec<(f64, graphene_core::Color)>, graphene_core::vector::style::GradientSpreadMethod]),
+		register_node!(graphene_core::vector::SetStrokeNode<_, _, _, _, _, _, _, _>, input: VectorData, params: [Option<graphene_core::Color>, f64, Vec<f64>, f64, graphene_core::vector::style::LineCap, graphene_core::vector::style::LineJoin, f64, Option<graphene_core::vector::style::Gradient>]),
 		register_node!(graphene_core::vector::RepeatNode<_, _, _>, input: VectorData, params: [DVec2, f64, u32]),
 		register_node!(graphene_core::vector::BoundingBoxNode, input: VectorData, params: []),
 		register_node!(graphene_core::vector::SolidifyStrokeNode, input: VectorData, params: []),
+		register_node!(graphene_core::vector::OffsetPathNode<_, _, _>, input: VectorData, params: [f64, graphene_core::vector::style::LineJoin, f64]),
 		register_node!(graphene_core::vector::CircularRepeatNode<_, _, _>, input: VectorData, params: [f64, f64, u32]),
 		vec![(
 			ProtoNodeIdentifier::new("graphene_core::transform::CullNode<_>"),
@@ -775,6 +786,7 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		register_node!(graphene_core::vector::LengthsOfSegmentsOfSubpaths, input: VectorData, params: []),
 		register_node!(graphene_core::vector::SplinesFromPointsNode, input: VectorData, params: []),
 		async_node!(graphene_core::vector::MorphNode<_, _, _, _>, input: Footprint, output: VectorData, fn_params: [Footprint => VectorData, Footprint => VectorData, () => u32, () => f64]),
+		async_node!(graphene_core::vector::BooleanOperationNode<_, _, _>, input: Footprint, output: VectorData, fn_params: [Footprint => VectorData, Footprint => VectorData, () => graphene_core::vector::bezier_rs::BooleanOperation]),
 		register_node!(graphene_core::vector::generator_nodes::CircleGenerator<_>, input: (), params: [f64]),
 		register_node!(graphene_core::vector::generator_nodes::EllipseGenerator<_, _>, input: (), params: [f64, f64]),
 		register_node!(graphene_core::vector::generator_nodes::RectangleGenerator<_, _, _, _, _>, input: (), params: [f64, f64, bool, f64, bool]),
@@ -789,17 +801,38 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 			params: [Vec<graphene_core::uuid::ManipulatorGroupId>]
 		),
 		register_node!(graphene_core::text::TextGeneratorNode<_, _, _>, input: WasmEditorApi, params: [String, graphene_core::text::Font, f64]),
+		register_node!(
+			graphene_core::text::TypographyNode<_, _, _, _, _, _, _, _>,
+			input: WasmEditorApi,
+			params: [String, graphene_core::text::Font, f64, f64, f64, graphene_core::text::TextAlignment, f64, Color]
+		),
+		register_node!(graphene_core::text::AppendTextNode<_, _>, input: WasmEditorApi, params: [Typography, Typography]),
+		register_node!(graphene_core::text::OutlineTextNode<_>, input: WasmEditorApi, params: [Typography]),
 		register_node!(graphene_std::brush::VectorPointsNode, input: VectorData, params: []),
 		register_node!(graphene_core::ExtractImageFrame, input: WasmEditorApi, params: []),
 		async_node!(graphene_core::ConstructLayerNode<_, _>, input: Footprint, output: GraphicGroup, fn_params: [Footprint => graphene_core::GraphicElement, Footprint => GraphicGroup]),
 		register_node!(graphene_core::ToGraphicElementNode, input: graphene_core::vector::VectorData, params: []),
 		register_node!(graphene_core::ToGraphicElementNode, input: ImageFrame<Color>, params: []),
+		register_node!(graphene_core::ToGraphicElementNode, input: Typography, params: []),
 		register_node!(graphene_core::ToGraphicElementNode, input: GraphicGroup, params: []),
 		register_node!(graphene_core::ToGraphicElementNode, input: Artboard, params: []),
 		register_node!(graphene_core::ToGraphicGroupNode, input: graphene_core::vector::VectorData, params: []),
 		register_node!(graphene_core::ToGraphicGroupNode, input: ImageFrame<Color>, params: []),
+		register_node!(graphene_core::ToGraphicGroupNode, input: Typography, params: []),
 		register_node!(graphene_core::ToGraphicGroupNode, input: GraphicGroup, params: []),
 		register_node!(graphene_core::ToGraphicGroupNode, input: Artboard, params: []),
+		register_node!(graphene_core::effects::DropShadowNode<_, _, _, _>, input: graphene_core::vector::VectorData, params: [Color, DVec2, f64, BlendMode]),
+		register_node!(graphene_core::effects::DropShadowNode<_, _, _, _>, input: ImageFrame<Color>, params: [Color, DVec2, f64, BlendMode]),
+		register_node!(graphene_core::effects::DropShadowNode<_, _, _, _>, input: GraphicGroup, params: [Color, DVec2, f64, BlendMode]),
+		register_node!(graphene_core::effects::InnerShadowNode<_, _, _, _>, input: graphene_core::vector::VectorData, params: [Color, DVec2, f64, BlendMode]),
+		register_node!(graphene_core::effects::InnerShadowNode<_, _, _, _>, input: ImageFrame<Color>, params: [Color, DVec2, f64, BlendMode]),
+		register_node!(graphene_core::effects::InnerShadowNode<_, _, _, _>, input: GraphicGroup, params: [Color, DVec2, f64, BlendMode]),
+		register_node!(graphene_core::effects::OuterGlowNode<_, _, _>, input: graphene_core::vector::VectorData, params: [Color, f64, BlendMode]),
+		register_node!(graphene_core::effects::OuterGlowNode<_, _, _>, input: ImageFrame<Color>, params: [Color, f64, BlendMode]),
+		register_node!(graphene_core::effects::OuterGlowNode<_, _, _>, input: GraphicGroup, params: [Color, f64, BlendMode]),
+		register_node!(graphene_core::effects::StrokeEffectNode<_, _>, input: graphene_core::vector::VectorData, params: [Color, f64]),
+		register_node!(graphene_core::effects::StrokeEffectNode<_, _>, input: ImageFrame<Color>, params: [Color, f64]),
+		register_node!(graphene_core::effects::StrokeEffectNode<_, _>, input: GraphicGroup, params: [Color, f64]),
 		async_node!(graphene_core::ConstructArtboardNode<_, _, _, _, _>, input: Footprint, output: Artboard, fn_params: [Footprint => GraphicGroup, () => glam::IVec2, () => glam::IVec2, () => Color, () => bool]),
 		async_node!(graphene_core::AddArtboardNode<_, _>, input: Footprint, output: ArtboardGroup, fn_params: [Footprint => Artboard, Footprint => ArtboardGroup]),
 	];
@@ -0,0 +1,177 @@
+use crate::dynamic_executor::{is_memo_node, BorrowTree};
+
+use graph_craft::concrete;
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::NodeId;
+use graph_craft::proto::{ProtoNetwork, ProtoNode, TypingContext};
+use graph_craft::render_cache::{is_cacheable, RenderCache};
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// Evaluates the independent branches of the network concurrently, one thread per core, ahead of the usual evaluation which only follows the single chain of demand from the output.
+///
+/// A branch is the cache node of an expensive output together with everything upstream of it, as long as none of those nodes depend on the input
+/// fed into the graph (such as the footprint), none of them are monitored for introspection, and none of them already hold a cached output in the tree.
+/// Branches nested inside a larger branch are evaluated as part of it. The returned outputs are keyed by the ID of the cache node they belong to.
+/// Branches which fail to evaluate are left out, so they are evaluated lazily as before.
+pub(crate) fn evaluate_independent_branches(
+	proto_network: &ProtoNetwork,
+	typing_context: &TypingContext,
+	is_built: impl Fn(NodeId) -> bool,
+	render_cache: Option<&RenderCache>,
+) -> HashMap<NodeId, TaggedValue> {
+	let nodes: HashMap<NodeId, &ProtoNode> = proto_network.nodes.iter().map(|(id, node)| (*id, node)).collect();
+	let inwards_edges = proto_network.collect_inwards_edges();
+
+	let upstream_of = |id: NodeId| {
+		let mut upstream = HashSet::new();
+		let mut stack = vec![id];
+		while let Some(id) = stack.pop() {
+			if upstream.insert(id) {
+				stack.extend(inwards_edges.get(&id).into_iter().flatten().copied());
+			}
+		}
+		upstream
+	};
+	let is_independent = |id: NodeId, upstream: &HashSet<NodeId>| {
+		upstream.iter().all(|&upstream_id| {
+			let Some(node) = nodes.get(&upstream_id) else { return false };
+			let without_input = typing_context.type_of(upstream_id).map_or(false, |types| types.input == concrete!(()));
+			let monitored = node.identifier.name.starts_with("graphene_core::memo::MonitorNode");
+			let holds_cached_output = upstream_id != id && is_memo_node(node) && is_built(upstream_id);
+			without_input && !monitored && !holds_cached_output
+		})
+	};
+
+	let mut branches: Vec<(NodeId, HashSet<NodeId>)> = proto_network
+		.nodes
+		.iter()
+		.filter(|(id, node)| !is_built(*id) && is_memo_node(node) && typing_context.type_of(*id).map_or(false, |types| is_cacheable(&types.output)))
+		// Outputs already in the render cache are cheaper to read back than to evaluate
+		.filter(|(id, _)| render_cache.map_or(true, |render_cache| !render_cache.contains(id.0)))
+		.map(|(id, _)| (*id, upstream_of(*id)))
+		.filter(|(id, upstream)| is_independent(*id, upstream))
+		.collect();
+	let nested: HashSet<NodeId> = branches
+		.iter()
+		.flat_map(|(id, upstream)| upstream.iter().filter(move |upstream_id| *upstream_id != id))
+		.copied()
+		.collect();
+	branches.retain(|(id, _)| !nested.contains(id));
+
+	// A lone branch is evaluated just as quickly on the calling thread when it's demanded
+	if branches.len() < 2 {
+		return HashMap::new();
+	}
+
+	let Ok(sorted) = proto_network.topological_sort() else { return HashMap::new() };
+	let branches: Vec<ProtoNetwork> = branches
+		.into_iter()
+		.map(|(id, upstream)| ProtoNetwork {
+			inputs: Vec::new(),
+			output: id,
+			nodes: sorted.iter().filter(|node_id| upstream.contains(node_id)).map(|node_id| (*node_id, nodes[node_id].clone())).collect(),
+		})
+		.collect();
+
+	let workers = std::thread::available_parallelism().map_or(1, usize::from).min(branches.len());
+	let queue = Mutex::new(branches);
+	let values = Mutex::new(HashMap::new());
+	std::thread::scope(|scope| {
+		for _ in 0..workers {
+			scope.spawn(|| {
+				let next = || queue.lock().unwrap().pop();
+				while let Some(branch) = next() {
+					let id = branch.output;
+					match futures::executor::block_on(evaluate_branch(branch, typing_context)) {
+						Ok(value) => {
+							if let Some(render_cache) = render_cache {
+								render_cache.store(id.0, &value);
+							}
+							values.lock().unwrap().insert(id, value);
+						}
+						Err(error) => log::warn!("Failed to evaluate the branch of node {id:?} ahead of time: {error}"),
+					}
+				}
+			});
+		}
+	});
+
+	values.into_inner().unwrap()
+}
+
+/// Builds the nodes of the branch in a tree of its own, since the nodes of a tree can't be shared between threads.
+async fn evaluate_branch(branch: ProtoNetwork, typing_context: &TypingContext) -> Result<TaggedValue, String> {
+	let output = branch.output;
+	let mut tree = BorrowTree::default();
+	for (id, node) in branch.nodes {
+		tree.push_node(id, node, typing_context).await.map_err(|errors| format!("{errors:?}"))?;
+	}
+	tree.eval_tagged_value(output, ()).await
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graph_craft::document::*;
+	use graph_craft::graphene_compiler::Compiler;
+	use graph_craft::ProtoNodeIdentifier;
+	use graphene_core::raster::{Color, Image, ImageFrame};
+
+	fn cached_image(color: Color) -> DocumentNode {
+		let image = ImageFrame {
+			image: Image::new(2, 2, color),
+			..Default::default()
+		};
+		DocumentNode {
+			name: "Cache".into(),
+			inputs: vec![NodeInput::value(TaggedValue::ImageFrame(image), false)],
+			implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::memo::MemoNode<_, _>")),
+			manual_composition: Some(concrete!(())),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn evaluates_independent_branches() {
+		let network = NodeNetwork {
+			exports: vec![NodeOutput::new(NodeId(2), 0)],
+			nodes: [
+				(NodeId(0), cached_image(Color::RED)),
+				(NodeId(1), cached_image(Color::BLUE)),
+				(
+					NodeId(2),
+					DocumentNode {
+						name: "Set Transform".into(),
+						inputs: vec![NodeInput::node(NodeId(0), 0), NodeInput::node(NodeId(1), 0)],
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::transform::SetTransformNode<_>")),
+						..Default::default()
+					},
+				),
+			]
+			.into_iter()
+			.collect(),
+			..Default::default()
+		};
+
+		let proto_network = Compiler {}.compile_single(network).unwrap();
+		let mut typing_context = TypingContext::new(&crate::node_registry::NODE_REGISTRY);
+		typing_context.update(&proto_network).unwrap();
+
+		let values = evaluate_independent_branches(&proto_network, &typing_context, |_| false, None);
+		let mut colors: Vec<_> = values
+			.into_values()
+			.map(|value| match value {
+				TaggedValue::ImageFrame(frame) => frame.image.data[0],
+				_ => panic!("The branches should evaluate to images"),
+			})
+			.collect();
+		colors.sort_by(|a, b| a.b().total_cmp(&b.b()));
+		assert_eq!(colors, vec![Color::RED, Color::BLUE]);
+
+		// Nothing is evaluated ahead of time when the branches are already built
+		assert!(evaluate_independent_branches(&proto_network, &typing_context, |_| true, None).is_empty());
+	}
+}